@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS order_status_history_order_id_idx;
+DROP TABLE IF EXISTS order_status_history;
@@ -0,0 +1,30 @@
+CREATE TABLE order_status_history (
+    id INTEGER NOT NULL PRIMARY KEY,
+    order_id INTEGER NOT NULL,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    changed_by_email TEXT NOT NULL,
+    changed_by_name TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (order_id) REFERENCES orders(id) ON DELETE CASCADE,
+    CHECK (
+        from_status IN (
+            'Draft',
+            'Pending',
+            'Processing',
+            'Completed',
+            'Cancelled'
+        )
+    ),
+    CHECK (
+        to_status IN (
+            'Draft',
+            'Pending',
+            'Processing',
+            'Completed',
+            'Cancelled'
+        )
+    )
+);
+
+CREATE INDEX order_status_history_order_id_idx ON order_status_history(order_id);
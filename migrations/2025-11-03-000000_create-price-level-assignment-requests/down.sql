@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS price_level_assignment_requests_customer_id_idx;
+DROP INDEX IF EXISTS price_level_assignment_requests_hub_id_status_idx;
+DROP TABLE IF EXISTS price_level_assignment_requests;
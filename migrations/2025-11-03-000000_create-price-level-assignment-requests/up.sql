@@ -0,0 +1,21 @@
+CREATE TABLE price_level_assignment_requests (
+    id INTEGER NOT NULL PRIMARY KEY,
+    hub_id INTEGER NOT NULL,
+    customer_id INTEGER NOT NULL,
+    price_level_id INTEGER,
+    status TEXT NOT NULL DEFAULT 'Pending',
+    requested_by_email TEXT NOT NULL,
+    requested_by_name TEXT NOT NULL,
+    decided_by_email TEXT,
+    decided_by_name TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    decided_at TIMESTAMP,
+    FOREIGN KEY (customer_id) REFERENCES customers(id) ON DELETE CASCADE,
+    FOREIGN KEY (price_level_id) REFERENCES price_levels(id) ON DELETE CASCADE,
+    CHECK (status IN ('Pending', 'Approved', 'Rejected'))
+);
+
+CREATE INDEX price_level_assignment_requests_hub_id_status_idx
+    ON price_level_assignment_requests(hub_id, status);
+CREATE INDEX price_level_assignment_requests_customer_id_idx
+    ON price_level_assignment_requests(customer_id);
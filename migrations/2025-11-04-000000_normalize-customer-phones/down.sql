@@ -0,0 +1,2 @@
+-- The original phone formatting is not kept, so normalisation cannot be undone.
+SELECT 1;
@@ -0,0 +1,62 @@
+-- Rewrite stored customer phones in E.164 (`+79001234567`) using the Russian
+-- numbering plan, the default for DEFAULT_PHONE_COUNTRY. Phones that are not
+-- recognisable, or whose normalised form would clash with another customer
+-- sharing the email, are left as they are and show up in the duplicate report.
+CREATE TEMP TABLE customer_phone_normalisation AS
+WITH stripped AS (
+    SELECT
+        id,
+        hub_id,
+        email,
+        phone,
+        replace(replace(replace(replace(replace(replace(
+            trim(phone), ' ', ''), '-', ''), '(', ''), ')', ''), '.', ''), '/', '') AS compact
+    FROM customers
+    WHERE phone IS NOT NULL
+),
+candidates AS (
+    SELECT
+        id,
+        hub_id,
+        email,
+        phone,
+        CASE
+            WHEN compact GLOB '+[0-9]*'
+                AND substr(compact, 2) NOT GLOB '*[^0-9]*'
+                AND length(compact) BETWEEN 8 AND 16
+                THEN compact
+            WHEN compact GLOB '00[0-9]*'
+                AND compact NOT GLOB '*[^0-9]*'
+                AND length(compact) BETWEEN 9 AND 17
+                THEN '+' || substr(compact, 3)
+            WHEN compact NOT GLOB '*[^0-9]*' AND length(compact) = 10
+                THEN '+7' || compact
+            WHEN compact NOT GLOB '*[^0-9]*'
+                AND length(compact) = 11
+                AND substr(compact, 1, 1) IN ('7', '8')
+                THEN '+7' || substr(compact, 2)
+        END AS normalized
+    FROM stripped
+)
+SELECT candidate.id, candidate.normalized
+FROM candidates AS candidate
+WHERE candidate.normalized IS NOT NULL
+    AND candidate.normalized <> candidate.phone
+    AND NOT EXISTS (
+        SELECT 1
+        FROM candidates AS other
+        WHERE other.id <> candidate.id
+            AND other.hub_id = candidate.hub_id
+            AND other.email = candidate.email
+            AND (other.normalized = candidate.normalized OR other.phone = candidate.normalized)
+    );
+
+UPDATE customers
+SET phone = (
+    SELECT normalized
+    FROM customer_phone_normalisation
+    WHERE customer_phone_normalisation.id = customers.id
+)
+WHERE id IN (SELECT id FROM customer_phone_normalisation);
+
+DROP TABLE customer_phone_normalisation;
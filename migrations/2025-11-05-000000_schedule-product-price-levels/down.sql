@@ -0,0 +1,29 @@
+-- Scheduled rates cannot be represented without a validity window and are
+-- dropped; base rates are kept.
+CREATE TABLE product_price_levels_unscheduled (
+    id INTEGER NOT NULL PRIMARY KEY,
+    product_id INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    price_level_id INTEGER NOT NULL REFERENCES price_levels(id) ON DELETE CASCADE,
+    price_cents INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(product_id, price_level_id)
+);
+
+INSERT INTO product_price_levels_unscheduled (
+    id, product_id, price_level_id, price_cents, created_at, updated_at
+)
+SELECT id, product_id, price_level_id, price_cents, created_at, updated_at
+FROM product_price_levels
+WHERE valid_from IS NULL AND valid_to IS NULL;
+
+DROP INDEX product_price_levels_base_rate_idx;
+DROP INDEX product_price_levels_product_id_idx;
+DROP INDEX product_price_levels_price_level_id_idx;
+DROP TABLE product_price_levels;
+ALTER TABLE product_price_levels_unscheduled RENAME TO product_price_levels;
+
+CREATE INDEX product_price_levels_product_id_idx
+    ON product_price_levels(product_id);
+CREATE INDEX product_price_levels_price_level_id_idx
+    ON product_price_levels(price_level_id);
@@ -0,0 +1,32 @@
+-- Rates gain an optional validity window. A product keeps at most one base
+-- rate (no window) per price level; scheduled rates may overlap it.
+CREATE TABLE product_price_levels_scheduled (
+    id INTEGER NOT NULL PRIMARY KEY,
+    product_id INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    price_level_id INTEGER NOT NULL REFERENCES price_levels(id) ON DELETE CASCADE,
+    price_cents INTEGER NOT NULL,
+    valid_from TIMESTAMP,
+    valid_to TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (valid_from IS NULL OR valid_to IS NULL OR valid_from < valid_to)
+);
+
+INSERT INTO product_price_levels_scheduled (
+    id, product_id, price_level_id, price_cents, created_at, updated_at
+)
+SELECT id, product_id, price_level_id, price_cents, created_at, updated_at
+FROM product_price_levels;
+
+DROP INDEX product_price_levels_product_id_idx;
+DROP INDEX product_price_levels_price_level_id_idx;
+DROP TABLE product_price_levels;
+ALTER TABLE product_price_levels_scheduled RENAME TO product_price_levels;
+
+CREATE INDEX product_price_levels_product_id_idx
+    ON product_price_levels(product_id);
+CREATE INDEX product_price_levels_price_level_id_idx
+    ON product_price_levels(price_level_id);
+CREATE UNIQUE INDEX product_price_levels_base_rate_idx
+    ON product_price_levels(product_id, price_level_id)
+    WHERE valid_from IS NULL AND valid_to IS NULL;
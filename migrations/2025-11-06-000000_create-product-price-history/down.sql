@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS product_price_history_product_id_idx;
+DROP TABLE IF EXISTS product_price_history;
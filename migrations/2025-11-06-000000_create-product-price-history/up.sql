@@ -0,0 +1,19 @@
+CREATE TABLE product_price_history (
+    id INTEGER NOT NULL PRIMARY KEY,
+    product_id INTEGER,
+    price_level_id INTEGER,
+    product_name TEXT NOT NULL,
+    price_level_name TEXT NOT NULL,
+    old_price_cents INTEGER,
+    new_price_cents INTEGER,
+    valid_from TIMESTAMP,
+    valid_to TIMESTAMP,
+    changed_by_email TEXT NOT NULL,
+    changed_by_name TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE SET NULL,
+    FOREIGN KEY (price_level_id) REFERENCES price_levels(id) ON DELETE SET NULL
+);
+
+CREATE INDEX product_price_history_product_id_idx
+    ON product_price_history(product_id, created_at);
@@ -0,0 +1,6 @@
+DROP INDEX price_levels_base_price_level_id_idx;
+
+ALTER TABLE price_levels DROP COLUMN rounding;
+ALTER TABLE price_levels DROP COLUMN adjustment_value;
+ALTER TABLE price_levels DROP COLUMN adjustment_kind;
+ALTER TABLE price_levels DROP COLUMN base_price_level_id;
@@ -0,0 +1,10 @@
+-- A derived level computes prices from its base level for products without
+-- an explicit rate. The base reference is checked by the application so the
+-- columns can be dropped again without rebuilding the table.
+ALTER TABLE price_levels ADD COLUMN base_price_level_id INTEGER;
+ALTER TABLE price_levels ADD COLUMN adjustment_kind TEXT NOT NULL DEFAULT 'Percent';
+ALTER TABLE price_levels ADD COLUMN adjustment_value INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE price_levels ADD COLUMN rounding TEXT NOT NULL DEFAULT 'None';
+
+CREATE INDEX price_levels_base_price_level_id_idx
+    ON price_levels(base_price_level_id);
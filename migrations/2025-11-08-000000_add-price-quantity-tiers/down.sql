@@ -0,0 +1,11 @@
+-- Quantity breaks cannot be represented without the column and are dropped.
+ALTER TABLE product_price_history DROP COLUMN min_quantity;
+
+DELETE FROM product_price_levels WHERE min_quantity > 1;
+
+DROP INDEX product_price_levels_base_rate_idx;
+CREATE UNIQUE INDEX product_price_levels_base_rate_idx
+    ON product_price_levels(product_id, price_level_id)
+    WHERE valid_from IS NULL AND valid_to IS NULL;
+
+ALTER TABLE product_price_levels DROP COLUMN min_quantity;
@@ -0,0 +1,12 @@
+-- A base rate with `min_quantity` above one is a quantity break: it applies to
+-- order lines of at least that many units. Existing rates keep the 1+ tier.
+ALTER TABLE product_price_levels
+    ADD COLUMN min_quantity INTEGER NOT NULL DEFAULT 1 CHECK (min_quantity >= 1);
+
+DROP INDEX product_price_levels_base_rate_idx;
+CREATE UNIQUE INDEX product_price_levels_base_rate_idx
+    ON product_price_levels(product_id, price_level_id, min_quantity)
+    WHERE valid_from IS NULL AND valid_to IS NULL;
+
+ALTER TABLE product_price_history
+    ADD COLUMN min_quantity INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,4 @@
+ALTER TABLE order_products DROP COLUMN exchange_rate_micros;
+
+DROP INDEX IF EXISTS exchange_rates_hub_id_pair_idx;
+DROP TABLE IF EXISTS exchange_rates;
@@ -0,0 +1,19 @@
+-- One unit of `base_currency` is worth `rate_micros / 1_000_000` units of
+-- `quote_currency`.
+CREATE TABLE exchange_rates (
+    id INTEGER NOT NULL PRIMARY KEY,
+    hub_id INTEGER NOT NULL,
+    base_currency TEXT NOT NULL,
+    quote_currency TEXT NOT NULL,
+    rate_micros BIGINT NOT NULL CHECK (rate_micros > 0),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (base_currency <> quote_currency)
+);
+
+CREATE UNIQUE INDEX exchange_rates_hub_id_pair_idx
+    ON exchange_rates(hub_id, base_currency, quote_currency);
+
+-- Lines priced in the order currency keep the 1:1 rate.
+ALTER TABLE order_products
+    ADD COLUMN exchange_rate_micros BIGINT NOT NULL DEFAULT 1000000;
@@ -0,0 +1,29 @@
+ALTER TABLE product_price_levels ADD COLUMN price_cents_wide INTEGER NOT NULL DEFAULT 0;
+UPDATE product_price_levels SET price_cents_wide = price_cents;
+ALTER TABLE product_price_levels DROP COLUMN price_cents;
+ALTER TABLE product_price_levels RENAME COLUMN price_cents_wide TO price_cents;
+
+ALTER TABLE product_price_history ADD COLUMN old_price_cents_wide INTEGER;
+UPDATE product_price_history SET old_price_cents_wide = old_price_cents;
+ALTER TABLE product_price_history DROP COLUMN old_price_cents;
+ALTER TABLE product_price_history RENAME COLUMN old_price_cents_wide TO old_price_cents;
+
+ALTER TABLE product_price_history ADD COLUMN new_price_cents_wide INTEGER;
+UPDATE product_price_history SET new_price_cents_wide = new_price_cents;
+ALTER TABLE product_price_history DROP COLUMN new_price_cents;
+ALTER TABLE product_price_history RENAME COLUMN new_price_cents_wide TO new_price_cents;
+
+ALTER TABLE order_products ADD COLUMN price_cents_wide INTEGER NOT NULL DEFAULT 0;
+UPDATE order_products SET price_cents_wide = price_cents;
+ALTER TABLE order_products DROP COLUMN price_cents;
+ALTER TABLE order_products RENAME COLUMN price_cents_wide TO price_cents;
+
+ALTER TABLE orders ADD COLUMN total_cents_wide INTEGER NOT NULL DEFAULT 0;
+UPDATE orders SET total_cents_wide = total_cents;
+ALTER TABLE orders DROP COLUMN total_cents;
+ALTER TABLE orders RENAME COLUMN total_cents_wide TO total_cents;
+
+ALTER TABLE price_levels ADD COLUMN adjustment_value_wide INTEGER NOT NULL DEFAULT 0;
+UPDATE price_levels SET adjustment_value_wide = adjustment_value;
+ALTER TABLE price_levels DROP COLUMN adjustment_value;
+ALTER TABLE price_levels RENAME COLUMN adjustment_value_wide TO adjustment_value;
@@ -0,0 +1,16 @@
+ALTER TABLE orders DROP COLUMN tax_cents;
+ALTER TABLE orders DROP COLUMN net_cents;
+
+ALTER TABLE order_products DROP COLUMN gross_cents;
+ALTER TABLE order_products DROP COLUMN tax_cents;
+ALTER TABLE order_products DROP COLUMN net_cents;
+ALTER TABLE order_products DROP COLUMN prices_include_tax;
+ALTER TABLE order_products DROP COLUMN tax_rate_basis_points;
+
+ALTER TABLE price_levels DROP COLUMN prices_include_tax;
+
+DROP INDEX IF EXISTS products_tax_category_id_idx;
+ALTER TABLE products DROP COLUMN tax_category_id;
+
+DROP INDEX IF EXISTS tax_categories_hub_id_name_idx;
+DROP TABLE IF EXISTS tax_categories;
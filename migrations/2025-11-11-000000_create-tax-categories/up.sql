@@ -0,0 +1,36 @@
+-- `rate_basis_points` is the tax rate in hundredths of a percent, e.g. 2000
+-- for 20%.
+CREATE TABLE tax_categories (
+    id INTEGER NOT NULL PRIMARY KEY,
+    hub_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    rate_basis_points INTEGER NOT NULL CHECK (rate_basis_points >= 0),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX tax_categories_hub_id_name_idx ON tax_categories(hub_id, name);
+
+ALTER TABLE products
+    ADD COLUMN tax_category_id INTEGER REFERENCES tax_categories(id) ON DELETE SET NULL;
+
+CREATE INDEX products_tax_category_id_idx ON products(tax_category_id);
+
+ALTER TABLE price_levels ADD COLUMN prices_include_tax BOOLEAN NOT NULL DEFAULT 1;
+
+-- Amounts are in the order currency. Lines placed before taxes existed are
+-- untaxed, so their net and gross amounts are the converted line total.
+ALTER TABLE order_products ADD COLUMN tax_rate_basis_points INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE order_products ADD COLUMN prices_include_tax BOOLEAN NOT NULL DEFAULT 1;
+ALTER TABLE order_products ADD COLUMN net_cents BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE order_products ADD COLUMN tax_cents BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE order_products ADD COLUMN gross_cents BIGINT NOT NULL DEFAULT 0;
+
+UPDATE order_products
+SET net_cents = CAST(ROUND(price_cents * quantity * exchange_rate_micros / 1000000.0) AS BIGINT),
+    gross_cents = CAST(ROUND(price_cents * quantity * exchange_rate_micros / 1000000.0) AS BIGINT);
+
+ALTER TABLE orders ADD COLUMN net_cents BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE orders ADD COLUMN tax_cents BIGINT NOT NULL DEFAULT 0;
+
+UPDATE orders SET net_cents = total_cents;
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS order_discounts_promotion_id_idx;
+DROP INDEX IF EXISTS order_discounts_order_id_idx;
+DROP TABLE IF EXISTS order_discounts;
+
+DROP INDEX IF EXISTS promotions_hub_id_code_idx;
+DROP TABLE IF EXISTS promotions;
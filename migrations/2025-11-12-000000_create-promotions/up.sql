@@ -0,0 +1,45 @@
+-- `value` is in basis points for `Percent` promotions and in the minor unit
+-- of `currency` for `Fixed` ones; `min_order_cents` is in `currency` too.
+CREATE TABLE promotions (
+    id INTEGER NOT NULL PRIMARY KEY,
+    hub_id INTEGER NOT NULL,
+    code TEXT NOT NULL,
+    kind TEXT NOT NULL DEFAULT 'Percent',
+    value BIGINT NOT NULL CHECK (value > 0),
+    currency TEXT NOT NULL,
+    min_order_cents BIGINT,
+    valid_from TIMESTAMP,
+    valid_to TIMESTAMP,
+    usage_limit INTEGER CHECK (usage_limit > 0),
+    usage_count INTEGER NOT NULL DEFAULT 0,
+    category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL,
+    tag_id INTEGER REFERENCES tags(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX promotions_hub_id_code_idx ON promotions(hub_id, code);
+
+-- Discount applied to an order, with the terms of the promotion captured when
+-- the code was redeemed. `currency` is the promotion currency of `value` and
+-- `min_order_cents`; the amounts are in the order currency.
+CREATE TABLE order_discounts (
+    id INTEGER NOT NULL PRIMARY KEY,
+    order_id INTEGER NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    promotion_id INTEGER REFERENCES promotions(id) ON DELETE SET NULL,
+    code TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    value BIGINT NOT NULL,
+    currency TEXT NOT NULL,
+    category_id INTEGER,
+    tag_id INTEGER,
+    min_order_cents BIGINT,
+    net_cents BIGINT NOT NULL,
+    tax_cents BIGINT NOT NULL,
+    gross_cents BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX order_discounts_order_id_idx ON order_discounts(order_id);
+CREATE INDEX order_discounts_promotion_id_idx ON order_discounts(promotion_id);
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS inventory_movements_order_id_idx;
+DROP INDEX IF EXISTS inventory_movements_product_id_idx;
+DROP TABLE IF EXISTS inventory_movements;
+
+ALTER TABLE products DROP COLUMN allow_oversell;
+ALTER TABLE products DROP COLUMN stock_reserved;
+ALTER TABLE products DROP COLUMN stock_on_hand;
@@ -0,0 +1,70 @@
+-- Available stock is `stock_on_hand - stock_reserved`. Products allowing
+-- oversell accept orders beyond it, which keeps existing catalogs working.
+ALTER TABLE products ADD COLUMN stock_on_hand INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE products ADD COLUMN stock_reserved INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE products ADD COLUMN allow_oversell BOOLEAN NOT NULL DEFAULT 1;
+
+-- Ledger of stock changes. `quantity` is the signed change of the on-hand
+-- quantity for adjustments and the number of units moved otherwise; the
+-- levels after the movement are captured next to it.
+CREATE TABLE inventory_movements (
+    id INTEGER NOT NULL PRIMARY KEY,
+    product_id INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    order_id INTEGER REFERENCES orders(id) ON DELETE SET NULL,
+    kind TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    stock_on_hand INTEGER NOT NULL,
+    stock_reserved INTEGER NOT NULL,
+    note TEXT,
+    changed_by_email TEXT NOT NULL,
+    changed_by_name TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (kind IN ('Adjustment', 'Reservation', 'Release', 'Deduction'))
+);
+
+CREATE INDEX inventory_movements_product_id_idx ON inventory_movements(product_id);
+CREATE INDEX inventory_movements_order_id_idx ON inventory_movements(order_id);
+
+-- Orders already in processing hold their units, so releasing or completing
+-- them later moves stock that was actually reserved. Every backfilled
+-- reservation is recorded with the levels reached after it.
+UPDATE products SET stock_reserved = (
+    SELECT COALESCE(SUM(order_products.quantity), 0)
+    FROM order_products
+    JOIN orders ON orders.id = order_products.order_id
+    WHERE order_products.product_id = products.id
+        AND orders.hub_id = products.hub_id
+        AND orders.status = 'Processing'
+);
+
+INSERT INTO inventory_movements (
+    product_id,
+    order_id,
+    kind,
+    quantity,
+    stock_on_hand,
+    stock_reserved,
+    note,
+    changed_by_email,
+    changed_by_name
+)
+SELECT
+    products.id,
+    orders.id,
+    'Reservation',
+    SUM(order_products.quantity),
+    products.stock_on_hand,
+    SUM(SUM(order_products.quantity)) OVER (
+        PARTITION BY products.id
+        ORDER BY orders.id
+    ),
+    'Reserved for an order in processing when stock tracking was enabled',
+    'system',
+    'System'
+FROM order_products
+JOIN orders ON orders.id = order_products.order_id
+JOIN products ON products.id = order_products.product_id
+    AND products.hub_id = orders.hub_id
+WHERE orders.status = 'Processing'
+GROUP BY products.id, orders.id
+ORDER BY orders.id, products.id;
@@ -1,6 +1,9 @@
+use chrono::NaiveDateTime;
 use pushkind_common::pagination::Pagination;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::price_level_assignment::NewPriceLevelAssignmentRequest;
+
 /// Domain representation of a customer that belongs to a hub.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Customer {
@@ -65,6 +68,110 @@ impl NewCustomer {
     }
 }
 
+/// Patch data applied when editing a customer's contact details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateCustomer {
+    /// Human-friendly display name of the customer.
+    pub name: String,
+    /// Primary email address stored in lowercase for comparisons.
+    pub email: String,
+    /// Optional contact phone number; `None` clears the stored phone.
+    pub phone: Option<String>,
+    /// Timestamp captured when the patch was created.
+    pub updated_at: NaiveDateTime,
+}
+
+impl UpdateCustomer {
+    /// Build a patch while normalising the email to lowercase.
+    #[must_use]
+    pub fn new(
+        name: impl Into<String>,
+        email: impl Into<String>,
+        updated_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            name: name.into().trim().to_string(),
+            email: email.into().trim().to_lowercase(),
+            phone: None,
+            updated_at,
+        }
+    }
+
+    /// Attach a phone number to the patch.
+    #[must_use]
+    pub fn with_phone(mut self, phone: impl Into<String>) -> Self {
+        let value = phone.into().trim().to_string();
+        self.phone = if value.is_empty() { None } else { Some(value) };
+        self
+    }
+}
+
+/// Merge of duplicate customers into one surviving record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomerMerge {
+    /// Hub identifier that owns every merged customer.
+    pub hub_id: i32,
+    /// Customer that keeps its identity and receives the orders.
+    pub survivor_id: i32,
+    /// Customers whose orders move to the survivor before they are deleted.
+    pub merged_ids: Vec<i32>,
+    /// Price level kept by the survivor; `None` falls back to the hub default.
+    pub price_level_id: Option<i32>,
+    /// Timestamp captured when the merge was requested.
+    pub updated_at: NaiveDateTime,
+}
+
+impl CustomerMerge {
+    /// Build a merge, dropping the survivor and repeated ids from `merged_ids`.
+    #[must_use]
+    pub fn new(
+        hub_id: i32,
+        survivor_id: i32,
+        merged_ids: impl IntoIterator<Item = i32>,
+        price_level_id: Option<i32>,
+        updated_at: NaiveDateTime,
+    ) -> Self {
+        let mut merged_ids: Vec<i32> = merged_ids
+            .into_iter()
+            .filter(|id| *id != survivor_id)
+            .collect();
+        merged_ids.sort_unstable();
+        merged_ids.dedup();
+
+        Self {
+            hub_id,
+            survivor_id,
+            merged_ids,
+            price_level_id,
+            updated_at,
+        }
+    }
+}
+
+/// Customer written by one row of a customer import.
+#[derive(Debug, Clone)]
+pub enum CustomerImportTarget {
+    /// Insert a new customer.
+    Create(NewCustomer),
+    /// Overwrite an existing customer of the hub.
+    Update {
+        customer_id: i32,
+        updates: UpdateCustomer,
+    },
+    /// Keep an existing customer of the hub as it is.
+    Existing(i32),
+}
+
+/// Row of a customer import.
+#[derive(Debug, Clone)]
+pub struct CustomerImportItem {
+    /// Customer created, updated or kept by the row.
+    pub target: CustomerImportTarget,
+    /// Price level change filed for the customer; its `customer_id` is set to
+    /// the written customer.
+    pub price_level_request: Option<NewPriceLevelAssignmentRequest>,
+}
+
 #[derive(Debug, Clone)]
 /// Query definition used to list customers for a hub.
 pub struct CustomerListQuery {
@@ -0,0 +1,131 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Fixed-point scale of exchange rates: a rate of `EXCHANGE_RATE_SCALE` is 1:1.
+pub const EXCHANGE_RATE_SCALE: i64 = 1_000_000;
+
+/// Hub-level rate between two currencies.
+///
+/// One unit of `base_currency` is worth `rate_micros / EXCHANGE_RATE_SCALE`
+/// units of `quote_currency`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExchangeRate {
+    /// Unique identifier of the rate.
+    pub id: i32,
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// ISO 4217 code of the currency being converted from.
+    pub base_currency: String,
+    /// ISO 4217 code of the currency being converted to.
+    pub quote_currency: String,
+    /// Units of `quote_currency` per unit of `base_currency`, in millionths.
+    pub rate_micros: i64,
+    /// Timestamp for when the rate record was created.
+    pub created_at: NaiveDateTime,
+    /// Timestamp for the last update to the rate record.
+    pub updated_at: NaiveDateTime,
+}
+
+/// Payload required to store a rate for a currency pair.
+///
+/// Saving a pair that already exists replaces its rate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewExchangeRate {
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// ISO 4217 code of the currency being converted from.
+    pub base_currency: String,
+    /// ISO 4217 code of the currency being converted to.
+    pub quote_currency: String,
+    /// Units of `quote_currency` per unit of `base_currency`, in millionths.
+    pub rate_micros: i64,
+}
+
+impl NewExchangeRate {
+    /// Construct a rate payload with upper-cased currency codes.
+    pub fn new(
+        hub_id: i32,
+        base_currency: impl Into<String>,
+        quote_currency: impl Into<String>,
+        rate_micros: i64,
+    ) -> Self {
+        Self {
+            hub_id,
+            base_currency: base_currency.into().trim().to_ascii_uppercase(),
+            quote_currency: quote_currency.into().trim().to_ascii_uppercase(),
+            rate_micros,
+        }
+    }
+}
+
+/// Rate converting amounts in `from` into `to`, in millionths.
+///
+/// Equal currencies convert 1:1. A pair stored only in the opposite direction
+/// is used inverted. Returns `None` when the hub has no rate for the pair.
+pub fn conversion_rate(rates: &[ExchangeRate], from: &str, to: &str) -> Option<i64> {
+    if from == to {
+        return Some(EXCHANGE_RATE_SCALE);
+    }
+
+    if let Some(rate) = rates
+        .iter()
+        .find(|rate| rate.base_currency == from && rate.quote_currency == to)
+    {
+        return Some(rate.rate_micros);
+    }
+
+    rates
+        .iter()
+        .find(|rate| rate.base_currency == to && rate.quote_currency == from)
+        .and_then(|rate| {
+            let scale = i128::from(EXCHANGE_RATE_SCALE);
+            let inverted =
+                (scale * scale + i128::from(rate.rate_micros) / 2) / i128::from(rate.rate_micros);
+            i64::try_from(inverted).ok().filter(|rate| *rate > 0)
+        })
+}
+
+/// Formats a rate in millionths as a decimal without trailing zeros, e.g. `92.5`.
+pub fn format_rate(rate_micros: i64) -> String {
+    let whole = rate_micros / EXCHANGE_RATE_SCALE;
+    let fraction = (rate_micros % EXCHANGE_RATE_SCALE).unsigned_abs();
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let digits = format!("{fraction:06}");
+    format!("{whole}.{}", digits.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(base: &str, quote: &str, rate_micros: i64) -> ExchangeRate {
+        ExchangeRate {
+            id: 1,
+            hub_id: 1,
+            base_currency: base.to_string(),
+            quote_currency: quote.to_string(),
+            rate_micros,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn conversion_rate_uses_direct_inverse_or_identity() {
+        let rates = vec![rate("USD", "RUB", 80_000_000)];
+
+        assert_eq!(conversion_rate(&rates, "RUB", "RUB"), Some(1_000_000));
+        assert_eq!(conversion_rate(&rates, "USD", "RUB"), Some(80_000_000));
+        assert_eq!(conversion_rate(&rates, "RUB", "USD"), Some(12_500));
+        assert_eq!(conversion_rate(&rates, "EUR", "RUB"), None);
+    }
+
+    #[test]
+    fn format_rate_drops_trailing_zeros() {
+        assert_eq!(format_rate(92_500_000), "92.5");
+        assert_eq!(format_rate(12_500), "0.0125");
+        assert_eq!(format_rate(1_000_000), "1");
+    }
+}
@@ -0,0 +1,224 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::order::OrderStatus;
+
+/// Kind of change recorded in the inventory ledger.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InventoryMovementKind {
+    /// Manual change of the quantity on hand, e.g. a delivery or a stocktake.
+    #[default]
+    Adjustment,
+    /// Units held for an order moved to processing.
+    Reservation,
+    /// Units of an order returned to the available stock.
+    Release,
+    /// Units of a completed order taken off the shelf.
+    Deduction,
+}
+
+impl InventoryMovementKind {
+    /// Movement applied to the lines of an order moving from `from` to `to`.
+    ///
+    /// Processing orders hold their units: entering processing reserves them,
+    /// completing deducts them and going back or cancelling releases them.
+    pub fn for_transition(from: OrderStatus, to: OrderStatus) -> Option<Self> {
+        match (from, to) {
+            (_, OrderStatus::Processing) if from != to => Some(Self::Reservation),
+            (OrderStatus::Processing, OrderStatus::Completed) => Some(Self::Deduction),
+            (OrderStatus::Processing, OrderStatus::Pending | OrderStatus::Cancelled) => {
+                Some(Self::Release)
+            }
+            _ => None,
+        }
+    }
+
+    /// Stock levels after moving `quantity` units.
+    ///
+    /// Releasing or deducting more units than are reserved leaves a negative
+    /// reserved quantity instead of hiding the mismatch.
+    pub fn apply(self, level: StockLevel, quantity: i32) -> StockLevel {
+        let unreserved = level.reserved.saturating_sub(quantity);
+        match self {
+            Self::Adjustment => StockLevel {
+                on_hand: level.on_hand.saturating_add(quantity),
+                ..level
+            },
+            Self::Reservation => StockLevel {
+                reserved: level.reserved.saturating_add(quantity),
+                ..level
+            },
+            Self::Release => StockLevel {
+                reserved: unreserved,
+                ..level
+            },
+            Self::Deduction => StockLevel {
+                on_hand: level.on_hand.saturating_sub(quantity),
+                reserved: unreserved,
+            },
+        }
+    }
+}
+
+impl From<&str> for InventoryMovementKind {
+    fn from(value: &str) -> Self {
+        match value {
+            "Reservation" => Self::Reservation,
+            "Release" => Self::Release,
+            "Deduction" => Self::Deduction,
+            _ => Self::Adjustment,
+        }
+    }
+}
+
+impl From<InventoryMovementKind> for &'static str {
+    fn from(value: InventoryMovementKind) -> Self {
+        match value {
+            InventoryMovementKind::Adjustment => "Adjustment",
+            InventoryMovementKind::Reservation => "Reservation",
+            InventoryMovementKind::Release => "Release",
+            InventoryMovementKind::Deduction => "Deduction",
+        }
+    }
+}
+
+/// Units of a product on hand and held for processing orders.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StockLevel {
+    /// Units physically in stock.
+    pub on_hand: i32,
+    /// Units held for processing orders.
+    pub reserved: i32,
+}
+
+impl StockLevel {
+    /// Units that can still be promised to new orders.
+    pub fn available(&self) -> i32 {
+        self.on_hand.saturating_sub(self.reserved)
+    }
+}
+
+/// Recorded change of the stock of a product.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InventoryMovement {
+    /// Unique identifier of the ledger record.
+    pub id: i32,
+    /// Identifier of the product whose stock changed.
+    pub product_id: i32,
+    /// Order that caused the movement, if any.
+    pub order_id: Option<i32>,
+    /// Kind of change.
+    pub kind: InventoryMovementKind,
+    /// Signed change of the quantity on hand for adjustments, units moved
+    /// otherwise.
+    pub quantity: i32,
+    /// Stock levels after the movement.
+    pub stock: StockLevel,
+    /// Optional reason entered with an adjustment.
+    pub note: Option<String>,
+    /// Email of the user who caused the movement.
+    pub changed_by_email: String,
+    /// Display name of the user who caused the movement.
+    pub changed_by_name: String,
+    /// Timestamp for when the movement was recorded.
+    pub created_at: NaiveDateTime,
+}
+
+/// Payload required to adjust the quantity on hand of a product.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewStockAdjustment {
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// Identifier of the product to adjust.
+    pub product_id: i32,
+    /// Signed change of the quantity on hand.
+    pub quantity: i32,
+    /// Optional reason such as a delivery or a write-off.
+    pub note: Option<String>,
+    /// Email of the user performing the adjustment.
+    pub changed_by_email: String,
+    /// Display name of the user performing the adjustment.
+    pub changed_by_name: String,
+}
+
+impl NewStockAdjustment {
+    /// Construct an adjustment of `quantity` units for `product_id`.
+    pub fn new(
+        hub_id: i32,
+        product_id: i32,
+        quantity: i32,
+        changed_by_email: impl Into<String>,
+        changed_by_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            hub_id,
+            product_id,
+            quantity,
+            note: None,
+            changed_by_email: changed_by_email.into(),
+            changed_by_name: changed_by_name.into(),
+        }
+    }
+
+    /// Record the reason of the adjustment.
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into().trim().to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transitions_reserve_deduct_and_release() {
+        use OrderStatus::*;
+
+        assert_eq!(
+            InventoryMovementKind::for_transition(Pending, Processing),
+            Some(InventoryMovementKind::Reservation)
+        );
+        assert_eq!(
+            InventoryMovementKind::for_transition(Processing, Completed),
+            Some(InventoryMovementKind::Deduction)
+        );
+        assert_eq!(
+            InventoryMovementKind::for_transition(Processing, Cancelled),
+            Some(InventoryMovementKind::Release)
+        );
+        assert_eq!(
+            InventoryMovementKind::for_transition(Processing, Pending),
+            Some(InventoryMovementKind::Release)
+        );
+        assert_eq!(
+            InventoryMovementKind::for_transition(Pending, Cancelled),
+            None
+        );
+        assert_eq!(InventoryMovementKind::for_transition(Draft, Pending), None);
+    }
+
+    #[test]
+    fn movements_update_stock_levels() {
+        let level = StockLevel {
+            on_hand: 10,
+            reserved: 2,
+        };
+        assert_eq!(level.available(), 8);
+
+        let reserved = InventoryMovementKind::Reservation.apply(level, 3);
+        assert_eq!((reserved.on_hand, reserved.reserved), (10, 5));
+
+        let deducted = InventoryMovementKind::Deduction.apply(reserved, 3);
+        assert_eq!((deducted.on_hand, deducted.reserved), (7, 2));
+
+        let released = InventoryMovementKind::Release.apply(deducted, 2);
+        assert_eq!((released.on_hand, released.reserved), (7, 0));
+
+        let adjusted = InventoryMovementKind::Adjustment.apply(released, -4);
+        assert_eq!(adjusted.available(), 3);
+
+        let unbalanced = InventoryMovementKind::Release.apply(adjusted, 1);
+        assert_eq!(unbalanced.reserved, -1);
+    }
+}
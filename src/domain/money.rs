@@ -0,0 +1,281 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::domain::exchange_rate::EXCHANGE_RATE_SCALE;
+
+/// Currencies whose minor unit has no fraction digits (ISO 4217).
+pub const ZERO_DECIMAL_CURRENCIES: &[&str] = &[
+    "BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "UYI", "VND",
+    "VUV", "XAF", "XOF", "XPF",
+];
+
+/// Currencies whose minor unit has three fraction digits (ISO 4217).
+pub const THREE_DECIMAL_CURRENCIES: &[&str] = &["BHD", "IQD", "JOD", "KWD", "LYD", "OMR", "TND"];
+
+/// Number of fraction digits of the minor unit of `currency`.
+///
+/// Unknown codes use two digits, like most currencies.
+pub fn minor_units(currency: &str) -> u32 {
+    if ZERO_DECIMAL_CURRENCIES.contains(&currency) {
+        0
+    } else if THREE_DECIMAL_CURRENCIES.contains(&currency) {
+        3
+    } else {
+        2
+    }
+}
+
+/// Errors produced by [`Money`] parsing and arithmetic.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MoneyError {
+    /// The input is not a non-negative decimal with the currency's precision.
+    #[error("invalid amount `{value}` for {currency}")]
+    InvalidAmount { value: String, currency: String },
+    /// The operands are in different currencies.
+    #[error("cannot combine {left} with {right}")]
+    CurrencyMismatch { left: String, right: String },
+    /// The result does not fit in 64 bits.
+    #[error("amount is too large")]
+    Overflow,
+}
+
+/// Amount of money in the minor unit of its currency.
+///
+/// `amount` counts the smallest unit of `currency`: cents for `USD`, yen for
+/// `JPY`, fils for `KWD`. Arithmetic is checked and never mixes currencies.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Money {
+    /// Amount in the smallest unit of `currency`.
+    pub amount: i64,
+    /// ISO 4217 currency code.
+    pub currency: String,
+}
+
+impl Money {
+    /// Construct an amount of `currency`, upper-casing the code.
+    pub fn new(amount: i64, currency: impl Into<String>) -> Self {
+        Self {
+            amount,
+            currency: currency.into().trim().to_ascii_uppercase(),
+        }
+    }
+
+    /// Zero in `currency`.
+    pub fn zero(currency: impl Into<String>) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Parses a non-negative decimal such as `12,50` or `1 200` in `currency`.
+    ///
+    /// Up to [`minor_units`] fraction digits are accepted; more digits, signs
+    /// and other characters are rejected.
+    pub fn parse(input: &str, currency: impl Into<String>) -> Result<Self, MoneyError> {
+        let currency = currency.into().trim().to_ascii_uppercase();
+        let digits = minor_units(&currency);
+        let invalid = || MoneyError::InvalidAmount {
+            value: input.trim().to_string(),
+            currency: currency.clone(),
+        };
+
+        let normalized: String = input
+            .chars()
+            .filter(|ch| !ch.is_whitespace())
+            .map(|ch| if ch == ',' { '.' } else { ch })
+            .collect();
+        let (whole, fraction) = match normalized.split_once('.') {
+            Some((whole, fraction)) if !fraction.is_empty() => (whole, fraction),
+            Some(_) => return Err(invalid()),
+            None => (normalized.as_str(), ""),
+        };
+        if whole.is_empty()
+            || fraction.len() > digits as usize
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|ch| ch.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let whole = whole.parse::<i64>().map_err(|_| invalid())?;
+        let fraction = if fraction.is_empty() {
+            0
+        } else {
+            format!("{fraction:0<width$}", width = digits as usize)
+                .parse::<i64>()
+                .map_err(|_| invalid())?
+        };
+        let amount = whole
+            .checked_mul(10i64.pow(digits))
+            .and_then(|amount| amount.checked_add(fraction))
+            .ok_or(MoneyError::Overflow)?;
+
+        Ok(Self { amount, currency })
+    }
+
+    /// Sum of two amounts of the same currency.
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch {
+                left: self.currency.clone(),
+                right: other.currency.clone(),
+            });
+        }
+        let amount = self
+            .amount
+            .checked_add(other.amount)
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self::new(amount, self.currency.clone()))
+    }
+
+    /// Difference of two amounts of the same currency.
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.checked_add(&Self {
+            amount: other.amount.checked_neg().ok_or(MoneyError::Overflow)?,
+            currency: other.currency.clone(),
+        })
+    }
+
+    /// The amount multiplied by `quantity`, e.g. a unit price into a line total.
+    pub fn checked_mul(&self, quantity: i32) -> Result<Money, MoneyError> {
+        let amount = self
+            .amount
+            .checked_mul(i64::from(quantity))
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self::new(amount, self.currency.clone()))
+    }
+
+    /// The amount in `currency` at a rate in millionths of `currency` per unit
+    /// of this currency, rounding half away from zero to the target minor unit.
+    pub fn convert(&self, currency: &str, rate_micros: i64) -> Result<Money, MoneyError> {
+        let target = Self::zero(currency);
+        if target.currency == self.currency && rate_micros == EXCHANGE_RATE_SCALE {
+            return Ok(self.clone());
+        }
+
+        let numerator = i128::from(self.amount)
+            * i128::from(rate_micros)
+            * 10i128.pow(minor_units(&target.currency));
+        let denominator = i128::from(EXCHANGE_RATE_SCALE) * 10i128.pow(minor_units(&self.currency));
+        let half = denominator / 2;
+        let rounded = if numerator < 0 {
+            (numerator - half) / denominator
+        } else {
+            (numerator + half) / denominator
+        };
+        let amount = i64::try_from(rounded).map_err(|_| MoneyError::Overflow)?;
+
+        Ok(Self::new(amount, target.currency))
+    }
+
+    /// The amount as a decimal with the currency's precision, e.g. `12.50`.
+    pub fn format_amount(&self) -> String {
+        format_amount(self.amount, &self.currency)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.format_amount(), self.currency)
+    }
+}
+
+/// Formats `amount` minor units of `currency` as a decimal, e.g. `12.50`.
+pub fn format_amount(amount: i64, currency: &str) -> String {
+    let digits = minor_units(currency);
+    let sign = if amount < 0 { "-" } else { "" };
+    let abs = amount.unsigned_abs();
+    if digits == 0 {
+        return format!("{sign}{abs}");
+    }
+    let scale = 10u64.pow(digits);
+    format!(
+        "{sign}{}.{:0width$}",
+        abs / scale,
+        abs % scale,
+        width = digits as usize
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_follows_currency_precision() {
+        assert_eq!(Money::parse("12,5", "usd"), Ok(Money::new(1250, "USD")));
+        assert_eq!(Money::parse("1 200", "JPY"), Ok(Money::new(1200, "JPY")));
+        assert_eq!(Money::parse("1.234", "KWD"), Ok(Money::new(1234, "KWD")));
+        assert_eq!(
+            Money::parse("30000000.00", "RUB"),
+            Ok(Money::new(3_000_000_000, "RUB"))
+        );
+
+        for input in ["", "-1", "1.", ".5", "1.234", "12a"] {
+            assert!(matches!(
+                Money::parse(input, "USD"),
+                Err(MoneyError::InvalidAmount { .. })
+            ));
+        }
+        assert!(Money::parse("1.5", "JPY").is_err());
+        assert_eq!(
+            Money::parse("99999999999999999999", "USD"),
+            Err(MoneyError::InvalidAmount {
+                value: "99999999999999999999".to_string(),
+                currency: "USD".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn arithmetic_is_checked_and_single_currency() {
+        let price = Money::new(1999, "USD");
+
+        assert_eq!(price.checked_mul(3), Ok(Money::new(5997, "USD")));
+        assert_eq!(
+            price.checked_add(&Money::new(1, "USD")),
+            Ok(Money::new(2000, "USD"))
+        );
+        assert!(matches!(
+            price.checked_add(&Money::new(1, "EUR")),
+            Err(MoneyError::CurrencyMismatch { .. })
+        ));
+        assert_eq!(
+            price.checked_sub(&Money::new(2000, "USD")),
+            Ok(Money::new(-1, "USD"))
+        );
+        assert_eq!(
+            Money::new(i64::MAX, "USD").checked_mul(2),
+            Err(MoneyError::Overflow)
+        );
+    }
+
+    #[test]
+    fn convert_accounts_for_minor_units() {
+        let price = Money::new(1999, "USD");
+
+        assert_eq!(
+            price.convert("RUB", 80_000_000),
+            Ok(Money::new(159_920, "RUB"))
+        );
+        assert_eq!(
+            price.convert("JPY", 150_000_000),
+            Ok(Money::new(2_999, "JPY"))
+        );
+        assert_eq!(
+            Money::new(1000, "JPY").convert("USD", 6_667),
+            Ok(Money::new(667, "USD"))
+        );
+        assert_eq!(price.convert("USD", EXCHANGE_RATE_SCALE), Ok(price.clone()));
+    }
+
+    #[test]
+    fn formats_with_currency_precision() {
+        assert_eq!(Money::new(1250, "USD").to_string(), "12.50 USD");
+        assert_eq!(Money::new(1200, "JPY").format_amount(), "1200");
+        assert_eq!(Money::new(1234, "KWD").format_amount(), "1.234");
+        assert_eq!(format_amount(-5, "EUR"), "-0.05");
+    }
+}
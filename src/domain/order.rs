@@ -2,6 +2,12 @@ use chrono::NaiveDateTime;
 use pushkind_common::pagination::Pagination;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::exchange_rate::EXCHANGE_RATE_SCALE;
+use crate::domain::money::{Money, MoneyError};
+use crate::domain::price_level::PriceAdjustmentKind;
+use crate::domain::promotion::{Promotion, PromotionError};
+use crate::domain::tax_category::{TAX_RATE_SCALE, TaxAmounts};
+
 /// Possible lifecycle states for an order managed by a hub.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum OrderStatus {
@@ -18,6 +24,46 @@ pub enum OrderStatus {
     Cancelled,
 }
 
+impl OrderStatus {
+    /// Every status in lifecycle order.
+    pub const ALL: [OrderStatus; 5] = [
+        Self::Draft,
+        Self::Pending,
+        Self::Processing,
+        Self::Completed,
+        Self::Cancelled,
+    ];
+
+    /// Statuses the order may move to from the current one.
+    ///
+    /// Completed and cancelled orders are terminal.
+    pub fn allowed_transitions(self) -> &'static [OrderStatus] {
+        match self {
+            Self::Draft => &[Self::Pending, Self::Cancelled],
+            Self::Pending => &[Self::Draft, Self::Processing, Self::Cancelled],
+            Self::Processing => &[Self::Pending, Self::Completed, Self::Cancelled],
+            Self::Completed | Self::Cancelled => &[],
+        }
+    }
+
+    /// Returns `true` while the order lines may still be edited.
+    pub fn is_editable(self) -> bool {
+        matches!(self, Self::Draft | Self::Pending)
+    }
+
+    /// Returns `true` when the order may move from `self` to `next`.
+    pub fn can_transition_to(self, next: OrderStatus) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    /// Parse a stored status name, returning `None` for unknown values.
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|status| <&'static str>::from(*status) == value)
+    }
+}
+
 impl From<&str> for OrderStatus {
     fn from(value: &str) -> Self {
         match value {
@@ -64,18 +110,35 @@ pub struct Order {
     pub status: OrderStatus,
     /// Optional notes supplied by the operator.
     pub notes: Option<String>,
-    /// Total amount represented in the smallest currency unit (for example cents).
-    pub total_cents: i32,
-    /// ISO 4217 currency code used for the order total.
+    /// Total amount including tax.
+    pub total: Money,
+    /// Total amount before tax.
+    pub net: Money,
+    /// Tax included in `total`.
+    pub tax: Money,
+    /// ISO 4217 currency code of the order and of its amounts.
     pub currency: String,
     /// Product snapshots captured when the order was created.
     pub products: Vec<OrderProduct>,
+    /// Discount of a redeemed promotion code, subtracted from the line totals.
+    pub discount: Option<OrderDiscount>,
     /// Timestamp for when the order record was created.
     pub created_at: NaiveDateTime,
     /// Timestamp for the last update to the order record.
     pub updated_at: NaiveDateTime,
 }
 
+impl Order {
+    /// Net, tax and gross totals of the order.
+    pub fn amounts(&self) -> TaxAmounts {
+        TaxAmounts {
+            net: self.net.clone(),
+            tax: self.tax.clone(),
+            gross: self.total.clone(),
+        }
+    }
+}
+
 /// Payload required to insert a new order for a hub.
 #[derive(Debug, Clone)]
 pub struct NewOrder {
@@ -87,12 +150,18 @@ pub struct NewOrder {
     pub reference: Option<String>,
     /// Optional notes supplied by the operator.
     pub notes: Option<String>,
-    /// Total amount represented in the smallest currency unit (for example cents).
-    pub total_cents: i32,
-    /// ISO 4217 currency code used for the order total.
+    /// Total amount including tax.
+    pub total: Money,
+    /// Total amount before tax.
+    pub net: Money,
+    /// Tax included in `total`.
+    pub tax: Money,
+    /// ISO 4217 currency code of the order and of its amounts.
     pub currency: String,
     /// Product snapshots captured when the order was created.
     pub products: Vec<OrderProduct>,
+    /// Discount of a redeemed promotion code.
+    pub discount: Option<OrderDiscount>,
     /// Current lifecycle status of the order.
     pub status: OrderStatus,
 }
@@ -108,30 +177,46 @@ pub struct OrderProduct {
     pub sku: Option<String>,
     /// Description captured at the time of ordering.
     pub description: Option<String>,
-    /// Price represented in the smallest currency unit for the ordered quantity.
-    pub price_cents: i32,
-    /// ISO 4217 currency captured at the time of ordering.
-    pub currency: String,
+    /// Unit price in the currency of the product, captured at the time of
+    /// ordering.
+    pub price: Money,
     /// Quantity of the product ordered.
     pub quantity: i32,
+    /// Rate converting `currency` into the order currency, in millionths,
+    /// captured when the line was added.
+    pub exchange_rate_micros: i64,
+    /// Tax rate of the product in basis points, captured when the line was added.
+    pub tax_rate_basis_points: i32,
+    /// Whether `price` already includes tax.
+    pub prices_include_tax: bool,
+    /// Line total before tax in the order currency.
+    pub net: Money,
+    /// Tax of the line in the order currency.
+    pub tax: Money,
+    /// Line total including tax in the order currency.
+    pub gross: Money,
 }
 
 impl OrderProduct {
     /// Create a new ordered product snapshot using the supplied fields.
-    pub fn new(
-        name: impl Into<String>,
-        price_cents: i32,
-        currency: impl Into<String>,
-        quantity: i32,
-    ) -> Self {
+    ///
+    /// The line amounts stay zero until [`Self::calculate_amounts`] computes
+    /// them in the order currency.
+    pub fn new(name: impl Into<String>, price: Money, quantity: i32) -> Self {
+        let amounts = TaxAmounts::zero(price.currency.as_str());
         Self {
             product_id: None,
             name: name.into(),
             sku: None,
             description: None,
-            price_cents,
-            currency: currency.into(),
+            price,
             quantity,
+            exchange_rate_micros: EXCHANGE_RATE_SCALE,
+            tax_rate_basis_points: 0,
+            prices_include_tax: true,
+            net: amounts.net,
+            tax: amounts.tax,
+            gross: amounts.gross,
         }
     }
 
@@ -152,20 +237,68 @@ impl OrderProduct {
         self.description = Some(description.into());
         self
     }
+
+    /// Capture the rate converting the line currency into the order currency.
+    pub fn with_exchange_rate(mut self, rate_micros: i64) -> Self {
+        self.exchange_rate_micros = rate_micros;
+        self
+    }
+
+    /// Capture the tax rate of the product and whether its price includes tax.
+    pub fn with_tax(mut self, rate_basis_points: i32, prices_include_tax: bool) -> Self {
+        self.tax_rate_basis_points = rate_basis_points;
+        self.prices_include_tax = prices_include_tax;
+        self
+    }
+
+    /// Line total in the line currency.
+    pub fn total(&self) -> Result<Money, MoneyError> {
+        self.price.checked_mul(self.quantity)
+    }
+
+    /// Line total converted into `order_currency` with the captured rate.
+    pub fn total_in_order_currency(&self, order_currency: &str) -> Result<Money, MoneyError> {
+        self.total()?
+            .convert(order_currency, self.exchange_rate_micros)
+    }
+
+    /// Stored net, tax and gross amounts of the line in the order currency.
+    pub fn amounts(&self) -> TaxAmounts {
+        TaxAmounts {
+            net: self.net.clone(),
+            tax: self.tax.clone(),
+            gross: self.gross.clone(),
+        }
+    }
+
+    /// Recomputes the stored net, tax and gross amounts in `order_currency`
+    /// from the captured exchange and tax rates.
+    pub fn calculate_amounts(&mut self, order_currency: &str) -> Result<TaxAmounts, MoneyError> {
+        let total = self.total_in_order_currency(order_currency)?;
+        let amounts =
+            TaxAmounts::from_amount(&total, self.tax_rate_basis_points, self.prices_include_tax)?;
+        self.net = amounts.net.clone();
+        self.tax = amounts.tax.clone();
+        self.gross = amounts.gross.clone();
+        Ok(amounts)
+    }
 }
 
 impl NewOrder {
-    /// Build a new order payload with the supplied details and current timestamp.
-    pub fn new(hub_id: i32, total_cents: i32, currency: impl Into<String>) -> Self {
+    /// Build a new untaxed order payload totalling `total` in its currency.
+    pub fn new(hub_id: i32, total: Money) -> Self {
         Self {
             hub_id,
             customer_id: None,
             reference: None,
             notes: None,
-            total_cents,
-            currency: currency.into(),
+            net: total.clone(),
+            tax: Money::zero(total.currency.as_str()),
+            currency: total.currency.clone(),
+            total,
             status: OrderStatus::default(),
             products: Vec::new(),
+            discount: None,
         }
     }
 
@@ -187,6 +320,14 @@ impl NewOrder {
         self
     }
 
+    /// Set the gross, net and tax totals of the order.
+    pub fn with_amounts(mut self, amounts: TaxAmounts) -> Self {
+        self.total = amounts.gross;
+        self.net = amounts.net;
+        self.tax = amounts.tax;
+        self
+    }
+
     /// Override the default status for the new order.
     pub fn with_status(mut self, status: OrderStatus) -> Self {
         self.status = status;
@@ -198,17 +339,173 @@ impl NewOrder {
         self.products = products.into();
         self
     }
+
+    /// Attach the discount line of a redeemed promotion code.
+    pub fn with_discount(mut self, discount: OrderDiscount) -> Self {
+        self.discount = Some(discount);
+        self
+    }
+}
+
+/// Discount line of an order with the terms of the promotion captured when
+/// its code was redeemed.
+///
+/// The amounts are in the order currency and are subtracted from the line
+/// totals; the tax part is the share of tax in the discounted lines.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct OrderDiscount {
+    /// Identifier of the promotion, if it still exists.
+    pub promotion_id: Option<i32>,
+    /// Code of the promotion.
+    pub code: String,
+    /// Whether `value` is a percentage or a fixed amount.
+    pub kind: PriceAdjustmentKind,
+    /// Basis points for percentages, smallest units of `currency` for fixed
+    /// discounts.
+    pub value: i64,
+    /// Currency of the promotion, of fixed discounts and of the minimum order
+    /// amount.
+    pub currency: String,
+    /// Category whose subtree limits the discounted lines.
+    pub category_id: Option<i32>,
+    /// Tag limiting the discounted lines.
+    pub tag_id: Option<i32>,
+    /// Minimum order total, including tax and before the discount, in
+    /// `currency`.
+    pub min_order: Option<Money>,
+    /// Discount before tax.
+    pub net: Money,
+    /// Tax part of the discount.
+    pub tax: Money,
+    /// Discount including tax.
+    pub gross: Money,
+}
+
+impl OrderDiscount {
+    /// Capture the terms of `promotion` for an order in `order_currency`;
+    /// the amounts start at zero.
+    pub fn from_promotion(promotion: &Promotion, order_currency: &str) -> Self {
+        let amounts = TaxAmounts::zero(order_currency);
+        Self {
+            promotion_id: Some(promotion.id),
+            code: promotion.code.clone(),
+            kind: promotion.kind,
+            value: promotion.value,
+            currency: promotion.currency.clone(),
+            category_id: promotion.category_id,
+            tag_id: promotion.tag_id,
+            min_order: promotion
+                .min_order_cents
+                .map(|minimum| Money::new(minimum, promotion.currency.as_str())),
+            net: amounts.net,
+            tax: amounts.tax,
+            gross: amounts.gross,
+        }
+    }
+
+    /// Returns `true` when only some products are discounted.
+    pub fn is_restricted(&self) -> bool {
+        self.category_id.is_some() || self.tag_id.is_some()
+    }
+
+    /// Checks that an order totalling `order_total` before the discount
+    /// still reaches the captured minimum order amount, which is in the
+    /// promotion currency.
+    pub fn check_minimum(&self, order_total: &Money) -> Result<(), PromotionError> {
+        let Some(minimum) = &self.min_order else {
+            return Ok(());
+        };
+        if order_total.currency != minimum.currency {
+            return Err(PromotionError::CurrencyMismatch {
+                code: self.code.clone(),
+                currency: minimum.currency.clone(),
+            });
+        }
+        if order_total.amount < minimum.amount {
+            return Err(PromotionError::BelowMinimum {
+                code: self.code.clone(),
+                minimum: minimum.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Recomputes and stores the discount of the `eligible` line totals.
+    ///
+    /// Percentages are rounded half away from zero and fixed amounts are
+    /// capped at the eligible total, so the discount never exceeds it.
+    pub fn calculate_amounts(&mut self, eligible: &TaxAmounts) -> Result<TaxAmounts, MoneyError> {
+        let currency = eligible.gross.currency.as_str();
+        let eligible_gross = eligible.gross.amount.max(0);
+        let gross_cents = match self.kind {
+            PriceAdjustmentKind::Percent => {
+                let discount = rounded_div(
+                    i128::from(eligible_gross) * i128::from(self.value),
+                    i128::from(TAX_RATE_SCALE),
+                );
+                i64::try_from(discount).map_err(|_| MoneyError::Overflow)?
+            }
+            PriceAdjustmentKind::Fixed => self.value,
+        }
+        .clamp(0, eligible_gross);
+
+        let tax_cents = if eligible_gross == 0 {
+            0
+        } else {
+            let tax = rounded_div(
+                i128::from(gross_cents) * i128::from(eligible.tax.amount),
+                i128::from(eligible_gross),
+            );
+            i64::try_from(tax).map_err(|_| MoneyError::Overflow)?
+        };
+
+        self.gross = Money::new(gross_cents, currency);
+        self.tax = Money::new(tax_cents, currency);
+        self.net = self.gross.checked_sub(&self.tax)?;
+        Ok(self.amounts())
+    }
+
+    /// Net, tax and gross parts of the discount.
+    pub fn amounts(&self) -> TaxAmounts {
+        TaxAmounts {
+            net: self.net.clone(),
+            tax: self.tax.clone(),
+            gross: self.gross.clone(),
+        }
+    }
+}
+
+/// Divides rounding half away from zero; `denominator` must be positive.
+fn rounded_div(numerator: i128, denominator: i128) -> i128 {
+    let half = denominator / 2;
+    if numerator < 0 {
+        (numerator - half) / denominator
+    } else {
+        (numerator + half) / denominator
+    }
 }
 
 /// Patch data applied when updating an existing order.
+///
+/// The status is not part of the patch: it only changes through
+/// [`OrderWriter::transition_order_status`], which enforces the allowed
+/// transitions and records the history.
+///
+/// [`OrderWriter::transition_order_status`]: crate::repository::OrderWriter::transition_order_status
 #[derive(Debug, Clone)]
 pub struct UpdateOrder {
-    /// Status update.
-    pub status: OrderStatus,
+    /// Status the order must still have; edits of an order that moved to
+    /// another status in the meantime are rejected.
+    pub expected_status: OrderStatus,
     /// Optional notes update.
     pub notes: Option<String>,
-    /// Total amount update.
-    pub total_cents: i32,
+    /// Total amount update, including tax.
+    pub total: Money,
+    /// Total before tax update.
+    pub net: Money,
+    /// Tax total update.
+    pub tax: Money,
     /// Currency update.
     pub currency: String,
     /// Optional customer reference update.
@@ -217,6 +514,9 @@ pub struct UpdateOrder {
     pub reference: Option<String>,
     /// Optional product list update.
     pub products: Option<Vec<OrderProduct>>,
+    /// Discount line stored with the product list; ignored when `products`
+    /// is `None`.
+    pub discount: Option<OrderDiscount>,
     /// Timestamp captured when the patch was created.
     pub updated_at: NaiveDateTime,
 }
@@ -226,10 +526,20 @@ pub struct UpdateOrder {
 pub struct OrderListQuery {
     /// Owning hub identifier.
     pub hub_id: i32,
-    /// Optional status filter.
-    pub status: Option<OrderStatus>,
+    /// Statuses to include; empty matches every status.
+    pub statuses: Vec<OrderStatus>,
     /// Optional customer identifier filter.
     pub customer_id: Option<i32>,
+    /// Only include orders created at or after this moment.
+    pub created_from: Option<NaiveDateTime>,
+    /// Only include orders created strictly before this moment.
+    pub created_before: Option<NaiveDateTime>,
+    /// Only include orders in the currency of this amount whose total is at
+    /// least the amount.
+    pub min_total: Option<Money>,
+    /// Only include orders in the currency of this amount whose total is at
+    /// most the amount.
+    pub max_total: Option<Money>,
     /// Optional search term that matches the reference or notes.
     pub search: Option<String>,
     /// Optional pagination options applied to the query.
@@ -241,25 +551,62 @@ impl OrderListQuery {
     pub fn new(hub_id: i32) -> Self {
         Self {
             hub_id,
-            status: None,
+            statuses: Vec::new(),
             customer_id: None,
+            created_from: None,
+            created_before: None,
+            min_total: None,
+            max_total: None,
             search: None,
             pagination: None,
         }
     }
 
-    /// Filter the results by the provided status.
+    /// Add the provided status to the set of matching statuses.
     pub fn status(mut self, status: OrderStatus) -> Self {
-        self.status = Some(status);
+        if !self.statuses.contains(&status) {
+            self.statuses.push(status);
+        }
         self
     }
 
+    /// Filter the results by any of the provided statuses.
+    pub fn statuses(self, statuses: impl IntoIterator<Item = OrderStatus>) -> Self {
+        statuses.into_iter().fold(self, Self::status)
+    }
+
     /// Filter the results by customer identifier.
     pub fn customer_id(mut self, customer_id: i32) -> Self {
         self.customer_id = Some(customer_id);
         self
     }
 
+    /// Only include orders created at or after `from`.
+    pub fn created_from(mut self, from: NaiveDateTime) -> Self {
+        self.created_from = Some(from);
+        self
+    }
+
+    /// Only include orders created strictly before `before`.
+    pub fn created_before(mut self, before: NaiveDateTime) -> Self {
+        self.created_before = Some(before);
+        self
+    }
+
+    /// Only include orders in the currency of `amount` totalling at least
+    /// `amount`.
+    pub fn min_total(mut self, amount: Money) -> Self {
+        self.min_total = Some(amount);
+        self
+    }
+
+    /// Only include orders in the currency of `amount` totalling at most
+    /// `amount`.
+    pub fn max_total(mut self, amount: Money) -> Self {
+        self.max_total = Some(amount);
+        self
+    }
+
     /// Filter the results by a search term applied to notes or reference fields.
     pub fn search(mut self, term: impl Into<String>) -> Self {
         self.search = Some(term.into());
@@ -272,3 +619,94 @@ impl OrderListQuery {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discount(kind: PriceAdjustmentKind, value: i64) -> OrderDiscount {
+        let amounts = TaxAmounts::zero("USD");
+        OrderDiscount {
+            promotion_id: Some(1),
+            code: "SALE".to_string(),
+            kind,
+            value,
+            currency: "USD".to_string(),
+            category_id: None,
+            tag_id: None,
+            min_order: None,
+            net: amounts.net,
+            tax: amounts.tax,
+            gross: amounts.gross,
+        }
+    }
+
+    fn amounts(net: i64, tax: i64, gross: i64) -> TaxAmounts {
+        TaxAmounts {
+            net: Money::new(net, "USD"),
+            tax: Money::new(tax, "USD"),
+            gross: Money::new(gross, "USD"),
+        }
+    }
+
+    #[test]
+    fn percentage_discount_takes_its_share_of_tax() {
+        let mut sale = discount(PriceAdjustmentKind::Percent, 1_000);
+
+        assert_eq!(
+            sale.calculate_amounts(&amounts(10_000, 2_000, 12_000)),
+            Ok(amounts(1_000, 200, 1_200))
+        );
+        assert_eq!(sale.gross, Money::new(1_200, "USD"));
+    }
+
+    #[test]
+    fn minimum_is_checked_in_the_promotion_currency() {
+        let mut sale = discount(PriceAdjustmentKind::Percent, 1_000);
+        sale.min_order = Some(Money::new(5_000, "KWD"));
+
+        assert_eq!(
+            sale.check_minimum(&Money::new(4_999, "KWD")),
+            Err(PromotionError::BelowMinimum {
+                code: "SALE".to_string(),
+                minimum: "5.000 KWD".to_string(),
+            })
+        );
+        assert_eq!(sale.check_minimum(&Money::new(5_000, "KWD")), Ok(()));
+        assert_eq!(
+            sale.check_minimum(&Money::new(9_999, "USD")),
+            Err(PromotionError::CurrencyMismatch {
+                code: "SALE".to_string(),
+                currency: "KWD".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn fixed_discount_is_capped_at_eligible_total() {
+        let mut sale = discount(PriceAdjustmentKind::Fixed, 500);
+
+        assert_eq!(
+            sale.calculate_amounts(&amounts(300, 0, 300))
+                .map(|amounts| amounts.gross),
+            Ok(Money::new(300, "USD"))
+        );
+        assert_eq!(
+            sale.calculate_amounts(&TaxAmounts::zero("USD")),
+            Ok(TaxAmounts::zero("USD"))
+        );
+    }
+
+    #[test]
+    fn line_amounts_are_in_the_order_currency() {
+        let mut line = OrderProduct::new("Coffee", Money::new(1_999, "USD"), 2)
+            .with_exchange_rate(80_000_000)
+            .with_tax(2_000, true);
+
+        let amounts = line.calculate_amounts("RUB").expect("amounts");
+
+        assert_eq!(amounts.gross, Money::new(319_840, "RUB"));
+        assert_eq!(amounts.tax, Money::new(53_307, "RUB"));
+        assert_eq!(line.net, Money::new(266_533, "RUB"));
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::order::OrderStatus;
+
+/// Recorded change of an order's lifecycle status.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrderStatusChange {
+    /// Unique identifier of the history record.
+    pub id: i32,
+    /// Identifier of the order whose status changed.
+    pub order_id: i32,
+    /// Status the order had before the change.
+    pub from_status: OrderStatus,
+    /// Status the order moved to.
+    pub to_status: OrderStatus,
+    /// Email of the user who performed the change.
+    pub changed_by_email: String,
+    /// Display name of the user who performed the change.
+    pub changed_by_name: String,
+    /// Timestamp for when the change was recorded.
+    pub created_at: NaiveDateTime,
+}
+
+/// Payload required to record a status change for an order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewOrderStatusChange {
+    /// Identifier of the order whose status changes.
+    pub order_id: i32,
+    /// Status the order is expected to have before the change.
+    pub from_status: OrderStatus,
+    /// Status the order moves to.
+    pub to_status: OrderStatus,
+    /// Email of the user performing the change.
+    pub changed_by_email: String,
+    /// Display name of the user performing the change.
+    pub changed_by_name: String,
+}
+
+impl NewOrderStatusChange {
+    /// Construct a status change payload for the given order.
+    pub fn new(
+        order_id: i32,
+        from_status: OrderStatus,
+        to_status: OrderStatus,
+        changed_by_email: impl Into<String>,
+        changed_by_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            order_id,
+            from_status,
+            to_status,
+            changed_by_email: changed_by_email.into(),
+            changed_by_name: changed_by_name.into(),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::sync::OnceLock;
+
+/// Numbering plan used to read phones written without an international prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhoneCountry {
+    /// ISO 3166-1 alpha-2 code of the country.
+    pub code: &'static str,
+    /// Country calling code without the leading `+`.
+    pub calling_code: &'static str,
+    /// Number of digits in a national significant number.
+    pub national_len: usize,
+    /// Trunk prefix dialled before national numbers, e.g. `8` in Russia.
+    pub trunk_prefix: &'static str,
+}
+
+impl PhoneCountry {
+    /// Russia, the default when no other country is configured.
+    pub const RU: PhoneCountry = PhoneCountry {
+        code: "RU",
+        calling_code: "7",
+        national_len: 10,
+        trunk_prefix: "8",
+    };
+
+    /// Countries that can be configured as the default.
+    pub const SUPPORTED: [PhoneCountry; 6] = [
+        Self::RU,
+        PhoneCountry {
+            code: "KZ",
+            calling_code: "7",
+            national_len: 10,
+            trunk_prefix: "8",
+        },
+        PhoneCountry {
+            code: "BY",
+            calling_code: "375",
+            national_len: 9,
+            trunk_prefix: "80",
+        },
+        PhoneCountry {
+            code: "UA",
+            calling_code: "380",
+            national_len: 9,
+            trunk_prefix: "0",
+        },
+        PhoneCountry {
+            code: "GB",
+            calling_code: "44",
+            national_len: 10,
+            trunk_prefix: "0",
+        },
+        PhoneCountry {
+            code: "US",
+            calling_code: "1",
+            national_len: 10,
+            trunk_prefix: "1",
+        },
+    ];
+
+    /// Look up a supported country by its ISO code, ignoring case.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim();
+        Self::SUPPORTED
+            .into_iter()
+            .find(|country| country.code.eq_ignore_ascii_case(code))
+    }
+}
+
+impl Default for PhoneCountry {
+    fn default() -> Self {
+        Self::RU
+    }
+}
+
+static DEFAULT_COUNTRY: OnceLock<PhoneCountry> = OnceLock::new();
+
+/// Configure the country applied to national phone numbers.
+///
+/// Only the first call takes effect; returns `false` when a country was
+/// already configured.
+pub fn set_default_country(country: PhoneCountry) -> bool {
+    DEFAULT_COUNTRY.set(country).is_ok()
+}
+
+/// Country applied to national phone numbers, [`PhoneCountry::RU`] unless configured.
+pub fn default_country() -> PhoneCountry {
+    DEFAULT_COUNTRY.get().copied().unwrap_or_default()
+}
+
+/// Normalise a phone to E.164 (`+79001234567`) using the default country.
+///
+/// Returns `None` when the input is not a recognisable phone number.
+pub fn normalize_phone(input: &str) -> Option<String> {
+    normalize_phone_for(input, default_country())
+}
+
+/// Normalise a phone to E.164, reading national numbers with `country`'s plan.
+///
+/// Spaces, dashes, dots, slashes and parentheses are ignored. Numbers starting
+/// with `+` or `00` are taken as international; others must be a national
+/// number, optionally preceded by the trunk prefix or the calling code.
+pub fn normalize_phone_for(input: &str, country: PhoneCountry) -> Option<String> {
+    let trimmed = input.trim();
+    let (international, rest) = match trimmed.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+
+    let mut digits = String::with_capacity(rest.len());
+    for ch in rest.chars() {
+        match ch {
+            '0'..='9' => digits.push(ch),
+            ' ' | '-' | '.' | '/' | '(' | ')' | '\u{a0}' => {}
+            _ => return None,
+        }
+    }
+
+    let international_digits = if international {
+        Some(digits.as_str())
+    } else {
+        digits.strip_prefix("00")
+    };
+    if let Some(digits) = international_digits {
+        return (7..=15)
+            .contains(&digits.len())
+            .then(|| format!("+{digits}"));
+    }
+
+    let national = if digits.len() == country.national_len {
+        digits.as_str()
+    } else {
+        [country.trunk_prefix, country.calling_code]
+            .into_iter()
+            .filter(|prefix| !prefix.is_empty())
+            .find_map(|prefix| {
+                digits
+                    .strip_prefix(prefix)
+                    .filter(|national| national.len() == country.national_len)
+            })?
+    };
+
+    Some(format!("+{}{national}", country.calling_code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn russian_numbers_share_one_form() {
+        for input in [
+            "+7 (900) 123-45-67",
+            "89001234567",
+            "8 900 123 45 67",
+            "79001234567",
+            "9001234567",
+            "007 900 1234567",
+        ] {
+            assert_eq!(
+                normalize_phone_for(input, PhoneCountry::RU).as_deref(),
+                Some("+79001234567"),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn national_numbers_follow_the_given_country() {
+        let belarus = PhoneCountry::from_code("by").expect("supported country");
+
+        assert_eq!(
+            normalize_phone_for("80 29 123-45-67", belarus).as_deref(),
+            Some("+375291234567")
+        );
+        assert_eq!(
+            normalize_phone_for("+44 20 7946 0958", belarus).as_deref(),
+            Some("+442079460958")
+        );
+    }
+
+    #[test]
+    fn rejects_text_and_implausible_lengths() {
+        assert_eq!(normalize_phone_for("call me", PhoneCountry::RU), None);
+        assert_eq!(normalize_phone_for("12345", PhoneCountry::RU), None);
+        assert_eq!(normalize_phone_for("+1", PhoneCountry::RU), None);
+        assert_eq!(normalize_phone_for("", PhoneCountry::RU), None);
+    }
+}
@@ -2,6 +2,8 @@ use chrono::NaiveDateTime;
 use pushkind_common::pagination::Pagination;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::money::{Money, minor_units};
+
 /// Domain representation of a configurable price level.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PriceLevel {
@@ -18,6 +20,205 @@ pub struct PriceLevel {
     /// A flag indicating if the price level is default.
     /// Only one price level can be default at a time for a hub.
     pub is_default: bool,
+    /// Rule computing prices from another level; `None` for explicit-only levels.
+    pub derivation: Option<PriceDerivation>,
+    /// Whether the level's prices already include tax.
+    pub prices_include_tax: bool,
+}
+
+/// How a derived level adjusts the price of its base level.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriceAdjustmentKind {
+    /// Value is a percentage in basis points, e.g. `-1500` for −15%.
+    #[default]
+    Percent,
+    /// Value is an amount in the smallest currency unit added to the base price.
+    Fixed,
+}
+
+impl From<&str> for PriceAdjustmentKind {
+    fn from(value: &str) -> Self {
+        match value {
+            "Fixed" => Self::Fixed,
+            _ => Self::Percent,
+        }
+    }
+}
+
+impl From<PriceAdjustmentKind> for &'static str {
+    fn from(value: PriceAdjustmentKind) -> Self {
+        match value {
+            PriceAdjustmentKind::Percent => "Percent",
+            PriceAdjustmentKind::Fixed => "Fixed",
+        }
+    }
+}
+
+/// Rounding applied to a derived price after the adjustment.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriceRounding {
+    /// Keep the price to the minor unit.
+    #[default]
+    None,
+    /// Round to whole currency units.
+    Whole,
+    /// Round to tens of currency units.
+    Tens,
+    /// Round to whole currency units, then drop a hundredth of a unit
+    /// (`12.99`); prices in currencies without a minor unit are rounded to
+    /// hundreds and drop one unit (`1299`).
+    NinetyNine,
+}
+
+impl From<&str> for PriceRounding {
+    fn from(value: &str) -> Self {
+        match value {
+            "Whole" => Self::Whole,
+            "Tens" => Self::Tens,
+            "NinetyNine" => Self::NinetyNine,
+            _ => Self::None,
+        }
+    }
+}
+
+impl From<PriceRounding> for &'static str {
+    fn from(value: PriceRounding) -> Self {
+        match value {
+            PriceRounding::None => "None",
+            PriceRounding::Whole => "Whole",
+            PriceRounding::Tens => "Tens",
+            PriceRounding::NinetyNine => "NinetyNine",
+        }
+    }
+}
+
+impl PriceRounding {
+    /// Round `price` half away from zero according to the rule, in units of
+    /// its currency.
+    pub fn apply(self, price: &Money) -> Money {
+        let cents = price.amount;
+        let unit = 10_i64.pow(minor_units(&price.currency));
+        let round_to = |step: i64| cents.saturating_add(step / 2).div_euclid(step) * step;
+        let amount = match self {
+            Self::None => cents,
+            Self::Whole => round_to(unit),
+            Self::Tens => round_to(unit * 10),
+            Self::NinetyNine => {
+                let step = unit.max(100);
+                (round_to(step) - step / 100).max(0)
+            }
+        };
+        Money::new(amount, price.currency.as_str())
+    }
+}
+
+/// Rule computing a level's prices from another level of the same hub.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PriceDerivation {
+    /// Level whose price the adjustment is applied to.
+    pub base_price_level_id: i32,
+    /// Whether `adjustment_value` is a percentage or a fixed amount.
+    pub adjustment_kind: PriceAdjustmentKind,
+    /// Basis points for percentages, smallest currency units for fixed markups.
+    pub adjustment_value: i64,
+    /// Rounding applied to the adjusted price.
+    pub rounding: PriceRounding,
+}
+
+impl PriceDerivation {
+    /// Construct a rule without adjustment or rounding on top of `base_price_level_id`.
+    pub fn new(base_price_level_id: i32) -> Self {
+        Self {
+            base_price_level_id,
+            adjustment_kind: PriceAdjustmentKind::Percent,
+            adjustment_value: 0,
+            rounding: PriceRounding::None,
+        }
+    }
+
+    /// Adjust the base price by a percentage given in basis points.
+    pub fn with_percent(mut self, basis_points: i64) -> Self {
+        self.adjustment_kind = PriceAdjustmentKind::Percent;
+        self.adjustment_value = basis_points;
+        self
+    }
+
+    /// Add a fixed amount in the smallest currency unit to the base price.
+    pub fn with_fixed(mut self, cents: i64) -> Self {
+        self.adjustment_kind = PriceAdjustmentKind::Fixed;
+        self.adjustment_value = cents;
+        self
+    }
+
+    /// Round the adjusted price with the given rule.
+    pub fn with_rounding(mut self, rounding: PriceRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Compute the derived price from the base level's price, see
+    /// [`adjust_price`].
+    pub fn apply(&self, base: &Money) -> Money {
+        adjust_price(
+            base,
+            self.adjustment_kind,
+            self.adjustment_value,
+            self.rounding,
+        )
+    }
+}
+
+/// Adjust `price` by a percentage in basis points or a fixed amount in its
+/// minor unit, then apply `rounding`.
+///
+/// Percentages round to the nearest minor unit before the rounding rule is
+/// applied. The result never drops below zero.
+pub fn adjust_price(
+    price: &Money,
+    adjustment_kind: PriceAdjustmentKind,
+    adjustment_value: i64,
+    rounding: PriceRounding,
+) -> Money {
+    let cents = price.amount;
+    let adjusted = match adjustment_kind {
+        PriceAdjustmentKind::Percent => {
+            let scaled = i128::from(cents) * (10_000 + i128::from(adjustment_value));
+            i64::try_from((scaled + 5_000).div_euclid(10_000)).unwrap_or(i64::MAX)
+        }
+        PriceAdjustmentKind::Fixed => cents.saturating_add(adjustment_value),
+    };
+    let rounded = rounding.apply(&Money::new(adjusted, price.currency.as_str()));
+    Money::new(rounded.amount.max(0), rounded.currency)
+}
+
+/// Whether deriving `price_level_id` from `base_price_level_id` would make the
+/// level depend on itself, directly or through other derived levels.
+///
+/// `price_level_id` is `None` for a level that does not exist yet.
+pub fn derivation_forms_cycle(
+    price_levels: &[PriceLevel],
+    price_level_id: Option<i32>,
+    base_price_level_id: i32,
+) -> bool {
+    let Some(price_level_id) = price_level_id else {
+        return false;
+    };
+
+    let mut current = base_price_level_id;
+    for _ in 0..=price_levels.len() {
+        if current == price_level_id {
+            return true;
+        }
+        let next = price_levels
+            .iter()
+            .find(|level| level.id == current)
+            .and_then(|level| level.derivation.as_ref());
+        match next {
+            Some(derivation) => current = derivation.base_price_level_id,
+            None => return false,
+        }
+    }
+    true
 }
 
 /// Payload required to insert a new price level for a hub.
@@ -29,6 +230,10 @@ pub struct NewPriceLevel {
     pub name: String,
     /// Default flag for the price level.
     pub is_default: bool,
+    /// Optional rule computing prices from another level.
+    pub derivation: Option<PriceDerivation>,
+    /// Whether the level's prices already include tax.
+    pub prices_include_tax: bool,
 }
 
 impl NewPriceLevel {
@@ -39,8 +244,22 @@ impl NewPriceLevel {
             hub_id,
             name,
             is_default,
+            derivation: None,
+            prices_include_tax: true,
         }
     }
+
+    /// Compute the level's prices from another level.
+    pub fn with_derivation(mut self, derivation: PriceDerivation) -> Self {
+        self.derivation = Some(derivation);
+        self
+    }
+
+    /// Set whether the level's prices include tax.
+    pub fn with_prices_include_tax(mut self, prices_include_tax: bool) -> Self {
+        self.prices_include_tax = prices_include_tax;
+        self
+    }
 }
 
 /// Patch data applied when updating an existing price level.
@@ -52,6 +271,10 @@ pub struct UpdatePriceLevel {
     pub updated_at: NaiveDateTime,
     /// Default flag update for the price level.
     pub is_default: bool,
+    /// Derivation rule update; `None` makes the level explicit-only.
+    pub derivation: Option<PriceDerivation>,
+    /// Whether the level's prices already include tax.
+    pub prices_include_tax: bool,
 }
 
 impl UpdatePriceLevel {
@@ -63,8 +286,22 @@ impl UpdatePriceLevel {
             name,
             updated_at,
             is_default,
+            derivation: None,
+            prices_include_tax: true,
         }
     }
+
+    /// Compute the level's prices from another level.
+    pub fn with_derivation(mut self, derivation: PriceDerivation) -> Self {
+        self.derivation = Some(derivation);
+        self
+    }
+
+    /// Set whether the level's prices include tax.
+    pub fn with_prices_include_tax(mut self, prices_include_tax: bool) -> Self {
+        self.prices_include_tax = prices_include_tax;
+        self
+    }
 }
 
 /// Query definition used to list price levels for a hub.
@@ -100,3 +337,112 @@ impl PriceLevelListQuery {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn money(amount: i64, currency: &str) -> Money {
+        Money::new(amount, currency)
+    }
+
+    fn level(id: i32, derivation: Option<PriceDerivation>) -> PriceLevel {
+        let timestamp = NaiveDate::from_ymd_opt(2025, 11, 7)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .expect("valid timestamp");
+        PriceLevel {
+            id,
+            hub_id: 1,
+            name: format!("Level {id}"),
+            created_at: timestamp,
+            updated_at: timestamp,
+            is_default: id == 1,
+            derivation,
+            prices_include_tax: true,
+        }
+    }
+
+    #[test]
+    fn derivation_applies_adjustment_then_rounding() {
+        let wholesale = PriceDerivation::new(1).with_percent(-1500);
+        assert_eq!(wholesale.apply(&money(1000, "USD")).amount, 850);
+        assert_eq!(wholesale.apply(&money(999, "USD")).amount, 849);
+
+        let rounded = wholesale.with_rounding(PriceRounding::Whole);
+        assert_eq!(rounded.apply(&money(1999, "USD")).amount, 1700);
+
+        let charm = PriceDerivation::new(1)
+            .with_fixed(250)
+            .with_rounding(PriceRounding::NinetyNine);
+        assert_eq!(charm.apply(&money(1000, "USD")).amount, 1299);
+
+        let tens = PriceDerivation::new(1)
+            .with_percent(2000)
+            .with_rounding(PriceRounding::Tens);
+        assert_eq!(tens.apply(&money(12_345, "USD")).amount, 15_000);
+
+        assert_eq!(
+            PriceDerivation::new(1)
+                .with_fixed(-5000)
+                .apply(&money(1000, "USD"))
+                .amount,
+            0
+        );
+        assert_eq!(
+            PriceDerivation::new(1)
+                .with_fixed(5_000_000_000)
+                .apply(&money(0, "USD"))
+                .amount,
+            5_000_000_000
+        );
+    }
+
+    #[test]
+    fn rounding_follows_the_minor_unit_of_the_currency() {
+        assert_eq!(
+            PriceRounding::Whole.apply(&money(1_249, "JPY")).amount,
+            1_249
+        );
+        assert_eq!(
+            PriceRounding::Whole.apply(&money(12_499, "KWD")).amount,
+            12_000
+        );
+        assert_eq!(
+            PriceRounding::Tens.apply(&money(1_249, "JPY")).amount,
+            1_250
+        );
+        assert_eq!(
+            PriceRounding::Tens.apply(&money(12_499, "KWD")).amount,
+            10_000
+        );
+        assert_eq!(
+            PriceRounding::NinetyNine.apply(&money(1_249, "JPY")).amount,
+            1_199
+        );
+        assert_eq!(
+            PriceRounding::NinetyNine
+                .apply(&money(12_499, "KWD"))
+                .amount,
+            11_990
+        );
+        assert_eq!(
+            PriceRounding::NinetyNine.apply(&money(1_249, "RUB")).amount,
+            1_199
+        );
+    }
+
+    #[test]
+    fn derivation_cycles_are_detected() {
+        let levels = vec![
+            level(1, None),
+            level(2, Some(PriceDerivation::new(1))),
+            level(3, Some(PriceDerivation::new(2))),
+        ];
+
+        assert!(!derivation_forms_cycle(&levels, None, 3));
+        assert!(!derivation_forms_cycle(&levels, Some(3), 1));
+        assert!(derivation_forms_cycle(&levels, Some(1), 1));
+        assert!(derivation_forms_cycle(&levels, Some(1), 3));
+    }
+}
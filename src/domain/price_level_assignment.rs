@@ -0,0 +1,169 @@
+use chrono::NaiveDateTime;
+use pushkind_common::pagination::Pagination;
+use serde::{Deserialize, Serialize};
+
+/// Review state of a client price level assignment request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriceLevelAssignmentStatus {
+    /// Request awaits a decision from an approver.
+    #[default]
+    Pending,
+    /// Request was approved and applied to the customer.
+    Approved,
+    /// Request was rejected and left the customer unchanged.
+    Rejected,
+}
+
+impl From<&str> for PriceLevelAssignmentStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "Approved" => Self::Approved,
+            "Rejected" => Self::Rejected,
+            _ => Self::Pending,
+        }
+    }
+}
+
+impl From<PriceLevelAssignmentStatus> for &'static str {
+    fn from(value: PriceLevelAssignmentStatus) -> Self {
+        match value {
+            PriceLevelAssignmentStatus::Pending => "Pending",
+            PriceLevelAssignmentStatus::Approved => "Approved",
+            PriceLevelAssignmentStatus::Rejected => "Rejected",
+        }
+    }
+}
+
+/// Requested change of the price level assigned to a customer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PriceLevelAssignmentRequest {
+    /// Unique identifier of the request.
+    pub id: i32,
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// Customer whose price level should change.
+    pub customer_id: i32,
+    /// Requested price level; `None` resets the customer to the hub default.
+    pub price_level_id: Option<i32>,
+    /// Current review state.
+    pub status: PriceLevelAssignmentStatus,
+    /// Email of the user who requested the change.
+    pub requested_by_email: String,
+    /// Display name of the user who requested the change.
+    pub requested_by_name: String,
+    /// Email of the approver who decided on the request.
+    pub decided_by_email: Option<String>,
+    /// Display name of the approver who decided on the request.
+    pub decided_by_name: Option<String>,
+    /// Timestamp for when the request was created.
+    pub created_at: NaiveDateTime,
+    /// Timestamp for when the request was approved or rejected.
+    pub decided_at: Option<NaiveDateTime>,
+}
+
+/// Payload required to file a new assignment request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewPriceLevelAssignmentRequest {
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// Customer whose price level should change.
+    pub customer_id: i32,
+    /// Requested price level; `None` resets the customer to the hub default.
+    pub price_level_id: Option<i32>,
+    /// Email of the user requesting the change.
+    pub requested_by_email: String,
+    /// Display name of the user requesting the change.
+    pub requested_by_name: String,
+}
+
+impl NewPriceLevelAssignmentRequest {
+    /// Construct a pending request for the given customer.
+    pub fn new(
+        hub_id: i32,
+        customer_id: i32,
+        price_level_id: Option<i32>,
+        requested_by_email: impl Into<String>,
+        requested_by_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            hub_id,
+            customer_id,
+            price_level_id,
+            requested_by_email: requested_by_email.into(),
+            requested_by_name: requested_by_name.into(),
+        }
+    }
+}
+
+/// Approver decision applied to a pending request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceLevelAssignmentDecision {
+    /// Identifier of the pending request.
+    pub request_id: i32,
+    /// Whether the requested price level should be applied.
+    pub approve: bool,
+    /// Email of the approver.
+    pub decided_by_email: String,
+    /// Display name of the approver.
+    pub decided_by_name: String,
+}
+
+impl PriceLevelAssignmentDecision {
+    /// Construct a decision for the given request.
+    pub fn new(
+        request_id: i32,
+        approve: bool,
+        decided_by_email: impl Into<String>,
+        decided_by_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            request_id,
+            approve,
+            decided_by_email: decided_by_email.into(),
+            decided_by_name: decided_by_name.into(),
+        }
+    }
+
+    /// Status the request ends up with.
+    pub fn status(&self) -> PriceLevelAssignmentStatus {
+        if self.approve {
+            PriceLevelAssignmentStatus::Approved
+        } else {
+            PriceLevelAssignmentStatus::Rejected
+        }
+    }
+}
+
+/// Query definition used to list assignment requests for a hub.
+#[derive(Debug, Clone)]
+pub struct PriceLevelAssignmentListQuery {
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// Optional status filter.
+    pub status: Option<PriceLevelAssignmentStatus>,
+    /// Optional pagination options applied to the query.
+    pub pagination: Option<Pagination>,
+}
+
+impl PriceLevelAssignmentListQuery {
+    /// Construct a query that targets all requests belonging to `hub_id`.
+    pub fn new(hub_id: i32) -> Self {
+        Self {
+            hub_id,
+            status: None,
+            pagination: None,
+        }
+    }
+
+    /// Filter the results by review status.
+    pub fn status(mut self, status: PriceLevelAssignmentStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Apply pagination to the query with the given page number and page size.
+    pub fn paginate(mut self, page: usize, per_page: usize) -> Self {
+        self.pagination = Some(Pagination { page, per_page });
+        self
+    }
+}
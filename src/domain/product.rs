@@ -2,7 +2,15 @@ use chrono::NaiveDateTime;
 use pushkind_common::pagination::Pagination;
 use serde::{Deserialize, Serialize};
 
-use crate::domain::{product_price_level::ProductPriceLevelRate, tag::Tag};
+use crate::domain::{
+    inventory_movement::StockLevel,
+    money::Money,
+    price_level::{PriceDerivation, PriceLevel},
+    product_price_level::{
+        NewProductPriceLevelRate, ProductPriceLevelRate, rate_at, rate_for_quantity,
+    },
+    tag::Tag,
+};
 
 /// Domain representation of a product that can be managed by a hub.
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -25,6 +33,12 @@ pub struct Product {
     pub is_archived: bool,
     /// Optional identifier of the category the product belongs to.
     pub category_id: Option<i32>,
+    /// Optional identifier of the tax category applied to the product.
+    pub tax_category_id: Option<i32>,
+    /// Units on hand and reserved for processing orders.
+    pub stock: StockLevel,
+    /// Whether orders may be placed for more units than are available.
+    pub allow_oversell: bool,
     /// Price level rates configured for the product.
     pub price_levels: Vec<ProductPriceLevelRate>,
     /// Tags associated with the product.
@@ -35,6 +49,136 @@ pub struct Product {
     pub updated_at: NaiveDateTime,
 }
 
+impl Product {
+    /// Rate of `price_level_id` in effect at `at`, honouring scheduled rates.
+    pub fn rate_at(
+        &self,
+        price_level_id: i32,
+        at: NaiveDateTime,
+    ) -> Option<&ProductPriceLevelRate> {
+        rate_at(&self.price_levels, price_level_id, at)
+    }
+
+    /// Rate of `price_level_id` in effect at `at` for `quantity` units,
+    /// honouring quantity breaks and scheduled rates.
+    pub fn rate_for_quantity(
+        &self,
+        price_level_id: i32,
+        at: NaiveDateTime,
+        quantity: i32,
+    ) -> Option<&ProductPriceLevelRate> {
+        rate_for_quantity(&self.price_levels, price_level_id, at, quantity)
+    }
+
+    /// Base rates of the product, i.e. those without a validity window,
+    /// including quantity breaks.
+    pub fn base_rates(&self) -> impl Iterator<Item = &ProductPriceLevelRate> {
+        self.price_levels.iter().filter(|rate| !rate.is_scheduled())
+    }
+
+    /// Quantity breaks of the product: base rates for more than one unit.
+    pub fn quantity_breaks(&self) -> impl Iterator<Item = &ProductPriceLevelRate> {
+        self.base_rates().filter(|rate| rate.min_quantity > 1)
+    }
+
+    /// Unit price at `price_level_id` in effect at `at`.
+    ///
+    /// An explicit rate wins; otherwise the price is computed through the
+    /// level's derivation from the nearest base level with a rate.
+    pub fn price_at(
+        &self,
+        price_levels: &[PriceLevel],
+        price_level_id: i32,
+        at: NaiveDateTime,
+    ) -> Option<ProductPrice> {
+        self.price_for_quantity(price_levels, price_level_id, at, 1)
+    }
+
+    /// Unit price at `price_level_id` in effect at `at` for an order line of
+    /// `quantity` units, see [`Self::price_at`].
+    ///
+    /// Derived levels follow the quantity breaks of their base level.
+    pub fn price_for_quantity(
+        &self,
+        price_levels: &[PriceLevel],
+        price_level_id: i32,
+        at: NaiveDateTime,
+        quantity: i32,
+    ) -> Option<ProductPrice> {
+        self.resolve_price(price_levels, price_level_id, |level_id| {
+            self.rate_for_quantity(level_id, at, quantity)
+        })
+    }
+
+    /// Unit price at `price_level_id` from the base rates alone, ignoring
+    /// scheduled rates, see [`Self::price_at`].
+    pub fn base_price(
+        &self,
+        price_levels: &[PriceLevel],
+        price_level_id: i32,
+    ) -> Option<ProductPrice> {
+        self.resolve_price(price_levels, price_level_id, |level_id| {
+            self.base_rates()
+                .find(|rate| rate.price_level_id == level_id && rate.min_quantity == 1)
+        })
+    }
+
+    /// Follows the derivation chain of `price_level_id` until `rate_of` finds
+    /// a rate and applies the collected derivations to it.
+    fn resolve_price<'a>(
+        &'a self,
+        price_levels: &[PriceLevel],
+        price_level_id: i32,
+        rate_of: impl Fn(i32) -> Option<&'a ProductPriceLevelRate>,
+    ) -> Option<ProductPrice> {
+        let mut derivations: Vec<&PriceDerivation> = Vec::new();
+        let mut current = price_level_id;
+        // A well-formed chain visits every level at most once.
+        for _ in 0..=price_levels.len() {
+            if let Some(rate) = rate_of(current) {
+                let price = derivations
+                    .iter()
+                    .rev()
+                    .fold(rate.price.clone(), |price, derivation| {
+                        derivation.apply(&price)
+                    });
+                return Some(ProductPrice {
+                    price_level_id,
+                    price,
+                    is_derived: !derivations.is_empty(),
+                });
+            }
+            let derivation = price_levels
+                .iter()
+                .find(|level| level.id == current)?
+                .derivation
+                .as_ref()?;
+            derivations.push(derivation);
+            current = derivation.base_price_level_id;
+        }
+        None
+    }
+
+    /// Prices in effect at `at` for every level in `price_levels` that has one.
+    pub fn prices_at(&self, price_levels: &[PriceLevel], at: NaiveDateTime) -> Vec<ProductPrice> {
+        price_levels
+            .iter()
+            .filter_map(|level| self.price_at(price_levels, level.id, at))
+            .collect()
+    }
+}
+
+/// Price of a product at a level, either explicit or derived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductPrice {
+    /// Level the price applies to.
+    pub price_level_id: i32,
+    /// Price in the currency of the product.
+    pub price: Money,
+    /// Whether the price was computed from another level.
+    pub is_derived: bool,
+}
+
 /// Payload required to insert a new product for a hub.
 #[derive(Debug, Clone)]
 pub struct NewProduct {
@@ -52,6 +196,10 @@ pub struct NewProduct {
     pub currency: String,
     /// Optional identifier of the category the product belongs to.
     pub category_id: Option<i32>,
+    /// Optional identifier of the tax category applied to the product.
+    pub tax_category_id: Option<i32>,
+    /// Whether orders may be placed for more units than are available.
+    pub allow_oversell: bool,
 }
 
 impl NewProduct {
@@ -67,6 +215,8 @@ impl NewProduct {
             units: None,
             currency,
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: true,
         }
     }
 
@@ -93,6 +243,18 @@ impl NewProduct {
         self.category_id = Some(category_id);
         self
     }
+
+    /// Tax the product at the rate of a tax category.
+    pub fn with_tax_category_id(mut self, tax_category_id: i32) -> Self {
+        self.tax_category_id = Some(tax_category_id);
+        self
+    }
+
+    /// Set whether orders may exceed the available stock.
+    pub fn with_allow_oversell(mut self, allow_oversell: bool) -> Self {
+        self.allow_oversell = allow_oversell;
+        self
+    }
 }
 
 /// Patch data applied when updating an existing product.
@@ -112,6 +274,10 @@ pub struct UpdateProduct {
     pub is_archived: bool,
     /// Optional identifier of the category the product belongs to.
     pub category_id: Option<i32>,
+    /// Optional identifier of the tax category applied to the product.
+    pub tax_category_id: Option<i32>,
+    /// Whether orders may be placed for more units than are available.
+    pub allow_oversell: bool,
     /// Timestamp captured when the patch was created.
     pub updated_at: NaiveDateTime,
 }
@@ -130,6 +296,8 @@ impl UpdateProduct {
             currency,
             is_archived: false,
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: true,
             updated_at: now,
         }
     }
@@ -158,6 +326,18 @@ impl UpdateProduct {
         self
     }
 
+    /// Tax the product at the rate of a tax category.
+    pub fn with_tax_category_id(mut self, tax_category_id: i32) -> Self {
+        self.tax_category_id = Some(tax_category_id);
+        self
+    }
+
+    /// Set whether orders may exceed the available stock.
+    pub fn with_allow_oversell(mut self, allow_oversell: bool) -> Self {
+        self.allow_oversell = allow_oversell;
+        self
+    }
+
     /// Mark the product as archived.
     pub fn archive(mut self) -> Self {
         self.is_archived = true;
@@ -165,6 +345,71 @@ impl UpdateProduct {
     }
 }
 
+/// Outcome of removing a product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProductRemoval {
+    /// The product was never ordered and has been deleted.
+    Deleted,
+    /// The product appears in orders and has been archived instead.
+    Archived,
+}
+
+/// Change applied to several products at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProductBulkAction {
+    /// Archive the products.
+    Archive,
+    /// Restore archived products.
+    Restore,
+    /// Move the products to a category, or out of any category with `None`.
+    SetCategory(Option<i32>),
+    /// Attach the tags to the products, keeping the tags they already have.
+    AddTags(Vec<i32>),
+}
+
+/// Product written by one row of a product import.
+#[derive(Debug, Clone)]
+pub enum ProductImportTarget {
+    /// Insert a new product.
+    Create(NewProduct),
+    /// Overwrite an existing product of the hub.
+    Update {
+        product_id: i32,
+        updates: UpdateProduct,
+    },
+}
+
+/// Row of a product import together with the references it creates.
+#[derive(Debug, Clone)]
+pub struct ProductImportItem {
+    /// Product created or updated by the row.
+    pub target: ProductImportTarget,
+    /// Base rates replacing those of the product; the `product_id` of each
+    /// rate is set to the written product.
+    pub rates: Vec<NewProductPriceLevelRate>,
+    /// Existing tags replacing those of the product, together with
+    /// `missing_tags`; the tags are kept when both are empty.
+    pub tag_ids: Vec<i32>,
+    /// Names along the path of a category, root first, created as far as
+    /// missing and assigned to the product; empty to keep `target`'s category.
+    pub missing_category: Vec<String>,
+    /// Tag names created when the hub has no tag of that name yet.
+    pub missing_tags: Vec<String>,
+}
+
+impl ProductImportItem {
+    /// Construct a row writing `target` with `rates` and no tag changes.
+    pub fn new(target: ProductImportTarget, rates: Vec<NewProductPriceLevelRate>) -> Self {
+        Self {
+            target,
+            rates,
+            tag_ids: Vec::new(),
+            missing_category: Vec::new(),
+            missing_tags: Vec::new(),
+        }
+    }
+}
+
 /// Query definition used to list products for a hub.
 #[derive(Debug, Clone)]
 pub struct ProductListQuery {
@@ -174,6 +419,10 @@ pub struct ProductListQuery {
     pub search: Option<String>,
     /// Optional exact SKU filter.
     pub sku: Option<String>,
+    /// Optional set of categories the products must belong to.
+    pub category_ids: Option<Vec<i32>>,
+    /// Optional tag the products must carry.
+    pub tag_id: Option<i32>,
     /// Whether archived products should be included in the results.
     pub include_archived: bool,
     /// Optional pagination options applied to the query.
@@ -187,6 +436,8 @@ impl ProductListQuery {
             hub_id,
             search: None,
             sku: None,
+            category_ids: None,
+            tag_id: None,
             include_archived: false,
             pagination: None,
         }
@@ -204,6 +455,18 @@ impl ProductListQuery {
         self
     }
 
+    /// Filter the results to products in one of the given categories.
+    pub fn categories(mut self, category_ids: Vec<i32>) -> Self {
+        self.category_ids = Some(category_ids);
+        self
+    }
+
+    /// Filter the results to products carrying the given tag.
+    pub fn tag(mut self, tag_id: i32) -> Self {
+        self.tag_id = Some(tag_id);
+        self
+    }
+
     /// Include archived products in the results.
     pub fn include_archived(mut self) -> Self {
         self.include_archived = true;
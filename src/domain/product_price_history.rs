@@ -0,0 +1,161 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Recorded change of a product price at one price level.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProductPriceChange {
+    /// Unique identifier of the history record.
+    pub id: i32,
+    /// Identifier of the product whose price changed; `None` once it is deleted.
+    pub product_id: Option<i32>,
+    /// Identifier of the price level of the price; `None` once it is deleted.
+    pub price_level_id: Option<i32>,
+    /// Name of the product when the change was recorded.
+    pub product_name: String,
+    /// Name of the price level when the change was recorded.
+    pub price_level_name: String,
+    /// Smallest order quantity of the quantity break that changed.
+    pub min_quantity: i32,
+    /// Price before the change; `None` when the level had no price.
+    pub old_price_cents: Option<i64>,
+    /// Price after the change; `None` when the price was removed.
+    pub new_price_cents: Option<i64>,
+    /// Start of the window of a scheduled price.
+    pub valid_from: Option<NaiveDateTime>,
+    /// End of the window of a scheduled price.
+    pub valid_to: Option<NaiveDateTime>,
+    /// Email of the user who performed the change.
+    pub changed_by_email: String,
+    /// Display name of the user who performed the change.
+    pub changed_by_name: String,
+    /// Timestamp for when the change was recorded.
+    pub created_at: NaiveDateTime,
+}
+
+/// Payload required to record a price change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewProductPriceChange {
+    /// Identifier of the product whose price changes.
+    pub product_id: i32,
+    /// Identifier of the price level the price belongs to.
+    pub price_level_id: i32,
+    /// Smallest order quantity of the quantity break that changed.
+    pub min_quantity: i32,
+    /// Price before the change; `None` when the level had no price.
+    pub old_price_cents: Option<i64>,
+    /// Price after the change; `None` when the price is removed.
+    pub new_price_cents: Option<i64>,
+    /// Start of the window of a scheduled price.
+    pub valid_from: Option<NaiveDateTime>,
+    /// End of the window of a scheduled price.
+    pub valid_to: Option<NaiveDateTime>,
+    /// Email of the user performing the change.
+    pub changed_by_email: String,
+    /// Display name of the user performing the change.
+    pub changed_by_name: String,
+}
+
+impl NewProductPriceChange {
+    /// Construct a change of the price of `product_id` at `price_level_id`.
+    pub fn new(
+        product_id: i32,
+        price_level_id: i32,
+        old_price_cents: Option<i64>,
+        new_price_cents: Option<i64>,
+        changed_by: &PriceChangeAuthor,
+    ) -> Self {
+        Self {
+            product_id,
+            price_level_id,
+            min_quantity: 1,
+            old_price_cents,
+            new_price_cents,
+            valid_from: None,
+            valid_to: None,
+            changed_by_email: changed_by.email.clone(),
+            changed_by_name: changed_by.name.clone(),
+        }
+    }
+
+    /// Record the quantity break the change applies to.
+    pub fn with_min_quantity(mut self, min_quantity: i32) -> Self {
+        self.min_quantity = min_quantity;
+        self
+    }
+
+    /// Record the validity window of the scheduled price that changed.
+    pub fn with_window(
+        mut self,
+        valid_from: Option<NaiveDateTime>,
+        valid_to: Option<NaiveDateTime>,
+    ) -> Self {
+        self.valid_from = valid_from;
+        self.valid_to = valid_to;
+        self
+    }
+}
+
+/// User recorded as the author of price changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceChangeAuthor {
+    /// Email of the user changing prices.
+    pub email: String,
+    /// Display name of the user changing prices.
+    pub name: String,
+}
+
+impl PriceChangeAuthor {
+    /// Construct an author from the user's email and display name.
+    pub fn new(email: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            email: email.into(),
+            name: name.into(),
+        }
+    }
+}
+
+/// Query definition used to list the price history of a product.
+#[derive(Debug, Clone)]
+pub struct ProductPriceHistoryQuery {
+    /// Identifier of the product.
+    pub product_id: i32,
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// Optional price level filter.
+    pub price_level_id: Option<i32>,
+    /// Only include changes recorded at or after this moment.
+    pub changed_from: Option<NaiveDateTime>,
+    /// Only include changes recorded before this moment.
+    pub changed_to: Option<NaiveDateTime>,
+}
+
+impl ProductPriceHistoryQuery {
+    /// Construct a query for every change of the product.
+    pub fn new(product_id: i32, hub_id: i32) -> Self {
+        Self {
+            product_id,
+            hub_id,
+            price_level_id: None,
+            changed_from: None,
+            changed_to: None,
+        }
+    }
+
+    /// Filter the changes by price level.
+    pub fn price_level(mut self, price_level_id: i32) -> Self {
+        self.price_level_id = Some(price_level_id);
+        self
+    }
+
+    /// Only include changes recorded at or after `from`.
+    pub fn changed_from(mut self, from: NaiveDateTime) -> Self {
+        self.changed_from = Some(from);
+        self
+    }
+
+    /// Only include changes recorded before `to`.
+    pub fn changed_to(mut self, to: NaiveDateTime) -> Self {
+        self.changed_to = Some(to);
+        self
+    }
+}
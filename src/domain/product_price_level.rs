@@ -1,7 +1,16 @@
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::money::Money;
+use crate::domain::price_level::{PriceAdjustmentKind, PriceRounding, adjust_price};
+
 /// Domain representation tying a product to a specific price level with an amount.
+///
+/// A rate without a validity window is the base price of the product at the
+/// level. Scheduled rates carry `valid_from` and/or `valid_to` and take
+/// precedence over the base rate while they are in effect. Base rates with a
+/// `min_quantity` above one are quantity breaks for larger order lines; see
+/// [`rate_for_quantity`] for how they combine with scheduled rates.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ProductPriceLevelRate {
     /// Unique identifier of the association record.
@@ -10,14 +19,88 @@ pub struct ProductPriceLevelRate {
     pub product_id: i32,
     /// Identifier of the price level applied to the product.
     pub price_level_id: i32,
-    /// Price represented in the smallest currency unit (for example cents).
-    pub price_cents: i32,
+    /// Price in the currency of the product.
+    pub price: Money,
+    /// Smallest order line quantity the rate applies to.
+    pub min_quantity: i32,
+    /// Moment the rate starts to apply; `None` when it has always applied.
+    pub valid_from: Option<NaiveDateTime>,
+    /// Moment the rate stops applying (exclusive); `None` when open-ended.
+    pub valid_to: Option<NaiveDateTime>,
     /// Timestamp for when the association record was created.
     pub created_at: NaiveDateTime,
     /// Timestamp for the last update to the association record.
     pub updated_at: NaiveDateTime,
 }
 
+impl ProductPriceLevelRate {
+    /// Whether the rate has a validity window rather than being the base rate.
+    pub fn is_scheduled(&self) -> bool {
+        self.valid_from.is_some() || self.valid_to.is_some()
+    }
+
+    /// Whether the rate applies at `at`.
+    pub fn is_effective_at(&self, at: NaiveDateTime) -> bool {
+        self.valid_from.is_none_or(|from| from <= at) && self.valid_to.is_none_or(|to| at < to)
+    }
+
+    /// Whether the rate has stopped applying by `at`.
+    pub fn is_expired_at(&self, at: NaiveDateTime) -> bool {
+        self.valid_to.is_some_and(|to| to <= at)
+    }
+}
+
+/// Picks the rate of `price_level_id` in effect at `at` for a single unit.
+///
+/// When several rates apply, the one that started most recently wins, so a
+/// scheduled rate overrides the base rate for the duration of its window.
+pub fn rate_at(
+    rates: &[ProductPriceLevelRate],
+    price_level_id: i32,
+    at: NaiveDateTime,
+) -> Option<&ProductPriceLevelRate> {
+    rate_for_quantity(rates, price_level_id, at, 1)
+}
+
+/// Picks the rate of `price_level_id` in effect at `at` for an order line of
+/// `quantity` units.
+///
+/// Among the base rates the largest quantity break not above `quantity`
+/// applies. A scheduled rate in effect, the most recently started one when
+/// several are, takes precedence over that break unless the break starts at
+/// a larger quantity and is cheaper, so a promotion never makes a large line
+/// cost more than its quantity break.
+pub fn rate_for_quantity(
+    rates: &[ProductPriceLevelRate],
+    price_level_id: i32,
+    at: NaiveDateTime,
+    quantity: i32,
+) -> Option<&ProductPriceLevelRate> {
+    let effective = rates.iter().filter(|rate| {
+        rate.price_level_id == price_level_id
+            && rate.min_quantity <= quantity
+            && rate.is_effective_at(at)
+    });
+    let scheduled = effective
+        .clone()
+        .filter(|rate| rate.is_scheduled())
+        .max_by_key(|rate| (rate.min_quantity, rate.valid_from, rate.id));
+    let base = effective
+        .filter(|rate| !rate.is_scheduled())
+        .max_by_key(|rate| (rate.min_quantity, rate.id));
+
+    match (scheduled, base) {
+        (Some(scheduled), Some(base))
+            if base.min_quantity > scheduled.min_quantity
+                && base.price.amount < scheduled.price.amount =>
+        {
+            Some(base)
+        }
+        (Some(scheduled), _) => Some(scheduled),
+        (None, base) => base,
+    }
+}
+
 /// Payload required to insert a new price level rate for a product.
 #[derive(Debug, Clone, PartialEq)]
 pub struct NewProductPriceLevelRate {
@@ -25,26 +108,298 @@ pub struct NewProductPriceLevelRate {
     pub product_id: i32,
     /// Identifier of the price level applied to the product.
     pub price_level_id: i32,
-    /// Price represented in the smallest currency unit (for example cents).
-    pub price_cents: i32,
+    /// Price in the currency of the product.
+    pub price: Money,
+    /// Smallest order line quantity the rate applies to.
+    pub min_quantity: i32,
+    /// Moment the rate starts to apply; `None` when it applies immediately.
+    pub valid_from: Option<NaiveDateTime>,
+    /// Moment the rate stops applying (exclusive); `None` when open-ended.
+    pub valid_to: Option<NaiveDateTime>,
 }
 
 impl NewProductPriceLevelRate {
     /// Construct a new association payload between a product and a price level.
-    pub fn new(product_id: i32, price_level_id: i32, price_cents: i32) -> Self {
+    pub fn new(product_id: i32, price_level_id: i32, price: Money) -> Self {
         Self {
             product_id,
             price_level_id,
-            price_cents,
+            price,
+            min_quantity: 1,
+            valid_from: None,
+            valid_to: None,
         }
     }
+
+    /// Apply the rate to order lines of at least `min_quantity` units.
+    pub fn with_min_quantity(mut self, min_quantity: i32) -> Self {
+        self.min_quantity = min_quantity;
+        self
+    }
+
+    /// Start applying the rate at the given moment.
+    pub fn with_valid_from(mut self, valid_from: NaiveDateTime) -> Self {
+        self.valid_from = Some(valid_from);
+        self
+    }
+
+    /// Stop applying the rate at the given moment.
+    pub fn with_valid_to(mut self, valid_to: NaiveDateTime) -> Self {
+        self.valid_to = Some(valid_to);
+        self
+    }
+
+    /// Whether the payload has a validity window rather than being a base rate.
+    pub fn is_scheduled(&self) -> bool {
+        self.valid_from.is_some() || self.valid_to.is_some()
+    }
 }
 
 /// Patch data applied when updating an existing product price level rate.
 #[derive(Debug, Clone, PartialEq)]
 pub struct UpdateProductPriceLevelRate {
-    /// Price update in the smallest currency unit.
-    pub price_cents: i32,
+    /// Price update in the currency of the product.
+    pub price: Money,
     /// Timestamp captured when the patch was created.
     pub updated_at: NaiveDateTime,
 }
+
+/// Bulk change of the base rates of one price level by a percentage or a
+/// fixed amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceLevelRepricing {
+    /// Price level whose rates are changed.
+    pub price_level_id: i32,
+    /// Whether `adjustment_value` is a percentage or a fixed amount.
+    pub adjustment_kind: PriceAdjustmentKind,
+    /// Basis points for percentages, smallest currency units for fixed amounts.
+    pub adjustment_value: i64,
+    /// Rounding applied to the new prices.
+    pub rounding: PriceRounding,
+}
+
+impl PriceLevelRepricing {
+    /// Construct a repricing of `price_level_id` that leaves prices unchanged.
+    pub fn new(price_level_id: i32) -> Self {
+        Self {
+            price_level_id,
+            adjustment_kind: PriceAdjustmentKind::Percent,
+            adjustment_value: 0,
+            rounding: PriceRounding::None,
+        }
+    }
+
+    /// Change prices by a percentage given in basis points.
+    pub fn with_percent(mut self, basis_points: i64) -> Self {
+        self.adjustment_kind = PriceAdjustmentKind::Percent;
+        self.adjustment_value = basis_points;
+        self
+    }
+
+    /// Add a fixed amount in the smallest currency unit to prices.
+    pub fn with_fixed(mut self, cents: i64) -> Self {
+        self.adjustment_kind = PriceAdjustmentKind::Fixed;
+        self.adjustment_value = cents;
+        self
+    }
+
+    /// Round the new prices with the given rule.
+    pub fn with_rounding(mut self, rounding: PriceRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// New prices of the base rates, quantity breaks included, of the level
+    /// among `rates` of a product. Scheduled rates and rates whose price stays
+    /// the same are left out.
+    pub fn reprice(&self, rates: &[ProductPriceLevelRate]) -> Vec<RepricedRate> {
+        rates
+            .iter()
+            .filter(|rate| rate.price_level_id == self.price_level_id && !rate.is_scheduled())
+            .filter_map(|rate| {
+                let new_price = adjust_price(
+                    &rate.price,
+                    self.adjustment_kind,
+                    self.adjustment_value,
+                    self.rounding,
+                );
+                (new_price != rate.price).then(|| RepricedRate {
+                    rate_id: rate.id,
+                    product_id: rate.product_id,
+                    price_level_id: rate.price_level_id,
+                    min_quantity: rate.min_quantity,
+                    old_price: rate.price.clone(),
+                    new_price,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Price change of a single rate planned by a [`PriceLevelRepricing`].
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RepricedRate {
+    /// Identifier of the rate being changed.
+    pub rate_id: i32,
+    /// Identifier of the product the rate belongs to.
+    pub product_id: i32,
+    /// Identifier of the price level of the rate.
+    pub price_level_id: i32,
+    /// Smallest order line quantity the rate applies to.
+    pub min_quantity: i32,
+    /// Price before the change.
+    pub old_price: Money,
+    /// Price after the change.
+    pub new_price: Money,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn at(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 12, day)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .expect("valid date")
+    }
+
+    fn rate(
+        id: i32,
+        price_cents: i64,
+        valid_from: Option<NaiveDateTime>,
+        valid_to: Option<NaiveDateTime>,
+    ) -> ProductPriceLevelRate {
+        ProductPriceLevelRate {
+            id,
+            product_id: 1,
+            price_level_id: 1,
+            price: Money::new(price_cents, "USD"),
+            min_quantity: 1,
+            valid_from,
+            valid_to,
+            created_at: at(1),
+            updated_at: at(1),
+        }
+    }
+
+    #[test]
+    fn promotion_overrides_base_rate_within_its_window() {
+        let rates = vec![
+            rate(1, 1000, None, None),
+            rate(2, 800, Some(at(10)), Some(at(20))),
+        ];
+
+        let price = |day| rate_at(&rates, 1, at(day)).map(|rate| rate.price.amount);
+
+        assert_eq!(price(9), Some(1000));
+        assert_eq!(price(10), Some(800));
+        assert_eq!(price(19), Some(800));
+        assert_eq!(price(20), Some(1000));
+        assert_eq!(rate_at(&rates, 2, at(15)), None);
+    }
+
+    #[test]
+    fn latest_scheduled_change_wins() {
+        let rates = vec![
+            rate(1, 1000, None, None),
+            rate(2, 1100, Some(at(5)), None),
+            rate(3, 1200, Some(at(15)), None),
+        ];
+
+        let price = |at| rate_at(&rates, 1, at).map(|rate| rate.price.amount);
+
+        assert_eq!(price(at(4)), Some(1000));
+        assert_eq!(price(at(14) + Duration::hours(23)), Some(1100));
+        assert_eq!(price(at(15)), Some(1200));
+    }
+
+    #[test]
+    fn repricing_changes_base_rates_of_the_level_only() {
+        let mut other_level = rate(3, 1000, None, None);
+        other_level.price_level_id = 2;
+        let rates = vec![
+            rate(1, 1000, None, None),
+            rate(2, 800, Some(at(10)), None),
+            other_level,
+        ];
+
+        let repriced = PriceLevelRepricing::new(1)
+            .with_percent(1250)
+            .with_rounding(PriceRounding::NinetyNine)
+            .reprice(&rates);
+
+        let changes: Vec<(i32, i64, i64)> = repriced
+            .iter()
+            .map(|rate| (rate.rate_id, rate.old_price.amount, rate.new_price.amount))
+            .collect();
+        assert_eq!(changes, vec![(1, 1000, 1099)]);
+        assert!(
+            PriceLevelRepricing::new(1)
+                .with_fixed(0)
+                .reprice(&rates)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn largest_quantity_break_applies() {
+        let mut case = rate(2, 900, None, None);
+        case.min_quantity = 10;
+        let mut pallet = rate(3, 800, None, None);
+        pallet.min_quantity = 100;
+        let rates = vec![rate(1, 1000, None, None), case, pallet];
+
+        let price =
+            |quantity| rate_for_quantity(&rates, 1, at(1), quantity).map(|rate| rate.price.amount);
+
+        assert_eq!(price(1), Some(1000));
+        assert_eq!(price(9), Some(1000));
+        assert_eq!(price(10), Some(900));
+        assert_eq!(price(250), Some(800));
+        assert_eq!(
+            rate_at(&rates, 1, at(1)).map(|rate| rate.price.amount),
+            Some(1000)
+        );
+    }
+
+    #[test]
+    fn scheduled_rate_beats_quantity_break_unless_the_break_is_cheaper() {
+        let mut case = rate(2, 900, None, None);
+        case.min_quantity = 10;
+        let mut pallet = rate(3, 600, None, None);
+        pallet.min_quantity = 100;
+        let rates = vec![
+            rate(1, 1000, None, None),
+            case,
+            pallet,
+            rate(4, 700, Some(at(10)), Some(at(20))),
+        ];
+
+        let price = |day, quantity| {
+            rate_for_quantity(&rates, 1, at(day), quantity).map(|rate| rate.price.amount)
+        };
+
+        assert_eq!(price(15, 1), Some(700));
+        assert_eq!(price(15, 10), Some(700));
+        assert_eq!(price(15, 100), Some(600));
+        assert_eq!(price(20, 10), Some(900));
+    }
+
+    #[test]
+    fn scheduled_price_increase_keeps_cheaper_quantity_breaks() {
+        let mut case = rate(2, 900, None, None);
+        case.min_quantity = 10;
+        let rates = vec![
+            rate(1, 1000, None, None),
+            case,
+            rate(3, 1100, Some(at(10)), None),
+        ];
+
+        let price =
+            |quantity| rate_for_quantity(&rates, 1, at(15), quantity).map(|rate| rate.price.amount);
+
+        assert_eq!(price(1), Some(1100));
+        assert_eq!(price(10), Some(900));
+    }
+}
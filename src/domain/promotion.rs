@@ -0,0 +1,297 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::domain::money::Money;
+use crate::domain::price_level::PriceAdjustmentKind;
+
+/// Message of the `ConstraintViolation` returned when an order redeems a
+/// promotion whose usage limit was reached in the meantime.
+pub const PROMOTION_USED_UP: &str = "promotion usage limit reached";
+
+/// Discount code of a hub redeemable when placing an order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Promotion {
+    /// Unique identifier of the promotion.
+    pub id: i32,
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// Upper-cased code entered by the customer, unique within the hub.
+    pub code: String,
+    /// Whether `value` is a percentage or a fixed amount.
+    pub kind: PriceAdjustmentKind,
+    /// Basis points for percentages, smallest units of `currency` for fixed
+    /// discounts.
+    pub value: i64,
+    /// Currency of fixed discounts and of the minimum order amount.
+    pub currency: String,
+    /// Minimum order total, including tax and before the discount.
+    pub min_order_cents: Option<i64>,
+    /// The code can be redeemed from this moment (UTC), when set.
+    pub valid_from: Option<NaiveDateTime>,
+    /// The code can be redeemed until this moment (UTC), when set.
+    pub valid_to: Option<NaiveDateTime>,
+    /// Maximum number of orders the code can be redeemed on.
+    pub usage_limit: Option<i32>,
+    /// Number of orders the code was redeemed on.
+    pub usage_count: i32,
+    /// Only products of this category and its subcategories are discounted.
+    pub category_id: Option<i32>,
+    /// Only products carrying this tag are discounted.
+    pub tag_id: Option<i32>,
+    /// Timestamp for when the promotion record was created.
+    pub created_at: NaiveDateTime,
+    /// Timestamp for the last update to the promotion record.
+    pub updated_at: NaiveDateTime,
+}
+
+/// Reasons a promotion code cannot be redeemed on an order.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PromotionError {
+    /// The validity window has not started.
+    #[error("promotion code {code} is not active yet")]
+    NotStarted { code: String },
+    /// The validity window has ended.
+    #[error("promotion code {code} has expired")]
+    Expired { code: String },
+    /// The code reached its usage limit.
+    #[error("promotion code {code} has been used up")]
+    Exhausted { code: String },
+    /// The order is in another currency than the promotion amounts.
+    #[error("promotion code {code} only applies to {currency} orders")]
+    CurrencyMismatch { code: String, currency: String },
+    /// The order total is below the minimum, formatted with its currency.
+    #[error("promotion code {code} requires an order of at least {minimum}")]
+    BelowMinimum { code: String, minimum: String },
+}
+
+impl Promotion {
+    /// Checks that the code can be redeemed at `now` on an order totalling
+    /// `order_total` before the discount.
+    ///
+    /// The currency only has to match for fixed discounts and minimum order
+    /// amounts.
+    pub fn check_redeemable(
+        &self,
+        now: NaiveDateTime,
+        order_total: &Money,
+    ) -> Result<(), PromotionError> {
+        let code = || self.code.clone();
+
+        if self.valid_from.is_some_and(|from| now < from) {
+            return Err(PromotionError::NotStarted { code: code() });
+        }
+        if self.valid_to.is_some_and(|to| now >= to) {
+            return Err(PromotionError::Expired { code: code() });
+        }
+        if self
+            .usage_limit
+            .is_some_and(|limit| self.usage_count >= limit)
+        {
+            return Err(PromotionError::Exhausted { code: code() });
+        }
+
+        let needs_currency =
+            self.kind == PriceAdjustmentKind::Fixed || self.min_order_cents.is_some();
+        if needs_currency && order_total.currency != self.currency {
+            return Err(PromotionError::CurrencyMismatch {
+                code: code(),
+                currency: self.currency.clone(),
+            });
+        }
+        if let Some(minimum) = self.min_order_cents
+            && order_total.amount < minimum
+        {
+            return Err(PromotionError::BelowMinimum {
+                code: code(),
+                minimum: Money::new(minimum, self.currency.as_str()).to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Payload required to create a promotion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewPromotion {
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// Upper-cased code.
+    pub code: String,
+    /// Whether `value` is a percentage or a fixed amount.
+    pub kind: PriceAdjustmentKind,
+    /// Basis points or smallest currency units, depending on `kind`.
+    pub value: i64,
+    /// Currency of fixed discounts and of the minimum order amount.
+    pub currency: String,
+    /// Minimum order total before the discount.
+    pub min_order_cents: Option<i64>,
+    /// Start of the validity window (UTC).
+    pub valid_from: Option<NaiveDateTime>,
+    /// End of the validity window (UTC).
+    pub valid_to: Option<NaiveDateTime>,
+    /// Maximum number of redemptions.
+    pub usage_limit: Option<i32>,
+    /// Category restricting the discounted products.
+    pub category_id: Option<i32>,
+    /// Tag restricting the discounted products.
+    pub tag_id: Option<i32>,
+}
+
+impl NewPromotion {
+    /// Construct an unrestricted promotion payload with a normalized code.
+    pub fn new(
+        hub_id: i32,
+        code: &str,
+        kind: PriceAdjustmentKind,
+        value: i64,
+        currency: impl Into<String>,
+    ) -> Self {
+        Self {
+            hub_id,
+            code: normalize_code(code),
+            kind,
+            value,
+            currency: currency.into().trim().to_ascii_uppercase(),
+            min_order_cents: None,
+            valid_from: None,
+            valid_to: None,
+            usage_limit: None,
+            category_id: None,
+            tag_id: None,
+        }
+    }
+
+    /// Require an order total of at least `cents` before the discount.
+    pub fn with_min_order_cents(mut self, cents: i64) -> Self {
+        self.min_order_cents = Some(cents);
+        self
+    }
+
+    /// Only accept the code from `valid_from`.
+    pub fn with_valid_from(mut self, valid_from: NaiveDateTime) -> Self {
+        self.valid_from = Some(valid_from);
+        self
+    }
+
+    /// Only accept the code until `valid_to`.
+    pub fn with_valid_to(mut self, valid_to: NaiveDateTime) -> Self {
+        self.valid_to = Some(valid_to);
+        self
+    }
+
+    /// Limit the number of orders the code can be redeemed on.
+    pub fn with_usage_limit(mut self, usage_limit: i32) -> Self {
+        self.usage_limit = Some(usage_limit);
+        self
+    }
+
+    /// Only discount products of the category and its subcategories.
+    pub fn with_category_id(mut self, category_id: i32) -> Self {
+        self.category_id = Some(category_id);
+        self
+    }
+
+    /// Only discount products carrying the tag.
+    pub fn with_tag_id(mut self, tag_id: i32) -> Self {
+        self.tag_id = Some(tag_id);
+        self
+    }
+}
+
+/// Normalizes a code as entered by a customer: trimmed and upper-cased.
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 11, 12)
+            .and_then(|date| date.and_hms_opt(12, 0, 0))
+            .expect("valid datetime")
+    }
+
+    fn promotion(kind: PriceAdjustmentKind, value: i64) -> Promotion {
+        Promotion {
+            id: 1,
+            hub_id: 7,
+            code: "SALE".to_string(),
+            kind,
+            value,
+            currency: "RUB".to_string(),
+            min_order_cents: None,
+            valid_from: None,
+            valid_to: None,
+            usage_limit: None,
+            usage_count: 0,
+            category_id: None,
+            tag_id: None,
+            created_at: now(),
+            updated_at: now(),
+        }
+    }
+
+    #[test]
+    fn new_promotion_normalizes_code_and_currency() {
+        let promotion =
+            NewPromotion::new(7, "  summer10 ", PriceAdjustmentKind::Percent, 1_000, "rub");
+
+        assert_eq!(promotion.code, "SUMMER10");
+        assert_eq!(promotion.currency, "RUB");
+    }
+
+    #[test]
+    fn check_redeemable_enforces_window_and_usage() {
+        let mut sale = promotion(PriceAdjustmentKind::Percent, 1_000);
+        let total = Money::new(10_000, "USD");
+        assert_eq!(sale.check_redeemable(now(), &total), Ok(()));
+
+        sale.valid_from = Some(now() + Duration::hours(1));
+        assert!(matches!(
+            sale.check_redeemable(now(), &total),
+            Err(PromotionError::NotStarted { .. })
+        ));
+
+        sale.valid_from = None;
+        sale.valid_to = Some(now());
+        assert!(matches!(
+            sale.check_redeemable(now(), &total),
+            Err(PromotionError::Expired { .. })
+        ));
+
+        sale.valid_to = None;
+        sale.usage_limit = Some(2);
+        sale.usage_count = 2;
+        assert!(matches!(
+            sale.check_redeemable(now(), &total),
+            Err(PromotionError::Exhausted { .. })
+        ));
+    }
+
+    #[test]
+    fn check_redeemable_compares_minimum_in_promotion_currency() {
+        let mut sale = promotion(PriceAdjustmentKind::Fixed, 500);
+        assert!(matches!(
+            sale.check_redeemable(now(), &Money::new(10_000, "USD")),
+            Err(PromotionError::CurrencyMismatch { .. })
+        ));
+
+        sale.min_order_cents = Some(5_000);
+        assert_eq!(
+            sale.check_redeemable(now(), &Money::new(4_999, "RUB")),
+            Err(PromotionError::BelowMinimum {
+                code: "SALE".to_string(),
+                minimum: "50.00 RUB".to_string(),
+            })
+        );
+        assert_eq!(
+            sale.check_redeemable(now(), &Money::new(5_000, "RUB")),
+            Ok(())
+        );
+    }
+}
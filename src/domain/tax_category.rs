@@ -0,0 +1,202 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::money::{Money, MoneyError};
+
+/// Fixed-point scale of tax rates: a rate of `TAX_RATE_SCALE` is 100%.
+pub const TAX_RATE_SCALE: i32 = 10_000;
+
+/// Hub-level tax category such as standard or reduced VAT.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaxCategory {
+    /// Unique identifier of the tax category.
+    pub id: i32,
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// Human-readable name of the category, unique within the hub.
+    pub name: String,
+    /// Tax rate in basis points, e.g. `2000` for 20%.
+    pub rate_basis_points: i32,
+    /// Timestamp for when the category record was created.
+    pub created_at: NaiveDateTime,
+    /// Timestamp for the last update to the category record.
+    pub updated_at: NaiveDateTime,
+}
+
+/// Payload required to store a tax category.
+///
+/// Saving a name that already exists replaces its rate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewTaxCategory {
+    /// Owning hub identifier.
+    pub hub_id: i32,
+    /// Human-readable name of the category.
+    pub name: String,
+    /// Tax rate in basis points.
+    pub rate_basis_points: i32,
+}
+
+impl NewTaxCategory {
+    /// Construct a tax category payload with a trimmed name.
+    pub fn new(hub_id: i32, name: impl Into<String>, rate_basis_points: i32) -> Self {
+        Self {
+            hub_id,
+            name: name.into().trim().to_string(),
+            rate_basis_points,
+        }
+    }
+}
+
+/// Net, tax and gross parts of an amount of money.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaxAmounts {
+    /// Amount before tax.
+    pub net: Money,
+    /// Tax charged on the net amount.
+    pub tax: Money,
+    /// Amount including tax.
+    pub gross: Money,
+}
+
+impl TaxAmounts {
+    /// Zero net, tax and gross amounts in `currency`.
+    pub fn zero(currency: &str) -> Self {
+        Self {
+            net: Money::zero(currency),
+            tax: Money::zero(currency),
+            gross: Money::zero(currency),
+        }
+    }
+
+    /// Splits `amount` taxed at `rate_basis_points`.
+    ///
+    /// With `includes_tax` the amount is gross and the tax is extracted from it;
+    /// otherwise the amount is net and the tax is added on top. The tax is
+    /// rounded half away from zero to the minor unit.
+    pub fn from_amount(
+        amount: &Money,
+        rate_basis_points: i32,
+        includes_tax: bool,
+    ) -> Result<Self, MoneyError> {
+        let rate = i128::from(rate_basis_points);
+        let scale = i128::from(TAX_RATE_SCALE);
+        let numerator = i128::from(amount.amount) * rate;
+        let denominator = if includes_tax { scale + rate } else { scale };
+        let half = denominator / 2;
+        let rounded = if numerator < 0 {
+            (numerator - half) / denominator
+        } else {
+            (numerator + half) / denominator
+        };
+        let tax = Money::new(
+            i64::try_from(rounded).map_err(|_| MoneyError::Overflow)?,
+            amount.currency.as_str(),
+        );
+
+        if includes_tax {
+            Ok(Self {
+                net: amount.checked_sub(&tax)?,
+                tax,
+                gross: amount.clone(),
+            })
+        } else {
+            Ok(Self {
+                net: amount.clone(),
+                gross: amount.checked_add(&tax)?,
+                tax,
+            })
+        }
+    }
+
+    /// Sum of two breakdowns in the same currency.
+    pub fn checked_add(&self, other: &TaxAmounts) -> Result<TaxAmounts, MoneyError> {
+        Ok(Self {
+            net: self.net.checked_add(&other.net)?,
+            tax: self.tax.checked_add(&other.tax)?,
+            gross: self.gross.checked_add(&other.gross)?,
+        })
+    }
+
+    /// Difference of two breakdowns in the same currency.
+    pub fn checked_sub(&self, other: &TaxAmounts) -> Result<TaxAmounts, MoneyError> {
+        Ok(Self {
+            net: self.net.checked_sub(&other.net)?,
+            tax: self.tax.checked_sub(&other.tax)?,
+            gross: self.gross.checked_sub(&other.gross)?,
+        })
+    }
+}
+
+/// Formats a rate in basis points as a percentage without trailing zeros, e.g. `7.5`.
+pub fn format_tax_rate(rate_basis_points: i32) -> String {
+    let whole = rate_basis_points / 100;
+    let fraction = (rate_basis_points % 100).unsigned_abs();
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let digits = format!("{fraction:02}");
+    format!("{whole}.{}", digits.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amounts(net: i64, tax: i64, gross: i64) -> TaxAmounts {
+        TaxAmounts {
+            net: Money::new(net, "USD"),
+            tax: Money::new(tax, "USD"),
+            gross: Money::new(gross, "USD"),
+        }
+    }
+
+    #[test]
+    fn exclusive_amounts_add_tax_on_top() {
+        assert_eq!(
+            TaxAmounts::from_amount(&Money::new(10_000, "USD"), 2_000, false),
+            Ok(amounts(10_000, 2_000, 12_000))
+        );
+        assert_eq!(
+            TaxAmounts::from_amount(&Money::new(999, "USD"), 750, false),
+            Ok(amounts(999, 75, 1_074))
+        );
+    }
+
+    #[test]
+    fn inclusive_amounts_extract_tax() {
+        assert_eq!(
+            TaxAmounts::from_amount(&Money::new(12_000, "USD"), 2_000, true),
+            Ok(amounts(10_000, 2_000, 12_000))
+        );
+        assert_eq!(
+            TaxAmounts::from_amount(&Money::new(1_000, "USD"), 1_000, true),
+            Ok(amounts(909, 91, 1_000))
+        );
+        assert_eq!(
+            TaxAmounts::from_amount(&Money::new(1_000, "USD"), 0, true),
+            Ok(amounts(1_000, 0, 1_000))
+        );
+    }
+
+    #[test]
+    fn sums_are_checked() {
+        let max = TaxAmounts::from_amount(&Money::new(i64::MAX, "USD"), 0, false).expect("amounts");
+
+        assert_eq!(max.checked_add(&max), Err(MoneyError::Overflow));
+        assert_eq!(
+            TaxAmounts::from_amount(&Money::new(i64::MAX, "USD"), 2_000, false),
+            Err(MoneyError::Overflow)
+        );
+        assert!(matches!(
+            amounts(1, 0, 1).checked_add(&TaxAmounts::zero("EUR")),
+            Err(MoneyError::CurrencyMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn format_tax_rate_drops_trailing_zeros() {
+        assert_eq!(format_tax_rate(2_000), "20");
+        assert_eq!(format_tax_rate(750), "7.5");
+        assert_eq!(format_tax_rate(5), "0.05");
+    }
+}
@@ -0,0 +1,537 @@
+use std::io::{Read, Seek};
+
+use actix_multipart::form::{MultipartForm, tempfile::TempFile};
+use chrono::NaiveDateTime;
+use csv::Trim;
+use serde::Deserialize;
+use thiserror::Error;
+use validator::{Validate, ValidateEmail, ValidationErrors};
+
+use crate::domain::customer::{CustomerMerge, NewCustomer, UpdateCustomer};
+use crate::domain::phone::normalize_phone;
+
+/// Maximum allowed length for a customer name.
+const NAME_MAX_LEN: u64 = 255;
+/// Maximum allowed length for a customer phone number.
+const PHONE_MAX_LEN: u64 = 32;
+
+/// Columns read from and written to customer CSV files, in export order.
+pub const CUSTOMER_CSV_HEADERS: [&str; 4] = ["name", "email", "phone", "price_level"];
+
+/// Result type returned by the customer form helpers.
+pub type CustomerFormResult<T> = Result<T, CustomerFormError>;
+
+/// Errors that can occur while processing customer forms.
+#[derive(Debug, Error)]
+pub enum CustomerFormError {
+    /// Validation failures from the `validator` crate.
+    #[error("validation failed: {0}")]
+    Validation(#[from] ValidationErrors),
+    /// The provided name is empty after sanitization.
+    #[error("customer name cannot be empty")]
+    EmptyName,
+    /// The provided phone is not a recognisable phone number.
+    #[error("`{value}` is not a valid phone number")]
+    InvalidPhone { value: String },
+    /// A merge was submitted without any customer besides the survivor.
+    #[error("select at least one customer to merge")]
+    NothingToMerge,
+    /// The kept price level is not a valid identifier.
+    #[error("invalid price level id `{value}`")]
+    InvalidPriceLevelId { value: String },
+    /// The kept price level is not assigned to any of the merged customers.
+    #[error("the kept price level must be assigned to one of the merged customers")]
+    PriceLevelNotInMerge,
+}
+
+/// Form payload emitted when submitting the "Add customer" form.
+#[derive(Debug, Deserialize, Validate)]
+pub struct AddCustomerForm {
+    /// Display name entered by the user.
+    #[validate(length(min = 1, max = NAME_MAX_LEN))]
+    pub name: String,
+    /// Contact email of the customer.
+    #[validate(email)]
+    pub email: String,
+    /// Optional contact phone of the customer.
+    #[serde(default)]
+    #[validate(length(max = PHONE_MAX_LEN))]
+    pub phone: Option<String>,
+}
+
+impl AddCustomerForm {
+    /// Validates and sanitizes the payload into a domain `NewCustomer`.
+    pub fn into_new_customer(self, hub_id: i32) -> CustomerFormResult<NewCustomer> {
+        self.validate()?;
+
+        let name = sanitize_inline_text(&self.name);
+        if name.is_empty() {
+            return Err(CustomerFormError::EmptyName);
+        }
+
+        let mut customer = NewCustomer::new(hub_id, name, self.email);
+        if let Some(phone) = normalize_optional_phone(self.phone)? {
+            customer = customer.with_phone(phone);
+        }
+
+        Ok(customer)
+    }
+}
+
+/// Form payload emitted when editing an existing customer.
+#[derive(Debug, Deserialize, Validate)]
+pub struct EditCustomerForm {
+    /// Identifier of the customer to update.
+    #[validate(range(min = 1))]
+    pub customer_id: i32,
+    /// Updated display name.
+    #[validate(length(min = 1, max = NAME_MAX_LEN))]
+    pub name: String,
+    /// Updated contact email.
+    #[validate(email)]
+    pub email: String,
+    /// Updated contact phone; empty clears the stored phone.
+    #[serde(default)]
+    #[validate(length(max = PHONE_MAX_LEN))]
+    pub phone: Option<String>,
+}
+
+impl EditCustomerForm {
+    /// Validates and sanitizes the payload into a domain `UpdateCustomer`.
+    pub fn into_update_customer(
+        self,
+        updated_at: NaiveDateTime,
+    ) -> CustomerFormResult<UpdateCustomer> {
+        self.validate()?;
+
+        let name = sanitize_inline_text(&self.name);
+        if name.is_empty() {
+            return Err(CustomerFormError::EmptyName);
+        }
+
+        let mut updates = UpdateCustomer::new(name, self.email, updated_at);
+        if let Some(phone) = normalize_optional_phone(self.phone)? {
+            updates = updates.with_phone(phone);
+        }
+
+        Ok(updates)
+    }
+}
+
+/// Form payload emitted when merging a group of duplicate customers.
+#[derive(Debug, Deserialize)]
+pub struct MergeCustomersForm {
+    /// Customer that keeps its identity and receives the orders.
+    pub survivor_id: i32,
+    /// Customers of the group selected for the merge, survivor included or not.
+    #[serde(default)]
+    pub customer_ids: Vec<i32>,
+    /// Price level kept by the survivor; empty keeps the hub default.
+    #[serde(default)]
+    pub price_level_id: Option<String>,
+}
+
+impl MergeCustomersForm {
+    /// Validates the selection into a domain `CustomerMerge`.
+    pub fn into_customer_merge(
+        self,
+        hub_id: i32,
+        updated_at: NaiveDateTime,
+    ) -> CustomerFormResult<CustomerMerge> {
+        let price_level_id = match self.price_level_id.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(raw) => match raw.parse::<i32>() {
+                Ok(id) if id > 0 => Some(id),
+                _ => {
+                    return Err(CustomerFormError::InvalidPriceLevelId {
+                        value: raw.to_string(),
+                    });
+                }
+            },
+        };
+
+        let merge = CustomerMerge::new(
+            hub_id,
+            self.survivor_id,
+            self.customer_ids.into_iter().filter(|id| *id > 0),
+            price_level_id,
+            updated_at,
+        );
+        if merge.merged_ids.is_empty() {
+            return Err(CustomerFormError::NothingToMerge);
+        }
+
+        Ok(merge)
+    }
+}
+
+#[derive(MultipartForm)]
+/// Multipart form for uploading a CSV file with customers.
+pub struct UploadCustomersForm {
+    #[multipart(limit = "10MB")]
+    /// Uploaded CSV file containing customer data.
+    pub csv: TempFile,
+}
+
+#[derive(Debug, Error)]
+/// Errors that can occur while parsing an uploaded customers CSV file.
+pub enum UploadCustomersFormError {
+    #[error("Error reading csv file")]
+    FileReadError,
+    #[error("Error parsing csv file")]
+    CsvParseError,
+    #[error("upload must have `name` and `email` columns")]
+    MissingRequiredHeaders,
+    #[error("row {row} could not be parsed")]
+    MalformedRow { row: usize },
+    #[error("row {row} is missing a customer name")]
+    MissingName { row: usize },
+    #[error("row {row}: `{email}` is not a valid email")]
+    InvalidEmail { row: usize, email: String },
+    #[error("row {row}: `{phone}` is not a valid phone number")]
+    InvalidPhone { row: usize, phone: String },
+    #[error("row {row} repeats customer `{email}` from row {first_row}")]
+    DuplicateCustomer {
+        row: usize,
+        email: String,
+        first_row: usize,
+    },
+    #[error("row {row}: price level `{name}` does not exist")]
+    UnknownPriceLevel { row: usize, name: String },
+}
+
+impl UploadCustomersFormError {
+    /// Line of the uploaded CSV the error refers to, if it is a row error.
+    pub fn row(&self) -> Option<usize> {
+        match self {
+            Self::MalformedRow { row }
+            | Self::MissingName { row }
+            | Self::InvalidEmail { row, .. }
+            | Self::InvalidPhone { row, .. }
+            | Self::DuplicateCustomer { row, .. }
+            | Self::UnknownPriceLevel { row, .. } => Some(*row),
+            Self::FileReadError | Self::CsvParseError | Self::MissingRequiredHeaders => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for UploadCustomersFormError {
+    fn from(_: std::io::Error) -> Self {
+        UploadCustomersFormError::FileReadError
+    }
+}
+
+impl From<csv::Error> for UploadCustomersFormError {
+    fn from(_: csv::Error) -> Self {
+        UploadCustomersFormError::CsvParseError
+    }
+}
+
+/// Customer parsed from an uploaded CSV row.
+#[derive(Debug, Clone)]
+pub struct CustomerUploadRow {
+    /// Line of the uploaded CSV.
+    pub row: usize,
+    /// Customer contact details; the price level is resolved separately.
+    pub customer: NewCustomer,
+    /// Price level name from the `price_level` column, `None` when empty.
+    pub price_level: Option<String>,
+}
+
+/// Every row of a customer upload, parsed without stopping at the first error.
+#[derive(Debug, Default)]
+pub struct CustomerUploadReport {
+    /// Rows that parsed successfully.
+    pub rows: Vec<CustomerUploadRow>,
+    /// Errors of the rows that could not be parsed, in file order.
+    pub errors: Vec<UploadCustomersFormError>,
+}
+
+impl UploadCustomersForm {
+    /// Read the uploaded CSV as text so it can be re-submitted after a preview.
+    pub fn content(&mut self) -> Result<String, UploadCustomersFormError> {
+        self.csv.file.rewind()?;
+        let mut content = String::new();
+        self.csv.file.read_to_string(&mut content)?;
+        Ok(content)
+    }
+
+    /// Parse every row of the uploaded CSV, collecting row errors instead of
+    /// stopping at the first one.
+    pub fn into_upload_report(
+        &mut self,
+        hub_id: i32,
+    ) -> Result<CustomerUploadReport, UploadCustomersFormError> {
+        self.csv.file.rewind()?;
+        parse_customers(self.csv.file.by_ref(), hub_id)
+    }
+}
+
+fn parse_customers<R: Read>(
+    reader: R,
+    hub_id: i32,
+) -> Result<CustomerUploadReport, UploadCustomersFormError> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .trim(Trim::All)
+        .flexible(true)
+        .from_reader(reader);
+
+    let headers = csv_reader.headers()?.clone();
+    let locate = |expected: &str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(expected))
+    };
+    let (Some(name_index), Some(email_index)) = (locate("name"), locate("email")) else {
+        return Err(UploadCustomersFormError::MissingRequiredHeaders);
+    };
+    let phone_index = locate("phone");
+    let price_level_index = locate("price_level");
+
+    let mut report = CustomerUploadReport::default();
+    for (index, row) in csv_reader.records().enumerate() {
+        let row_number = index + 2; // account for header row
+        let Ok(record) = row else {
+            report
+                .errors
+                .push(UploadCustomersFormError::MalformedRow { row: row_number });
+            continue;
+        };
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .map(sanitize_inline_text)
+                .unwrap_or_default()
+        };
+
+        let name = cell(Some(name_index));
+        if name.is_empty() {
+            report
+                .errors
+                .push(UploadCustomersFormError::MissingName { row: row_number });
+            continue;
+        }
+
+        let email = cell(Some(email_index)).to_lowercase();
+        if !email.validate_email() {
+            report.errors.push(UploadCustomersFormError::InvalidEmail {
+                row: row_number,
+                email,
+            });
+            continue;
+        }
+
+        let raw_phone = cell(phone_index);
+        let phone = if raw_phone.is_empty() {
+            String::new()
+        } else if let Some(phone) = normalize_phone(&raw_phone) {
+            phone
+        } else {
+            report.errors.push(UploadCustomersFormError::InvalidPhone {
+                row: row_number,
+                phone: raw_phone,
+            });
+            continue;
+        };
+
+        let price_level = Some(cell(price_level_index)).filter(|name| !name.is_empty());
+
+        report.rows.push(CustomerUploadRow {
+            row: row_number,
+            customer: NewCustomer::new(hub_id, name, email).with_phone(phone),
+            price_level,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Normalises a submitted phone to E.164, treating blank input as no phone.
+fn normalize_optional_phone(phone: Option<String>) -> CustomerFormResult<Option<String>> {
+    let Some(value) = phone.map(|value| value.trim().to_string()) else {
+        return Ok(None);
+    };
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    normalize_phone(&value)
+        .map(Some)
+        .ok_or(CustomerFormError::InvalidPhone { value })
+}
+
+fn sanitize_inline_text(input: &str) -> String {
+    let mut sanitized = String::with_capacity(input.len());
+    let mut previous_whitespace = false;
+
+    for ch in input.trim().chars() {
+        if ch.is_whitespace() {
+            if !previous_whitespace {
+                sanitized.push(' ');
+                previous_whitespace = true;
+            }
+        } else if ch.is_control() {
+            continue;
+        } else {
+            sanitized.push(ch);
+            previous_whitespace = false;
+        }
+    }
+
+    sanitized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Seek, SeekFrom, Write};
+
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn add_customer_form_normalizes_contact_details() {
+        let form = AddCustomerForm {
+            name: "  Acme \t Corp ".to_string(),
+            email: "Sales@Acme.Example".to_string(),
+            phone: Some("   ".to_string()),
+        };
+
+        let customer = form
+            .into_new_customer(4)
+            .expect("expected conversion to succeed");
+
+        assert_eq!(customer.hub_id, 4);
+        assert_eq!(customer.name, "Acme Corp");
+        assert_eq!(customer.email, "sales@acme.example");
+        assert_eq!(customer.phone, None);
+    }
+
+    #[test]
+    fn add_customer_form_normalizes_phone_to_e164() {
+        let form = AddCustomerForm {
+            name: "Acme".to_string(),
+            email: "acme@example.com".to_string(),
+            phone: Some(" 8 (900) 123-45-67 ".to_string()),
+        };
+        let customer = form
+            .into_new_customer(1)
+            .expect("expected conversion to succeed");
+        assert_eq!(customer.phone.as_deref(), Some("+79001234567"));
+
+        let form = AddCustomerForm {
+            name: "Acme".to_string(),
+            email: "acme@example.com".to_string(),
+            phone: Some("ask the manager".to_string()),
+        };
+        assert!(matches!(
+            form.into_new_customer(1),
+            Err(CustomerFormError::InvalidPhone { .. })
+        ));
+    }
+
+    #[test]
+    fn edit_customer_form_rejects_invalid_email() {
+        let form = EditCustomerForm {
+            customer_id: 1,
+            name: "Acme".to_string(),
+            email: "not-an-email".to_string(),
+            phone: None,
+        };
+
+        let result = form.into_update_customer(NaiveDateTime::default());
+
+        assert!(matches!(result, Err(CustomerFormError::Validation(_))));
+    }
+
+    #[test]
+    fn merge_customers_form_excludes_survivor_from_merged_ids() {
+        let form = MergeCustomersForm {
+            survivor_id: 2,
+            customer_ids: vec![3, 2, 1, 3],
+            price_level_id: Some(" 4 ".to_string()),
+        };
+
+        let merge = form
+            .into_customer_merge(9, NaiveDateTime::default())
+            .expect("expected merge");
+
+        assert_eq!(merge.hub_id, 9);
+        assert_eq!(merge.survivor_id, 2);
+        assert_eq!(merge.merged_ids, vec![1, 3]);
+        assert_eq!(merge.price_level_id, Some(4));
+
+        let form = MergeCustomersForm {
+            survivor_id: 2,
+            customer_ids: vec![2],
+            price_level_id: None,
+        };
+        assert!(matches!(
+            form.into_customer_merge(9, NaiveDateTime::default()),
+            Err(CustomerFormError::NothingToMerge)
+        ));
+    }
+
+    #[test]
+    fn upload_report_parses_rows_and_collects_errors() {
+        let mut form = build_upload_form(
+            "name,email,phone,price_level\n\
+             Acme,Sales@Acme.Example,8 (900) 123-45-67,Wholesale\n\
+             ,nobody@example.com,,\n\
+             Beta,not-an-email,,\n\
+             Acme Again,sales@acme.example,+79001234567,\n\
+             Gamma,gamma@example.com,,\n",
+        );
+
+        let report = form.into_upload_report(7).expect("expected report");
+
+        assert_eq!(report.rows.len(), 3);
+        let acme = &report.rows[0];
+        assert_eq!(acme.row, 2);
+        assert_eq!(acme.customer.hub_id, 7);
+        assert_eq!(acme.customer.email, "sales@acme.example");
+        assert_eq!(acme.customer.phone.as_deref(), Some("+79001234567"));
+        assert_eq!(acme.price_level.as_deref(), Some("Wholesale"));
+        // Repeated customers are rejected when the import is planned.
+        assert_eq!(report.rows[1].row, 5);
+        assert_eq!(report.rows[1].customer.email, acme.customer.email);
+        assert_eq!(report.rows[1].customer.phone, acme.customer.phone);
+        assert_eq!(report.rows[2].customer.phone, None);
+        assert_eq!(report.rows[2].price_level, None);
+        assert!(matches!(
+            report.errors.as_slice(),
+            [
+                UploadCustomersFormError::MissingName { row: 3 },
+                UploadCustomersFormError::InvalidEmail { row: 4, .. },
+            ]
+        ));
+    }
+
+    #[test]
+    fn upload_report_requires_name_and_email_columns() {
+        let mut form = build_upload_form("name,phone\nAcme,+79001234567\n");
+
+        let result = form.into_upload_report(1);
+
+        assert!(matches!(
+            result,
+            Err(UploadCustomersFormError::MissingRequiredHeaders)
+        ));
+    }
+
+    fn build_upload_form(csv: &str) -> UploadCustomersForm {
+        let mut file = NamedTempFile::new().expect("create temp file");
+        file.write_all(csv.as_bytes()).expect("write csv file");
+        file.as_file_mut()
+            .seek(SeekFrom::Start(0))
+            .expect("seek to start");
+
+        UploadCustomersForm {
+            csv: TempFile {
+                file,
+                content_type: None,
+                file_name: Some("customers.csv".to_string()),
+                size: csv.len(),
+            },
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+use std::io::{Read, Seek};
+
+use actix_multipart::form::{MultipartForm, tempfile::TempFile};
+use csv::Trim;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::domain::exchange_rate::{EXCHANGE_RATE_SCALE, NewExchangeRate};
+
+/// ISO 4217 currency codes are three ASCII alphabetic characters.
+const CURRENCY_CODE_LEN: usize = 3;
+
+/// Rates are stored with six fraction digits.
+const RATE_FRACTION_DIGITS: usize = 6;
+
+/// Result type returned by the exchange rate form helpers.
+pub type ExchangeRateFormResult<T> = Result<T, ExchangeRateFormError>;
+
+/// Errors that can occur while processing exchange rate forms and uploads.
+#[derive(Debug, Error)]
+pub enum ExchangeRateFormError {
+    /// The provided currency code is invalid.
+    #[error("invalid currency code `{value}`")]
+    InvalidCurrency { value: String },
+    /// Both sides of the pair are the same currency.
+    #[error("a currency cannot be converted into itself")]
+    SameCurrency,
+    /// The rate is not a positive decimal with at most six fraction digits.
+    #[error("invalid exchange rate `{value}`")]
+    InvalidRate { value: String },
+    #[error("Error reading csv file")]
+    FileReadError,
+    #[error("Error parsing csv file")]
+    CsvParseError,
+    /// The upload lacks one of the required columns.
+    #[error("upload is missing the required `base_currency`, `quote_currency` or `rate` headers")]
+    MissingHeaders,
+    #[error("row {row} could not be parsed")]
+    MalformedRow { row: usize },
+    #[error("row {row} has invalid currency `{value}`")]
+    RowInvalidCurrency { row: usize, value: String },
+    #[error("row {row} converts a currency into itself")]
+    RowSameCurrency { row: usize },
+    #[error("row {row} has invalid exchange rate `{value}`")]
+    RowInvalidRate { row: usize, value: String },
+    #[error("row {row} repeats the pair {base_currency}/{quote_currency}")]
+    DuplicatePair {
+        row: usize,
+        base_currency: String,
+        quote_currency: String,
+    },
+}
+
+impl From<std::io::Error> for ExchangeRateFormError {
+    fn from(_: std::io::Error) -> Self {
+        ExchangeRateFormError::FileReadError
+    }
+}
+
+impl From<csv::Error> for ExchangeRateFormError {
+    fn from(_: csv::Error) -> Self {
+        ExchangeRateFormError::CsvParseError
+    }
+}
+
+/// Form payload emitted when saving a rate by hand.
+#[derive(Debug, Deserialize)]
+pub struct AddExchangeRateForm {
+    /// Currency being converted from.
+    pub base_currency: String,
+    /// Currency being converted to.
+    pub quote_currency: String,
+    /// Units of the quote currency per unit of the base currency, e.g. `92,5`.
+    pub rate: String,
+}
+
+impl AddExchangeRateForm {
+    /// Validates the payload into a domain [`NewExchangeRate`].
+    pub fn into_new_exchange_rate(self, hub_id: i32) -> ExchangeRateFormResult<NewExchangeRate> {
+        let base_currency = parse_currency(&self.base_currency).ok_or_else(|| {
+            ExchangeRateFormError::InvalidCurrency {
+                value: self.base_currency.trim().to_string(),
+            }
+        })?;
+        let quote_currency = parse_currency(&self.quote_currency).ok_or_else(|| {
+            ExchangeRateFormError::InvalidCurrency {
+                value: self.quote_currency.trim().to_string(),
+            }
+        })?;
+        if base_currency == quote_currency {
+            return Err(ExchangeRateFormError::SameCurrency);
+        }
+        let rate_micros =
+            parse_rate_micros(&self.rate).ok_or_else(|| ExchangeRateFormError::InvalidRate {
+                value: self.rate.trim().to_string(),
+            })?;
+
+        Ok(NewExchangeRate::new(
+            hub_id,
+            base_currency,
+            quote_currency,
+            rate_micros,
+        ))
+    }
+}
+
+#[derive(MultipartForm)]
+/// Multipart form carrying a CSV with `base_currency`, `quote_currency` and
+/// `rate` columns.
+pub struct UploadExchangeRatesForm {
+    #[multipart(limit = "10MB")]
+    /// Uploaded CSV file containing exchange rates.
+    pub csv: TempFile,
+}
+
+impl UploadExchangeRatesForm {
+    /// Parse the uploaded CSV file into a list of [`NewExchangeRate`] records.
+    ///
+    /// Fails on the first invalid row.
+    pub fn into_new_exchange_rates(
+        &mut self,
+        hub_id: i32,
+    ) -> ExchangeRateFormResult<Vec<NewExchangeRate>> {
+        self.csv.file.rewind()?;
+        parse_exchange_rates(self.csv.file.by_ref(), hub_id)
+    }
+}
+
+fn parse_exchange_rates<R: Read>(
+    reader: R,
+    hub_id: i32,
+) -> ExchangeRateFormResult<Vec<NewExchangeRate>> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .trim(Trim::All)
+        .flexible(true)
+        .from_reader(reader);
+
+    let headers = csv_reader.headers()?.clone();
+    let position = |name: &str| headers.iter().position(|header| header == name);
+    let (Some(base_index), Some(quote_index), Some(rate_index)) = (
+        position("base_currency"),
+        position("quote_currency"),
+        position("rate"),
+    ) else {
+        return Err(ExchangeRateFormError::MissingHeaders);
+    };
+
+    let mut rates = Vec::new();
+    let mut seen = HashSet::new();
+    for (index, row) in csv_reader.records().enumerate() {
+        let row_number = index + 2; // account for header row
+        let record = row.map_err(|_| ExchangeRateFormError::MalformedRow { row: row_number })?;
+
+        let currency = |index: usize| {
+            let raw = record.get(index).unwrap_or("");
+            parse_currency(raw).ok_or_else(|| ExchangeRateFormError::RowInvalidCurrency {
+                row: row_number,
+                value: raw.to_string(),
+            })
+        };
+        let base_currency = currency(base_index)?;
+        let quote_currency = currency(quote_index)?;
+        if base_currency == quote_currency {
+            return Err(ExchangeRateFormError::RowSameCurrency { row: row_number });
+        }
+
+        let rate_raw = record.get(rate_index).unwrap_or("");
+        let rate_micros =
+            parse_rate_micros(rate_raw).ok_or_else(|| ExchangeRateFormError::RowInvalidRate {
+                row: row_number,
+                value: rate_raw.to_string(),
+            })?;
+
+        if !seen.insert((base_currency.clone(), quote_currency.clone())) {
+            return Err(ExchangeRateFormError::DuplicatePair {
+                row: row_number,
+                base_currency,
+                quote_currency,
+            });
+        }
+
+        rates.push(NewExchangeRate::new(
+            hub_id,
+            base_currency,
+            quote_currency,
+            rate_micros,
+        ));
+    }
+
+    Ok(rates)
+}
+
+/// Upper-cased ISO 4217 code, or `None` when `input` is not one.
+fn parse_currency(input: &str) -> Option<String> {
+    let trimmed = input.trim();
+    (trimmed.len() == CURRENCY_CODE_LEN && trimmed.chars().all(|ch| ch.is_ascii_alphabetic()))
+        .then(|| trimmed.to_ascii_uppercase())
+}
+
+/// Parses a positive decimal such as `92,5` or `0.0125` into millionths.
+fn parse_rate_micros(input: &str) -> Option<i64> {
+    let normalized: String = input
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .map(|ch| if ch == ',' { '.' } else { ch })
+        .collect();
+
+    let (whole, fraction) = normalized.split_once('.').unwrap_or((&normalized, ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || fraction.len() > RATE_FRACTION_DIGITS
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|ch| ch.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let whole = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<i64>().ok()?
+    };
+    let fraction = format!("{fraction:0<RATE_FRACTION_DIGITS$}")
+        .parse::<i64>()
+        .ok()?;
+    whole
+        .checked_mul(EXCHANGE_RATE_SCALE)?
+        .checked_add(fraction)
+        .filter(|micros| *micros > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_form_parses_decimal_rate() {
+        let form = AddExchangeRateForm {
+            base_currency: " usd ".to_string(),
+            quote_currency: "RUB".to_string(),
+            rate: "92,5".to_string(),
+        };
+
+        let rate = form.into_new_exchange_rate(3).expect("valid form");
+
+        assert_eq!(rate, NewExchangeRate::new(3, "USD", "RUB", 92_500_000));
+    }
+
+    #[test]
+    fn add_form_rejects_invalid_rates_and_pairs() {
+        let form = |base: &str, quote: &str, rate: &str| AddExchangeRateForm {
+            base_currency: base.to_string(),
+            quote_currency: quote.to_string(),
+            rate: rate.to_string(),
+        };
+
+        for rate in ["0", "-1", "1.0000001", "abc", ""] {
+            assert!(matches!(
+                form("USD", "RUB", rate).into_new_exchange_rate(1),
+                Err(ExchangeRateFormError::InvalidRate { .. })
+            ));
+        }
+        assert!(matches!(
+            form("usd", "USD", "1").into_new_exchange_rate(1),
+            Err(ExchangeRateFormError::SameCurrency)
+        ));
+        assert!(matches!(
+            form("US", "RUB", "1").into_new_exchange_rate(1),
+            Err(ExchangeRateFormError::InvalidCurrency { .. })
+        ));
+    }
+
+    #[test]
+    fn upload_reads_rates_and_rejects_repeated_pairs() {
+        let csv = "base_currency,quote_currency,rate\nUSD,RUB,92.5\neur,rub,.99\n";
+        let rates = parse_exchange_rates(csv.as_bytes(), 1).expect("valid upload");
+        assert_eq!(
+            rates,
+            vec![
+                NewExchangeRate::new(1, "USD", "RUB", 92_500_000),
+                NewExchangeRate::new(1, "EUR", "RUB", 990_000),
+            ]
+        );
+
+        let csv = "base_currency,quote_currency,rate\nUSD,RUB,92.5\nusd,rub,93\n";
+        assert!(matches!(
+            parse_exchange_rates(csv.as_bytes(), 1),
+            Err(ExchangeRateFormError::DuplicatePair { row: 3, .. })
+        ));
+        assert!(matches!(
+            parse_exchange_rates("currency,rate\n".as_bytes(), 1),
+            Err(ExchangeRateFormError::MissingHeaders)
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::domain::inventory_movement::NewStockAdjustment;
+
+/// Maximum length allowed for an adjustment note.
+const NOTE_MAX_LEN: usize = 255;
+
+/// Result type returned by the inventory form helpers.
+pub type InventoryFormResult<T> = Result<T, InventoryFormError>;
+
+/// Errors that can occur while processing inventory forms.
+#[derive(Debug, Error)]
+pub enum InventoryFormError {
+    /// The quantity is not a whole number other than zero.
+    #[error("invalid stock quantity `{value}`")]
+    InvalidQuantity { value: String },
+    /// The note exceeds [`NOTE_MAX_LEN`] characters.
+    #[error("note must be at most {NOTE_MAX_LEN} characters")]
+    NoteTooLong,
+}
+
+/// Form payload emitted when adjusting the stock of a product.
+#[derive(Debug, Deserialize)]
+pub struct AdjustStockForm {
+    /// Identifier of the product to adjust.
+    pub product_id: i32,
+    /// Signed change of the quantity on hand, e.g. `24` or `-3`.
+    pub quantity: String,
+    /// Optional reason such as a delivery or a write-off.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl AdjustStockForm {
+    /// Validates the payload into a domain [`NewStockAdjustment`] made by the
+    /// given user.
+    pub fn into_new_stock_adjustment(
+        self,
+        hub_id: i32,
+        changed_by_email: &str,
+        changed_by_name: &str,
+    ) -> InventoryFormResult<NewStockAdjustment> {
+        let raw_quantity = self.quantity.trim();
+        let quantity = raw_quantity
+            .strip_prefix('+')
+            .unwrap_or(raw_quantity)
+            .parse::<i32>()
+            .ok()
+            .filter(|quantity| *quantity != 0)
+            .ok_or_else(|| InventoryFormError::InvalidQuantity {
+                value: raw_quantity.to_string(),
+            })?;
+
+        let mut adjustment = NewStockAdjustment::new(
+            hub_id,
+            self.product_id,
+            quantity,
+            changed_by_email,
+            changed_by_name,
+        );
+
+        if let Some(note) = self
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|note| !note.is_empty())
+        {
+            if note.chars().count() > NOTE_MAX_LEN {
+                return Err(InventoryFormError::NoteTooLong);
+            }
+            adjustment = adjustment.with_note(note);
+        }
+
+        Ok(adjustment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form(quantity: &str, note: Option<&str>) -> AdjustStockForm {
+        AdjustStockForm {
+            product_id: 3,
+            quantity: quantity.to_string(),
+            note: note.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn adjust_form_parses_signed_quantities() {
+        let adjustment = form(" +24 ", Some("  Delivery "))
+            .into_new_stock_adjustment(7, "user@example.com", "User")
+            .expect("valid form");
+        assert_eq!(adjustment.quantity, 24);
+        assert_eq!(adjustment.note.as_deref(), Some("Delivery"));
+        assert_eq!(adjustment.hub_id, 7);
+
+        let adjustment = form("-3", Some(" "))
+            .into_new_stock_adjustment(7, "user@example.com", "User")
+            .expect("valid form");
+        assert_eq!(adjustment.quantity, -3);
+        assert_eq!(adjustment.note, None);
+    }
+
+    #[test]
+    fn adjust_form_rejects_zero_and_fractions() {
+        for quantity in ["", "0", "1.5", "abc"] {
+            assert!(matches!(
+                form(quantity, None).into_new_stock_adjustment(7, "user@example.com", "User"),
+                Err(InventoryFormError::InvalidQuantity { .. })
+            ));
+        }
+
+        let note = "x".repeat(NOTE_MAX_LEN + 1);
+        assert!(matches!(
+            form("1", Some(&note)).into_new_stock_adjustment(7, "user@example.com", "User"),
+            Err(InventoryFormError::NoteTooLong)
+        ));
+    }
+}
@@ -1,5 +1,11 @@
 pub mod categories;
+pub mod customers;
+pub mod exchange_rates;
+pub mod inventory;
 pub mod main;
+pub mod orders;
 pub mod price_levels;
 pub mod products;
+pub mod promotions;
 pub mod tags;
+pub mod tax_categories;
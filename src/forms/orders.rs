@@ -0,0 +1,471 @@
+use serde::Deserialize;
+use thiserror::Error;
+use validator::{ValidationError, ValidationErrors};
+
+use crate::domain::order::OrderStatus;
+use crate::domain::promotion::normalize_code;
+
+/// Maximum length allowed for an order reference.
+const REFERENCE_MAX_LEN: usize = 64;
+
+/// ISO 4217 currency codes are three ASCII alphabetic characters.
+const CURRENCY_CODE_LEN: usize = 3;
+
+/// Result type returned by the order form helpers.
+pub type OrderFormResult<T> = Result<T, OrderFormError>;
+
+/// Errors that can occur while processing order payloads.
+#[derive(Debug, Error)]
+pub enum OrderFormError {
+    /// Validation failures from the `validator` crate.
+    #[error("validation failed: {0}")]
+    Validation(#[from] ValidationErrors),
+    /// The payload did not contain any line items.
+    #[error("order must contain at least one product")]
+    EmptyOrder,
+    /// The submitted status is not a known order status.
+    #[error("unknown order status `{value}`")]
+    UnknownStatus { value: String },
+    /// The product selected for a new line is not a valid identifier.
+    #[error("invalid product identifier `{value}`")]
+    InvalidProductId { value: String },
+    /// A quantity could not be parsed or is out of range.
+    #[error("invalid quantity `{value}`")]
+    InvalidQuantity { value: String },
+}
+
+/// Single line item submitted when placing an order.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlaceOrderItemPayload {
+    /// Identifier of the product being ordered.
+    pub product_id: i32,
+    /// Requested quantity of the product.
+    pub quantity: i32,
+}
+
+/// Payload emitted when placing a new order through the API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlaceOrderPayload {
+    /// Optional customer placing the order.
+    #[serde(default)]
+    pub customer_id: Option<i32>,
+    /// Optional external reference for the order.
+    #[serde(default)]
+    pub reference: Option<String>,
+    /// Optional notes supplied with the order.
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Optional ISO 4217 currency of the order total; defaults to the currency
+    /// of the first product.
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// Products and quantities being ordered.
+    #[serde(default)]
+    pub items: Vec<PlaceOrderItemPayload>,
+    /// Optional promotion code to redeem on the order.
+    #[serde(default)]
+    pub promo_code: Option<String>,
+}
+
+/// Normalized order request that can be passed to the service layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceOrderInput {
+    pub customer_id: Option<i32>,
+    pub reference: Option<String>,
+    pub notes: Option<String>,
+    /// Upper-cased currency of the order total, if requested.
+    pub currency: Option<String>,
+    /// Line items with duplicate products merged, in submission order.
+    pub items: Vec<PlaceOrderItemInput>,
+    /// Normalized promotion code, if any.
+    pub promo_code: Option<String>,
+}
+
+/// Normalized line item of a [`PlaceOrderInput`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaceOrderItemInput {
+    pub product_id: i32,
+    pub quantity: i32,
+}
+
+impl PlaceOrderPayload {
+    /// Validates and normalizes the payload into an order placement request.
+    pub fn into_place_order_request(self) -> OrderFormResult<PlaceOrderInput> {
+        if self.items.is_empty() {
+            return Err(OrderFormError::EmptyOrder);
+        }
+
+        let mut errors = ValidationErrors::new();
+
+        if let Some(id) = self.customer_id
+            && id < 1
+        {
+            errors.add("customer_id", ValidationError::new("invalid_customer_id"));
+        }
+
+        let reference = self.reference.as_deref().and_then(sanitize_inline_text);
+        if let Some(value) = reference.as_ref()
+            && value.chars().count() > REFERENCE_MAX_LEN
+        {
+            errors.add("reference", ValidationError::new("reference_too_long"));
+        }
+
+        let currency = self
+            .currency
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_ascii_uppercase);
+        if let Some(value) = currency.as_ref()
+            && (value.len() != CURRENCY_CODE_LEN
+                || !value.chars().all(|ch| ch.is_ascii_alphabetic()))
+        {
+            errors.add("currency", ValidationError::new("invalid_currency"));
+        }
+
+        let mut items: Vec<PlaceOrderItemInput> = Vec::with_capacity(self.items.len());
+        for item in &self.items {
+            if item.product_id < 1 {
+                errors.add("items", ValidationError::new("invalid_product_id"));
+                continue;
+            }
+            if item.quantity < 1 {
+                errors.add("items", ValidationError::new("invalid_quantity"));
+                continue;
+            }
+
+            match items
+                .iter_mut()
+                .find(|existing| existing.product_id == item.product_id)
+            {
+                Some(existing) => match existing.quantity.checked_add(item.quantity) {
+                    Some(quantity) => existing.quantity = quantity,
+                    None => errors.add("items", ValidationError::new("invalid_quantity")),
+                },
+                None => items.push(PlaceOrderItemInput {
+                    product_id: item.product_id,
+                    quantity: item.quantity,
+                }),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(OrderFormError::Validation(errors));
+        }
+
+        Ok(PlaceOrderInput {
+            customer_id: self.customer_id,
+            reference,
+            notes: self.notes.as_deref().and_then(sanitize_multiline_text),
+            currency,
+            items,
+            promo_code: self
+                .promo_code
+                .as_deref()
+                .map(normalize_code)
+                .filter(|code| !code.is_empty()),
+        })
+    }
+}
+
+/// Form payload emitted when editing an order on its detail page.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EditOrderForm {
+    /// Updated external reference (empty string clears it).
+    #[serde(default)]
+    pub reference: Option<String>,
+    /// Updated notes (empty string clears them).
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Quantities for the existing order lines.
+    #[serde(default)]
+    pub lines: Vec<EditOrderLineForm>,
+    /// Optional product to append as a new line.
+    #[serde(default)]
+    pub add_product_id: Option<String>,
+    /// Quantity of the appended product, defaults to one.
+    #[serde(default)]
+    pub add_quantity: Option<String>,
+}
+
+/// Quantity update for an existing order line.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EditOrderLineForm {
+    /// Position of the line within `Order.products`.
+    pub index: usize,
+    /// New quantity; zero removes the line.
+    pub quantity: String,
+}
+
+/// Sanitized edit request returned by [`EditOrderForm::into_edit_request`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditOrderInput {
+    pub reference: Option<String>,
+    pub notes: Option<String>,
+    /// `(line index, quantity)` pairs; a zero quantity removes the line.
+    pub quantities: Vec<(usize, i32)>,
+    /// Product appended as a new line, if any.
+    pub add_product: Option<PlaceOrderItemInput>,
+}
+
+impl EditOrderForm {
+    /// Validates and sanitizes the payload into an order edit request.
+    pub fn into_edit_request(self) -> OrderFormResult<EditOrderInput> {
+        let reference = self.reference.as_deref().and_then(sanitize_inline_text);
+        if let Some(value) = reference.as_ref()
+            && value.chars().count() > REFERENCE_MAX_LEN
+        {
+            let mut errors = ValidationErrors::new();
+            errors.add("reference", ValidationError::new("reference_too_long"));
+            return Err(OrderFormError::Validation(errors));
+        }
+
+        let mut quantities = Vec::with_capacity(self.lines.len());
+        for line in self.lines {
+            let quantity = parse_quantity(&line.quantity, 0)?;
+            quantities.push((line.index, quantity));
+        }
+
+        let add_product = match self.add_product_id.as_deref().map(str::trim) {
+            None | Some("") | Some("0") => None,
+            Some(raw) => {
+                let product_id = raw
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|id| *id > 0)
+                    .ok_or_else(|| OrderFormError::InvalidProductId {
+                        value: raw.to_string(),
+                    })?;
+                let quantity = match self.add_quantity.as_deref().map(str::trim) {
+                    None | Some("") => 1,
+                    Some(raw) => parse_quantity(raw, 1)?,
+                };
+                Some(PlaceOrderItemInput {
+                    product_id,
+                    quantity,
+                })
+            }
+        };
+
+        Ok(EditOrderInput {
+            reference,
+            notes: self.notes.as_deref().and_then(sanitize_multiline_text),
+            quantities,
+            add_product,
+        })
+    }
+}
+
+fn parse_quantity(raw: &str, min: i32) -> OrderFormResult<i32> {
+    let trimmed = raw.trim();
+    trimmed
+        .parse::<i32>()
+        .ok()
+        .filter(|value| *value >= min)
+        .ok_or_else(|| OrderFormError::InvalidQuantity {
+            value: trimmed.to_string(),
+        })
+}
+
+/// Form payload emitted when moving an order to another status.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransitionOrderForm {
+    /// Name of the target status, for example `Processing`.
+    pub status: String,
+}
+
+impl TransitionOrderForm {
+    /// Parses the requested target status.
+    pub fn into_status(self) -> OrderFormResult<OrderStatus> {
+        let value = self.status.trim();
+        OrderStatus::parse(value).ok_or_else(|| OrderFormError::UnknownStatus {
+            value: value.to_string(),
+        })
+    }
+}
+
+fn sanitize_inline_text(value: &str) -> Option<String> {
+    let collapsed = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        None
+    } else {
+        Some(collapsed)
+    }
+}
+
+fn sanitize_multiline_text(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(product_id: i32, quantity: i32) -> PlaceOrderItemPayload {
+        PlaceOrderItemPayload {
+            product_id,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn into_place_order_request_merges_duplicate_products() {
+        let payload = PlaceOrderPayload {
+            customer_id: Some(3),
+            reference: Some("  ORD   42 ".to_string()),
+            notes: Some("   ".to_string()),
+            currency: Some(" eur ".to_string()),
+            items: vec![item(1, 2), item(2, 1), item(1, 3)],
+            promo_code: Some(" summer10 ".to_string()),
+        };
+
+        let input = payload
+            .into_place_order_request()
+            .expect("expected payload to be valid");
+
+        assert_eq!(input.customer_id, Some(3));
+        assert_eq!(input.reference.as_deref(), Some("ORD 42"));
+        assert!(input.notes.is_none());
+        assert_eq!(input.currency.as_deref(), Some("EUR"));
+        assert_eq!(input.promo_code.as_deref(), Some("SUMMER10"));
+        assert_eq!(
+            input.items,
+            vec![
+                PlaceOrderItemInput {
+                    product_id: 1,
+                    quantity: 5
+                },
+                PlaceOrderItemInput {
+                    product_id: 2,
+                    quantity: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn into_place_order_request_rejects_empty_items() {
+        let payload = PlaceOrderPayload {
+            customer_id: None,
+            reference: None,
+            notes: None,
+            currency: None,
+            items: Vec::new(),
+            promo_code: None,
+        };
+
+        let result = payload.into_place_order_request();
+
+        assert!(matches!(result, Err(OrderFormError::EmptyOrder)));
+    }
+
+    #[test]
+    fn into_edit_request_parses_lines_and_new_product() {
+        let form = EditOrderForm {
+            reference: Some("  ".to_string()),
+            notes: Some(" Deliver after 5pm ".to_string()),
+            lines: vec![
+                EditOrderLineForm {
+                    index: 0,
+                    quantity: "3".to_string(),
+                },
+                EditOrderLineForm {
+                    index: 1,
+                    quantity: "0".to_string(),
+                },
+            ],
+            add_product_id: Some("12".to_string()),
+            add_quantity: Some(String::new()),
+        };
+
+        let input = form.into_edit_request().expect("expected valid edit");
+
+        assert!(input.reference.is_none());
+        assert_eq!(input.notes.as_deref(), Some("Deliver after 5pm"));
+        assert_eq!(input.quantities, vec![(0, 3), (1, 0)]);
+        assert_eq!(
+            input.add_product,
+            Some(PlaceOrderItemInput {
+                product_id: 12,
+                quantity: 1
+            })
+        );
+    }
+
+    #[test]
+    fn into_edit_request_rejects_negative_quantity() {
+        let form = EditOrderForm {
+            reference: None,
+            notes: None,
+            lines: vec![EditOrderLineForm {
+                index: 0,
+                quantity: "-1".to_string(),
+            }],
+            add_product_id: None,
+            add_quantity: None,
+        };
+
+        assert!(matches!(
+            form.into_edit_request(),
+            Err(OrderFormError::InvalidQuantity { .. })
+        ));
+    }
+
+    #[test]
+    fn transition_form_parses_known_status() {
+        let form = TransitionOrderForm {
+            status: " Processing ".to_string(),
+        };
+
+        assert_eq!(form.into_status().ok(), Some(OrderStatus::Processing));
+    }
+
+    #[test]
+    fn transition_form_rejects_unknown_status() {
+        let form = TransitionOrderForm {
+            status: "Shipped".to_string(),
+        };
+
+        assert!(matches!(
+            form.into_status(),
+            Err(OrderFormError::UnknownStatus { .. })
+        ));
+    }
+
+    #[test]
+    fn into_place_order_request_rejects_invalid_currency() {
+        let payload = PlaceOrderPayload {
+            customer_id: None,
+            reference: None,
+            notes: None,
+            currency: Some("rubles".to_string()),
+            items: vec![item(1, 1)],
+            promo_code: None,
+        };
+
+        let result = payload.into_place_order_request();
+
+        assert!(matches!(result, Err(OrderFormError::Validation(_))));
+    }
+
+    #[test]
+    fn into_place_order_request_rejects_non_positive_quantity() {
+        let payload = PlaceOrderPayload {
+            customer_id: None,
+            reference: None,
+            notes: None,
+            currency: None,
+            items: vec![item(1, 0)],
+            promo_code: None,
+        };
+
+        let result = payload.into_place_order_request();
+
+        assert!(matches!(result, Err(OrderFormError::Validation(_))));
+    }
+}
@@ -1,13 +1,16 @@
+use std::collections::HashSet;
 use std::io::{Read, Seek};
 
 use actix_multipart::form::{MultipartForm, tempfile::TempFile};
 use csv::Trim;
-use pushkind_common::routes::empty_string_as_none;
 use serde::Deserialize;
 use thiserror::Error;
 use validator::{Validate, ValidationError, ValidationErrors};
 
-use crate::domain::price_level::{NewPriceLevel, UpdatePriceLevel};
+use crate::domain::phone::normalize_phone;
+use crate::domain::price_level::{
+    NewPriceLevel, PriceAdjustmentKind, PriceDerivation, PriceRounding, UpdatePriceLevel,
+};
 
 /// Maximum length allowed for a price level name.
 const NAME_MAX_LEN: usize = 128;
@@ -37,10 +40,16 @@ pub enum PriceLevelFormError {
     /// CSV parsing failures.
     #[error("failed to parse CSV: {0}")]
     Csv(#[from] csv::Error),
+    /// The selected base price level identifier is not a valid id.
+    #[error("invalid base price level `{value}`")]
+    InvalidBasePriceLevel { value: String },
+    /// The adjustment is not a number with up to two decimals.
+    #[error("invalid price adjustment `{value}`")]
+    InvalidAdjustment { value: String },
 }
 
 /// Form payload emitted when submitting the "Add price level" form.
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Default, Deserialize, Validate)]
 pub struct AddPriceLevelForm {
     /// Name entered by the user.
     #[validate(length(min = 1, max = NAME_MAX_LEN_VALIDATOR))]
@@ -48,6 +57,21 @@ pub struct AddPriceLevelForm {
     /// Is this a default price level?
     #[serde(default)]
     pub default: bool,
+    /// Whether the level's prices include tax.
+    #[serde(default)]
+    pub prices_include_tax: bool,
+    /// Level the prices are derived from; empty for explicit-only levels.
+    #[serde(default)]
+    pub base_price_level_id: Option<String>,
+    /// `Percent` or `Fixed`, see [`PriceAdjustmentKind`].
+    #[serde(default)]
+    pub adjustment_kind: Option<String>,
+    /// Percentage or amount added to the base price, e.g. `-15` or `2.50`.
+    #[serde(default)]
+    pub adjustment: Option<String>,
+    /// Rounding rule name, see [`PriceRounding`].
+    #[serde(default)]
+    pub rounding: Option<String>,
 }
 
 /// Payload emitted when assigning a price level to a client.
@@ -94,19 +118,20 @@ impl AssignClientPriceLevelPayload {
             );
         }
 
+        let phone = self
+            .phone
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty());
+        let normalized_phone = phone.and_then(normalize_phone);
+        if phone.is_some() && normalized_phone.is_none() {
+            errors.add("phone", ValidationError::new("invalid_phone"));
+        }
+
         if !errors.is_empty() {
             return Err(PriceLevelFormError::Validation(errors));
         }
 
-        let normalized_phone = self.phone.and_then(|value| {
-            let trimmed = value.trim().to_string();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed)
-            }
-        });
-
         Ok(AssignClientPriceLevelInput {
             hub_id: self.hub_id,
             name: sanitized_name,
@@ -137,12 +162,24 @@ impl AddPriceLevelForm {
             return Err(PriceLevelFormError::EmptyName);
         }
 
-        Ok(NewPriceLevel::new(hub_id, sanitized_name, self.default))
+        let derivation = parse_derivation(
+            self.base_price_level_id.as_deref(),
+            self.adjustment_kind.as_deref(),
+            self.adjustment.as_deref(),
+            self.rounding.as_deref(),
+        )?;
+
+        let mut new_level = NewPriceLevel::new(hub_id, sanitized_name, self.default)
+            .with_prices_include_tax(self.prices_include_tax);
+        if let Some(derivation) = derivation {
+            new_level = new_level.with_derivation(derivation);
+        }
+        Ok(new_level)
     }
 }
 
 /// Form payload emitted when submitting the "Edit price level" form.
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Default, Deserialize, Validate)]
 pub struct EditPriceLevelForm {
     /// Updated name entered by the user.
     #[validate(length(min = 1, max = NAME_MAX_LEN_VALIDATOR))]
@@ -150,6 +187,21 @@ pub struct EditPriceLevelForm {
     /// Updated default flag for the price level.
     #[serde(default)]
     pub default: bool,
+    /// Whether the level's prices include tax.
+    #[serde(default)]
+    pub prices_include_tax: bool,
+    /// Level the prices are derived from; empty for explicit-only levels.
+    #[serde(default)]
+    pub base_price_level_id: Option<String>,
+    /// `Percent` or `Fixed`, see [`PriceAdjustmentKind`].
+    #[serde(default)]
+    pub adjustment_kind: Option<String>,
+    /// Percentage or amount added to the base price, e.g. `-15` or `2.50`.
+    #[serde(default)]
+    pub adjustment: Option<String>,
+    /// Rounding rule name, see [`PriceRounding`].
+    #[serde(default)]
+    pub rounding: Option<String>,
 }
 
 impl EditPriceLevelForm {
@@ -162,7 +214,19 @@ impl EditPriceLevelForm {
             return Err(PriceLevelFormError::EmptyName);
         }
 
-        Ok(UpdatePriceLevel::new(sanitized_name, self.default))
+        let derivation = parse_derivation(
+            self.base_price_level_id.as_deref(),
+            self.adjustment_kind.as_deref(),
+            self.adjustment.as_deref(),
+            self.rounding.as_deref(),
+        )?;
+
+        let mut updates = UpdatePriceLevel::new(sanitized_name, self.default)
+            .with_prices_include_tax(self.prices_include_tax);
+        if let Some(derivation) = derivation {
+            updates = updates.with_derivation(derivation);
+        }
+        Ok(updates)
     }
 }
 
@@ -181,6 +245,36 @@ pub enum UploadPriceLevelsFormError {
     FileReadError,
     #[error("Error parsing csv file")]
     CsvParseError,
+    #[error("row {row} could not be parsed")]
+    MalformedRow { row: usize },
+    #[error("row {row} is missing a price level name")]
+    MissingName { row: usize },
+    #[error("row {row} repeats price level `{name}`")]
+    DuplicateName { row: usize, name: String },
+    #[error("row {row}: price level `{name}` already exists")]
+    AlreadyExists { row: usize, name: String },
+}
+
+impl UploadPriceLevelsFormError {
+    /// Line of the uploaded CSV the error refers to, if it is a row error.
+    pub fn row(&self) -> Option<usize> {
+        match self {
+            Self::MalformedRow { row }
+            | Self::MissingName { row }
+            | Self::DuplicateName { row, .. }
+            | Self::AlreadyExists { row, .. } => Some(*row),
+            Self::FileReadError | Self::CsvParseError => None,
+        }
+    }
+}
+
+/// Every row of a price level upload, parsed without stopping at the first error.
+#[derive(Debug, Default)]
+pub struct PriceLevelUploadReport {
+    /// Rows that parsed successfully as `(line, price level)` pairs.
+    pub rows: Vec<(usize, NewPriceLevel)>,
+    /// Errors of the rows that could not be parsed, in file order.
+    pub errors: Vec<UploadPriceLevelsFormError>,
 }
 
 impl From<std::io::Error> for UploadPriceLevelsFormError {
@@ -196,43 +290,171 @@ impl From<csv::Error> for UploadPriceLevelsFormError {
 }
 
 impl UploadPriceLevelsForm {
+    /// Read the uploaded CSV as text so it can be re-submitted after a preview.
+    pub fn content(&mut self) -> Result<String, UploadPriceLevelsFormError> {
+        self.csv.file.rewind()?;
+        let mut content = String::new();
+        self.csv.file.read_to_string(&mut content)?;
+        Ok(content)
+    }
+
     /// Parse the uploaded CSV file into a list of [`NewPriceLevel`] records.
+    ///
+    /// Fails on the first invalid row, see [`Self::into_upload_report`] to collect all of them.
     pub fn into_new_price_levels(
         &mut self,
         hub_id: i32,
     ) -> Result<Vec<NewPriceLevel>, UploadPriceLevelsFormError> {
+        let report = self.into_upload_report(hub_id)?;
+        if let Some(error) = report.errors.into_iter().next() {
+            return Err(error);
+        }
+        Ok(report.rows.into_iter().map(|(_, level)| level).collect())
+    }
+
+    /// Parse every row of the uploaded CSV, collecting row errors instead of
+    /// stopping at the first one.
+    pub fn into_upload_report(
+        &mut self,
+        hub_id: i32,
+    ) -> Result<PriceLevelUploadReport, UploadPriceLevelsFormError> {
         self.csv.file.rewind()?;
         parse_price_levels(self.csv.file.by_ref(), hub_id)
     }
 }
 
-#[derive(Deserialize)]
-struct PriceLevelCsvRow {
-    #[serde(default, deserialize_with = "empty_string_as_none")]
-    name: Option<String>,
-}
-
 fn parse_price_levels<R: Read>(
     reader: R,
     hub_id: i32,
-) -> Result<Vec<NewPriceLevel>, UploadPriceLevelsFormError> {
+) -> Result<PriceLevelUploadReport, UploadPriceLevelsFormError> {
     let mut csv_reader = csv::ReaderBuilder::new()
         .trim(Trim::All)
+        .flexible(true)
         .from_reader(reader);
 
-    let mut price_levels = Vec::new();
+    let mut report = PriceLevelUploadReport::default();
+
+    // Files without a `name` column carry no price levels.
+    let Some(name_index) = csv_reader
+        .headers()?
+        .iter()
+        .position(|header| header == "name")
+    else {
+        return Ok(report);
+    };
+
+    let mut seen = HashSet::new();
+    for (index, row) in csv_reader.records().enumerate() {
+        let row_number = index + 2; // account for header row
+        let Ok(record) = row else {
+            report
+                .errors
+                .push(UploadPriceLevelsFormError::MalformedRow { row: row_number });
+            continue;
+        };
 
-    for row in csv_reader.deserialize::<PriceLevelCsvRow>() {
-        let record = row?;
+        let name = sanitize_plain_text(record.get(name_index).unwrap_or(""));
+        if name.is_empty() {
+            report
+                .errors
+                .push(UploadPriceLevelsFormError::MissingName { row: row_number });
+            continue;
+        }
+        if !seen.insert(name.to_lowercase()) {
+            report
+                .errors
+                .push(UploadPriceLevelsFormError::DuplicateName {
+                    row: row_number,
+                    name,
+                });
+            continue;
+        }
 
-        if let Some(name) = record.name {
-            let price_level = NewPriceLevel::new(hub_id, name, false);
+        report
+            .rows
+            .push((row_number, NewPriceLevel::new(hub_id, name, false)));
+    }
 
-            price_levels.push(price_level);
-        }
+    Ok(report)
+}
+
+/// Builds the derivation rule from the raw form fields.
+///
+/// An empty base level means the level only uses explicit rates; the other
+/// fields are then ignored.
+fn parse_derivation(
+    base_price_level_id: Option<&str>,
+    adjustment_kind: Option<&str>,
+    adjustment: Option<&str>,
+    rounding: Option<&str>,
+) -> PriceLevelFormResult<Option<PriceDerivation>> {
+    let Some(base_raw) = base_price_level_id
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let base_price_level_id = base_raw
+        .parse::<i32>()
+        .ok()
+        .filter(|id| *id > 0)
+        .ok_or_else(|| PriceLevelFormError::InvalidBasePriceLevel {
+            value: base_raw.to_string(),
+        })?;
+
+    let adjustment_raw = adjustment.map(str::trim).unwrap_or("");
+    let value = if adjustment_raw.is_empty() {
+        0
+    } else {
+        parse_hundredths(adjustment_raw).ok_or_else(|| PriceLevelFormError::InvalidAdjustment {
+            value: adjustment_raw.to_string(),
+        })?
+    };
+
+    let derivation = PriceDerivation::new(base_price_level_id);
+    let derivation = match PriceAdjustmentKind::from(adjustment_kind.unwrap_or("").trim()) {
+        PriceAdjustmentKind::Percent => derivation.with_percent(value),
+        PriceAdjustmentKind::Fixed => derivation.with_fixed(value),
+    };
+
+    Ok(Some(derivation.with_rounding(PriceRounding::from(
+        rounding.unwrap_or("").trim(),
+    ))))
+}
+
+/// Parses a signed decimal with up to two fraction digits into hundredths,
+/// so `-15` becomes `-1500` and `2,5` becomes `250`.
+pub(crate) fn parse_hundredths(input: &str) -> Option<i64> {
+    let normalized: String = input
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .map(|ch| match ch {
+            ',' => '.',
+            '\u{2212}' => '-',
+            other => other,
+        })
+        .collect();
+
+    let (negative, unsigned) = match normalized.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, normalized.strip_prefix('+').unwrap_or(&normalized)),
+    };
+
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if whole.is_empty()
+        || fraction.len() > 2
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|ch| ch.is_ascii_digit())
+    {
+        return None;
     }
 
-    Ok(price_levels)
+    let fraction_cents = format!("{fraction:0<2}").parse::<i64>().ok()?;
+    let hundredths = whole.parse::<i64>().ok()?.checked_mul(100)? + fraction_cents;
+    Some(if negative { -hundredths } else { hundredths })
 }
 
 fn sanitize_plain_text(input: &str) -> String {
@@ -269,6 +491,7 @@ mod tests {
         let form = AddPriceLevelForm {
             name: "  Premium\tLevel  ".to_string(),
             default: false,
+            ..Default::default()
         };
 
         let new_level = form.into_new_price_level(5).expect("expected success");
@@ -277,13 +500,48 @@ mod tests {
         assert_eq!(new_level.name, "Premium Level");
     }
 
+    #[test]
+    fn add_price_level_form_parses_derivation() {
+        let form = AddPriceLevelForm {
+            name: "Wholesale".to_string(),
+            base_price_level_id: Some("3".to_string()),
+            adjustment_kind: Some("Percent".to_string()),
+            adjustment: Some(" -12,5 ".to_string()),
+            rounding: Some("Whole".to_string()),
+            ..Default::default()
+        };
+
+        let new_level = form.into_new_price_level(5).expect("expected success");
+
+        assert_eq!(
+            new_level.derivation,
+            Some(
+                PriceDerivation::new(3)
+                    .with_percent(-1250)
+                    .with_rounding(PriceRounding::Whole)
+            )
+        );
+
+        let form = EditPriceLevelForm {
+            name: "Wholesale".to_string(),
+            base_price_level_id: Some("3".to_string()),
+            adjustment: Some("1.234".to_string()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            form.into_update_price_level(),
+            Err(PriceLevelFormError::InvalidAdjustment { .. })
+        ));
+    }
+
     #[test]
     fn assign_client_price_level_payload_validates_positive_ids() {
         let payload = AssignClientPriceLevelPayload {
             hub_id: 9,
             name: "   User Name  ".to_string(),
             email: "USER@example.com".to_string(),
-            phone: Some("  +1999  ".to_string()),
+            phone: Some("  8 (900) 123-45-67  ".to_string()),
             price_level_id: Some(3),
         };
 
@@ -294,7 +552,7 @@ mod tests {
         assert_eq!(assignment.hub_id, 9);
         assert_eq!(assignment.name, "User Name");
         assert_eq!(assignment.email, "user@example.com");
-        assert_eq!(assignment.phone.as_deref(), Some("+1999"));
+        assert_eq!(assignment.phone.as_deref(), Some("+79001234567"));
         assert_eq!(assignment.price_level_id, Some(3));
     }
 
@@ -318,6 +576,7 @@ mod tests {
         let form = AddPriceLevelForm {
             name: "   ".to_string(),
             default: false,
+            ..Default::default()
         };
 
         let result = form.into_new_price_level(1);
@@ -330,6 +589,7 @@ mod tests {
         let form = EditPriceLevelForm {
             name: "  Updated\nName  ".to_string(),
             default: true,
+            ..Default::default()
         };
 
         let update = form.into_update_price_level().expect("expected success");
@@ -343,6 +603,7 @@ mod tests {
         let form = EditPriceLevelForm {
             name: " \t".to_string(),
             default: false,
+            ..Default::default()
         };
 
         let result = form.into_update_price_level();
@@ -385,6 +646,27 @@ mod tests {
         assert!(price_levels.is_empty());
     }
 
+    #[test]
+    fn upload_report_collects_every_row_error() {
+        let mut form = build_upload_form("name\nSilver\n\"\"\nsilver\nGold\n");
+
+        let report = form.into_upload_report(4).expect("expected report");
+
+        let names: Vec<&str> = report
+            .rows
+            .iter()
+            .map(|(_, level)| level.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Silver", "Gold"]);
+        assert!(matches!(
+            report.errors.as_slice(),
+            [
+                UploadPriceLevelsFormError::MissingName { row: 3 },
+                UploadPriceLevelsFormError::DuplicateName { row: 4, .. },
+            ]
+        ));
+    }
+
     fn build_upload_form(csv: &str) -> UploadPriceLevelsForm {
         let mut file = NamedTempFile::new().expect("create temp file");
         file.write_all(csv.as_bytes()).expect("write csv file");
@@ -1,17 +1,24 @@
-use std::{collections::HashMap, io::Seek};
+use std::{
+    collections::HashMap,
+    io::{Read, Seek},
+};
 
-use actix_multipart::form::{MultipartForm, tempfile::TempFile};
+use actix_multipart::form::{MultipartForm, tempfile::TempFile, text::Text};
+use chrono::NaiveDateTime;
 use csv::{StringRecord, Trim};
-use serde::Deserialize;
 use serde::de::{Deserializer, Error as DeError};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 use validator::{Validate, ValidationErrors};
 
 use crate::domain::{
-    price_level::PriceLevel,
-    product::{NewProduct, UpdateProduct},
+    money::Money,
+    price_level::{PriceAdjustmentKind, PriceLevel, PriceRounding},
+    product::{NewProduct, ProductBulkAction, UpdateProduct},
+    product_price_level::{NewProductPriceLevelRate, PriceLevelRepricing},
 };
+use crate::forms::price_levels::parse_hundredths;
 
 /// Maximum allowed length for a product name.
 const NAME_MAX_LEN: usize = 128;
@@ -29,6 +36,16 @@ const UNITS_MAX_LEN_VALIDATOR: u64 = UNITS_MAX_LEN as u64;
 const CURRENCY_CODE_LEN: usize = 3;
 const CURRENCY_CODE_LEN_VALIDATOR: u64 = CURRENCY_CODE_LEN as u64;
 
+/// Separator between category names in the `category` upload column.
+pub const CATEGORY_PATH_SEPARATOR: char = '/';
+
+/// Separator between tag names in the `tags` upload column.
+pub const TAG_SEPARATOR: char = ',';
+
+/// Upload column listing the price levels whose prices are derived, separated
+/// by [`TAG_SEPARATOR`]. Prices of those levels in the row are ignored.
+pub const DERIVED_PRICES_HEADER: &str = "derived_prices";
+
 /// Result type returned by the product form helpers.
 pub type ProductFormResult<T> = Result<T, ProductFormError>;
 
@@ -81,6 +98,76 @@ pub enum ProductFormError {
     /// The provided category identifier could not be parsed.
     #[error("invalid category id `{value}`")]
     InvalidCategoryId { value: String },
+    /// The provided tax category identifier could not be parsed.
+    #[error("invalid tax category id `{value}`")]
+    InvalidTaxCategoryId { value: String },
+    /// A bulk action was submitted without any selected product.
+    #[error("no products selected")]
+    NoProductsSelected,
+    /// The submitted bulk action is not supported.
+    #[error("unknown bulk action `{value}`")]
+    UnknownBulkAction { value: String },
+    /// The bulk tag action was submitted without any tag.
+    #[error("no tags selected")]
+    NoTagsSelected,
+    /// A CSV row could not be read.
+    #[error("row {row} is malformed: {message}")]
+    UploadMalformedRow { row: usize, message: String },
+    /// A CSV row referenced a category that does not exist.
+    #[error("row {row} references unknown category `{path}`")]
+    UploadUnknownCategory { row: usize, path: String },
+    /// A CSV row referenced a category name shared by several categories.
+    #[error("row {row} references ambiguous category `{name}`, use its full path")]
+    UploadAmbiguousCategory { row: usize, name: String },
+    /// A CSV row referenced a tag that does not exist.
+    #[error("row {row} references unknown tag `{name}`")]
+    UploadUnknownTag { row: usize, name: String },
+    /// A CSV row contained an unreadable product identifier.
+    #[error("row {row} has invalid product id `{value}`")]
+    UploadInvalidProductId { row: usize, value: String },
+    /// A CSV row referenced a product that does not exist in the hub.
+    #[error("row {row} references unknown product {product_id}")]
+    UploadUnknownProduct { row: usize, product_id: i32 },
+    /// A scheduled price was submitted with an unreadable date.
+    #[error("invalid date `{value}`")]
+    InvalidScheduleDate { value: String },
+    /// A scheduled price ends before it starts.
+    #[error("scheduled price must end after it starts")]
+    ScheduleEndsBeforeStart,
+    /// A quantity break was submitted with an unreadable minimum quantity.
+    #[error("invalid minimum quantity `{value}` for price level `{price_level}`")]
+    InvalidMinQuantity { price_level: String, value: String },
+    /// The same quantity break was submitted twice for a price level.
+    #[error("price level `{price_level}` has several prices for {min_quantity}+ units")]
+    DuplicatePriceTier {
+        price_level: String,
+        min_quantity: i32,
+    },
+    /// The repricing adjustment is not a number with up to two decimals.
+    #[error("invalid price adjustment `{value}`")]
+    InvalidAdjustment { value: String },
+    /// The provided tag identifier could not be parsed.
+    #[error("invalid tag id `{value}`")]
+    InvalidTagId { value: String },
+}
+
+impl ProductFormError {
+    /// Line of the uploaded CSV the error refers to, if it is a row error.
+    pub fn row(&self) -> Option<usize> {
+        match self {
+            Self::UploadMissingName { row }
+            | Self::UploadMissingCurrency { row }
+            | Self::UploadInvalidCurrency { row, .. }
+            | Self::UploadInvalidPrice { row, .. }
+            | Self::UploadMalformedRow { row, .. }
+            | Self::UploadUnknownCategory { row, .. }
+            | Self::UploadAmbiguousCategory { row, .. }
+            | Self::UploadUnknownTag { row, .. }
+            | Self::UploadInvalidProductId { row, .. }
+            | Self::UploadUnknownProduct { row, .. } => Some(*row),
+            _ => None,
+        }
+    }
 }
 
 fn deserialize_optional_bool<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
@@ -137,6 +224,12 @@ pub struct AddProductForm {
     /// Optional category identifier selected by the user.
     #[serde(default)]
     pub category_id: Option<i32>,
+    /// Optional tax category identifier selected by the user.
+    #[serde(default)]
+    pub tax_category_id: Option<i32>,
+    /// Whether orders may exceed the available stock; allowed when omitted.
+    #[serde(default, deserialize_with = "deserialize_optional_bool")]
+    pub allow_oversell: Option<bool>,
     /// Optional price level amounts submitted with the product.
     #[serde(default)]
     pub price_levels: Vec<AddProductPriceLevelForm>,
@@ -148,6 +241,9 @@ pub struct AddProductPriceLevelForm {
     pub price_level_id: i32,
     #[serde(default)]
     pub price: Option<String>,
+    /// Smallest order line quantity the price applies to, one when empty.
+    #[serde(default)]
+    pub min_quantity: Option<String>,
 }
 
 impl AddProductForm {
@@ -214,6 +310,14 @@ impl AddProductForm {
             new_product = new_product.with_category_id(category_id);
         }
 
+        if let Some(tax_category_id) = self.tax_category_id.and_then(normalize_category_id) {
+            new_product = new_product.with_tax_category_id(tax_category_id);
+        }
+
+        if let Some(allow_oversell) = self.allow_oversell {
+            new_product = new_product.with_allow_oversell(allow_oversell);
+        }
+
         let price_level_map: HashMap<i32, &PriceLevel> =
             price_levels.iter().map(|level| (level.id, level)).collect();
 
@@ -234,41 +338,108 @@ impl AddProductForm {
                 },
             )?;
 
-            let price_cents = parse_price_to_cents(trimmed).ok_or_else(|| {
+            let price = Money::parse(trimmed, new_product.currency.as_str()).map_err(|_| {
                 ProductFormError::InvalidPriceLevelAmount {
                     price_level: price_level.name.clone(),
                     value: raw_price.to_string(),
                 }
             })?;
 
+            let min_quantity = match entry.min_quantity.as_deref().map(str::trim) {
+                None | Some("") => 1,
+                Some(value) => value
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|quantity| *quantity >= 1)
+                    .ok_or_else(|| ProductFormError::InvalidMinQuantity {
+                        price_level: price_level.name.clone(),
+                        value: value.to_string(),
+                    })?,
+            };
+
+            if parsed_price_levels
+                .iter()
+                .any(|parsed: &NewProductUploadPriceLevel| {
+                    parsed.price_level_id == price_level.id && parsed.min_quantity == min_quantity
+                })
+            {
+                return Err(ProductFormError::DuplicatePriceTier {
+                    price_level: price_level.name.clone(),
+                    min_quantity,
+                });
+            }
+
             parsed_price_levels.push(NewProductUploadPriceLevel {
                 price_level_id: price_level.id,
-                price_cents,
+                price,
+                min_quantity,
             });
         }
 
         Ok(NewProductUpload {
+            product_id: None,
             product: new_product,
             price_levels: parsed_price_levels,
+            category: None,
+            tags: Vec::new(),
         })
     }
 }
 
+/// How uploaded rows are applied to products that already exist.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProductImportMode {
+    /// Every row creates a new product unless an identical one exists.
+    #[default]
+    Create,
+    /// Rows whose SKU matches a product of the hub update it in place.
+    Upsert,
+}
+
 /// Multipart-backed upload payload for bulk product creation.
 #[derive(MultipartForm)]
 pub struct UploadProductsForm {
     #[multipart(limit = "10MB")]
     /// Uploaded CSV containing product data.
     pub csv: TempFile,
+    /// Import mode selected in the upload form, defaults to create.
+    pub mode: Option<Text<ProductImportMode>>,
+    /// Whether unknown categories and tags are created instead of rejected.
+    pub create_missing: Option<Text<bool>>,
 }
 
 /// Sanitized product plus associated price levels parsed from an upload row.
 #[derive(Debug, Clone)]
 pub struct NewProductUpload {
+    /// Existing product the row refers to, from the `id` column of an export.
+    pub product_id: Option<i32>,
     /// Product fields extracted from the CSV row.
     pub product: NewProduct,
     /// Optional price level amounts supplied for the product.
     pub price_levels: Vec<NewProductUploadPriceLevel>,
+    /// Category name or path such as `Drinks/Juice`, resolved by the service layer.
+    pub category: Option<String>,
+    /// Tag names, resolved by the service layer.
+    pub tags: Vec<String>,
+}
+
+/// Every row of a product upload, parsed without stopping at the first error.
+#[derive(Debug, Default)]
+pub struct ProductUploadReport {
+    /// Rows that parsed successfully, in file order.
+    pub rows: Vec<ProductUploadRow>,
+    /// Errors of the rows that could not be parsed, in file order.
+    pub errors: Vec<ProductFormError>,
+}
+
+/// Successfully parsed upload row together with its line in the file.
+#[derive(Debug, Clone)]
+pub struct ProductUploadRow {
+    /// Line number in the uploaded CSV, counting the header as line 1.
+    pub row: usize,
+    /// Parsed product payload.
+    pub upload: NewProductUpload,
 }
 
 /// Price level entry parsed for a newly uploaded product.
@@ -276,17 +447,56 @@ pub struct NewProductUpload {
 pub struct NewProductUploadPriceLevel {
     /// Identifier of the price level supplied in the CSV.
     pub price_level_id: i32,
-    /// Price represented in the smallest currency unit (for example cents).
-    pub price_cents: i32,
+    /// Price in the currency of the product.
+    pub price: Money,
+    /// Smallest order line quantity the price applies to.
+    pub min_quantity: i32,
 }
 
 impl UploadProductsForm {
+    /// Import mode requested by the user.
+    pub fn mode(&self) -> ProductImportMode {
+        self.mode.as_ref().map(|mode| **mode).unwrap_or_default()
+    }
+
+    /// Whether unknown categories and tags should be created during import.
+    pub fn create_missing(&self) -> bool {
+        self.create_missing.as_ref().is_some_and(|flag| **flag)
+    }
+
+    /// Read the uploaded CSV as text so it can be re-submitted after a preview.
+    pub fn content(&mut self) -> ProductFormResult<String> {
+        self.csv.file.rewind()?;
+        let mut content = String::new();
+        self.csv.file.read_to_string(&mut content)?;
+        Ok(content)
+    }
+
     /// Parse the uploaded CSV and convert it into product payloads with optional price levels.
+    ///
+    /// Fails on the first invalid row, see [`Self::into_upload_report`] to collect all of them.
     pub fn into_new_products(
         &mut self,
         hub_id: i32,
         price_levels: &[PriceLevel],
     ) -> ProductFormResult<Vec<NewProductUpload>> {
+        let report = self.into_upload_report(hub_id, price_levels)?;
+        if let Some(error) = report.errors.into_iter().next() {
+            return Err(error);
+        }
+        Ok(report.rows.into_iter().map(|row| row.upload).collect())
+    }
+
+    /// Parse every row of the uploaded CSV, collecting row errors instead of
+    /// stopping at the first one.
+    ///
+    /// Only problems affecting the whole file, such as missing headers, are
+    /// returned as an error.
+    pub fn into_upload_report(
+        &mut self,
+        hub_id: i32,
+        price_levels: &[PriceLevel],
+    ) -> ProductFormResult<ProductUploadReport> {
         self.csv.file.rewind()?;
         let reader_source = self.csv.file.as_file_mut();
         let mut reader = csv::ReaderBuilder::new()
@@ -305,102 +515,184 @@ impl UploadProductsForm {
             .currency_index
             .ok_or(ProductFormError::MissingRequiredHeaders)?;
 
-        let price_level_columns = locate_price_level_headers(&headers, price_levels);
+        let layout = ProductUploadLayout {
+            headers: header_indexes,
+            name_index,
+            currency_index,
+            price_level_columns: locate_price_level_headers(&headers, price_levels),
+        };
 
-        let mut products = Vec::new();
-        let mut processed_rows = 0;
+        let mut report = ProductUploadReport::default();
 
         for (index, row) in reader.records().enumerate() {
-            processed_rows += 1;
             let row_number = index + 2; // account for header row
-            let record = row?;
-
-            let raw_name = record.get(name_index).unwrap_or("");
-            let sanitized_name = sanitize_inline_text(raw_name);
-            if sanitized_name.is_empty() {
-                return Err(ProductFormError::UploadMissingName { row: row_number });
-            }
-
-            let currency_raw = record.get(currency_index).unwrap_or("").trim();
-            if currency_raw.is_empty() {
-                return Err(ProductFormError::UploadMissingCurrency { row: row_number });
+            let parsed = row
+                .map_err(|err| ProductFormError::UploadMalformedRow {
+                    row: row_number,
+                    message: err.to_string(),
+                })
+                .and_then(|record| layout.parse_row(&record, row_number, hub_id));
+
+            match parsed {
+                Ok(upload) => report.rows.push(ProductUploadRow {
+                    row: row_number,
+                    upload,
+                }),
+                Err(err) => report.errors.push(err),
             }
+        }
 
-            let currency = match sanitize_currency(currency_raw) {
-                Ok(value) => value,
-                Err(ProductFormError::InvalidCurrency { value }) => {
-                    return Err(ProductFormError::UploadInvalidCurrency {
-                        row: row_number,
-                        value,
-                    });
-                }
-                Err(other) => return Err(other),
-            };
+        if report.rows.is_empty() && report.errors.is_empty() {
+            return Err(ProductFormError::EmptyUpload);
+        }
 
-            let sku = header_indexes
-                .sku_index
-                .and_then(|idx| record.get(idx))
-                .map(sanitize_sku)
-                .filter(|value| !value.is_empty());
+        Ok(report)
+    }
+}
 
-            let description = header_indexes
-                .description_index
-                .and_then(|idx| record.get(idx))
-                .map(sanitize_multiline_text)
-                .filter(|value| !value.is_empty());
+/// Column positions used to turn CSV records into upload rows.
+struct ProductUploadLayout<'a> {
+    headers: ProductHeaderIndexes,
+    name_index: usize,
+    currency_index: usize,
+    price_level_columns: Vec<PriceLevelColumn<'a>>,
+}
 
-            let units = header_indexes
-                .units_index
-                .and_then(|idx| record.get(idx))
-                .map(sanitize_inline_text)
-                .filter(|value| !value.is_empty());
+impl ProductUploadLayout<'_> {
+    fn parse_row(
+        &self,
+        record: &StringRecord,
+        row_number: usize,
+        hub_id: i32,
+    ) -> ProductFormResult<NewProductUpload> {
+        let raw_name = record.get(self.name_index).unwrap_or("");
+        let sanitized_name = sanitize_inline_text(raw_name);
+        if sanitized_name.is_empty() {
+            return Err(ProductFormError::UploadMissingName { row: row_number });
+        }
 
-            let mut product = NewProduct::new(hub_id, sanitized_name, currency);
+        let currency_raw = record.get(self.currency_index).unwrap_or("").trim();
+        if currency_raw.is_empty() {
+            return Err(ProductFormError::UploadMissingCurrency { row: row_number });
+        }
 
-            if let Some(sku) = sku {
-                product = product.with_sku(sku);
+        let currency = match sanitize_currency(currency_raw) {
+            Ok(value) => value,
+            Err(ProductFormError::InvalidCurrency { value }) => {
+                return Err(ProductFormError::UploadInvalidCurrency {
+                    row: row_number,
+                    value,
+                });
             }
+            Err(other) => return Err(other),
+        };
 
-            if let Some(description) = description {
-                product = product.with_description(description);
-            }
+        let product_id = match self
+            .headers
+            .id_index
+            .and_then(|idx| record.get(idx))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            Some(value) => Some(value.parse::<i32>().ok().filter(|id| *id > 0).ok_or_else(
+                || ProductFormError::UploadInvalidProductId {
+                    row: row_number,
+                    value: value.to_string(),
+                },
+            )?),
+            None => None,
+        };
 
-            if let Some(units) = units {
-                product = product.with_units(units);
-            }
+        let sku = self
+            .headers
+            .sku_index
+            .and_then(|idx| record.get(idx))
+            .map(sanitize_sku)
+            .filter(|value| !value.is_empty());
 
-            let mut parsed_price_levels = Vec::new();
-            for column in &price_level_columns {
-                let value = record.get(column.index).unwrap_or("").trim();
-                if value.is_empty() {
-                    continue;
-                }
+        let description = self
+            .headers
+            .description_index
+            .and_then(|idx| record.get(idx))
+            .map(sanitize_multiline_text)
+            .filter(|value| !value.is_empty());
 
-                let price_cents = parse_price_to_cents(value).ok_or_else(|| {
-                    ProductFormError::UploadInvalidPrice {
-                        row: row_number,
-                        price_level: column.price_level.name.clone(),
-                        value: value.to_string(),
-                    }
-                })?;
+        let units = self
+            .headers
+            .units_index
+            .and_then(|idx| record.get(idx))
+            .map(sanitize_inline_text)
+            .filter(|value| !value.is_empty());
 
-                parsed_price_levels.push(NewProductUploadPriceLevel {
-                    price_level_id: column.price_level.id,
-                    price_cents,
-                });
+        let mut product = NewProduct::new(hub_id, sanitized_name, currency);
+
+        if let Some(sku) = sku {
+            product = product.with_sku(sku);
+        }
+
+        if let Some(description) = description {
+            product = product.with_description(description);
+        }
+
+        if let Some(units) = units {
+            product = product.with_units(units);
+        }
+
+        let derived_levels = self
+            .headers
+            .derived_prices_index
+            .and_then(|idx| record.get(idx))
+            .map(split_tag_names)
+            .unwrap_or_default();
+
+        let mut parsed_price_levels = Vec::new();
+        for column in &self.price_level_columns {
+            let value = record.get(column.index).unwrap_or("").trim();
+            if value.is_empty()
+                || (column.min_quantity == 1
+                    && derived_levels
+                        .iter()
+                        .any(|name| name.eq_ignore_ascii_case(&column.price_level.name)))
+            {
+                continue;
             }
 
-            products.push(NewProductUpload {
-                product,
-                price_levels: parsed_price_levels,
+            let price = Money::parse(value, product.currency.as_str()).map_err(|_| {
+                ProductFormError::UploadInvalidPrice {
+                    row: row_number,
+                    price_level: price_tier_header(&column.price_level.name, column.min_quantity),
+                    value: value.to_string(),
+                }
+            })?;
+
+            parsed_price_levels.push(NewProductUploadPriceLevel {
+                price_level_id: column.price_level.id,
+                price,
+                min_quantity: column.min_quantity,
             });
         }
 
-        if processed_rows == 0 || products.is_empty() {
-            return Err(ProductFormError::EmptyUpload);
-        }
+        let category = self
+            .headers
+            .category_index
+            .and_then(|idx| record.get(idx))
+            .map(sanitize_category_path)
+            .filter(|value| !value.is_empty());
 
-        Ok(products)
+        let tags = self
+            .headers
+            .tags_index
+            .and_then(|idx| record.get(idx))
+            .map(split_tag_names)
+            .unwrap_or_default();
+
+        Ok(NewProductUpload {
+            product_id,
+            product,
+            price_levels: parsed_price_levels,
+            category,
+            tags,
+        })
     }
 }
 
@@ -426,6 +718,12 @@ pub struct EditProductForm {
     /// Optional category update (negative or zero clears the category).
     #[serde(default)]
     pub category_id: Option<String>,
+    /// Optional tax category update (negative or zero makes the product untaxed).
+    #[serde(default)]
+    pub tax_category_id: Option<String>,
+    /// Optional oversell toggle; products allow oversell when omitted.
+    #[serde(default, deserialize_with = "deserialize_optional_bool")]
+    pub allow_oversell: Option<bool>,
     /// Optional set of tags to associate with the product.
     #[serde(default)]
     pub tag_ids: Vec<String>,
@@ -453,10 +751,15 @@ impl EditProductForm {
             currency,
             is_archived,
             category_id,
+            tax_category_id,
+            allow_oversell,
             tag_ids,
         } = self;
 
-        let mut updates = UpdateProduct::default();
+        let mut updates = UpdateProduct {
+            allow_oversell: allow_oversell.unwrap_or(true),
+            ..UpdateProduct::default()
+        };
 
         if let Some(name) = name {
             let sanitized = sanitize_inline_text(&name);
@@ -525,6 +828,21 @@ impl EditProductForm {
             }
         }
 
+        if let Some(tax_category_raw) = tax_category_id {
+            let trimmed = tax_category_raw.trim();
+            if trimmed.is_empty() {
+                updates.tax_category_id = None;
+            } else {
+                let parsed =
+                    trimmed
+                        .parse::<i32>()
+                        .map_err(|_| ProductFormError::InvalidTaxCategoryId {
+                            value: tax_category_raw,
+                        })?;
+                updates.tax_category_id = normalize_category_id(parsed);
+            }
+        }
+
         let mut sanitized_tags: Vec<i32> = tag_ids
             .into_iter()
             .filter_map(|raw| raw.trim().parse::<i32>().ok())
@@ -540,21 +858,275 @@ impl EditProductForm {
     }
 }
 
+/// Form payload emitted by the delete button of the edit product modal.
+#[derive(Debug, Deserialize)]
+pub struct DeleteProductForm {
+    /// Identifier of the product to delete.
+    pub product_id: i32,
+}
+
+/// Form payload emitted when scheduling a price change or promotion.
+#[derive(Debug, Deserialize)]
+pub struct ScheduleProductPriceForm {
+    /// Identifier of the product receiving the price.
+    pub product_id: i32,
+    /// Identifier of the price level the price applies to.
+    pub price_level_id: i32,
+    /// Price entered by the user, e.g. `12.50`.
+    pub price: String,
+    /// Start of the window (UTC), as sent by a `datetime-local` input.
+    pub valid_from: String,
+    /// Optional end of the window (UTC); empty for an open-ended change.
+    #[serde(default)]
+    pub valid_to: Option<String>,
+}
+
+impl ScheduleProductPriceForm {
+    /// Validates the payload into a scheduled rate for one of `price_levels`,
+    /// reading the price with the precision of the product's `currency`.
+    pub fn into_new_rate(
+        self,
+        price_levels: &[PriceLevel],
+        currency: &str,
+    ) -> ProductFormResult<NewProductPriceLevelRate> {
+        let price_level = price_levels
+            .iter()
+            .find(|level| level.id == self.price_level_id)
+            .ok_or(ProductFormError::UnknownPriceLevel {
+                price_level_id: self.price_level_id,
+            })?;
+
+        let price = Money::parse(&self.price, currency).map_err(|_| {
+            ProductFormError::InvalidPriceLevelAmount {
+                price_level: price_level.name.clone(),
+                value: self.price.clone(),
+            }
+        })?;
+
+        let valid_from = parse_schedule_date(&self.valid_from)?;
+        let mut rate = NewProductPriceLevelRate::new(self.product_id, price_level.id, price)
+            .with_valid_from(valid_from);
+
+        if let Some(raw) = self
+            .valid_to
+            .as_deref()
+            .filter(|raw| !raw.trim().is_empty())
+        {
+            let valid_to = parse_schedule_date(raw)?;
+            if valid_to <= valid_from {
+                return Err(ProductFormError::ScheduleEndsBeforeStart);
+            }
+            rate = rate.with_valid_to(valid_to);
+        }
+
+        Ok(rate)
+    }
+}
+
+/// Form payload emitted when cancelling a scheduled price.
+#[derive(Debug, Deserialize)]
+pub struct CancelScheduledPriceForm {
+    /// Identifier of the product owning the rate.
+    pub product_id: i32,
+    /// Identifier of the scheduled rate.
+    pub rate_id: i32,
+}
+
+/// Form payload emitted by the bulk action bar of the products list.
+#[derive(Debug, Deserialize)]
+pub struct BulkProductsForm {
+    /// Identifiers of the selected products.
+    #[serde(default)]
+    pub product_ids: Vec<i32>,
+    /// Action to apply: `archive`, `restore`, `categorize` or `tag`.
+    pub action: String,
+    /// Target category of `categorize`; empty or zero removes the category.
+    #[serde(default)]
+    pub category_id: Option<String>,
+    /// Tags attached by `tag`.
+    #[serde(default)]
+    pub tag_ids: Vec<String>,
+}
+
+/// Sanitized bulk request returned by [`BulkProductsForm::into_bulk_update`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductBulkUpdate {
+    /// Unique identifiers of the selected products.
+    pub product_ids: Vec<i32>,
+    pub action: ProductBulkAction,
+}
+
+impl BulkProductsForm {
+    /// Validates the selection and parses the requested action.
+    pub fn into_bulk_update(self) -> ProductFormResult<ProductBulkUpdate> {
+        let mut product_ids: Vec<i32> = self.product_ids.into_iter().filter(|id| *id > 0).collect();
+        product_ids.sort_unstable();
+        product_ids.dedup();
+        if product_ids.is_empty() {
+            return Err(ProductFormError::NoProductsSelected);
+        }
+
+        let action = match self.action.trim() {
+            "archive" => ProductBulkAction::Archive,
+            "restore" => ProductBulkAction::Restore,
+            "categorize" => {
+                let category_id = match self.category_id.as_deref().map(str::trim) {
+                    None | Some("") => None,
+                    Some(raw) => {
+                        let parsed = raw.parse::<i32>().map_err(|_| {
+                            ProductFormError::InvalidCategoryId {
+                                value: raw.to_string(),
+                            }
+                        })?;
+                        normalize_category_id(parsed)
+                    }
+                };
+                ProductBulkAction::SetCategory(category_id)
+            }
+            "tag" => {
+                let mut tag_ids: Vec<i32> = self
+                    .tag_ids
+                    .iter()
+                    .filter_map(|raw| raw.trim().parse::<i32>().ok())
+                    .filter(|id| *id > 0)
+                    .collect();
+                tag_ids.sort_unstable();
+                tag_ids.dedup();
+                if tag_ids.is_empty() {
+                    return Err(ProductFormError::NoTagsSelected);
+                }
+                ProductBulkAction::AddTags(tag_ids)
+            }
+            other => {
+                return Err(ProductFormError::UnknownBulkAction {
+                    value: other.to_string(),
+                });
+            }
+        };
+
+        Ok(ProductBulkUpdate {
+            product_ids,
+            action,
+        })
+    }
+}
+
+/// Form payload emitted when repricing the products of a price level in bulk.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RepriceProductsForm {
+    /// Price level whose prices change.
+    pub price_level_id: i32,
+    /// `Percent` or `Fixed`, see [`PriceAdjustmentKind`].
+    #[serde(default)]
+    pub adjustment_kind: Option<String>,
+    /// Percentage or amount added to the prices, e.g. `7.5` or `-20`.
+    pub adjustment: String,
+    /// Rounding rule name, see [`PriceRounding`].
+    #[serde(default)]
+    pub rounding: Option<String>,
+    /// Only reprice products of this category and its subcategories.
+    #[serde(default)]
+    pub category_id: Option<String>,
+    /// Only reprice products carrying this tag.
+    #[serde(default)]
+    pub tag_id: Option<String>,
+    /// Only reprice products whose name or description contains this term.
+    #[serde(default)]
+    pub search: Option<String>,
+}
+
+/// Sanitized repricing returned by [`RepriceProductsForm::into_repricing`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductRepricing {
+    /// Adjustment applied to the rates of the price level.
+    pub repricing: PriceLevelRepricing,
+    /// Category whose subtree limits the repriced products.
+    pub category_id: Option<i32>,
+    /// Tag limiting the repriced products.
+    pub tag_id: Option<i32>,
+    /// Search term limiting the repriced products.
+    pub search: Option<String>,
+}
+
+impl RepriceProductsForm {
+    /// Parses the adjustment and the optional product scope.
+    pub fn into_repricing(self) -> ProductFormResult<ProductRepricing> {
+        let adjustment = self.adjustment.trim();
+        let value =
+            parse_hundredths(adjustment).ok_or_else(|| ProductFormError::InvalidAdjustment {
+                value: adjustment.to_string(),
+            })?;
+
+        let repricing = PriceLevelRepricing::new(self.price_level_id);
+        let repricing =
+            match PriceAdjustmentKind::from(self.adjustment_kind.as_deref().unwrap_or("").trim()) {
+                PriceAdjustmentKind::Percent => repricing.with_percent(value),
+                PriceAdjustmentKind::Fixed => repricing.with_fixed(value),
+            }
+            .with_rounding(PriceRounding::from(
+                self.rounding.as_deref().unwrap_or("").trim(),
+            ));
+
+        let category_id = match self.category_id.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(raw) => {
+                let parsed =
+                    raw.parse::<i32>()
+                        .map_err(|_| ProductFormError::InvalidCategoryId {
+                            value: raw.to_string(),
+                        })?;
+                normalize_category_id(parsed)
+            }
+        };
+
+        let tag_id =
+            match self.tag_id.as_deref().map(str::trim) {
+                None | Some("") => None,
+                Some(raw) => Some(raw.parse::<i32>().ok().filter(|id| *id > 0).ok_or_else(
+                    || ProductFormError::InvalidTagId {
+                        value: raw.to_string(),
+                    },
+                )?),
+            };
+
+        let search = self
+            .search
+            .as_deref()
+            .map(sanitize_inline_text)
+            .filter(|value| !value.is_empty());
+
+        Ok(ProductRepricing {
+            repricing,
+            category_id,
+            tag_id,
+            search,
+        })
+    }
+}
+
 struct ProductHeaderIndexes {
+    id_index: Option<usize>,
     name_index: Option<usize>,
     sku_index: Option<usize>,
     description_index: Option<usize>,
     units_index: Option<usize>,
     currency_index: Option<usize>,
+    category_index: Option<usize>,
+    tags_index: Option<usize>,
+    derived_prices_index: Option<usize>,
 }
 
 fn locate_product_headers(headers: &StringRecord) -> ProductHeaderIndexes {
     ProductHeaderIndexes {
+        id_index: locate_header(headers, "id"),
         name_index: locate_header(headers, "name"),
         sku_index: locate_header(headers, "sku"),
         description_index: locate_header(headers, "description"),
         units_index: locate_header(headers, "units"),
         currency_index: locate_header(headers, "currency"),
+        category_index: locate_header(headers, "category"),
+        tags_index: locate_header(headers, "tags"),
+        derived_prices_index: locate_header(headers, DERIVED_PRICES_HEADER),
     }
 }
 
@@ -570,69 +1142,77 @@ fn locate_header(headers: &StringRecord, expected: &str) -> Option<usize> {
 
 struct PriceLevelColumn<'a> {
     price_level: &'a PriceLevel,
+    min_quantity: i32,
     index: usize,
 }
 
+/// Header of the column holding the quantity break of `price_level_name`
+/// starting at `min_quantity`, for example `Wholesale 10+`.
+pub fn price_tier_header(price_level_name: &str, min_quantity: i32) -> String {
+    if min_quantity > 1 {
+        format!("{price_level_name} {min_quantity}+")
+    } else {
+        price_level_name.to_string()
+    }
+}
+
+/// Matches headers named after a price level, optionally followed by a
+/// quantity break such as `Wholesale 10+`.
 fn locate_price_level_headers<'a>(
     headers: &StringRecord,
     price_levels: &'a [PriceLevel],
 ) -> Vec<PriceLevelColumn<'a>> {
-    price_levels
+    headers
         .iter()
-        .filter_map(|price_level| {
-            locate_header(headers, price_level.name.as_str())
-                .map(|index| PriceLevelColumn { price_level, index })
+        .enumerate()
+        .filter_map(|(index, header)| {
+            let exact = price_levels
+                .iter()
+                .find(|price_level| header.eq_ignore_ascii_case(&price_level.name))
+                .map(|price_level| (price_level, 1));
+            exact
+                .or_else(|| {
+                    price_levels.iter().find_map(|price_level| {
+                        parse_tier_header(header, &price_level.name)
+                            .map(|min_quantity| (price_level, min_quantity))
+                    })
+                })
+                .map(|(price_level, min_quantity)| PriceLevelColumn {
+                    price_level,
+                    min_quantity,
+                    index,
+                })
         })
         .collect()
 }
 
-fn parse_price_to_cents(input: &str) -> Option<i32> {
-    let trimmed = input.trim();
-    if trimmed.is_empty() {
-        return None;
-    }
-
-    let mut normalized = String::with_capacity(trimmed.len());
-    for ch in trimmed.chars() {
-        if ch.is_ascii_digit() || ch == '.' || ch == ',' {
-            normalized.push(ch);
-        } else if ch.is_whitespace() {
-            continue;
-        } else {
-            return None;
-        }
-    }
-
-    let normalized = normalized.replace(',', ".");
-    let mut parts = normalized.split('.');
-    let whole_part = parts.next()?;
-    if whole_part.is_empty() || !whole_part.chars().all(|c| c.is_ascii_digit()) {
+fn parse_tier_header(header: &str, price_level_name: &str) -> Option<i32> {
+    let prefix = header.get(..price_level_name.len())?;
+    if !prefix.eq_ignore_ascii_case(price_level_name) {
         return None;
     }
+    header[price_level_name.len()..]
+        .trim()
+        .strip_suffix('+')?
+        .trim()
+        .parse::<i32>()
+        .ok()
+        .filter(|quantity| *quantity > 1)
+}
 
-    let mut cents = whole_part.parse::<i64>().ok()?.checked_mul(100)?;
-
-    if let Some(frac_part) = parts.next() {
-        if parts.next().is_some() {
-            return None;
-        }
-
-        if frac_part.is_empty() || !frac_part.chars().all(|c| c.is_ascii_digit()) {
-            return None;
-        }
-
-        let mut buffer = frac_part.to_string();
-        if buffer.len() == 1 {
-            buffer.push('0');
-        } else if buffer.len() > 2 {
-            return None;
-        }
-
-        let fractional = buffer.parse::<i64>().ok()?;
-        cents = cents.checked_add(fractional)?;
-    }
+/// Parses `datetime-local` values (`2025-12-01T09:30`), seconds optional.
+fn parse_schedule_date(input: &str) -> ProductFormResult<NaiveDateTime> {
+    parse_datetime_local(input).ok_or_else(|| ProductFormError::InvalidScheduleDate {
+        value: input.to_string(),
+    })
+}
 
-    i32::try_from(cents).ok()
+/// Parses the value of a `datetime-local` input, with or without seconds.
+pub(crate) fn parse_datetime_local(input: &str) -> Option<NaiveDateTime> {
+    let trimmed = input.trim();
+    ["%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(trimmed, format).ok())
 }
 
 fn sanitize_inline_text(input: &str) -> String {
@@ -656,6 +1236,27 @@ fn sanitize_inline_text(input: &str) -> String {
     sanitized
 }
 
+/// Normalizes a category path by trimming each `/`-separated segment.
+fn sanitize_category_path(input: &str) -> String {
+    input
+        .split(CATEGORY_PATH_SEPARATOR)
+        .map(sanitize_inline_text)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join(&CATEGORY_PATH_SEPARATOR.to_string())
+}
+
+/// Splits a `,`-separated tag list into unique, sanitized names.
+fn split_tag_names(input: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in input.split(TAG_SEPARATOR).map(sanitize_inline_text) {
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
 fn sanitize_sku(input: &str) -> String {
     input
         .trim()
@@ -734,14 +1335,18 @@ mod tests {
             units: Some("  Box  ".to_string()),
             currency: "usd".to_string(),
             category_id: Some(7),
+            tax_category_id: None,
+            allow_oversell: None,
             price_levels: vec![
                 AddProductPriceLevelForm {
                     price_level_id: 1,
                     price: Some("12.34".to_string()),
+                    min_quantity: None,
                 },
                 AddProductPriceLevelForm {
                     price_level_id: 2,
                     price: Some("  ".to_string()),
+                    min_quantity: None,
                 },
             ],
         };
@@ -766,7 +1371,36 @@ mod tests {
         assert_eq!(payload.product.category_id, Some(7));
         assert_eq!(payload.price_levels.len(), 1);
         assert_eq!(payload.price_levels[0].price_level_id, 1);
-        assert_eq!(payload.price_levels[0].price_cents, 1234);
+        assert_eq!(payload.price_levels[0].price.amount, 1234);
+    }
+
+    #[test]
+    fn add_product_form_rejects_duplicate_quantity_breaks() {
+        let tier = |min_quantity: &str, price: &str| AddProductPriceLevelForm {
+            price_level_id: 1,
+            price: Some(price.to_string()),
+            min_quantity: Some(min_quantity.to_string()),
+        };
+        let form = AddProductForm {
+            name: "Widget".to_string(),
+            sku: None,
+            description: None,
+            units: None,
+            currency: "USD".to_string(),
+            category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
+            price_levels: vec![tier("", "1.00"), tier("10", "0.90"), tier(" 10 ", "0.80")],
+        };
+        let levels = vec![build_price_level(1, "Retail")];
+
+        let result = form.into_new_product_with_prices(1, &levels);
+
+        assert!(matches!(
+            result,
+            Err(ProductFormError::DuplicatePriceTier { price_level, min_quantity: 10 })
+                if price_level == "Retail"
+        ));
     }
 
     #[test]
@@ -778,6 +1412,8 @@ mod tests {
             units: None,
             currency: "USD".to_string(),
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             price_levels: Vec::new(),
         };
 
@@ -795,6 +1431,8 @@ mod tests {
             units: None,
             currency: "US!".to_string(),
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             price_levels: Vec::new(),
         };
 
@@ -815,9 +1453,12 @@ mod tests {
             units: None,
             currency: "USD".to_string(),
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             price_levels: vec![AddProductPriceLevelForm {
                 price_level_id: 1,
                 price: Some("oops".to_string()),
+                min_quantity: None,
             }],
         };
         let levels = vec![build_price_level(1, "Retail")];
@@ -840,9 +1481,12 @@ mod tests {
             units: None,
             currency: "USD".to_string(),
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             price_levels: vec![AddProductPriceLevelForm {
                 price_level_id: 999,
                 price: Some("10".to_string()),
+                min_quantity: None,
             }],
         };
         let levels = vec![build_price_level(1, "Retail")];
@@ -858,9 +1502,9 @@ mod tests {
     #[test]
     fn upload_products_form_converts_rows() {
         let csv = "\
-name,currency,sku,description,units,Retail,Wholesale
-Apple,usd,APL-1,Fresh apple, Each ,12.34,9.99
-Banana,usd,,Ripe banana,,8.50,
+name,currency,sku,description,units,Retail,Wholesale,category,tags
+Apple,usd,APL-1,Fresh apple, Each ,12.34,9.99, Fruit / Red ,\"Fresh, Local,Fresh\"
+Banana,usd,,Ripe banana,,8.50,,,
 ";
         let mut form = build_upload_form(csv);
         let price_levels = vec![
@@ -881,9 +1525,11 @@ Banana,usd,,Ripe banana,,8.50,
         assert_eq!(first.product.currency, "USD");
         assert_eq!(first.price_levels.len(), 2);
         assert_eq!(first.price_levels[0].price_level_id, 1);
-        assert_eq!(first.price_levels[0].price_cents, 1234);
+        assert_eq!(first.price_levels[0].price.amount, 1234);
         assert_eq!(first.price_levels[1].price_level_id, 2);
-        assert_eq!(first.price_levels[1].price_cents, 999);
+        assert_eq!(first.price_levels[1].price.amount, 999);
+        assert_eq!(first.category.as_deref(), Some("Fruit/Red"));
+        assert_eq!(first.tags, vec!["Fresh".to_string(), "Local".to_string()]);
 
         let second = &products[1];
         assert_eq!(second.product.name, "Banana");
@@ -892,7 +1538,33 @@ Banana,usd,,Ripe banana,,8.50,
         assert_eq!(second.product.currency, "USD");
         assert_eq!(second.price_levels.len(), 1);
         assert_eq!(second.price_levels[0].price_level_id, 1);
-        assert_eq!(second.price_levels[0].price_cents, 850);
+        assert_eq!(second.price_levels[0].price.amount, 850);
+        assert!(second.category.is_none());
+        assert!(second.tags.is_empty());
+    }
+
+    #[test]
+    fn upload_products_form_reads_quantity_break_columns() {
+        let csv = "\
+name,currency,Retail,Retail 10+,retail 100 +,Wholesale 1+
+Apple,usd,1.00,0.90,0.80,0.50
+";
+        let mut form = build_upload_form(csv);
+        let price_levels = vec![
+            build_price_level(1, "Retail"),
+            build_price_level(2, "Wholesale"),
+        ];
+
+        let products = form
+            .into_new_products(5, &price_levels)
+            .expect("expected upload to succeed");
+
+        let tiers: Vec<(i32, i32, i64)> = products[0]
+            .price_levels
+            .iter()
+            .map(|level| (level.price_level_id, level.min_quantity, level.price.amount))
+            .collect();
+        assert_eq!(tiers, vec![(1, 1, 100), (1, 10, 90), (1, 100, 80)]);
     }
 
     #[test]
@@ -939,6 +1611,97 @@ Banana,usd,,Ripe banana,,8.50,
         ));
     }
 
+    #[test]
+    fn upload_report_collects_every_row_error() {
+        let csv = "name,currency,Retail\n,usd,1\nApple,usd,1.50\nPear,dollars,2\nPlum,usd,oops\n";
+        let mut form = build_upload_form(csv);
+        let price_levels = vec![build_price_level(42, "Retail")];
+
+        let report = form
+            .into_upload_report(1, &price_levels)
+            .expect("expected report");
+
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.rows[0].row, 3);
+        assert_eq!(report.rows[0].upload.product.name, "Apple");
+        assert_eq!(
+            report
+                .errors
+                .iter()
+                .map(ProductFormError::row)
+                .collect::<Vec<_>>(),
+            vec![Some(2), Some(4), Some(5)]
+        );
+        assert!(matches!(
+            report.errors[1],
+            ProductFormError::UploadInvalidCurrency { .. }
+        ));
+    }
+
+    #[test]
+    fn bulk_form_parses_tag_action() {
+        let form = BulkProductsForm {
+            product_ids: vec![3, 1, 3, 0],
+            action: " tag ".to_string(),
+            category_id: None,
+            tag_ids: vec!["7".to_string(), "x".to_string(), "7".to_string()],
+        };
+
+        let update = form.into_bulk_update().expect("expected valid bulk update");
+
+        assert_eq!(update.product_ids, vec![1, 3]);
+        assert_eq!(update.action, ProductBulkAction::AddTags(vec![7]));
+    }
+
+    #[test]
+    fn reprice_form_parses_adjustment_and_scope() {
+        let form = RepriceProductsForm {
+            price_level_id: 3,
+            adjustment_kind: Some("Fixed".to_string()),
+            adjustment: " -1,50 ".to_string(),
+            rounding: Some("Whole".to_string()),
+            category_id: Some("0".to_string()),
+            tag_id: Some("7".to_string()),
+            search: Some("  Cold  brew ".to_string()),
+        };
+
+        let parsed = form.into_repricing().expect("expected repricing");
+
+        assert_eq!(
+            parsed.repricing,
+            PriceLevelRepricing::new(3)
+                .with_fixed(-150)
+                .with_rounding(PriceRounding::Whole)
+        );
+        assert_eq!(parsed.category_id, None);
+        assert_eq!(parsed.tag_id, Some(7));
+        assert_eq!(parsed.search.as_deref(), Some("Cold brew"));
+
+        let invalid = RepriceProductsForm {
+            adjustment: "ten".to_string(),
+            ..RepriceProductsForm::default()
+        };
+        assert!(matches!(
+            invalid.into_repricing(),
+            Err(ProductFormError::InvalidAdjustment { value }) if value == "ten"
+        ));
+    }
+
+    #[test]
+    fn bulk_form_rejects_empty_selection() {
+        let form = BulkProductsForm {
+            product_ids: Vec::new(),
+            action: "archive".to_string(),
+            category_id: None,
+            tag_ids: Vec::new(),
+        };
+
+        assert!(matches!(
+            form.into_bulk_update(),
+            Err(ProductFormError::NoProductsSelected)
+        ));
+    }
+
     fn build_upload_form(csv: &str) -> UploadProductsForm {
         let mut file = NamedTempFile::new().expect("create temp file");
         file.write_all(csv.as_bytes()).expect("write csv contents");
@@ -953,6 +1716,8 @@ Banana,usd,,Ripe banana,,8.50,
                 file_name: Some("products.csv".to_string()),
                 size: csv.len(),
             },
+            mode: None,
+            create_missing: None,
         }
     }
 
@@ -968,6 +1733,8 @@ Banana,usd,,Ripe banana,,8.50,
             created_at: epoch,
             updated_at: epoch,
             is_default: false,
+            derivation: None,
+            prices_include_tax: true,
         }
     }
 
@@ -981,6 +1748,8 @@ Banana,usd,,Ripe banana,,8.50,
             currency: Some("eur".to_string()),
             is_archived: Some(true),
             category_id: Some("12".to_string()),
+            tax_category_id: None,
+            allow_oversell: None,
             tag_ids: vec!["5".to_string(), "7".to_string(), "5".to_string()],
         };
 
@@ -1007,6 +1776,8 @@ Banana,usd,,Ripe banana,,8.50,
             currency: Some("1".to_string()),
             is_archived: None,
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             tag_ids: Vec::new(),
         };
 
@@ -1017,4 +1788,48 @@ Banana,usd,,Ripe banana,,8.50,
             Err(ProductFormError::InvalidCurrency { value }) if value == "1"
         ));
     }
+
+    #[test]
+    fn schedule_product_price_form_builds_window() {
+        let form = ScheduleProductPriceForm {
+            product_id: 4,
+            price_level_id: 1,
+            price: "9,90".to_string(),
+            valid_from: "2025-12-01T09:30".to_string(),
+            valid_to: Some("2025-12-08T00:00".to_string()),
+        };
+
+        let rate = form
+            .into_new_rate(&[build_price_level(1, "Retail")], "USD")
+            .expect("expected success");
+
+        assert_eq!(rate.product_id, 4);
+        assert_eq!(rate.price.amount, 990);
+        assert_eq!(
+            rate.valid_from.map(|at| at.to_string()).as_deref(),
+            Some("2025-12-01 09:30:00")
+        );
+        assert_eq!(
+            rate.valid_to.map(|at| at.to_string()).as_deref(),
+            Some("2025-12-08 00:00:00")
+        );
+    }
+
+    #[test]
+    fn schedule_product_price_form_rejects_inverted_window() {
+        let form = ScheduleProductPriceForm {
+            product_id: 4,
+            price_level_id: 1,
+            price: "9.90".to_string(),
+            valid_from: "2025-12-08T00:00".to_string(),
+            valid_to: Some("2025-12-01T00:00".to_string()),
+        };
+
+        let result = form.into_new_rate(&[build_price_level(1, "Retail")], "USD");
+
+        assert!(matches!(
+            result,
+            Err(ProductFormError::ScheduleEndsBeforeStart)
+        ));
+    }
 }
@@ -0,0 +1,271 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::domain::money::Money;
+use crate::domain::price_level::PriceAdjustmentKind;
+use crate::domain::promotion::{NewPromotion, normalize_code};
+use crate::domain::tax_category::TAX_RATE_SCALE;
+use crate::forms::price_levels::parse_hundredths;
+use crate::forms::products::parse_datetime_local;
+
+/// Maximum length allowed for a promotion code.
+const CODE_MAX_LEN: usize = 32;
+
+/// ISO 4217 currency codes are three ASCII alphabetic characters.
+const CURRENCY_CODE_LEN: usize = 3;
+
+/// Result type returned by the promotion form helpers.
+pub type PromotionFormResult<T> = Result<T, PromotionFormError>;
+
+/// Errors that can occur while processing promotion forms.
+#[derive(Debug, Error)]
+pub enum PromotionFormError {
+    /// The code is empty, too long or contains characters other than letters,
+    /// digits, `-` and `_`.
+    #[error("invalid promotion code `{value}`")]
+    InvalidCode { value: String },
+    /// The currency is not a three-letter code.
+    #[error("invalid currency `{value}`")]
+    InvalidCurrency { value: String },
+    /// The discount is not a positive percentage up to 100 or a positive
+    /// amount in the currency.
+    #[error("invalid discount `{value}`")]
+    InvalidValue { value: String },
+    /// The minimum order amount is not a valid amount in the currency.
+    #[error("invalid minimum order amount `{value}`")]
+    InvalidMinOrder { value: String },
+    /// A validity date could not be parsed.
+    #[error("invalid date `{value}`")]
+    InvalidDate { value: String },
+    /// The validity window ends before it starts.
+    #[error("promotion must end after it starts")]
+    EndsBeforeStart,
+    /// The usage limit is not a positive number.
+    #[error("invalid usage limit `{value}`")]
+    InvalidUsageLimit { value: String },
+    /// The restricting category is not a valid identifier.
+    #[error("invalid category identifier `{value}`")]
+    InvalidCategoryId { value: String },
+    /// The restricting tag is not a valid identifier.
+    #[error("invalid tag identifier `{value}`")]
+    InvalidTagId { value: String },
+}
+
+/// Form payload emitted when creating a promotion.
+#[derive(Debug, Deserialize)]
+pub struct AddPromotionForm {
+    /// Code entered by customers, case-insensitive.
+    pub code: String,
+    /// `Percent` or `Fixed`, see [`PriceAdjustmentKind`].
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// Percentage such as `10` or amount such as `500.00`.
+    pub value: String,
+    /// Currency of fixed discounts and of the minimum order amount.
+    pub currency: String,
+    /// Optional minimum order total before the discount.
+    #[serde(default)]
+    pub min_order: Option<String>,
+    /// Optional start of the validity window (UTC).
+    #[serde(default)]
+    pub valid_from: Option<String>,
+    /// Optional end of the validity window (UTC).
+    #[serde(default)]
+    pub valid_to: Option<String>,
+    /// Optional maximum number of orders.
+    #[serde(default)]
+    pub usage_limit: Option<String>,
+    /// Only discount products of this category and its subcategories.
+    #[serde(default)]
+    pub category_id: Option<String>,
+    /// Only discount products carrying this tag.
+    #[serde(default)]
+    pub tag_id: Option<String>,
+}
+
+impl AddPromotionForm {
+    /// Validates the payload into a domain [`NewPromotion`].
+    pub fn into_new_promotion(self, hub_id: i32) -> PromotionFormResult<NewPromotion> {
+        let code = normalize_code(&self.code);
+        if code.is_empty()
+            || code.chars().count() > CODE_MAX_LEN
+            || !code
+                .chars()
+                .all(|ch| ch.is_alphanumeric() || ch == '-' || ch == '_')
+        {
+            return Err(PromotionFormError::InvalidCode {
+                value: self.code.trim().to_string(),
+            });
+        }
+
+        let currency = self.currency.trim().to_ascii_uppercase();
+        if currency.len() != CURRENCY_CODE_LEN
+            || !currency.chars().all(|ch| ch.is_ascii_alphabetic())
+        {
+            return Err(PromotionFormError::InvalidCurrency {
+                value: self.currency.trim().to_string(),
+            });
+        }
+
+        let kind = PriceAdjustmentKind::from(self.kind.as_deref().unwrap_or("").trim());
+        let raw_value = self.value.trim();
+        let value = match kind {
+            PriceAdjustmentKind::Percent => parse_hundredths(raw_value)
+                .filter(|basis_points| (1..=i64::from(TAX_RATE_SCALE)).contains(basis_points)),
+            PriceAdjustmentKind::Fixed => Money::parse(raw_value, currency.as_str())
+                .ok()
+                .map(|money| money.amount)
+                .filter(|amount| *amount > 0),
+        }
+        .ok_or_else(|| PromotionFormError::InvalidValue {
+            value: raw_value.to_string(),
+        })?;
+
+        let mut promotion = NewPromotion::new(hub_id, &code, kind, value, currency.as_str());
+
+        if let Some(raw) = non_empty(self.min_order.as_deref()) {
+            let minimum = Money::parse(raw, currency.as_str()).map_err(|_| {
+                PromotionFormError::InvalidMinOrder {
+                    value: raw.to_string(),
+                }
+            })?;
+            promotion = promotion.with_min_order_cents(minimum.amount);
+        }
+
+        let valid_from = non_empty(self.valid_from.as_deref())
+            .map(parse_date)
+            .transpose()?;
+        let valid_to = non_empty(self.valid_to.as_deref())
+            .map(parse_date)
+            .transpose()?;
+        if let (Some(from), Some(to)) = (valid_from, valid_to)
+            && to <= from
+        {
+            return Err(PromotionFormError::EndsBeforeStart);
+        }
+        if let Some(from) = valid_from {
+            promotion = promotion.with_valid_from(from);
+        }
+        if let Some(to) = valid_to {
+            promotion = promotion.with_valid_to(to);
+        }
+
+        if let Some(raw) = non_empty(self.usage_limit.as_deref()) {
+            let limit =
+                parse_positive(raw).ok_or_else(|| PromotionFormError::InvalidUsageLimit {
+                    value: raw.to_string(),
+                })?;
+            promotion = promotion.with_usage_limit(limit);
+        }
+
+        if let Some(raw) = non_empty(self.category_id.as_deref()) {
+            let category_id =
+                parse_positive(raw).ok_or_else(|| PromotionFormError::InvalidCategoryId {
+                    value: raw.to_string(),
+                })?;
+            promotion = promotion.with_category_id(category_id);
+        }
+
+        if let Some(raw) = non_empty(self.tag_id.as_deref()) {
+            let tag_id = parse_positive(raw).ok_or_else(|| PromotionFormError::InvalidTagId {
+                value: raw.to_string(),
+            })?;
+            promotion = promotion.with_tag_id(tag_id);
+        }
+
+        Ok(promotion)
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+fn parse_positive(input: &str) -> Option<i32> {
+    input.parse::<i32>().ok().filter(|value| *value > 0)
+}
+
+fn parse_date(input: &str) -> PromotionFormResult<NaiveDateTime> {
+    parse_datetime_local(input).ok_or_else(|| PromotionFormError::InvalidDate {
+        value: input.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form(kind: &str, value: &str) -> AddPromotionForm {
+        AddPromotionForm {
+            code: " summer-10 ".to_string(),
+            kind: Some(kind.to_string()),
+            value: value.to_string(),
+            currency: "rub".to_string(),
+            min_order: None,
+            valid_from: None,
+            valid_to: None,
+            usage_limit: None,
+            category_id: None,
+            tag_id: None,
+        }
+    }
+
+    #[test]
+    fn add_form_parses_percentage_and_restrictions() {
+        let mut payload = form("Percent", "7,5");
+        payload.min_order = Some("1000".to_string());
+        payload.valid_from = Some("2025-11-01T00:00".to_string());
+        payload.valid_to = Some("2025-12-01T00:00".to_string());
+        payload.usage_limit = Some("100".to_string());
+        payload.category_id = Some("3".to_string());
+        payload.tag_id = Some("".to_string());
+
+        let promotion = payload.into_new_promotion(7).expect("valid form");
+
+        assert_eq!(promotion.code, "SUMMER-10");
+        assert_eq!(promotion.kind, PriceAdjustmentKind::Percent);
+        assert_eq!(promotion.value, 750);
+        assert_eq!(promotion.currency, "RUB");
+        assert_eq!(promotion.min_order_cents, Some(100_000));
+        assert!(promotion.valid_from.is_some() && promotion.valid_to.is_some());
+        assert_eq!(promotion.usage_limit, Some(100));
+        assert_eq!(promotion.category_id, Some(3));
+        assert_eq!(promotion.tag_id, None);
+    }
+
+    #[test]
+    fn add_form_parses_fixed_amount_in_currency() {
+        let promotion = form("Fixed", "500.50")
+            .into_new_promotion(7)
+            .expect("valid form");
+
+        assert_eq!(promotion.kind, PriceAdjustmentKind::Fixed);
+        assert_eq!(promotion.value, 50_050);
+    }
+
+    #[test]
+    fn add_form_rejects_invalid_values() {
+        for value in ["0", "100.01", "-5", "abc"] {
+            assert!(matches!(
+                form("Percent", value).into_new_promotion(1),
+                Err(PromotionFormError::InvalidValue { .. })
+            ));
+        }
+
+        let mut payload = form("Percent", "10");
+        payload.code = "SPRING SALE".to_string();
+        assert!(matches!(
+            payload.into_new_promotion(1),
+            Err(PromotionFormError::InvalidCode { .. })
+        ));
+
+        let mut payload = form("Percent", "10");
+        payload.valid_from = Some("2025-12-01T00:00".to_string());
+        payload.valid_to = Some("2025-11-01T00:00".to_string());
+        assert!(matches!(
+            payload.into_new_promotion(1),
+            Err(PromotionFormError::EndsBeforeStart)
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::domain::tax_category::{NewTaxCategory, TAX_RATE_SCALE};
+
+/// Rates are percentages with up to two fraction digits.
+const RATE_FRACTION_DIGITS: usize = 2;
+
+/// Result type returned by the tax category form helpers.
+pub type TaxCategoryFormResult<T> = Result<T, TaxCategoryFormError>;
+
+/// Errors that can occur while processing tax category forms.
+#[derive(Debug, Error)]
+pub enum TaxCategoryFormError {
+    /// The category name is blank.
+    #[error("tax category name is required")]
+    EmptyName,
+    /// The rate is not a percentage between 0 and 100 with at most two
+    /// fraction digits.
+    #[error("invalid tax rate `{value}`")]
+    InvalidRate { value: String },
+}
+
+/// Form payload emitted when saving a tax category.
+#[derive(Debug, Deserialize)]
+pub struct AddTaxCategoryForm {
+    /// Name of the category, e.g. `НДС 20%`.
+    pub name: String,
+    /// Rate as a percentage, e.g. `20` or `7,5`.
+    pub rate: String,
+}
+
+impl AddTaxCategoryForm {
+    /// Validates the payload into a domain [`NewTaxCategory`].
+    pub fn into_new_tax_category(self, hub_id: i32) -> TaxCategoryFormResult<NewTaxCategory> {
+        if self.name.trim().is_empty() {
+            return Err(TaxCategoryFormError::EmptyName);
+        }
+        let rate_basis_points = parse_rate_basis_points(&self.rate).ok_or_else(|| {
+            TaxCategoryFormError::InvalidRate {
+                value: self.rate.trim().to_string(),
+            }
+        })?;
+
+        Ok(NewTaxCategory::new(hub_id, self.name, rate_basis_points))
+    }
+}
+
+/// Parses a percentage such as `20` or `7,5` into basis points.
+fn parse_rate_basis_points(input: &str) -> Option<i32> {
+    let normalized: String = input
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .map(|ch| if ch == ',' { '.' } else { ch })
+        .collect();
+
+    let (whole, fraction) = normalized.split_once('.').unwrap_or((&normalized, ""));
+    if whole.is_empty()
+        || fraction.len() > RATE_FRACTION_DIGITS
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|ch| ch.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let whole = whole.parse::<i32>().ok()?;
+    let fraction = format!("{fraction:0<RATE_FRACTION_DIGITS$}")
+        .parse::<i32>()
+        .ok()?;
+    whole
+        .checked_mul(100)?
+        .checked_add(fraction)
+        .filter(|basis_points| *basis_points <= TAX_RATE_SCALE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form(name: &str, rate: &str) -> AddTaxCategoryForm {
+        AddTaxCategoryForm {
+            name: name.to_string(),
+            rate: rate.to_string(),
+        }
+    }
+
+    #[test]
+    fn add_form_parses_percentage() {
+        let category = form(" НДС 20% ", "20")
+            .into_new_tax_category(3)
+            .expect("valid form");
+        assert_eq!(category, NewTaxCategory::new(3, "НДС 20%", 2_000));
+
+        let category = form("Reduced", "7,5")
+            .into_new_tax_category(3)
+            .expect("valid form");
+        assert_eq!(category.rate_basis_points, 750);
+    }
+
+    #[test]
+    fn add_form_rejects_invalid_rates_and_names() {
+        for rate in ["", "-1", "100.01", "1.234", "abc", ".5"] {
+            assert!(matches!(
+                form("VAT", rate).into_new_tax_category(1),
+                Err(TaxCategoryFormError::InvalidRate { .. })
+            ));
+        }
+        assert!(matches!(
+            form("  ", "20").into_new_tax_category(1),
+            Err(TaxCategoryFormError::EmptyName)
+        ));
+    }
+}
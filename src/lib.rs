@@ -7,3 +7,5 @@ pub mod schema;
 pub mod services;
 
 pub const SERVICE_ACCESS_ROLE: &str = "admin";
+/// Role allowed to approve or reject client price level assignments.
+pub const PRICE_LEVEL_APPROVER_ROLE: &str = "orders_manager";
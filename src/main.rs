@@ -11,24 +11,47 @@ use pushkind_common::db::establish_connection_pool;
 use pushkind_common::middleware::RedirectUnauthorized;
 use pushkind_common::models::config::CommonServerConfig;
 use pushkind_common::routes::{logout, not_assigned};
+use pushkind_orders::domain::phone::{self, PhoneCountry};
 use pushkind_orders::models::config::ServerConfig;
 use tera::Tera;
 
 use pushkind_orders::repository::DieselRepository;
 use pushkind_orders::routes::api::{
-    api_v1_client_price_levels, api_v1_orders, api_v1_update_client_price_level,
+    api_v1_client_price_levels, api_v1_create_order, api_v1_order_history, api_v1_orders,
+    api_v1_product_inventory, api_v1_product_price_history, api_v1_update_client_price_level,
 };
 use pushkind_orders::routes::categories::{
     add_category, delete_category, edit_category, show_categories,
 };
+use pushkind_orders::routes::customers::{
+    add_customer, delete_customer, download_customer_upload_errors, edit_customer,
+    export_customers, merge_duplicate_customers, preview_customer_upload, show_customer,
+    show_customer_duplicates, show_customers, upload_customers,
+};
+use pushkind_orders::routes::exchange_rates::{
+    add_exchange_rate, delete_exchange_rate, show_exchange_rates, upload_exchange_rates,
+};
 use pushkind_orders::routes::main::show_index;
+use pushkind_orders::routes::orders::{
+    edit_order, export_orders, show_order, transition_order_status,
+};
 use pushkind_orders::routes::price_levels::{
-    add_price_level, delete_price_level, edit_price_level, show_price_levels, upload_price_levels,
+    add_price_level, approve_price_level_request, delete_price_level,
+    download_price_level_upload_errors, edit_price_level, preview_price_level_upload,
+    reject_price_level_request, show_price_level_requests, show_price_levels, upload_price_levels,
 };
 use pushkind_orders::routes::products::{
-    add_product, edit_product, show_products, upload_products,
+    add_product, adjust_product_stock, bulk_update_products, cancel_scheduled_product_price,
+    delete_product, download_product_upload_errors, edit_product, export_products,
+    preview_product_repricing, preview_product_upload, reprice_products, schedule_product_price,
+    show_products, upload_products,
 };
+use pushkind_orders::routes::promotions::{add_promotion, delete_promotion, show_promotions};
+use pushkind_orders::routes::register_template_filters;
 use pushkind_orders::routes::tags::{add_tag, delete_tag, edit_tag, show_tags};
+use pushkind_orders::routes::tax_categories::{
+    add_tax_category, delete_tax_category, show_tax_categories,
+};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -63,6 +86,18 @@ async fn main() -> std::io::Result<()> {
     let crm_service_url = env::var("CRM_SERVICE_URL").unwrap_or_default();
     let server_config = ServerConfig { crm_service_url };
 
+    if let Ok(code) = env::var("DEFAULT_PHONE_COUNTRY") {
+        match PhoneCountry::from_code(&code) {
+            Some(country) => {
+                phone::set_default_country(country);
+            }
+            None => {
+                log::error!("Unsupported DEFAULT_PHONE_COUNTRY `{code}`");
+                std::process::exit(1);
+            }
+        }
+    }
+
     let domain = env::var("DOMAIN").unwrap_or("localhost".to_string());
 
     let pool = match establish_connection_pool(&database_url) {
@@ -77,13 +112,14 @@ async fn main() -> std::io::Result<()> {
     let message_store = CookieMessageStore::builder(secret_key.clone()).build();
     let message_framework = FlashMessagesFramework::builder(message_store).build();
 
-    let tera = match Tera::new("templates/**/*") {
+    let mut tera = match Tera::new("templates/**/*") {
         Ok(t) => t,
         Err(e) => {
             log::error!("Parsing error(s): {e}");
             std::process::exit(1);
         }
     };
+    register_template_filters(&mut tera);
 
     HttpServer::new(move || {
         App::new()
@@ -103,6 +139,10 @@ async fn main() -> std::io::Result<()> {
                 web::scope("/api")
                     .wrap(RedirectUnauthorized)
                     .service(api_v1_orders)
+                    .service(api_v1_create_order)
+                    .service(api_v1_order_history)
+                    .service(api_v1_product_price_history)
+                    .service(api_v1_product_inventory)
                     .service(api_v1_client_price_levels)
                     .service(api_v1_update_client_price_level),
             )
@@ -110,23 +150,63 @@ async fn main() -> std::io::Result<()> {
                 web::scope("")
                     .wrap(RedirectUnauthorized)
                     .service(show_index)
+                    .service(export_orders)
+                    .service(show_order)
+                    .service(edit_order)
+                    .service(transition_order_status)
                     .service(show_categories)
                     .service(add_category)
                     .service(edit_category)
                     .service(delete_category)
+                    .service(show_customers)
+                    .service(export_customers)
+                    .service(show_customer_duplicates)
+                    .service(show_customer)
+                    .service(add_customer)
+                    .service(edit_customer)
+                    .service(upload_customers)
+                    .service(preview_customer_upload)
+                    .service(download_customer_upload_errors)
+                    .service(merge_duplicate_customers)
+                    .service(delete_customer)
                     .service(show_tags)
                     .service(add_tag)
                     .service(edit_tag)
                     .service(delete_tag)
+                    .service(show_exchange_rates)
+                    .service(add_exchange_rate)
+                    .service(upload_exchange_rates)
+                    .service(delete_exchange_rate)
+                    .service(show_tax_categories)
+                    .service(add_tax_category)
+                    .service(delete_tax_category)
+                    .service(show_promotions)
+                    .service(add_promotion)
+                    .service(delete_promotion)
                     .service(show_price_levels)
                     .service(add_price_level)
                     .service(edit_price_level)
                     .service(upload_price_levels)
+                    .service(preview_price_level_upload)
+                    .service(download_price_level_upload_errors)
                     .service(delete_price_level)
+                    .service(show_price_level_requests)
+                    .service(approve_price_level_request)
+                    .service(reject_price_level_request)
                     .service(show_products)
                     .service(add_product)
                     .service(edit_product)
+                    .service(delete_product)
+                    .service(bulk_update_products)
+                    .service(adjust_product_stock)
                     .service(upload_products)
+                    .service(preview_product_upload)
+                    .service(download_product_upload_errors)
+                    .service(export_products)
+                    .service(schedule_product_price)
+                    .service(cancel_scheduled_product_price)
+                    .service(preview_product_repricing)
+                    .service(reprice_products)
                     .service(logout),
             )
             .app_data(web::Data::new(tera.clone()))
@@ -1,7 +1,10 @@
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
-use crate::domain::customer::{Customer as DomainCustomer, NewCustomer as DomainNewCustomer};
+use crate::domain::customer::{
+    Customer as DomainCustomer, NewCustomer as DomainNewCustomer,
+    UpdateCustomer as DomainUpdateCustomer,
+};
 
 #[derive(Debug, Clone, Identifiable, Queryable, Selectable, Associations)]
 #[diesel(
@@ -29,6 +32,15 @@ pub struct NewCustomer<'a> {
     pub price_level_id: Option<i32>,
 }
 
+#[derive(AsChangeset)]
+#[diesel(table_name = crate::schema::customers, treat_none_as_null = true)]
+pub struct UpdateCustomer<'a> {
+    pub name: &'a str,
+    pub email: &'a str,
+    pub phone: Option<&'a str>,
+    pub updated_at: NaiveDateTime,
+}
+
 impl From<Customer> for DomainCustomer {
     fn from(value: Customer) -> Self {
         Self {
@@ -53,3 +65,14 @@ impl<'a> From<&'a DomainNewCustomer> for NewCustomer<'a> {
         }
     }
 }
+
+impl<'a> From<&'a DomainUpdateCustomer> for UpdateCustomer<'a> {
+    fn from(value: &'a DomainUpdateCustomer) -> Self {
+        Self {
+            name: value.name.as_str(),
+            email: value.email.as_str(),
+            phone: value.phone.as_deref(),
+            updated_at: value.updated_at,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::domain::exchange_rate::{
+    ExchangeRate as DomainExchangeRate, NewExchangeRate as DomainNewExchangeRate,
+};
+
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::exchange_rates)]
+pub struct ExchangeRate {
+    pub id: i32,
+    pub hub_id: i32,
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate_micros: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::exchange_rates)]
+pub struct NewExchangeRate<'a> {
+    pub hub_id: i32,
+    pub base_currency: &'a str,
+    pub quote_currency: &'a str,
+    pub rate_micros: i64,
+}
+
+impl From<ExchangeRate> for DomainExchangeRate {
+    fn from(value: ExchangeRate) -> Self {
+        Self {
+            id: value.id,
+            hub_id: value.hub_id,
+            base_currency: value.base_currency,
+            quote_currency: value.quote_currency,
+            rate_micros: value.rate_micros,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+impl<'a> From<&'a DomainNewExchangeRate> for NewExchangeRate<'a> {
+    fn from(value: &'a DomainNewExchangeRate) -> Self {
+        Self {
+            hub_id: value.hub_id,
+            base_currency: value.base_currency.as_str(),
+            quote_currency: value.quote_currency.as_str(),
+            rate_micros: value.rate_micros,
+        }
+    }
+}
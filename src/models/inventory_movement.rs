@@ -0,0 +1,83 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::domain::inventory_movement::{
+    InventoryMovement as DomainInventoryMovement, InventoryMovementKind, StockLevel,
+};
+
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Associations)]
+#[diesel(
+    table_name = crate::schema::inventory_movements,
+    belongs_to(super::product::Product, foreign_key = product_id)
+)]
+pub struct InventoryMovement {
+    pub id: i32,
+    pub product_id: i32,
+    pub order_id: Option<i32>,
+    pub kind: String,
+    pub quantity: i32,
+    pub stock_on_hand: i32,
+    pub stock_reserved: i32,
+    pub note: Option<String>,
+    pub changed_by_email: String,
+    pub changed_by_name: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::inventory_movements)]
+pub struct NewInventoryMovement<'a> {
+    pub product_id: i32,
+    pub order_id: Option<i32>,
+    pub kind: &'static str,
+    pub quantity: i32,
+    pub stock_on_hand: i32,
+    pub stock_reserved: i32,
+    pub note: Option<&'a str>,
+    pub changed_by_email: &'a str,
+    pub changed_by_name: &'a str,
+}
+
+impl<'a> NewInventoryMovement<'a> {
+    /// Ledger row for `quantity` units of `kind` leaving the product at `stock`.
+    pub fn new(
+        product_id: i32,
+        kind: InventoryMovementKind,
+        quantity: i32,
+        stock: StockLevel,
+        changed_by_email: &'a str,
+        changed_by_name: &'a str,
+    ) -> Self {
+        Self {
+            product_id,
+            order_id: None,
+            kind: kind.into(),
+            quantity,
+            stock_on_hand: stock.on_hand,
+            stock_reserved: stock.reserved,
+            note: None,
+            changed_by_email,
+            changed_by_name,
+        }
+    }
+}
+
+impl From<InventoryMovement> for DomainInventoryMovement {
+    fn from(value: InventoryMovement) -> Self {
+        Self {
+            id: value.id,
+            product_id: value.product_id,
+            order_id: value.order_id,
+            kind: value.kind.as_str().into(),
+            quantity: value.quantity,
+            stock: StockLevel {
+                on_hand: value.stock_on_hand,
+                reserved: value.stock_reserved,
+            },
+            note: value.note,
+            changed_by_email: value.changed_by_email,
+            changed_by_name: value.changed_by_name,
+            created_at: value.created_at,
+        }
+    }
+}
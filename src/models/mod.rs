@@ -1,10 +1,17 @@
 pub mod category;
 pub mod config;
 pub mod customer;
+pub mod exchange_rate;
+pub mod inventory_movement;
 pub mod order;
+pub mod order_status_history;
 pub mod price_level;
+pub mod price_level_assignment;
 pub mod product;
+pub mod product_price_history;
 pub mod product_price_level;
 pub mod product_tag;
+pub mod promotion;
 pub mod tag;
+pub mod tax_category;
 pub mod user;
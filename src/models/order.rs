@@ -1,10 +1,12 @@
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
+use crate::domain::money::Money;
 use crate::domain::order::{
-    NewOrder as DomainNewOrder, Order as DomainOrder, OrderProduct as DomainOrderProduct,
-    UpdateOrder as DomainUpdateOrder,
+    NewOrder as DomainNewOrder, Order as DomainOrder, OrderDiscount as DomainOrderDiscount,
+    OrderProduct as DomainOrderProduct, UpdateOrder as DomainUpdateOrder,
 };
+use crate::domain::price_level::PriceAdjustmentKind;
 
 #[derive(Debug, Clone, Identifiable, Queryable, Selectable, Associations)]
 #[diesel(
@@ -18,10 +20,12 @@ pub struct Order {
     pub reference: Option<String>,
     pub status: String,
     pub notes: Option<String>,
-    pub total_cents: i32,
     pub currency: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub total_cents: i64,
+    pub net_cents: i64,
+    pub tax_cents: i64,
 }
 
 #[derive(Debug, Clone, Identifiable, Queryable, Selectable, Associations)]
@@ -34,11 +38,38 @@ pub struct OrderProduct {
     pub name: String,
     pub sku: Option<String>,
     pub description: Option<String>,
-    pub price_cents: i32,
     pub currency: String,
     pub quantity: i32,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub exchange_rate_micros: i64,
+    pub price_cents: i64,
+    pub tax_rate_basis_points: i32,
+    pub prices_include_tax: bool,
+    pub net_cents: i64,
+    pub tax_cents: i64,
+    pub gross_cents: i64,
+}
+
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Associations)]
+#[diesel(table_name = crate::schema::order_discounts)]
+#[diesel(belongs_to(Order, foreign_key = order_id))]
+pub struct OrderDiscount {
+    pub id: i32,
+    pub order_id: i32,
+    pub promotion_id: Option<i32>,
+    pub code: String,
+    pub kind: String,
+    pub value: i64,
+    pub currency: String,
+    pub category_id: Option<i32>,
+    pub tag_id: Option<i32>,
+    pub min_order_cents: Option<i64>,
+    pub net_cents: i64,
+    pub tax_cents: i64,
+    pub gross_cents: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
 }
 
 #[derive(Insertable)]
@@ -49,8 +80,10 @@ pub struct NewOrder<'a> {
     pub reference: Option<&'a str>,
     pub status: &'a str,
     pub notes: Option<&'a str>,
-    pub total_cents: i32,
+    pub total_cents: i64,
     pub currency: &'a str,
+    pub net_cents: i64,
+    pub tax_cents: i64,
 }
 
 #[derive(Insertable)]
@@ -61,26 +94,54 @@ pub struct NewOrderProduct<'a> {
     pub name: &'a str,
     pub sku: Option<&'a str>,
     pub description: Option<&'a str>,
-    pub price_cents: i32,
+    pub price_cents: i64,
     pub currency: &'a str,
     pub quantity: i32,
+    pub exchange_rate_micros: i64,
+    pub tax_rate_basis_points: i32,
+    pub prices_include_tax: bool,
+    pub net_cents: i64,
+    pub tax_cents: i64,
+    pub gross_cents: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::order_discounts)]
+pub struct NewOrderDiscount<'a> {
+    pub order_id: i32,
+    pub promotion_id: Option<i32>,
+    pub code: &'a str,
+    pub kind: &'static str,
+    pub value: i64,
+    pub currency: &'a str,
+    pub category_id: Option<i32>,
+    pub tag_id: Option<i32>,
+    pub min_order_cents: Option<i64>,
+    pub net_cents: i64,
+    pub tax_cents: i64,
+    pub gross_cents: i64,
 }
 
 #[derive(AsChangeset)]
 #[diesel(table_name = crate::schema::orders)]
 #[diesel(treat_none_as_null = true)]
 pub struct UpdateOrder<'a> {
-    pub status: &'a str,
     pub notes: Option<&'a str>,
-    pub total_cents: i32,
+    pub total_cents: i64,
     pub currency: &'a str,
     pub customer_id: Option<i32>,
     pub reference: Option<&'a str>,
     pub updated_at: NaiveDateTime,
+    pub net_cents: i64,
+    pub tax_cents: i64,
 }
 
 impl Order {
-    pub fn into_domain(self, products: Vec<OrderProduct>) -> DomainOrder {
+    pub fn into_domain(
+        self,
+        products: Vec<OrderProduct>,
+        discount: Option<OrderDiscount>,
+    ) -> DomainOrder {
         DomainOrder {
             id: self.id,
             hub_id: self.hub_id,
@@ -88,12 +149,15 @@ impl Order {
             reference: self.reference,
             status: self.status.as_str().into(),
             notes: self.notes,
-            total_cents: self.total_cents,
-            currency: self.currency,
+            total: Money::new(self.total_cents, self.currency.as_str()),
+            net: Money::new(self.net_cents, self.currency.as_str()),
+            tax: Money::new(self.tax_cents, self.currency.as_str()),
             products: products
                 .into_iter()
-                .map(OrderProduct::into_domain)
+                .map(|product| product.into_domain(&self.currency))
                 .collect(),
+            discount: discount.map(|discount| discount.into_domain(&self.currency)),
+            currency: self.currency,
             created_at: self.created_at,
             updated_at: self.updated_at,
         }
@@ -101,22 +165,51 @@ impl Order {
 }
 
 impl OrderProduct {
-    pub fn into_domain(self) -> DomainOrderProduct {
+    /// Convert into the domain line; amounts other than the unit price are in
+    /// `order_currency`.
+    pub fn into_domain(self, order_currency: &str) -> DomainOrderProduct {
         DomainOrderProduct {
             product_id: self.product_id,
             name: self.name,
             sku: self.sku,
             description: self.description,
-            price_cents: self.price_cents,
-            currency: self.currency,
+            price: Money::new(self.price_cents, self.currency),
             quantity: self.quantity,
+            exchange_rate_micros: self.exchange_rate_micros,
+            tax_rate_basis_points: self.tax_rate_basis_points,
+            prices_include_tax: self.prices_include_tax,
+            net: Money::new(self.net_cents, order_currency),
+            tax: Money::new(self.tax_cents, order_currency),
+            gross: Money::new(self.gross_cents, order_currency),
+        }
+    }
+}
+
+impl OrderDiscount {
+    /// Convert into the domain discount; the discounted amounts are in
+    /// `order_currency`.
+    pub fn into_domain(self, order_currency: &str) -> DomainOrderDiscount {
+        DomainOrderDiscount {
+            promotion_id: self.promotion_id,
+            code: self.code,
+            kind: PriceAdjustmentKind::from(self.kind.as_str()),
+            value: self.value,
+            min_order: self
+                .min_order_cents
+                .map(|cents| Money::new(cents, self.currency.as_str())),
+            currency: self.currency,
+            category_id: self.category_id,
+            tag_id: self.tag_id,
+            net: Money::new(self.net_cents, order_currency),
+            tax: Money::new(self.tax_cents, order_currency),
+            gross: Money::new(self.gross_cents, order_currency),
         }
     }
 }
 
-impl From<(Order, Vec<OrderProduct>)> for DomainOrder {
-    fn from(value: (Order, Vec<OrderProduct>)) -> Self {
-        value.0.into_domain(value.1)
+impl From<(Order, Vec<OrderProduct>, Option<OrderDiscount>)> for DomainOrder {
+    fn from(value: (Order, Vec<OrderProduct>, Option<OrderDiscount>)) -> Self {
+        value.0.into_domain(value.1, value.2)
     }
 }
 
@@ -128,8 +221,10 @@ impl<'a> From<&'a DomainNewOrder> for NewOrder<'a> {
             reference: value.reference.as_deref(),
             status: value.status.into(),
             notes: value.notes.as_deref(),
-            total_cents: value.total_cents,
+            total_cents: value.total.amount,
             currency: value.currency.as_str(),
+            net_cents: value.net.amount,
+            tax_cents: value.tax.amount,
         }
     }
 }
@@ -142,9 +237,34 @@ impl<'a> NewOrderProduct<'a> {
             name: value.name.as_str(),
             sku: value.sku.as_deref(),
             description: value.description.as_deref(),
-            price_cents: value.price_cents,
-            currency: value.currency.as_str(),
+            price_cents: value.price.amount,
+            currency: value.price.currency.as_str(),
             quantity: value.quantity,
+            exchange_rate_micros: value.exchange_rate_micros,
+            tax_rate_basis_points: value.tax_rate_basis_points,
+            prices_include_tax: value.prices_include_tax,
+            net_cents: value.net.amount,
+            tax_cents: value.tax.amount,
+            gross_cents: value.gross.amount,
+        }
+    }
+}
+
+impl<'a> NewOrderDiscount<'a> {
+    pub fn from_domain(order_id: i32, value: &'a DomainOrderDiscount) -> Self {
+        Self {
+            order_id,
+            promotion_id: value.promotion_id,
+            code: value.code.as_str(),
+            kind: value.kind.into(),
+            value: value.value,
+            currency: value.currency.as_str(),
+            category_id: value.category_id,
+            tag_id: value.tag_id,
+            min_order_cents: value.min_order.as_ref().map(|minimum| minimum.amount),
+            net_cents: value.net.amount,
+            tax_cents: value.tax.amount,
+            gross_cents: value.gross.amount,
         }
     }
 }
@@ -152,13 +272,14 @@ impl<'a> NewOrderProduct<'a> {
 impl<'a> From<&'a DomainUpdateOrder> for UpdateOrder<'a> {
     fn from(value: &'a DomainUpdateOrder) -> Self {
         Self {
-            status: value.status.into(),
             notes: value.notes.as_deref(),
-            total_cents: value.total_cents,
+            total_cents: value.total.amount,
             currency: value.currency.as_str(),
             customer_id: value.customer_id,
             reference: value.reference.as_deref(),
             updated_at: value.updated_at,
+            net_cents: value.net.amount,
+            tax_cents: value.tax.amount,
         }
     }
 }
@@ -0,0 +1,58 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::domain::order_status_history::{
+    NewOrderStatusChange as DomainNewOrderStatusChange,
+    OrderStatusChange as DomainOrderStatusChange,
+};
+
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Associations)]
+#[diesel(
+    table_name = crate::schema::order_status_history,
+    belongs_to(super::order::Order, foreign_key = order_id)
+)]
+pub struct OrderStatusChange {
+    pub id: i32,
+    pub order_id: i32,
+    pub from_status: String,
+    pub to_status: String,
+    pub changed_by_email: String,
+    pub changed_by_name: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::order_status_history)]
+pub struct NewOrderStatusChange<'a> {
+    pub order_id: i32,
+    pub from_status: &'a str,
+    pub to_status: &'a str,
+    pub changed_by_email: &'a str,
+    pub changed_by_name: &'a str,
+}
+
+impl From<OrderStatusChange> for DomainOrderStatusChange {
+    fn from(value: OrderStatusChange) -> Self {
+        Self {
+            id: value.id,
+            order_id: value.order_id,
+            from_status: value.from_status.as_str().into(),
+            to_status: value.to_status.as_str().into(),
+            changed_by_email: value.changed_by_email,
+            changed_by_name: value.changed_by_name,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl<'a> From<&'a DomainNewOrderStatusChange> for NewOrderStatusChange<'a> {
+    fn from(value: &'a DomainNewOrderStatusChange) -> Self {
+        Self {
+            order_id: value.order_id,
+            from_status: value.from_status.into(),
+            to_status: value.to_status.into(),
+            changed_by_email: value.changed_by_email.as_str(),
+            changed_by_name: value.changed_by_name.as_str(),
+        }
+    }
+}
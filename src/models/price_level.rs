@@ -2,8 +2,8 @@ use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
 use crate::domain::price_level::{
-    NewPriceLevel as DomainNewPriceLevel, PriceLevel as DomainPriceLevel,
-    UpdatePriceLevel as DomainUpdatePriceLevel,
+    NewPriceLevel as DomainNewPriceLevel, PriceAdjustmentKind, PriceDerivation,
+    PriceLevel as DomainPriceLevel, PriceRounding, UpdatePriceLevel as DomainUpdatePriceLevel,
 };
 
 #[derive(Debug, Clone, Identifiable, Queryable, Selectable)]
@@ -15,6 +15,11 @@ pub struct PriceLevel {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub is_default: bool,
+    pub base_price_level_id: Option<i32>,
+    pub adjustment_kind: String,
+    pub rounding: String,
+    pub adjustment_value: i64,
+    pub prices_include_tax: bool,
 }
 
 #[derive(Insertable)]
@@ -23,6 +28,11 @@ pub struct NewPriceLevel<'a> {
     pub hub_id: i32,
     pub name: &'a str,
     pub is_default: bool,
+    pub base_price_level_id: Option<i32>,
+    pub adjustment_kind: &'static str,
+    pub adjustment_value: i64,
+    pub rounding: &'static str,
+    pub prices_include_tax: bool,
 }
 
 #[derive(AsChangeset)]
@@ -32,10 +42,43 @@ pub struct UpdatePriceLevel<'a> {
     pub name: &'a str,
     pub updated_at: NaiveDateTime,
     pub is_default: bool,
+    pub base_price_level_id: Option<i32>,
+    pub adjustment_kind: &'static str,
+    pub adjustment_value: i64,
+    pub rounding: &'static str,
+    pub prices_include_tax: bool,
+}
+
+/// Column values stored for an optional derivation rule.
+fn derivation_columns(
+    derivation: Option<&PriceDerivation>,
+) -> (Option<i32>, &'static str, i64, &'static str) {
+    match derivation {
+        Some(derivation) => (
+            Some(derivation.base_price_level_id),
+            derivation.adjustment_kind.into(),
+            derivation.adjustment_value,
+            derivation.rounding.into(),
+        ),
+        None => (
+            None,
+            PriceAdjustmentKind::default().into(),
+            0,
+            PriceRounding::default().into(),
+        ),
+    }
 }
 
 impl From<PriceLevel> for DomainPriceLevel {
     fn from(value: PriceLevel) -> Self {
+        let derivation = value
+            .base_price_level_id
+            .map(|base_price_level_id| PriceDerivation {
+                base_price_level_id,
+                adjustment_kind: value.adjustment_kind.as_str().into(),
+                adjustment_value: value.adjustment_value,
+                rounding: value.rounding.as_str().into(),
+            });
         Self {
             id: value.id,
             hub_id: value.hub_id,
@@ -43,26 +86,42 @@ impl From<PriceLevel> for DomainPriceLevel {
             created_at: value.created_at,
             updated_at: value.updated_at,
             is_default: value.is_default,
+            derivation,
+            prices_include_tax: value.prices_include_tax,
         }
     }
 }
 
 impl<'a> From<&'a DomainNewPriceLevel> for NewPriceLevel<'a> {
     fn from(value: &'a DomainNewPriceLevel) -> Self {
+        let (base_price_level_id, adjustment_kind, adjustment_value, rounding) =
+            derivation_columns(value.derivation.as_ref());
         Self {
             hub_id: value.hub_id,
             name: value.name.as_str(),
             is_default: value.is_default,
+            base_price_level_id,
+            adjustment_kind,
+            adjustment_value,
+            rounding,
+            prices_include_tax: value.prices_include_tax,
         }
     }
 }
 
 impl<'a> From<&'a DomainUpdatePriceLevel> for UpdatePriceLevel<'a> {
     fn from(value: &'a DomainUpdatePriceLevel) -> Self {
+        let (base_price_level_id, adjustment_kind, adjustment_value, rounding) =
+            derivation_columns(value.derivation.as_ref());
         Self {
             name: value.name.as_str(),
             updated_at: value.updated_at,
             is_default: value.is_default,
+            base_price_level_id,
+            adjustment_kind,
+            adjustment_value,
+            rounding,
+            prices_include_tax: value.prices_include_tax,
         }
     }
 }
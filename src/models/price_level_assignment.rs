@@ -0,0 +1,66 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::domain::price_level_assignment::{
+    NewPriceLevelAssignmentRequest as DomainNewPriceLevelAssignmentRequest,
+    PriceLevelAssignmentRequest as DomainPriceLevelAssignmentRequest,
+};
+
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Associations)]
+#[diesel(
+    table_name = crate::schema::price_level_assignment_requests,
+    belongs_to(super::customer::Customer, foreign_key = customer_id)
+)]
+pub struct PriceLevelAssignmentRequest {
+    pub id: i32,
+    pub hub_id: i32,
+    pub customer_id: i32,
+    pub price_level_id: Option<i32>,
+    pub status: String,
+    pub requested_by_email: String,
+    pub requested_by_name: String,
+    pub decided_by_email: Option<String>,
+    pub decided_by_name: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub decided_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::price_level_assignment_requests)]
+pub struct NewPriceLevelAssignmentRequest<'a> {
+    pub hub_id: i32,
+    pub customer_id: i32,
+    pub price_level_id: Option<i32>,
+    pub requested_by_email: &'a str,
+    pub requested_by_name: &'a str,
+}
+
+impl From<PriceLevelAssignmentRequest> for DomainPriceLevelAssignmentRequest {
+    fn from(value: PriceLevelAssignmentRequest) -> Self {
+        Self {
+            id: value.id,
+            hub_id: value.hub_id,
+            customer_id: value.customer_id,
+            price_level_id: value.price_level_id,
+            status: value.status.as_str().into(),
+            requested_by_email: value.requested_by_email,
+            requested_by_name: value.requested_by_name,
+            decided_by_email: value.decided_by_email,
+            decided_by_name: value.decided_by_name,
+            created_at: value.created_at,
+            decided_at: value.decided_at,
+        }
+    }
+}
+
+impl<'a> From<&'a DomainNewPriceLevelAssignmentRequest> for NewPriceLevelAssignmentRequest<'a> {
+    fn from(value: &'a DomainNewPriceLevelAssignmentRequest) -> Self {
+        Self {
+            hub_id: value.hub_id,
+            customer_id: value.customer_id,
+            price_level_id: value.price_level_id,
+            requested_by_email: value.requested_by_email.as_str(),
+            requested_by_name: value.requested_by_name.as_str(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
+use crate::domain::inventory_movement::StockLevel;
 use crate::domain::product::{
     NewProduct as DomainNewProduct, Product as DomainProduct, UpdateProduct as DomainUpdateProduct,
 };
@@ -19,6 +20,10 @@ pub struct Product {
     pub updated_at: NaiveDateTime,
     pub units: Option<String>,
     pub category_id: Option<i32>,
+    pub tax_category_id: Option<i32>,
+    pub stock_on_hand: i32,
+    pub stock_reserved: i32,
+    pub allow_oversell: bool,
 }
 
 #[derive(Insertable)]
@@ -31,6 +36,8 @@ pub struct NewProduct<'a> {
     pub units: Option<&'a str>,
     pub currency: &'a str,
     pub category_id: Option<i32>,
+    pub tax_category_id: Option<i32>,
+    pub allow_oversell: bool,
 }
 
 #[derive(AsChangeset)]
@@ -45,6 +52,8 @@ pub struct UpdateProduct<'a> {
     pub is_archived: bool,
     pub updated_at: NaiveDateTime,
     pub category_id: Option<i32>,
+    pub tax_category_id: Option<i32>,
+    pub allow_oversell: bool,
 }
 
 impl From<Product> for DomainProduct {
@@ -59,6 +68,12 @@ impl From<Product> for DomainProduct {
             currency: value.currency,
             is_archived: value.is_archived,
             category_id: value.category_id,
+            tax_category_id: value.tax_category_id,
+            stock: StockLevel {
+                on_hand: value.stock_on_hand,
+                reserved: value.stock_reserved,
+            },
+            allow_oversell: value.allow_oversell,
             price_levels: Vec::new(),
             tags: Vec::new(),
             created_at: value.created_at,
@@ -77,6 +92,8 @@ impl<'a> From<&'a DomainNewProduct> for NewProduct<'a> {
             units: value.units.as_deref(),
             currency: value.currency.as_str(),
             category_id: value.category_id,
+            tax_category_id: value.tax_category_id,
+            allow_oversell: value.allow_oversell,
         }
     }
 }
@@ -92,6 +109,8 @@ impl<'a> From<&'a DomainUpdateProduct> for UpdateProduct<'a> {
             is_archived: value.is_archived,
             updated_at: value.updated_at,
             category_id: value.category_id,
+            tax_category_id: value.tax_category_id,
+            allow_oversell: value.allow_oversell,
         }
     }
 }
@@ -0,0 +1,87 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::domain::product_price_history::{
+    NewProductPriceChange as DomainNewProductPriceChange,
+    ProductPriceChange as DomainProductPriceChange,
+};
+
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable, Associations)]
+#[diesel(
+    table_name = crate::schema::product_price_history,
+    belongs_to(super::product::Product, foreign_key = product_id)
+)]
+pub struct ProductPriceChange {
+    pub id: i32,
+    pub product_id: Option<i32>,
+    pub price_level_id: Option<i32>,
+    pub product_name: String,
+    pub price_level_name: String,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_to: Option<NaiveDateTime>,
+    pub changed_by_email: String,
+    pub changed_by_name: String,
+    pub created_at: NaiveDateTime,
+    pub min_quantity: i32,
+    pub old_price_cents: Option<i64>,
+    pub new_price_cents: Option<i64>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::product_price_history)]
+pub struct NewProductPriceChange<'a> {
+    pub product_id: i32,
+    pub price_level_id: i32,
+    pub product_name: &'a str,
+    pub price_level_name: &'a str,
+    pub old_price_cents: Option<i64>,
+    pub new_price_cents: Option<i64>,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_to: Option<NaiveDateTime>,
+    pub changed_by_email: &'a str,
+    pub changed_by_name: &'a str,
+    pub min_quantity: i32,
+}
+
+impl From<ProductPriceChange> for DomainProductPriceChange {
+    fn from(value: ProductPriceChange) -> Self {
+        Self {
+            id: value.id,
+            product_id: value.product_id,
+            price_level_id: value.price_level_id,
+            product_name: value.product_name,
+            price_level_name: value.price_level_name,
+            min_quantity: value.min_quantity,
+            old_price_cents: value.old_price_cents,
+            new_price_cents: value.new_price_cents,
+            valid_from: value.valid_from,
+            valid_to: value.valid_to,
+            changed_by_email: value.changed_by_email,
+            changed_by_name: value.changed_by_name,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl<'a> NewProductPriceChange<'a> {
+    /// Build the row of `value` with snapshots of the product and price level names.
+    pub fn from_domain(
+        value: &'a DomainNewProductPriceChange,
+        product_name: &'a str,
+        price_level_name: &'a str,
+    ) -> Self {
+        Self {
+            product_id: value.product_id,
+            price_level_id: value.price_level_id,
+            product_name,
+            price_level_name,
+            min_quantity: value.min_quantity,
+            old_price_cents: value.old_price_cents,
+            new_price_cents: value.new_price_cents,
+            valid_from: value.valid_from,
+            valid_to: value.valid_to,
+            changed_by_email: value.changed_by_email.as_str(),
+            changed_by_name: value.changed_by_name.as_str(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
+use crate::domain::money::Money;
 use crate::domain::product_price_level::{
     NewProductPriceLevelRate as DomainNewProductPriceLevelRate,
     ProductPriceLevelRate as DomainProductPriceLevelRate,
@@ -17,9 +18,12 @@ pub struct ProductPriceLevel {
     pub id: i32,
     pub product_id: i32,
     pub price_level_id: i32,
-    pub price_cents: i32,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_to: Option<NaiveDateTime>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub min_quantity: i32,
+    pub price_cents: i64,
 }
 
 #[derive(Insertable)]
@@ -27,26 +31,33 @@ pub struct ProductPriceLevel {
 pub struct NewProductPriceLevel {
     pub product_id: i32,
     pub price_level_id: i32,
-    pub price_cents: i32,
+    pub price_cents: i64,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_to: Option<NaiveDateTime>,
+    pub min_quantity: i32,
 }
 
 #[derive(AsChangeset)]
 #[diesel(table_name = crate::schema::product_price_levels)]
 #[diesel(treat_none_as_null = true)]
 pub struct UpdateProductPriceLevel {
-    pub price_cents: i32,
+    pub price_cents: i64,
     pub updated_at: NaiveDateTime,
 }
 
-impl From<ProductPriceLevel> for DomainProductPriceLevelRate {
-    fn from(value: ProductPriceLevel) -> Self {
-        Self {
-            id: value.id,
-            product_id: value.product_id,
-            price_level_id: value.price_level_id,
-            price_cents: value.price_cents,
-            created_at: value.created_at,
-            updated_at: value.updated_at,
+impl ProductPriceLevel {
+    /// Convert into the domain rate priced in the product's `currency`.
+    pub fn into_domain(self, currency: &str) -> DomainProductPriceLevelRate {
+        DomainProductPriceLevelRate {
+            id: self.id,
+            product_id: self.product_id,
+            price_level_id: self.price_level_id,
+            price: Money::new(self.price_cents, currency),
+            min_quantity: self.min_quantity,
+            valid_from: self.valid_from,
+            valid_to: self.valid_to,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
         }
     }
 }
@@ -56,7 +67,10 @@ impl From<&DomainNewProductPriceLevelRate> for NewProductPriceLevel {
         Self {
             product_id: value.product_id,
             price_level_id: value.price_level_id,
-            price_cents: value.price_cents,
+            price_cents: value.price.amount,
+            valid_from: value.valid_from,
+            valid_to: value.valid_to,
+            min_quantity: value.min_quantity,
         }
     }
 }
@@ -64,7 +78,7 @@ impl From<&DomainNewProductPriceLevelRate> for NewProductPriceLevel {
 impl From<&DomainUpdateProductPriceLevelRate> for UpdateProductPriceLevel {
     fn from(value: &DomainUpdateProductPriceLevelRate) -> Self {
         Self {
-            price_cents: value.price_cents,
+            price_cents: value.price.amount,
             updated_at: value.updated_at,
         }
     }
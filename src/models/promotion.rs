@@ -0,0 +1,81 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::domain::price_level::PriceAdjustmentKind;
+use crate::domain::promotion::{NewPromotion as DomainNewPromotion, Promotion as DomainPromotion};
+
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::promotions)]
+pub struct Promotion {
+    pub id: i32,
+    pub hub_id: i32,
+    pub code: String,
+    pub kind: String,
+    pub value: i64,
+    pub currency: String,
+    pub min_order_cents: Option<i64>,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_to: Option<NaiveDateTime>,
+    pub usage_limit: Option<i32>,
+    pub usage_count: i32,
+    pub category_id: Option<i32>,
+    pub tag_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::promotions)]
+pub struct NewPromotion<'a> {
+    pub hub_id: i32,
+    pub code: &'a str,
+    pub kind: &'static str,
+    pub value: i64,
+    pub currency: &'a str,
+    pub min_order_cents: Option<i64>,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_to: Option<NaiveDateTime>,
+    pub usage_limit: Option<i32>,
+    pub category_id: Option<i32>,
+    pub tag_id: Option<i32>,
+}
+
+impl From<Promotion> for DomainPromotion {
+    fn from(value: Promotion) -> Self {
+        Self {
+            id: value.id,
+            hub_id: value.hub_id,
+            code: value.code,
+            kind: PriceAdjustmentKind::from(value.kind.as_str()),
+            value: value.value,
+            currency: value.currency,
+            min_order_cents: value.min_order_cents,
+            valid_from: value.valid_from,
+            valid_to: value.valid_to,
+            usage_limit: value.usage_limit,
+            usage_count: value.usage_count,
+            category_id: value.category_id,
+            tag_id: value.tag_id,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+impl<'a> From<&'a DomainNewPromotion> for NewPromotion<'a> {
+    fn from(value: &'a DomainNewPromotion) -> Self {
+        Self {
+            hub_id: value.hub_id,
+            code: value.code.as_str(),
+            kind: value.kind.into(),
+            value: value.value,
+            currency: value.currency.as_str(),
+            min_order_cents: value.min_order_cents,
+            valid_from: value.valid_from,
+            valid_to: value.valid_to,
+            usage_limit: value.usage_limit,
+            category_id: value.category_id,
+            tag_id: value.tag_id,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+use crate::domain::tax_category::{
+    NewTaxCategory as DomainNewTaxCategory, TaxCategory as DomainTaxCategory,
+};
+
+#[derive(Debug, Clone, Identifiable, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::tax_categories)]
+pub struct TaxCategory {
+    pub id: i32,
+    pub hub_id: i32,
+    pub name: String,
+    pub rate_basis_points: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::tax_categories)]
+pub struct NewTaxCategory<'a> {
+    pub hub_id: i32,
+    pub name: &'a str,
+    pub rate_basis_points: i32,
+}
+
+impl From<TaxCategory> for DomainTaxCategory {
+    fn from(value: TaxCategory) -> Self {
+        Self {
+            id: value.id,
+            hub_id: value.hub_id,
+            name: value.name,
+            rate_basis_points: value.rate_basis_points,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+impl<'a> From<&'a DomainNewTaxCategory> for NewTaxCategory<'a> {
+    fn from(value: &'a DomainNewTaxCategory) -> Self {
+        Self {
+            hub_id: value.hub_id,
+            name: value.name.as_str(),
+            rate_basis_points: value.rate_basis_points,
+        }
+    }
+}
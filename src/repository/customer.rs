@@ -3,9 +3,18 @@ use diesel::prelude::*;
 use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
 
 use crate::{
-    domain::customer::{Customer as DomainCustomer, NewCustomer as DomainNewCustomer},
-    models::customer::{Customer as DbCustomer, NewCustomer as DbNewCustomer},
-    repository::{CustomerListQuery, CustomerReader, CustomerWriter, DieselRepository},
+    domain::customer::{
+        Customer as DomainCustomer, CustomerImportItem, CustomerImportTarget, CustomerMerge,
+        NewCustomer as DomainNewCustomer, UpdateCustomer as DomainUpdateCustomer,
+    },
+    domain::phone::normalize_phone,
+    models::customer::{
+        Customer as DbCustomer, NewCustomer as DbNewCustomer, UpdateCustomer as DbUpdateCustomer,
+    },
+    repository::{
+        CustomerListQuery, CustomerReader, CustomerWriter, DieselRepository,
+        price_level_assignment::insert_assignment_request,
+    },
 };
 
 impl CustomerReader for DieselRepository {
@@ -50,12 +59,13 @@ impl CustomerReader for DieselRepository {
         use crate::schema::customers;
 
         let normalized_email = email.trim().to_lowercase();
+        // Unrecognisable phones predate normalisation and are matched as stored.
         let normalized_phone = phone.and_then(|value| {
             let trimmed = value.trim();
             if trimmed.is_empty() {
                 None
             } else {
-                Some(trimmed.to_string())
+                Some(normalize_phone(trimmed).unwrap_or_else(|| trimmed.to_string()))
             }
         });
 
@@ -143,21 +153,8 @@ impl CustomerWriter for DieselRepository {
         &self,
         new_customer: &DomainNewCustomer,
     ) -> RepositoryResult<DomainCustomer> {
-        use crate::schema::customers;
-
         let mut conn = self.conn()?;
-
-        if let Some(level_id) = new_customer.price_level_id {
-            ensure_price_level_with_hub(&mut conn, new_customer.hub_id, level_id)?;
-        }
-
-        let db_new = DbNewCustomer::from(new_customer);
-
-        let created = diesel::insert_into(customers::table)
-            .values(&db_new)
-            .get_result::<DbCustomer>(&mut conn)?;
-
-        Ok(created.into())
+        insert_customer(&mut conn, new_customer)
     }
 
     fn assign_price_level_to_customers(
@@ -192,9 +189,178 @@ impl CustomerWriter for DieselRepository {
 
         Ok(())
     }
+
+    fn update_customer(
+        &self,
+        customer_id: i32,
+        hub_id: i32,
+        updates: &DomainUpdateCustomer,
+    ) -> RepositoryResult<DomainCustomer> {
+        let mut conn = self.conn()?;
+        update_customer_in_hub(&mut conn, customer_id, hub_id, updates)
+    }
+
+    fn delete_customer(&self, customer_id: i32, hub_id: i32) -> RepositoryResult<()> {
+        use crate::schema::{customers, orders};
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<(), RepositoryError, _>(|conn| {
+            let has_orders: bool = select(exists(
+                orders::table
+                    .filter(orders::customer_id.eq(customer_id))
+                    .filter(orders::hub_id.eq(hub_id)),
+            ))
+            .get_result(conn)?;
+            if has_orders {
+                return Err(RepositoryError::ConstraintViolation(
+                    "customer has orders".to_string(),
+                ));
+            }
+
+            let deleted = diesel::delete(
+                customers::table
+                    .filter(customers::id.eq(customer_id))
+                    .filter(customers::hub_id.eq(hub_id)),
+            )
+            .execute(conn)?;
+            if deleted == 0 {
+                return Err(RepositoryError::NotFound);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn merge_customers(&self, merge: &CustomerMerge) -> RepositoryResult<DomainCustomer> {
+        use crate::schema::{customers, orders, price_level_assignment_requests as requests};
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<DomainCustomer, RepositoryError, _>(|conn| {
+            let mut customer_ids = merge.merged_ids.clone();
+            customer_ids.push(merge.survivor_id);
+
+            let found: i64 = customers::table
+                .filter(customers::hub_id.eq(merge.hub_id))
+                .filter(customers::id.eq_any(&customer_ids))
+                .count()
+                .get_result(conn)?;
+            if found as usize != customer_ids.len() {
+                return Err(RepositoryError::NotFound);
+            }
+
+            if let Some(level_id) = merge.price_level_id {
+                ensure_price_level_with_hub(conn, merge.hub_id, level_id)?;
+            }
+
+            diesel::update(
+                orders::table
+                    .filter(orders::hub_id.eq(merge.hub_id))
+                    .filter(orders::customer_id.eq_any(&merge.merged_ids)),
+            )
+            .set(orders::customer_id.eq(merge.survivor_id))
+            .execute(conn)?;
+
+            diesel::update(
+                requests::table
+                    .filter(requests::hub_id.eq(merge.hub_id))
+                    .filter(requests::customer_id.eq_any(&merge.merged_ids)),
+            )
+            .set(requests::customer_id.eq(merge.survivor_id))
+            .execute(conn)?;
+
+            diesel::delete(
+                customers::table
+                    .filter(customers::hub_id.eq(merge.hub_id))
+                    .filter(customers::id.eq_any(&merge.merged_ids)),
+            )
+            .execute(conn)?;
+
+            let survivor = diesel::update(
+                customers::table
+                    .filter(customers::id.eq(merge.survivor_id))
+                    .filter(customers::hub_id.eq(merge.hub_id)),
+            )
+            .set((
+                customers::price_level_id.eq(merge.price_level_id),
+                customers::updated_at.eq(merge.updated_at),
+            ))
+            .get_result::<DbCustomer>(conn)?;
+
+            Ok(survivor.into())
+        })
+    }
+
+    fn bulk_import_customers(
+        &self,
+        hub_id: i32,
+        items: &[CustomerImportItem],
+    ) -> RepositoryResult<()> {
+        let mut conn = self.conn()?;
+
+        conn.transaction(|conn| {
+            for item in items {
+                let customer_id = match &item.target {
+                    CustomerImportTarget::Create(new_customer) => {
+                        insert_customer(conn, new_customer)?.id
+                    }
+                    CustomerImportTarget::Update {
+                        customer_id,
+                        updates,
+                    } => update_customer_in_hub(conn, *customer_id, hub_id, updates)?.id,
+                    CustomerImportTarget::Existing(customer_id) => *customer_id,
+                };
+
+                if let Some(request) = &item.price_level_request {
+                    let mut request = request.clone();
+                    request.customer_id = customer_id;
+                    insert_assignment_request(conn, &request)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+fn insert_customer(
+    conn: &mut SqliteConnection,
+    new_customer: &DomainNewCustomer,
+) -> RepositoryResult<DomainCustomer> {
+    use crate::schema::customers;
+
+    if let Some(level_id) = new_customer.price_level_id {
+        ensure_price_level_with_hub(conn, new_customer.hub_id, level_id)?;
+    }
+
+    let created = diesel::insert_into(customers::table)
+        .values(&DbNewCustomer::from(new_customer))
+        .get_result::<DbCustomer>(conn)?;
+
+    Ok(created.into())
+}
+
+fn update_customer_in_hub(
+    conn: &mut SqliteConnection,
+    customer_id: i32,
+    hub_id: i32,
+    updates: &DomainUpdateCustomer,
+) -> RepositoryResult<DomainCustomer> {
+    use crate::schema::customers;
+
+    let target = customers::table
+        .filter(customers::id.eq(customer_id))
+        .filter(customers::hub_id.eq(hub_id));
+
+    let updated = diesel::update(target)
+        .set(&DbUpdateCustomer::from(updates))
+        .get_result::<DbCustomer>(conn)?;
+
+    Ok(updated.into())
 }
 
-fn ensure_price_level_with_hub(
+pub(super) fn ensure_price_level_with_hub(
     conn: &mut SqliteConnection,
     hub_id: i32,
     price_level_id: i32,
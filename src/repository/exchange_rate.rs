@@ -0,0 +1,86 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
+
+use crate::domain::exchange_rate::{
+    ExchangeRate as DomainExchangeRate, NewExchangeRate as DomainNewExchangeRate,
+};
+use crate::models::exchange_rate::{
+    ExchangeRate as DbExchangeRate, NewExchangeRate as DbNewExchangeRate,
+};
+use crate::repository::{DieselRepository, ExchangeRateReader, ExchangeRateWriter};
+
+impl ExchangeRateReader for DieselRepository {
+    fn list_exchange_rates(&self, hub_id: i32) -> RepositoryResult<Vec<DomainExchangeRate>> {
+        use crate::schema::exchange_rates;
+
+        let mut conn = self.conn()?;
+
+        let rates = exchange_rates::table
+            .filter(exchange_rates::hub_id.eq(hub_id))
+            .order((
+                exchange_rates::base_currency.asc(),
+                exchange_rates::quote_currency.asc(),
+            ))
+            .load::<DbExchangeRate>(&mut conn)?;
+
+        Ok(rates.into_iter().map(DomainExchangeRate::from).collect())
+    }
+}
+
+impl ExchangeRateWriter for DieselRepository {
+    fn save_exchange_rates(
+        &self,
+        hub_id: i32,
+        rates: &[DomainNewExchangeRate],
+    ) -> RepositoryResult<usize> {
+        use crate::schema::exchange_rates;
+
+        let mut conn = self.conn()?;
+        let now = Utc::now().naive_utc();
+
+        conn.transaction::<usize, RepositoryError, _>(|conn| {
+            for rate in rates {
+                let updated = diesel::update(
+                    exchange_rates::table
+                        .filter(exchange_rates::hub_id.eq(hub_id))
+                        .filter(exchange_rates::base_currency.eq(&rate.base_currency))
+                        .filter(exchange_rates::quote_currency.eq(&rate.quote_currency)),
+                )
+                .set((
+                    exchange_rates::rate_micros.eq(rate.rate_micros),
+                    exchange_rates::updated_at.eq(now),
+                ))
+                .execute(conn)?;
+
+                if updated == 0 {
+                    let insertable = DbNewExchangeRate {
+                        hub_id,
+                        ..DbNewExchangeRate::from(rate)
+                    };
+                    diesel::insert_into(exchange_rates::table)
+                        .values(&insertable)
+                        .execute(conn)?;
+                }
+            }
+
+            Ok(rates.len())
+        })
+    }
+
+    fn delete_exchange_rate(&self, exchange_rate_id: i32, hub_id: i32) -> RepositoryResult<()> {
+        use crate::schema::exchange_rates;
+
+        let mut conn = self.conn()?;
+        let target = exchange_rates::table
+            .filter(exchange_rates::id.eq(exchange_rate_id))
+            .filter(exchange_rates::hub_id.eq(hub_id));
+
+        let deleted = diesel::delete(target).execute(&mut conn)?;
+        if deleted == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
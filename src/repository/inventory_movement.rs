@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
+
+use crate::domain::inventory_movement::{
+    InventoryMovement as DomainInventoryMovement, InventoryMovementKind,
+    NewStockAdjustment as DomainNewStockAdjustment, StockLevel,
+};
+use crate::domain::order_status_history::NewOrderStatusChange as DomainNewOrderStatusChange;
+use crate::models::inventory_movement::{
+    InventoryMovement as DbInventoryMovement, NewInventoryMovement as DbNewInventoryMovement,
+};
+use crate::repository::{DieselRepository, InventoryReader, InventoryWriter};
+
+impl InventoryReader for DieselRepository {
+    fn list_inventory_movements(
+        &self,
+        product_id: i32,
+        hub_id: i32,
+    ) -> RepositoryResult<Vec<DomainInventoryMovement>> {
+        use crate::schema::{inventory_movements, products};
+        use diesel::dsl::{exists, select};
+
+        let mut conn = self.conn()?;
+
+        let is_owned: bool = select(exists(
+            products::table
+                .filter(products::id.eq(product_id))
+                .filter(products::hub_id.eq(hub_id)),
+        ))
+        .get_result(&mut conn)?;
+        if !is_owned {
+            return Err(RepositoryError::NotFound);
+        }
+
+        let movements = inventory_movements::table
+            .filter(inventory_movements::product_id.eq(product_id))
+            .order((
+                inventory_movements::created_at.desc(),
+                inventory_movements::id.desc(),
+            ))
+            .load::<DbInventoryMovement>(&mut conn)?;
+
+        Ok(movements
+            .into_iter()
+            .map(DomainInventoryMovement::from)
+            .collect())
+    }
+}
+
+impl InventoryWriter for DieselRepository {
+    fn adjust_stock(
+        &self,
+        adjustment: &DomainNewStockAdjustment,
+    ) -> RepositoryResult<DomainInventoryMovement> {
+        use crate::schema::{inventory_movements, products};
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<DomainInventoryMovement, RepositoryError, _>(|conn| {
+            let (on_hand, reserved) = products::table
+                .filter(products::id.eq(adjustment.product_id))
+                .filter(products::hub_id.eq(adjustment.hub_id))
+                .select((products::stock_on_hand, products::stock_reserved))
+                .first::<(i32, i32)>(conn)
+                .optional()?
+                .ok_or(RepositoryError::NotFound)?;
+
+            let stock = InventoryMovementKind::Adjustment
+                .apply(StockLevel { on_hand, reserved }, adjustment.quantity);
+            if stock.on_hand < 0 {
+                return Err(RepositoryError::ConstraintViolation(format!(
+                    "product {} has only {on_hand} units on hand",
+                    adjustment.product_id
+                )));
+            }
+
+            set_stock(conn, adjustment.product_id, stock)?;
+
+            let mut movement = DbNewInventoryMovement::new(
+                adjustment.product_id,
+                InventoryMovementKind::Adjustment,
+                adjustment.quantity,
+                stock,
+                adjustment.changed_by_email.as_str(),
+                adjustment.changed_by_name.as_str(),
+            );
+            movement.note = adjustment.note.as_deref();
+
+            let movement = diesel::insert_into(inventory_movements::table)
+                .values(&movement)
+                .get_result::<DbInventoryMovement>(conn)?;
+
+            Ok(movement.into())
+        })
+    }
+}
+
+/// Moves the stock of every product ordered on `change.order_id` as required
+/// by the status change, recording one ledger row per product.
+///
+/// Lines of deleted products are skipped. Reserving more units than are
+/// available of a product that does not allow oversell, or releasing and
+/// deducting more units than are reserved, fails with `ConstraintViolation`.
+/// Must run inside the transaction updating the order status.
+pub(crate) fn move_order_stock(
+    conn: &mut SqliteConnection,
+    hub_id: i32,
+    change: &DomainNewOrderStatusChange,
+) -> RepositoryResult<()> {
+    use crate::schema::{inventory_movements, order_products, products};
+
+    let Some(kind) = InventoryMovementKind::for_transition(change.from_status, change.to_status)
+    else {
+        return Ok(());
+    };
+
+    let lines = order_products::table
+        .filter(order_products::order_id.eq(change.order_id))
+        .filter(order_products::product_id.is_not_null())
+        .select((order_products::product_id, order_products::quantity))
+        .load::<(Option<i32>, i32)>(conn)?;
+
+    let mut quantities: BTreeMap<i32, i32> = BTreeMap::new();
+    for (product_id, quantity) in lines {
+        if let Some(product_id) = product_id {
+            let total = quantities.entry(product_id).or_default();
+            *total = total.saturating_add(quantity);
+        }
+    }
+
+    for (product_id, quantity) in quantities {
+        let level = products::table
+            .filter(products::id.eq(product_id))
+            .filter(products::hub_id.eq(hub_id))
+            .select((
+                products::stock_on_hand,
+                products::stock_reserved,
+                products::allow_oversell,
+            ))
+            .first::<(i32, i32, bool)>(conn)
+            .optional()?;
+        let Some((on_hand, reserved, allow_oversell)) = level else {
+            continue;
+        };
+
+        let level = StockLevel { on_hand, reserved };
+        if kind == InventoryMovementKind::Reservation
+            && !allow_oversell
+            && quantity > level.available()
+        {
+            return Err(RepositoryError::ConstraintViolation(format!(
+                "product {product_id} has only {} units available",
+                level.available().max(0)
+            )));
+        }
+
+        let stock = kind.apply(level, quantity);
+        if stock.reserved < 0 {
+            return Err(RepositoryError::ConstraintViolation(format!(
+                "product {product_id} has only {reserved} units reserved"
+            )));
+        }
+        set_stock(conn, product_id, stock)?;
+
+        let mut movement = DbNewInventoryMovement::new(
+            product_id,
+            kind,
+            quantity,
+            stock,
+            change.changed_by_email.as_str(),
+            change.changed_by_name.as_str(),
+        );
+        movement.order_id = Some(change.order_id);
+
+        diesel::insert_into(inventory_movements::table)
+            .values(&movement)
+            .execute(conn)?;
+    }
+
+    Ok(())
+}
+
+fn set_stock(
+    conn: &mut SqliteConnection,
+    product_id: i32,
+    stock: StockLevel,
+) -> Result<(), diesel::result::Error> {
+    use crate::schema::products;
+
+    diesel::update(products::table.filter(products::id.eq(product_id)))
+        .set((
+            products::stock_on_hand.eq(stock.on_hand),
+            products::stock_reserved.eq(stock.reserved),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
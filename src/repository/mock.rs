@@ -1,18 +1,33 @@
 use mockall::mock;
 
 use super::{
-    CategoryReader, CategoryWriter, CustomerListQuery, CustomerReader, CustomerWriter, OrderReader,
-    OrderWriter, PriceLevelReader, PriceLevelWriter, ProductReader, ProductWriter, TagReader,
-    TagWriter, UserListQuery, UserReader, UserWriter,
+    CategoryReader, CategoryWriter, CustomerListQuery, CustomerReader, CustomerWriter,
+    ExchangeRateReader, ExchangeRateWriter, InventoryReader, InventoryWriter, OrderReader,
+    OrderWriter, PriceLevelAssignmentReader, PriceLevelAssignmentWriter, PriceLevelReader,
+    PriceLevelWriter, ProductReader, ProductWriter, PromotionReader, PromotionWriter, TagReader,
+    TagWriter, TaxCategoryReader, TaxCategoryWriter, UserListQuery, UserReader, UserWriter,
 };
 use crate::domain::{
     category::{Category, CategoryTreeQuery, NewCategory, UpdateCategory},
-    customer::{Customer, NewCustomer},
+    customer::{Customer, CustomerImportItem, CustomerMerge, NewCustomer, UpdateCustomer},
+    exchange_rate::{ExchangeRate, NewExchangeRate},
+    inventory_movement::{InventoryMovement, NewStockAdjustment},
     order::{NewOrder, Order, OrderListQuery, UpdateOrder},
+    order_status_history::{NewOrderStatusChange, OrderStatusChange},
     price_level::{NewPriceLevel, PriceLevel, PriceLevelListQuery, UpdatePriceLevel},
-    product::{NewProduct, Product, ProductListQuery, UpdateProduct},
-    product_price_level::NewProductPriceLevelRate,
+    price_level_assignment::{
+        NewPriceLevelAssignmentRequest, PriceLevelAssignmentDecision,
+        PriceLevelAssignmentListQuery, PriceLevelAssignmentRequest,
+    },
+    product::{
+        NewProduct, Product, ProductBulkAction, ProductImportItem, ProductListQuery,
+        ProductRemoval, UpdateProduct,
+    },
+    product_price_history::{PriceChangeAuthor, ProductPriceChange, ProductPriceHistoryQuery},
+    product_price_level::{NewProductPriceLevelRate, RepricedRate},
+    promotion::{NewPromotion, Promotion},
     tag::{NewTag, Tag, TagListQuery, UpdateTag},
+    tax_category::{NewTaxCategory, TaxCategory},
     user::{NewUser, UpdateUser, User},
 };
 use pushkind_common::repository::errors::RepositoryResult;
@@ -23,6 +38,7 @@ mock! {
     impl ProductReader for ProductReader {
         fn get_product_by_id(&self, id: i32, hub_id: i32) -> RepositoryResult<Option<Product>>;
         fn list_products(&self, query: ProductListQuery) -> RepositoryResult<(usize, Vec<Product>)>;
+        fn list_product_price_history(&self, query: ProductPriceHistoryQuery) -> RepositoryResult<Vec<ProductPriceChange>>;
     }
 }
 
@@ -33,8 +49,14 @@ mock! {
         fn create_product(&self, new_product: &NewProduct) -> RepositoryResult<Product>;
         fn update_product(&self, product_id: i32, hub_id: i32, updates: &UpdateProduct) -> RepositoryResult<Product>;
         fn delete_product(&self, product_id: i32, hub_id: i32) -> RepositoryResult<()>;
-        fn replace_product_price_levels(&self, product_id: i32, hub_id: i32, rates: &[NewProductPriceLevelRate]) -> RepositoryResult<()>;
+        fn replace_product_price_levels(&self, product_id: i32, hub_id: i32, rates: &[NewProductPriceLevelRate], changed_by: &PriceChangeAuthor) -> RepositoryResult<()>;
+        fn schedule_product_price_levels(&self, product_id: i32, hub_id: i32, rates: &[NewProductPriceLevelRate], changed_by: &PriceChangeAuthor) -> RepositoryResult<()>;
+        fn delete_scheduled_product_price_level(&self, product_id: i32, hub_id: i32, rate_id: i32, changed_by: &PriceChangeAuthor) -> RepositoryResult<()>;
         fn replace_product_tags(&self, product_id: i32, hub_id: i32, tag_ids: &[i32]) -> RepositoryResult<()>;
+        fn remove_product(&self, product_id: i32, hub_id: i32) -> RepositoryResult<ProductRemoval>;
+        fn bulk_update_products(&self, hub_id: i32, product_ids: &[i32], action: &ProductBulkAction) -> RepositoryResult<usize>;
+        fn bulk_import_products(&self, hub_id: i32, items: &[ProductImportItem], changed_by: &PriceChangeAuthor) -> RepositoryResult<()>;
+        fn reprice_product_price_levels(&self, hub_id: i32, rates: &[RepricedRate], changed_by: &PriceChangeAuthor) -> RepositoryResult<usize>;
     }
 }
 
@@ -60,6 +82,10 @@ mock! {
     impl CustomerWriter for CustomerWriter {
         fn create_customer(&self, new_customer: &NewCustomer) -> RepositoryResult<Customer>;
         fn assign_price_level_to_customers(&self, hub_id: i32, customer_ids: &[i32], price_level_id: Option<i32>) -> RepositoryResult<()>;
+        fn update_customer(&self, customer_id: i32, hub_id: i32, updates: &UpdateCustomer) -> RepositoryResult<Customer>;
+        fn delete_customer(&self, customer_id: i32, hub_id: i32) -> RepositoryResult<()>;
+        fn merge_customers(&self, merge: &CustomerMerge) -> RepositoryResult<Customer>;
+        fn bulk_import_customers(&self, hub_id: i32, items: &[CustomerImportItem]) -> RepositoryResult<()>;
     }
 }
 
@@ -88,6 +114,7 @@ mock! {
     impl OrderReader for OrderReader {
         fn get_order_by_id(&self, id: i32, hub_id: i32) -> RepositoryResult<Option<Order>>;
         fn list_orders(&self, query: OrderListQuery) -> RepositoryResult<(usize, Vec<Order>)>;
+        fn list_order_status_history(&self, order_id: i32, hub_id: i32) -> RepositoryResult<Vec<OrderStatusChange>>;
     }
 }
 
@@ -98,6 +125,7 @@ mock! {
         fn create_order(&self, new_order: &NewOrder) -> RepositoryResult<Order>;
         fn update_order(&self, order_id: i32, hub_id: i32, updates: &UpdateOrder) -> RepositoryResult<Order>;
         fn delete_order(&self, order_id: i32, hub_id: i32) -> RepositoryResult<()>;
+        fn transition_order_status(&self, hub_id: i32, change: &NewOrderStatusChange) -> RepositoryResult<Order>;
     }
 }
 
@@ -158,3 +186,88 @@ mock! {
         fn assign_child_categories(&self, hub_id: i32, parent_id: i32, child_ids: &[i32]) -> RepositoryResult<Category>;
     }
 }
+
+mock! {
+    pub PriceLevelAssignmentReader {}
+
+    impl PriceLevelAssignmentReader for PriceLevelAssignmentReader {
+        fn list_price_level_assignment_requests(&self, query: PriceLevelAssignmentListQuery) -> RepositoryResult<(usize, Vec<PriceLevelAssignmentRequest>)>;
+    }
+}
+
+mock! {
+    pub PriceLevelAssignmentWriter {}
+
+    impl PriceLevelAssignmentWriter for PriceLevelAssignmentWriter {
+        fn create_price_level_assignment_request(&self, new_request: &NewPriceLevelAssignmentRequest) -> RepositoryResult<PriceLevelAssignmentRequest>;
+        fn decide_price_level_assignment_request(&self, hub_id: i32, decision: &PriceLevelAssignmentDecision) -> RepositoryResult<PriceLevelAssignmentRequest>;
+    }
+}
+
+mock! {
+    pub ExchangeRateReader {}
+
+    impl ExchangeRateReader for ExchangeRateReader {
+        fn list_exchange_rates(&self, hub_id: i32) -> RepositoryResult<Vec<ExchangeRate>>;
+    }
+}
+
+mock! {
+    pub ExchangeRateWriter {}
+
+    impl ExchangeRateWriter for ExchangeRateWriter {
+        fn save_exchange_rates(&self, hub_id: i32, rates: &[NewExchangeRate]) -> RepositoryResult<usize>;
+        fn delete_exchange_rate(&self, exchange_rate_id: i32, hub_id: i32) -> RepositoryResult<()>;
+    }
+}
+
+mock! {
+    pub TaxCategoryReader {}
+
+    impl TaxCategoryReader for TaxCategoryReader {
+        fn list_tax_categories(&self, hub_id: i32) -> RepositoryResult<Vec<TaxCategory>>;
+    }
+}
+
+mock! {
+    pub TaxCategoryWriter {}
+
+    impl TaxCategoryWriter for TaxCategoryWriter {
+        fn save_tax_category(&self, tax_category: &NewTaxCategory) -> RepositoryResult<TaxCategory>;
+        fn delete_tax_category(&self, tax_category_id: i32, hub_id: i32) -> RepositoryResult<()>;
+    }
+}
+
+mock! {
+    pub PromotionReader {}
+
+    impl PromotionReader for PromotionReader {
+        fn list_promotions(&self, hub_id: i32) -> RepositoryResult<Vec<Promotion>>;
+        fn get_promotion_by_code(&self, code: &str, hub_id: i32) -> RepositoryResult<Option<Promotion>>;
+    }
+}
+
+mock! {
+    pub PromotionWriter {}
+
+    impl PromotionWriter for PromotionWriter {
+        fn create_promotion(&self, promotion: &NewPromotion) -> RepositoryResult<Promotion>;
+        fn delete_promotion(&self, promotion_id: i32, hub_id: i32) -> RepositoryResult<()>;
+    }
+}
+
+mock! {
+    pub InventoryReader {}
+
+    impl InventoryReader for InventoryReader {
+        fn list_inventory_movements(&self, product_id: i32, hub_id: i32) -> RepositoryResult<Vec<InventoryMovement>>;
+    }
+}
+
+mock! {
+    pub InventoryWriter {}
+
+    impl InventoryWriter for InventoryWriter {
+        fn adjust_stock(&self, adjustment: &NewStockAdjustment) -> RepositoryResult<InventoryMovement>;
+    }
+}
@@ -5,21 +5,39 @@ use pushkind_common::repository::errors::RepositoryResult;
 use crate::domain::customer::CustomerListQuery;
 use crate::domain::{
     category::{Category, CategoryTreeQuery, NewCategory, UpdateCategory},
-    customer::{Customer, NewCustomer},
+    customer::{Customer, CustomerImportItem, CustomerMerge, NewCustomer, UpdateCustomer},
+    exchange_rate::{ExchangeRate, NewExchangeRate},
+    inventory_movement::{InventoryMovement, NewStockAdjustment},
     order::{NewOrder, Order, OrderListQuery, UpdateOrder},
+    order_status_history::{NewOrderStatusChange, OrderStatusChange},
     price_level::{NewPriceLevel, PriceLevel, PriceLevelListQuery, UpdatePriceLevel},
-    product::{NewProduct, Product, ProductListQuery, UpdateProduct},
-    product_price_level::NewProductPriceLevelRate,
+    price_level_assignment::{
+        NewPriceLevelAssignmentRequest, PriceLevelAssignmentDecision,
+        PriceLevelAssignmentListQuery, PriceLevelAssignmentRequest,
+    },
+    product::{
+        NewProduct, Product, ProductBulkAction, ProductImportItem, ProductListQuery,
+        ProductRemoval, UpdateProduct,
+    },
+    product_price_history::{PriceChangeAuthor, ProductPriceChange, ProductPriceHistoryQuery},
+    product_price_level::{NewProductPriceLevelRate, RepricedRate},
+    promotion::{NewPromotion, Promotion},
     tag::{NewTag, Tag, TagListQuery, UpdateTag},
+    tax_category::{NewTaxCategory, TaxCategory},
     user::{NewUser, UpdateUser, User},
 };
 
 pub mod category;
 pub mod customer;
+pub mod exchange_rate;
+pub mod inventory_movement;
 pub mod order;
 pub mod price_level;
+pub mod price_level_assignment;
 pub mod product;
+pub mod promotion;
 pub mod tag;
+pub mod tax_category;
 pub mod user;
 
 #[cfg(test)]
@@ -48,6 +66,26 @@ pub trait CustomerWriter {
         customer_ids: &[i32],
         price_level_id: Option<i32>,
     ) -> RepositoryResult<()>;
+    fn update_customer(
+        &self,
+        customer_id: i32,
+        hub_id: i32,
+        updates: &UpdateCustomer,
+    ) -> RepositoryResult<Customer>;
+    /// Deletes the customer, failing with `ConstraintViolation` while it still
+    /// has orders.
+    fn delete_customer(&self, customer_id: i32, hub_id: i32) -> RepositoryResult<()>;
+    /// Moves the orders and price level requests of the merged customers to
+    /// the survivor, applies the chosen price level and deletes the merged
+    /// customers in one transaction.
+    fn merge_customers(&self, merge: &CustomerMerge) -> RepositoryResult<Customer>;
+    /// Writes every row of a customer import in one transaction together with
+    /// the price level requests it files. Nothing is written when a row fails.
+    fn bulk_import_customers(
+        &self,
+        hub_id: i32,
+        items: &[CustomerImportItem],
+    ) -> RepositoryResult<()>;
 }
 
 #[derive(Clone)]
@@ -71,6 +109,11 @@ impl DieselRepository {
 pub trait ProductReader {
     fn get_product_by_id(&self, id: i32, hub_id: i32) -> RepositoryResult<Option<Product>>;
     fn list_products(&self, query: ProductListQuery) -> RepositoryResult<(usize, Vec<Product>)>;
+    /// Lists recorded price changes of a hub's product, oldest first.
+    fn list_product_price_history(
+        &self,
+        query: ProductPriceHistoryQuery,
+    ) -> RepositoryResult<Vec<ProductPriceChange>>;
 }
 
 /// Write operations over product records.
@@ -83,11 +126,31 @@ pub trait ProductWriter {
         updates: &UpdateProduct,
     ) -> RepositoryResult<Product>;
     fn delete_product(&self, product_id: i32, hub_id: i32) -> RepositoryResult<()>;
+    /// Replaces the base rates of the product, keeping scheduled ones.
+    ///
+    /// Every level whose price changes is recorded in the price history.
     fn replace_product_price_levels(
         &self,
         product_id: i32,
         hub_id: i32,
         rates: &[NewProductPriceLevelRate],
+        changed_by: &PriceChangeAuthor,
+    ) -> RepositoryResult<()>;
+    /// Adds rates with a validity window without touching existing rates.
+    fn schedule_product_price_levels(
+        &self,
+        product_id: i32,
+        hub_id: i32,
+        rates: &[NewProductPriceLevelRate],
+        changed_by: &PriceChangeAuthor,
+    ) -> RepositoryResult<()>;
+    /// Deletes a scheduled rate of the product; base rates are not affected.
+    fn delete_scheduled_product_price_level(
+        &self,
+        product_id: i32,
+        hub_id: i32,
+        rate_id: i32,
+        changed_by: &PriceChangeAuthor,
     ) -> RepositoryResult<()>;
     fn replace_product_tags(
         &self,
@@ -95,6 +158,35 @@ pub trait ProductWriter {
         hub_id: i32,
         tag_ids: &[i32],
     ) -> RepositoryResult<()>;
+    /// Deletes the product when it was never ordered, archives it otherwise.
+    fn remove_product(&self, product_id: i32, hub_id: i32) -> RepositoryResult<ProductRemoval>;
+    /// Applies `action` to every listed product of the hub in one transaction,
+    /// returning the number of products changed.
+    fn bulk_update_products(
+        &self,
+        hub_id: i32,
+        product_ids: &[i32],
+        action: &ProductBulkAction,
+    ) -> RepositoryResult<usize>;
+    /// Writes every row of a product import in one transaction, creating the
+    /// missing categories and tags first. Nothing is written when a row fails.
+    fn bulk_import_products(
+        &self,
+        hub_id: i32,
+        items: &[ProductImportItem],
+        changed_by: &PriceChangeAuthor,
+    ) -> RepositoryResult<()>;
+    /// Sets the new prices of a bulk repricing in one transaction and records
+    /// them in the price history, returning the number of rates changed.
+    ///
+    /// Fails with `ConstraintViolation` when a rate no longer has its old
+    /// price, leaving every rate untouched.
+    fn reprice_product_price_levels(
+        &self,
+        hub_id: i32,
+        rates: &[RepricedRate],
+        changed_by: &PriceChangeAuthor,
+    ) -> RepositoryResult<usize>;
 }
 
 /// Read-only operations over price level records.
@@ -118,15 +210,59 @@ pub trait PriceLevelWriter {
     fn delete_price_level(&self, price_level_id: i32, hub_id: i32) -> RepositoryResult<()>;
 }
 
+/// Read-only operations over client price level assignment requests.
+pub trait PriceLevelAssignmentReader {
+    fn list_price_level_assignment_requests(
+        &self,
+        query: PriceLevelAssignmentListQuery,
+    ) -> RepositoryResult<(usize, Vec<PriceLevelAssignmentRequest>)>;
+}
+
+/// Write operations over client price level assignment requests.
+pub trait PriceLevelAssignmentWriter {
+    /// File a pending request, returning the already pending identical request
+    /// instead of queueing a duplicate.
+    fn create_price_level_assignment_request(
+        &self,
+        new_request: &NewPriceLevelAssignmentRequest,
+    ) -> RepositoryResult<PriceLevelAssignmentRequest>;
+    /// Approve or reject a pending request; approval updates the customer's
+    /// price level in the same transaction.
+    ///
+    /// Fails with `NotFound` when the request does not exist in the hub or is no
+    /// longer pending.
+    fn decide_price_level_assignment_request(
+        &self,
+        hub_id: i32,
+        decision: &PriceLevelAssignmentDecision,
+    ) -> RepositoryResult<PriceLevelAssignmentRequest>;
+}
+
 /// Read-only operations over order records including their products.
 pub trait OrderReader {
     fn get_order_by_id(&self, id: i32, hub_id: i32) -> RepositoryResult<Option<Order>>;
     fn list_orders(&self, query: OrderListQuery) -> RepositoryResult<(usize, Vec<Order>)>;
+    fn list_order_status_history(
+        &self,
+        order_id: i32,
+        hub_id: i32,
+    ) -> RepositoryResult<Vec<OrderStatusChange>>;
 }
 
 /// Write operations over order records.
 pub trait OrderWriter {
+    /// Inserts the order with its lines and discount.
+    ///
+    /// Redeeming a promotion increments its usage count in the same
+    /// transaction, failing with `ConstraintViolation` carrying
+    /// [`PROMOTION_USED_UP`] once the usage limit is reached.
+    ///
+    /// [`PROMOTION_USED_UP`]: crate::domain::promotion::PROMOTION_USED_UP
     fn create_order(&self, new_order: &NewOrder) -> RepositoryResult<Order>;
+    /// Replace the editable fields of an order, leaving its status untouched.
+    ///
+    /// Fails with `NotFound` when the order does not exist in the hub or no longer
+    /// has the expected status.
     fn update_order(
         &self,
         order_id: i32,
@@ -134,6 +270,24 @@ pub trait OrderWriter {
         updates: &UpdateOrder,
     ) -> RepositoryResult<Order>;
     fn delete_order(&self, order_id: i32, hub_id: i32) -> RepositoryResult<()>;
+    /// Move the order to a new status and record the change in its history.
+    ///
+    /// Stock of the ordered products is reserved, deducted or released in the
+    /// same transaction, see [`InventoryMovementKind::for_transition`]. Cancelling
+    /// an order with a promotion discount gives the redemption back to the
+    /// promotion.
+    ///
+    /// Fails with `NotFound` when the order does not exist in the hub or no longer
+    /// has the expected `from_status`, and with `ConstraintViolation` when a
+    /// product that does not allow oversell lacks the units to reserve or a
+    /// product holds fewer reserved units than the order releases.
+    ///
+    /// [`InventoryMovementKind::for_transition`]: crate::domain::inventory_movement::InventoryMovementKind::for_transition
+    fn transition_order_status(
+        &self,
+        hub_id: i32,
+        change: &NewOrderStatusChange,
+    ) -> RepositoryResult<Order>;
 }
 
 /// Read-only operations over tag records.
@@ -177,6 +331,77 @@ pub trait CategoryWriter {
     ) -> RepositoryResult<Category>;
 }
 
+/// Read operations over exchange rate records.
+pub trait ExchangeRateReader {
+    /// Lists the hub's rates ordered by currency pair.
+    fn list_exchange_rates(&self, hub_id: i32) -> RepositoryResult<Vec<ExchangeRate>>;
+}
+
+/// Write operations over exchange rate records.
+pub trait ExchangeRateWriter {
+    /// Stores the rates in one transaction, replacing the rate of pairs the
+    /// hub already has. Returns the number of rates saved.
+    fn save_exchange_rates(
+        &self,
+        hub_id: i32,
+        rates: &[NewExchangeRate],
+    ) -> RepositoryResult<usize>;
+    fn delete_exchange_rate(&self, exchange_rate_id: i32, hub_id: i32) -> RepositoryResult<()>;
+}
+
+/// Read operations over tax category records.
+pub trait TaxCategoryReader {
+    /// Lists the hub's tax categories ordered by name.
+    fn list_tax_categories(&self, hub_id: i32) -> RepositoryResult<Vec<TaxCategory>>;
+}
+
+/// Write operations over tax category records.
+pub trait TaxCategoryWriter {
+    /// Stores the category, replacing the rate of a category with the same
+    /// name in the hub.
+    fn save_tax_category(&self, tax_category: &NewTaxCategory) -> RepositoryResult<TaxCategory>;
+    fn delete_tax_category(&self, tax_category_id: i32, hub_id: i32) -> RepositoryResult<()>;
+}
+
+/// Read operations over promotion records.
+pub trait PromotionReader {
+    /// Lists the hub's promotions ordered by code.
+    fn list_promotions(&self, hub_id: i32) -> RepositoryResult<Vec<Promotion>>;
+    /// Finds a promotion by its normalized code.
+    fn get_promotion_by_code(&self, code: &str, hub_id: i32)
+    -> RepositoryResult<Option<Promotion>>;
+}
+
+/// Write operations over promotion records.
+pub trait PromotionWriter {
+    /// Inserts the promotion, failing with `NotFound` when its category or
+    /// tag belongs to another hub.
+    fn create_promotion(&self, promotion: &NewPromotion) -> RepositoryResult<Promotion>;
+    /// Deletes the promotion; discounts already applied to orders keep its code.
+    fn delete_promotion(&self, promotion_id: i32, hub_id: i32) -> RepositoryResult<()>;
+}
+
+/// Read operations over the inventory ledger.
+pub trait InventoryReader {
+    /// Lists the stock movements of a product, newest first.
+    ///
+    /// Fails with `NotFound` for products of other hubs.
+    fn list_inventory_movements(
+        &self,
+        product_id: i32,
+        hub_id: i32,
+    ) -> RepositoryResult<Vec<InventoryMovement>>;
+}
+
+/// Write operations over product stock.
+pub trait InventoryWriter {
+    /// Changes the quantity on hand and records the adjustment in the ledger.
+    ///
+    /// Fails with `NotFound` for products of other hubs and with
+    /// `ConstraintViolation` when the quantity on hand would become negative.
+    fn adjust_stock(&self, adjustment: &NewStockAdjustment) -> RepositoryResult<InventoryMovement>;
+}
+
 #[derive(Debug, Clone)]
 /// Query definition used to list users for a hub.
 pub struct UserListQuery {
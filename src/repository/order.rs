@@ -1,23 +1,34 @@
 use std::collections::HashMap;
 
+use chrono::Utc;
 use diesel::prelude::*;
 use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
 
 use crate::{
     domain::order::{
-        NewOrder as DomainNewOrder, Order as DomainOrder, OrderListQuery,
+        NewOrder as DomainNewOrder, Order as DomainOrder, OrderListQuery, OrderStatus,
         UpdateOrder as DomainUpdateOrder,
     },
+    domain::order_status_history::{
+        NewOrderStatusChange as DomainNewOrderStatusChange,
+        OrderStatusChange as DomainOrderStatusChange,
+    },
+    domain::promotion::PROMOTION_USED_UP,
     models::order::{
-        NewOrder as DbNewOrder, NewOrderProduct as DbNewOrderProduct, Order as DbOrder,
+        NewOrder as DbNewOrder, NewOrderDiscount as DbNewOrderDiscount,
+        NewOrderProduct as DbNewOrderProduct, Order as DbOrder, OrderDiscount as DbOrderDiscount,
         OrderProduct as DbOrderProduct, UpdateOrder as DbUpdateOrder,
     },
+    models::order_status_history::{
+        NewOrderStatusChange as DbNewOrderStatusChange, OrderStatusChange as DbOrderStatusChange,
+    },
+    repository::inventory_movement::move_order_stock,
     repository::{DieselRepository, OrderReader, OrderWriter},
 };
 
 impl OrderReader for DieselRepository {
     fn get_order_by_id(&self, id: i32, hub_id: i32) -> RepositoryResult<Option<DomainOrder>> {
-        use crate::schema::{order_products, orders};
+        use crate::schema::orders;
 
         let mut conn = self.conn()?;
         let order = orders::table
@@ -30,77 +41,22 @@ impl OrderReader for DieselRepository {
             return Ok(None);
         };
 
-        let order_id = order.id;
-
-        let products = order_products::table
-            .filter(order_products::order_id.eq(order_id))
-            .order(order_products::id.asc())
-            .load::<DbOrderProduct>(&mut conn)?;
-
-        Ok(Some(DomainOrder::from((order, products))))
+        Ok(Some(load_order_details(&mut conn, order)?))
     }
 
     fn list_orders(&self, query: OrderListQuery) -> RepositoryResult<(usize, Vec<DomainOrder>)> {
-        use crate::schema::{order_products, orders};
+        use crate::schema::{order_discounts, order_products, orders};
 
         let mut conn = self.conn()?;
 
-        let OrderListQuery {
-            hub_id,
-            status,
-            customer_id,
-            search,
-            pagination,
-        } = query;
-
-        let status_filter = status.map(String::from);
-        let search_pattern = search.as_ref().map(|term| format!("%{}%", term));
+        let total = filtered_orders(&query)
+            .count()
+            .get_result::<i64>(&mut conn)? as usize;
 
-        let mut count_query = orders::table
-            .filter(orders::hub_id.eq(hub_id))
-            .into_boxed::<diesel::sqlite::Sqlite>();
-
-        if let Some(ref status_value) = status_filter {
-            count_query = count_query.filter(orders::status.eq(status_value.as_str()));
-        }
-
-        if let Some(customer) = customer_id {
-            count_query = count_query.filter(orders::customer_id.eq(Some(customer)));
-        }
-
-        if let Some(ref pattern) = search_pattern {
-            count_query = count_query.filter(
-                orders::reference
-                    .like(pattern.clone())
-                    .or(orders::notes.like(pattern.clone())),
-            );
-        }
+        let mut items =
+            filtered_orders(&query).order((orders::created_at.desc(), orders::id.desc()));
 
-        let total = count_query.count().get_result::<i64>(&mut conn)? as usize;
-
-        let mut items = orders::table
-            .filter(orders::hub_id.eq(hub_id))
-            .into_boxed::<diesel::sqlite::Sqlite>();
-
-        if let Some(ref status_value) = status_filter {
-            items = items.filter(orders::status.eq(status_value.as_str()));
-        }
-
-        if let Some(customer) = customer_id {
-            items = items.filter(orders::customer_id.eq(Some(customer)));
-        }
-
-        if let Some(ref pattern) = search_pattern {
-            items = items.filter(
-                orders::reference
-                    .like(pattern.clone())
-                    .or(orders::notes.like(pattern.clone())),
-            );
-        }
-
-        items = items.order(orders::created_at.desc());
-
-        if let Some(pagination) = pagination {
+        if let Some(pagination) = query.pagination {
             let offset = ((pagination.page.max(1) - 1) * pagination.per_page) as i64;
             let limit = pagination.per_page as i64;
             items = items.offset(offset).limit(limit);
@@ -129,26 +85,87 @@ impl OrderReader for DieselRepository {
             }
         }
 
+        let mut discounts_by_order: HashMap<i32, DbOrderDiscount> = order_discounts::table
+            .filter(order_discounts::order_id.eq_any(&order_ids))
+            .load::<DbOrderDiscount>(&mut conn)?
+            .into_iter()
+            .map(|discount| (discount.order_id, discount))
+            .collect();
+
         let orders = db_orders
             .into_iter()
             .map(|order| {
                 let order_id = order.id;
                 let products = products_by_order.remove(&order_id).unwrap_or_default();
-                DomainOrder::from((order, products))
+                let discount = discounts_by_order.remove(&order_id);
+                DomainOrder::from((order, products, discount))
             })
             .collect();
 
         Ok((total, orders))
     }
+
+    fn list_order_status_history(
+        &self,
+        order_id: i32,
+        hub_id: i32,
+    ) -> RepositoryResult<Vec<DomainOrderStatusChange>> {
+        use crate::schema::{order_status_history, orders};
+
+        let mut conn = self.conn()?;
+
+        let rows = order_status_history::table
+            .inner_join(orders::table)
+            .filter(order_status_history::order_id.eq(order_id))
+            .filter(orders::hub_id.eq(hub_id))
+            .select(DbOrderStatusChange::as_select())
+            .order((
+                order_status_history::created_at.asc(),
+                order_status_history::id.asc(),
+            ))
+            .load::<DbOrderStatusChange>(&mut conn)?;
+
+        Ok(rows
+            .into_iter()
+            .map(DomainOrderStatusChange::from)
+            .collect())
+    }
 }
 
 impl OrderWriter for DieselRepository {
     fn create_order(&self, new_order: &DomainNewOrder) -> RepositoryResult<DomainOrder> {
-        use crate::schema::{order_products, orders};
+        use crate::schema::{order_discounts, order_products, orders, promotions};
 
         let mut conn = self.conn()?;
 
         conn.transaction::<DomainOrder, RepositoryError, _>(|conn| {
+            if let Some(promotion_id) = new_order
+                .discount
+                .as_ref()
+                .and_then(|discount| discount.promotion_id)
+            {
+                let redeemed =
+                    diesel::update(
+                        promotions::table
+                            .filter(promotions::id.eq(promotion_id))
+                            .filter(promotions::hub_id.eq(new_order.hub_id))
+                            .filter(promotions::usage_limit.is_null().or(
+                                promotions::usage_limit.gt(promotions::usage_count.nullable()),
+                            )),
+                    )
+                    .set((
+                        promotions::usage_count.eq(promotions::usage_count + 1),
+                        promotions::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .execute(conn)?;
+
+                if redeemed == 0 {
+                    return Err(RepositoryError::ConstraintViolation(
+                        PROMOTION_USED_UP.to_string(),
+                    ));
+                }
+            }
+
             let db_new = DbNewOrder::from(new_order);
 
             let created = diesel::insert_into(orders::table)
@@ -169,12 +186,13 @@ impl OrderWriter for DieselRepository {
                     .execute(conn)?;
             }
 
-            let products = order_products::table
-                .filter(order_products::order_id.eq(order_id))
-                .order(order_products::id.asc())
-                .load::<DbOrderProduct>(conn)?;
+            if let Some(discount) = new_order.discount.as_ref() {
+                diesel::insert_into(order_discounts::table)
+                    .values(&DbNewOrderDiscount::from_domain(order_id, discount))
+                    .execute(conn)?;
+            }
 
-            Ok(DomainOrder::from((created, products)))
+            load_order_details(conn, created)
         })
     }
 
@@ -184,20 +202,27 @@ impl OrderWriter for DieselRepository {
         hub_id: i32,
         updates: &DomainUpdateOrder,
     ) -> RepositoryResult<DomainOrder> {
-        use crate::schema::{order_products, orders};
+        use crate::schema::{order_discounts, order_products, orders};
 
         let mut conn = self.conn()?;
 
         conn.transaction::<DomainOrder, RepositoryError, _>(|conn| {
             let db_updates = DbUpdateOrder::from(updates);
+            let expected_status: &str = updates.expected_status.into();
 
             let target = orders::table
                 .filter(orders::id.eq(order_id))
-                .filter(orders::hub_id.eq(hub_id));
+                .filter(orders::hub_id.eq(hub_id))
+                .filter(orders::status.eq(expected_status));
 
             let updated = diesel::update(target)
                 .set(&db_updates)
-                .get_result::<DbOrder>(conn)?;
+                .get_result::<DbOrder>(conn)
+                .optional()?;
+
+            let Some(updated) = updated else {
+                return Err(RepositoryError::NotFound);
+            };
 
             if let Some(products) = updates.products.as_ref() {
                 diesel::delete(order_products::table.filter(order_products::order_id.eq(order_id)))
@@ -213,14 +238,20 @@ impl OrderWriter for DieselRepository {
                         .values(&payload)
                         .execute(conn)?;
                 }
-            }
 
-            let products = order_products::table
-                .filter(order_products::order_id.eq(order_id))
-                .order(order_products::id.asc())
-                .load::<DbOrderProduct>(conn)?;
+                diesel::delete(
+                    order_discounts::table.filter(order_discounts::order_id.eq(order_id)),
+                )
+                .execute(conn)?;
 
-            Ok(DomainOrder::from((updated, products)))
+                if let Some(discount) = updates.discount.as_ref() {
+                    diesel::insert_into(order_discounts::table)
+                        .values(&DbNewOrderDiscount::from_domain(order_id, discount))
+                        .execute(conn)?;
+                }
+            }
+
+            load_order_details(conn, updated)
         })
     }
 
@@ -240,4 +271,156 @@ impl OrderWriter for DieselRepository {
 
         Ok(())
     }
+
+    fn transition_order_status(
+        &self,
+        hub_id: i32,
+        change: &DomainNewOrderStatusChange,
+    ) -> RepositoryResult<DomainOrder> {
+        use crate::schema::{order_status_history, orders};
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<DomainOrder, RepositoryError, _>(|conn| {
+            let from_status: &str = change.from_status.into();
+            let to_status: &str = change.to_status.into();
+
+            let target = orders::table
+                .filter(orders::id.eq(change.order_id))
+                .filter(orders::hub_id.eq(hub_id))
+                .filter(orders::status.eq(from_status));
+
+            let updated = diesel::update(target)
+                .set((
+                    orders::status.eq(to_status),
+                    orders::updated_at.eq(Utc::now().naive_utc()),
+                ))
+                .get_result::<DbOrder>(conn)
+                .optional()?;
+
+            let Some(updated) = updated else {
+                return Err(RepositoryError::NotFound);
+            };
+
+            diesel::insert_into(order_status_history::table)
+                .values(&DbNewOrderStatusChange::from(change))
+                .execute(conn)?;
+
+            move_order_stock(conn, hub_id, change)?;
+
+            if change.to_status == OrderStatus::Cancelled {
+                release_promotion(conn, hub_id, change.order_id)?;
+            }
+
+            load_order_details(conn, updated)
+        })
+    }
+}
+
+/// Gives back the redemption of the promotion whose discount `order_id`
+/// carries, so the code counts only orders that were not cancelled.
+fn release_promotion(
+    conn: &mut SqliteConnection,
+    hub_id: i32,
+    order_id: i32,
+) -> RepositoryResult<()> {
+    use crate::schema::{order_discounts, promotions};
+
+    let promotion_id = order_discounts::table
+        .filter(order_discounts::order_id.eq(order_id))
+        .select(order_discounts::promotion_id)
+        .first::<Option<i32>>(conn)
+        .optional()?
+        .flatten();
+
+    if let Some(promotion_id) = promotion_id {
+        diesel::update(
+            promotions::table
+                .filter(promotions::id.eq(promotion_id))
+                .filter(promotions::hub_id.eq(hub_id))
+                .filter(promotions::usage_count.gt(0)),
+        )
+        .set((
+            promotions::usage_count.eq(promotions::usage_count - 1),
+            promotions::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .execute(conn)?;
+    }
+
+    Ok(())
+}
+
+/// Loads the lines and the discount of `order`.
+fn load_order_details(
+    conn: &mut SqliteConnection,
+    order: DbOrder,
+) -> RepositoryResult<DomainOrder> {
+    use crate::schema::{order_discounts, order_products};
+
+    let products = order_products::table
+        .filter(order_products::order_id.eq(order.id))
+        .order(order_products::id.asc())
+        .load::<DbOrderProduct>(conn)?;
+
+    let discount = order_discounts::table
+        .filter(order_discounts::order_id.eq(order.id))
+        .first::<DbOrderDiscount>(conn)
+        .optional()?;
+
+    Ok(DomainOrder::from((order, products, discount)))
+}
+
+/// Builds the boxed `orders` query with every [`OrderListQuery`] filter applied.
+fn filtered_orders(
+    query: &OrderListQuery,
+) -> crate::schema::orders::BoxedQuery<'static, diesel::sqlite::Sqlite> {
+    use crate::schema::orders;
+
+    let mut items = orders::table
+        .filter(orders::hub_id.eq(query.hub_id))
+        .into_boxed::<diesel::sqlite::Sqlite>();
+
+    if !query.statuses.is_empty() {
+        let statuses: Vec<String> = query.statuses.iter().copied().map(String::from).collect();
+        items = items.filter(orders::status.eq_any(statuses));
+    }
+
+    if let Some(customer) = query.customer_id {
+        items = items.filter(orders::customer_id.eq(Some(customer)));
+    }
+
+    if let Some(from) = query.created_from {
+        items = items.filter(orders::created_at.ge(from));
+    }
+
+    if let Some(before) = query.created_before {
+        items = items.filter(orders::created_at.lt(before));
+    }
+
+    if let Some(min_total) = query.min_total.as_ref() {
+        items = items.filter(
+            orders::currency
+                .eq(min_total.currency.clone())
+                .and(orders::total_cents.ge(min_total.amount)),
+        );
+    }
+
+    if let Some(max_total) = query.max_total.as_ref() {
+        items = items.filter(
+            orders::currency
+                .eq(max_total.currency.clone())
+                .and(orders::total_cents.le(max_total.amount)),
+        );
+    }
+
+    if let Some(term) = query.search.as_ref() {
+        let pattern = format!("%{}%", term);
+        items = items.filter(
+            orders::reference
+                .like(pattern.clone())
+                .or(orders::notes.like(pattern)),
+        );
+    }
+
+    items
 }
@@ -147,15 +147,26 @@ impl PriceLevelWriter for DieselRepository {
 
         let mut conn = self.conn()?;
 
-        let target = price_levels::table
-            .filter(price_levels::id.eq(price_level_id))
-            .filter(price_levels::hub_id.eq(hub_id));
+        conn.transaction(|conn| {
+            let target = price_levels::table
+                .filter(price_levels::id.eq(price_level_id))
+                .filter(price_levels::hub_id.eq(hub_id));
 
-        let deleted = diesel::delete(target).execute(&mut conn)?;
-        if deleted == 0 {
-            return Err(RepositoryError::NotFound);
-        }
+            let deleted = diesel::delete(target).execute(conn)?;
+            if deleted == 0 {
+                return Err(RepositoryError::NotFound);
+            }
 
-        Ok(())
+            // Levels derived from the deleted one keep only their explicit rates.
+            diesel::update(
+                price_levels::table
+                    .filter(price_levels::hub_id.eq(hub_id))
+                    .filter(price_levels::base_price_level_id.eq(price_level_id)),
+            )
+            .set(price_levels::base_price_level_id.eq(None::<i32>))
+            .execute(conn)?;
+
+            Ok(())
+        })
     }
 }
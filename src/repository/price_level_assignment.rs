@@ -0,0 +1,172 @@
+use chrono::Utc;
+use diesel::dsl::{exists, select};
+use diesel::prelude::*;
+use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
+
+use crate::{
+    domain::price_level_assignment::{
+        NewPriceLevelAssignmentRequest as DomainNewPriceLevelAssignmentRequest,
+        PriceLevelAssignmentDecision, PriceLevelAssignmentListQuery,
+        PriceLevelAssignmentRequest as DomainPriceLevelAssignmentRequest,
+        PriceLevelAssignmentStatus,
+    },
+    models::price_level_assignment::{
+        NewPriceLevelAssignmentRequest as DbNewPriceLevelAssignmentRequest,
+        PriceLevelAssignmentRequest as DbPriceLevelAssignmentRequest,
+    },
+    repository::{
+        DieselRepository, PriceLevelAssignmentReader, PriceLevelAssignmentWriter,
+        customer::ensure_price_level_with_hub,
+    },
+};
+
+impl PriceLevelAssignmentReader for DieselRepository {
+    fn list_price_level_assignment_requests(
+        &self,
+        query: PriceLevelAssignmentListQuery,
+    ) -> RepositoryResult<(usize, Vec<DomainPriceLevelAssignmentRequest>)> {
+        use crate::schema::price_level_assignment_requests as requests;
+
+        let mut conn = self.conn()?;
+
+        let filtered = || {
+            let mut items = requests::table
+                .filter(requests::hub_id.eq(query.hub_id))
+                .into_boxed::<diesel::sqlite::Sqlite>();
+            if let Some(status) = query.status {
+                let status: &'static str = status.into();
+                items = items.filter(requests::status.eq(status));
+            }
+            items
+        };
+
+        let total = filtered().count().get_result::<i64>(&mut conn)? as usize;
+
+        let mut items = filtered().order((requests::created_at.desc(), requests::id.desc()));
+
+        if let Some(pagination) = &query.pagination {
+            let offset = ((pagination.page.max(1) - 1) * pagination.per_page) as i64;
+            let limit = pagination.per_page as i64;
+            items = items.offset(offset).limit(limit);
+        }
+
+        let rows = items.load::<DbPriceLevelAssignmentRequest>(&mut conn)?;
+
+        Ok((total, rows.into_iter().map(Into::into).collect()))
+    }
+}
+
+impl PriceLevelAssignmentWriter for DieselRepository {
+    fn create_price_level_assignment_request(
+        &self,
+        new_request: &DomainNewPriceLevelAssignmentRequest,
+    ) -> RepositoryResult<DomainPriceLevelAssignmentRequest> {
+        let mut conn = self.conn()?;
+
+        conn.transaction(|conn| insert_assignment_request(conn, new_request))
+    }
+
+    fn decide_price_level_assignment_request(
+        &self,
+        hub_id: i32,
+        decision: &PriceLevelAssignmentDecision,
+    ) -> RepositoryResult<DomainPriceLevelAssignmentRequest> {
+        use crate::schema::{customers, price_level_assignment_requests as requests};
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<DomainPriceLevelAssignmentRequest, RepositoryError, _>(|conn| {
+            let pending: &'static str = PriceLevelAssignmentStatus::Pending.into();
+            let decided: &'static str = decision.status().into();
+            let now = Utc::now().naive_utc();
+
+            let target = requests::table
+                .filter(requests::id.eq(decision.request_id))
+                .filter(requests::hub_id.eq(hub_id))
+                .filter(requests::status.eq(pending));
+
+            let updated = diesel::update(target)
+                .set((
+                    requests::status.eq(decided),
+                    requests::decided_by_email.eq(decision.decided_by_email.as_str()),
+                    requests::decided_by_name.eq(decision.decided_by_name.as_str()),
+                    requests::decided_at.eq(now),
+                ))
+                .get_result::<DbPriceLevelAssignmentRequest>(conn)
+                .optional()?;
+
+            let Some(updated) = updated else {
+                return Err(RepositoryError::NotFound);
+            };
+
+            if decision.approve {
+                if let Some(level_id) = updated.price_level_id {
+                    ensure_price_level_with_hub(conn, hub_id, level_id)?;
+                }
+
+                let customer = customers::table
+                    .filter(customers::id.eq(updated.customer_id))
+                    .filter(customers::hub_id.eq(hub_id));
+
+                let changed = diesel::update(customer)
+                    .set((
+                        customers::price_level_id.eq(updated.price_level_id),
+                        customers::updated_at.eq(now),
+                    ))
+                    .execute(conn)?;
+                if changed == 0 {
+                    return Err(RepositoryError::NotFound);
+                }
+            }
+
+            Ok(updated.into())
+        })
+    }
+}
+
+/// Files a pending request unless the same one is already pending, which is
+/// returned instead.
+pub(super) fn insert_assignment_request(
+    conn: &mut SqliteConnection,
+    new_request: &DomainNewPriceLevelAssignmentRequest,
+) -> RepositoryResult<DomainPriceLevelAssignmentRequest> {
+    use crate::schema::{customers, price_level_assignment_requests as requests};
+
+    let customer_exists: bool = select(exists(
+        customers::table
+            .filter(customers::id.eq(new_request.customer_id))
+            .filter(customers::hub_id.eq(new_request.hub_id)),
+    ))
+    .get_result(conn)?;
+    if !customer_exists {
+        return Err(RepositoryError::NotFound);
+    }
+
+    if let Some(level_id) = new_request.price_level_id {
+        ensure_price_level_with_hub(conn, new_request.hub_id, level_id)?;
+    }
+
+    // Repeating a request that is still pending must not queue it twice.
+    let pending: &'static str = PriceLevelAssignmentStatus::Pending.into();
+    let mut duplicate = requests::table
+        .filter(requests::hub_id.eq(new_request.hub_id))
+        .filter(requests::customer_id.eq(new_request.customer_id))
+        .filter(requests::status.eq(pending))
+        .into_boxed::<diesel::sqlite::Sqlite>();
+    duplicate = match new_request.price_level_id {
+        Some(level_id) => duplicate.filter(requests::price_level_id.eq(level_id)),
+        None => duplicate.filter(requests::price_level_id.is_null()),
+    };
+    if let Some(existing) = duplicate
+        .first::<DbPriceLevelAssignmentRequest>(conn)
+        .optional()?
+    {
+        return Ok(existing.into());
+    }
+
+    let created = diesel::insert_into(requests::table)
+        .values(&DbNewPriceLevelAssignmentRequest::from(new_request))
+        .get_result::<DbPriceLevelAssignmentRequest>(conn)?;
+
+    Ok(created.into())
+}
@@ -1,28 +1,41 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::Local;
 
 use diesel::prelude::*;
 use diesel::sqlite::SqliteConnection;
 use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
 
 use crate::{
+    domain::category::NewCategory as DomainNewCategory,
     domain::product::{
-        NewProduct as DomainNewProduct, Product as DomainProduct, ProductListQuery,
+        NewProduct as DomainNewProduct, Product as DomainProduct, ProductBulkAction,
+        ProductImportItem, ProductImportTarget, ProductListQuery, ProductRemoval,
         UpdateProduct as DomainUpdateProduct,
     },
+    domain::product_price_history::{
+        NewProductPriceChange as DomainNewProductPriceChange, PriceChangeAuthor,
+        ProductPriceChange as DomainProductPriceChange, ProductPriceHistoryQuery,
+    },
     domain::product_price_level::{
         NewProductPriceLevelRate as DomainNewProductPriceLevelRate,
-        ProductPriceLevelRate as DomainProductPriceLevelRate,
+        ProductPriceLevelRate as DomainProductPriceLevelRate, RepricedRate,
     },
     domain::product_tag::NewProductTag as DomainNewProductTag,
-    domain::tag::Tag as DomainTag,
+    domain::tag::{NewTag as DomainNewTag, Tag as DomainTag},
+    models::category::NewCategory as DbNewCategory,
     models::product::{
         NewProduct as DbNewProduct, Product as DbProduct, UpdateProduct as DbUpdateProduct,
     },
+    models::product_price_history::{
+        NewProductPriceChange as DbNewProductPriceChange,
+        ProductPriceChange as DbProductPriceChange,
+    },
     models::product_price_level::{
         NewProductPriceLevel as DbNewProductPriceLevel, ProductPriceLevel as DbProductPriceLevel,
     },
     models::product_tag::{NewProductTag as DbNewProductTag, ProductTag as DbProductTag},
-    models::tag::Tag as DbTag,
+    models::tag::{NewTag as DbNewTag, Tag as DbTag},
     repository::{DieselRepository, ProductReader, ProductWriter},
 };
 
@@ -53,7 +66,7 @@ impl ProductReader for DieselRepository {
         &self,
         query: ProductListQuery,
     ) -> RepositoryResult<(usize, Vec<DomainProduct>)> {
-        use crate::schema::products;
+        use crate::schema::{product_tags, products};
 
         let mut conn = self.conn()?;
 
@@ -78,6 +91,20 @@ impl ProductReader for DieselRepository {
             count_query = count_query.filter(products::sku.eq(sku));
         }
 
+        if let Some(category_ids) = query.category_ids.as_ref() {
+            count_query = count_query.filter(products::category_id.eq_any(category_ids));
+        }
+
+        if let Some(tag_id) = query.tag_id {
+            count_query = count_query.filter(
+                products::id.eq_any(
+                    product_tags::table
+                        .filter(product_tags::tag_id.eq(tag_id))
+                        .select(product_tags::product_id),
+                ),
+            );
+        }
+
         let total = count_query.count().get_result::<i64>(&mut conn)? as usize;
 
         let mut items = products::table
@@ -101,6 +128,20 @@ impl ProductReader for DieselRepository {
             items = items.filter(products::sku.eq(sku));
         }
 
+        if let Some(category_ids) = query.category_ids.as_ref() {
+            items = items.filter(products::category_id.eq_any(category_ids));
+        }
+
+        if let Some(tag_id) = query.tag_id {
+            items = items.filter(
+                products::id.eq_any(
+                    product_tags::table
+                        .filter(product_tags::tag_id.eq(tag_id))
+                        .select(product_tags::product_id),
+                ),
+            );
+        }
+
         items = items.order((products::is_archived.asc(), products::created_at.desc()));
 
         if let Some(pagination) = &query.pagination {
@@ -129,43 +170,51 @@ impl ProductReader for DieselRepository {
 
         Ok((total, domain_products))
     }
-}
 
-impl ProductWriter for DieselRepository {
-    fn create_product(&self, new_product: &DomainNewProduct) -> RepositoryResult<DomainProduct> {
-        use crate::schema::products;
+    fn list_product_price_history(
+        &self,
+        query: ProductPriceHistoryQuery,
+    ) -> RepositoryResult<Vec<DomainProductPriceChange>> {
+        use crate::schema::{product_price_history, products};
 
         let mut conn = self.conn()?;
+        let mut items = product_price_history::table
+            .inner_join(products::table)
+            .filter(product_price_history::product_id.eq(query.product_id))
+            .filter(products::hub_id.eq(query.hub_id))
+            .select(DbProductPriceChange::as_select())
+            .into_boxed();
 
-        if let Some(category_id) = new_product.category_id {
-            use crate::schema::categories;
-            use diesel::dsl::{exists, select};
-
-            let category_exists: bool = select(exists(
-                categories::table
-                    .filter(categories::id.eq(category_id))
-                    .filter(categories::hub_id.eq(new_product.hub_id)),
-            ))
-            .get_result(&mut conn)?;
+        if let Some(price_level_id) = query.price_level_id {
+            items = items.filter(product_price_history::price_level_id.eq(price_level_id));
+        }
 
-            if !category_exists {
-                return Err(RepositoryError::NotFound);
-            }
+        if let Some(from) = query.changed_from {
+            items = items.filter(product_price_history::created_at.ge(from));
         }
 
-        let db_new = DbNewProduct::from(new_product);
+        if let Some(to) = query.changed_to {
+            items = items.filter(product_price_history::created_at.lt(to));
+        }
 
-        let created = diesel::insert_into(products::table)
-            .values(&db_new)
-            .get_result::<DbProduct>(&mut conn)?;
+        let rows = items
+            .order((
+                product_price_history::created_at.asc(),
+                product_price_history::id.asc(),
+            ))
+            .load::<DbProductPriceChange>(&mut conn)?;
 
-        let mut domain: DomainProduct = created.into();
-        let mut price_levels = load_price_levels_for_products(&mut conn, &[domain.id])?;
-        domain.price_levels = price_levels.remove(&domain.id).unwrap_or_default();
-        let mut tags = load_tags_for_products(&mut conn, &[domain.id])?;
-        domain.tags = tags.remove(&domain.id).unwrap_or_default();
+        Ok(rows
+            .into_iter()
+            .map(DomainProductPriceChange::from)
+            .collect())
+    }
+}
 
-        Ok(domain)
+impl ProductWriter for DieselRepository {
+    fn create_product(&self, new_product: &DomainNewProduct) -> RepositoryResult<DomainProduct> {
+        let mut conn = self.conn()?;
+        insert_product(&mut conn, new_product)
     }
 
     fn update_product(
@@ -174,45 +223,8 @@ impl ProductWriter for DieselRepository {
         hub_id: i32,
         updates: &DomainUpdateProduct,
     ) -> RepositoryResult<DomainProduct> {
-        use crate::schema::products;
-
         let mut conn = self.conn()?;
-
-        if let Some(category_id) = updates.category_id {
-            log::info!("{category_id}");
-
-            use crate::schema::categories;
-            use diesel::dsl::{exists, select};
-
-            let category_exists: bool = select(exists(
-                categories::table
-                    .filter(categories::id.eq(category_id))
-                    .filter(categories::hub_id.eq(hub_id)),
-            ))
-            .get_result(&mut conn)?;
-
-            if !category_exists {
-                return Err(RepositoryError::NotFound);
-            }
-        }
-
-        let db_updates = DbUpdateProduct::from(updates);
-
-        let target = products::table
-            .filter(products::id.eq(product_id))
-            .filter(products::hub_id.eq(hub_id));
-
-        let updated = diesel::update(target)
-            .set(&db_updates)
-            .get_result::<DbProduct>(&mut conn)?;
-
-        let mut domain: DomainProduct = updated.into();
-        let mut price_levels = load_price_levels_for_products(&mut conn, &[domain.id])?;
-        domain.price_levels = price_levels.remove(&domain.id).unwrap_or_default();
-        let mut tags = load_tags_for_products(&mut conn, &[domain.id])?;
-        domain.tags = tags.remove(&domain.id).unwrap_or_default();
-
-        Ok(domain)
+        update_product_in_hub(&mut conn, product_id, hub_id, updates)
     }
 
     fn delete_product(&self, product_id: i32, hub_id: i32) -> RepositoryResult<()> {
@@ -237,55 +249,109 @@ impl ProductWriter for DieselRepository {
         product_id: i32,
         hub_id: i32,
         rates: &[DomainNewProductPriceLevelRate],
+        changed_by: &PriceChangeAuthor,
+    ) -> RepositoryResult<()> {
+        let mut conn = self.conn()?;
+
+        conn.transaction(|conn| replace_base_rates(conn, product_id, hub_id, rates, changed_by))
+            .map_err(RepositoryError::from)
+    }
+
+    fn schedule_product_price_levels(
+        &self,
+        product_id: i32,
+        hub_id: i32,
+        rates: &[DomainNewProductPriceLevelRate],
+        changed_by: &PriceChangeAuthor,
     ) -> RepositoryResult<()> {
-        use crate::schema::price_levels;
         use crate::schema::product_price_levels;
-        use crate::schema::products;
-        use diesel::dsl::{delete, exists};
-        use diesel::dsl::{insert_into, select};
+        use diesel::dsl::insert_into;
 
         let mut conn = self.conn()?;
 
         conn.transaction::<_, diesel::result::Error, _>(|conn| {
-            let is_owned: bool = select(exists(
-                products::table
-                    .filter(products::id.eq(product_id))
-                    .filter(products::hub_id.eq(hub_id)),
-            ))
-            .get_result(conn)?;
+            ensure_product_in_hub(conn, product_id, hub_id)?;
 
-            if !is_owned {
-                return Err(diesel::result::Error::NotFound);
+            if rates.is_empty() {
+                return Ok(());
             }
 
-            delete(
-                product_price_levels::table.filter(product_price_levels::product_id.eq(product_id)),
-            )
-            .execute(conn)?;
+            ensure_price_levels_in_hub(conn, hub_id, rates)?;
+
+            let base_prices: HashMap<i32, i64> = product_price_levels::table
+                .filter(product_price_levels::product_id.eq(product_id))
+                .filter(product_price_levels::valid_from.is_null())
+                .filter(product_price_levels::valid_to.is_null())
+                .filter(product_price_levels::min_quantity.eq(1))
+                .select((
+                    product_price_levels::price_level_id,
+                    product_price_levels::price_cents,
+                ))
+                .load::<(i32, i64)>(conn)?
+                .into_iter()
+                .collect();
+
+            let rows: Vec<DbNewProductPriceLevel> =
+                rates.iter().map(DbNewProductPriceLevel::from).collect();
+            insert_into(product_price_levels::table)
+                .values(&rows)
+                .execute(conn)?;
 
-            if !rates.is_empty() {
-                let price_level_ids: std::collections::BTreeSet<i32> =
-                    rates.iter().map(|rate| rate.price_level_id).collect();
-                let expected_count = price_level_ids.len() as i64;
+            let changes: Vec<DomainNewProductPriceChange> = rates
+                .iter()
+                .map(|rate| {
+                    DomainNewProductPriceChange::new(
+                        product_id,
+                        rate.price_level_id,
+                        base_prices.get(&rate.price_level_id).copied(),
+                        Some(rate.price.amount),
+                        changed_by,
+                    )
+                    .with_window(rate.valid_from, rate.valid_to)
+                })
+                .collect();
+            record_price_changes(conn, &changes)?;
 
-                if expected_count > 0 {
-                    let actual_count: i64 = price_levels::table
-                        .filter(price_levels::id.eq_any(price_level_ids))
-                        .filter(price_levels::hub_id.eq(hub_id))
-                        .count()
-                        .get_result(conn)?;
+            Ok(())
+        })
+        .map_err(RepositoryError::from)
+    }
 
-                    if actual_count != expected_count {
-                        return Err(diesel::result::Error::NotFound);
-                    }
-                }
+    fn delete_scheduled_product_price_level(
+        &self,
+        product_id: i32,
+        hub_id: i32,
+        rate_id: i32,
+        changed_by: &PriceChangeAuthor,
+    ) -> RepositoryResult<()> {
+        use crate::schema::product_price_levels;
 
-                let rows: Vec<DbNewProductPriceLevel> =
-                    rates.iter().map(DbNewProductPriceLevel::from).collect();
-                insert_into(product_price_levels::table)
-                    .values(&rows)
-                    .execute(conn)?;
-            }
+        let mut conn = self.conn()?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            ensure_product_in_hub(conn, product_id, hub_id)?;
+
+            let target = product_price_levels::table
+                .filter(product_price_levels::id.eq(rate_id))
+                .filter(product_price_levels::product_id.eq(product_id))
+                .filter(
+                    product_price_levels::valid_from
+                        .is_not_null()
+                        .or(product_price_levels::valid_to.is_not_null()),
+                );
+
+            let rate = target.first::<DbProductPriceLevel>(conn)?;
+            diesel::delete(target).execute(conn)?;
+
+            let change = DomainNewProductPriceChange::new(
+                product_id,
+                rate.price_level_id,
+                Some(rate.price_cents),
+                None,
+                changed_by,
+            )
+            .with_window(rate.valid_from, rate.valid_to);
+            record_price_changes(conn, &[change])?;
 
             Ok(())
         })
@@ -298,84 +364,704 @@ impl ProductWriter for DieselRepository {
         hub_id: i32,
         tag_ids: &[i32],
     ) -> RepositoryResult<()> {
-        use crate::schema::product_tags;
+        let mut conn = self.conn()?;
+
+        conn.transaction(|conn| replace_tags(conn, product_id, hub_id, tag_ids))
+            .map_err(RepositoryError::from)
+    }
+
+    fn remove_product(&self, product_id: i32, hub_id: i32) -> RepositoryResult<ProductRemoval> {
+        use crate::schema::inventory_movements;
+        use crate::schema::order_products;
         use crate::schema::products;
-        use crate::schema::tags;
-        use diesel::dsl::{delete, exists, insert_into, select};
+        use diesel::dsl::{exists, select};
 
         let mut conn = self.conn()?;
 
-        conn.transaction::<_, diesel::result::Error, _>(|conn| {
-            let is_owned: bool = select(exists(
-                products::table
-                    .filter(products::id.eq(product_id))
-                    .filter(products::hub_id.eq(hub_id)),
-            ))
-            .get_result(conn)?;
+        conn.transaction::<ProductRemoval, RepositoryError, _>(|conn| {
+            let target = products::table
+                .filter(products::id.eq(product_id))
+                .filter(products::hub_id.eq(hub_id));
 
+            let is_owned: bool = select(exists(target)).get_result(conn)?;
             if !is_owned {
-                return Err(diesel::result::Error::NotFound);
+                return Err(RepositoryError::NotFound);
             }
 
-            delete(product_tags::table.filter(product_tags::product_id.eq(product_id)))
+            let is_ordered: bool = select(exists(
+                order_products::table.filter(order_products::product_id.eq(product_id)),
+            ))
+            .get_result(conn)?;
+
+            if is_ordered {
+                diesel::update(target)
+                    .set((
+                        products::is_archived.eq(true),
+                        products::updated_at.eq(Local::now().naive_utc()),
+                    ))
+                    .execute(conn)?;
+                Ok(ProductRemoval::Archived)
+            } else {
+                diesel::delete(
+                    inventory_movements::table
+                        .filter(inventory_movements::product_id.eq(product_id)),
+                )
                 .execute(conn)?;
+                diesel::delete(target).execute(conn)?;
+                Ok(ProductRemoval::Deleted)
+            }
+        })
+    }
+
+    fn bulk_update_products(
+        &self,
+        hub_id: i32,
+        product_ids: &[i32],
+        action: &ProductBulkAction,
+    ) -> RepositoryResult<usize> {
+        use crate::schema::categories;
+        use crate::schema::product_tags;
+        use crate::schema::products;
+        use crate::schema::tags;
+        use diesel::dsl::{exists, insert_or_ignore_into, select};
 
-            if !tag_ids.is_empty() {
-                let unique_ids: std::collections::BTreeSet<i32> =
-                    tag_ids.iter().copied().filter(|id| *id > 0).collect();
+        let unique_ids: BTreeSet<i32> = product_ids.iter().copied().filter(|id| *id > 0).collect();
+        if unique_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<usize, RepositoryError, _>(|conn| {
+            let owned: i64 = products::table
+                .filter(products::id.eq_any(&unique_ids))
+                .filter(products::hub_id.eq(hub_id))
+                .count()
+                .get_result(conn)?;
+            if owned != unique_ids.len() as i64 {
+                return Err(RepositoryError::NotFound);
+            }
 
-                if !unique_ids.is_empty() {
-                    let expected_count = unique_ids.len() as i64;
-                    let actual_count: i64 = tags::table
-                        .filter(tags::id.eq_any(&unique_ids))
+            let now = Local::now().naive_utc();
+            let target = products::table
+                .filter(products::id.eq_any(&unique_ids))
+                .filter(products::hub_id.eq(hub_id));
+
+            match action {
+                ProductBulkAction::Archive | ProductBulkAction::Restore => {
+                    let is_archived = matches!(action, ProductBulkAction::Archive);
+                    diesel::update(target)
+                        .set((
+                            products::is_archived.eq(is_archived),
+                            products::updated_at.eq(now),
+                        ))
+                        .execute(conn)?;
+                }
+                ProductBulkAction::SetCategory(category_id) => {
+                    if let Some(category_id) = category_id {
+                        let category_exists: bool = select(exists(
+                            categories::table
+                                .filter(categories::id.eq(category_id))
+                                .filter(categories::hub_id.eq(hub_id)),
+                        ))
+                        .get_result(conn)?;
+                        if !category_exists {
+                            return Err(RepositoryError::NotFound);
+                        }
+                    }
+                    diesel::update(target)
+                        .set((
+                            products::category_id.eq(category_id),
+                            products::updated_at.eq(now),
+                        ))
+                        .execute(conn)?;
+                }
+                ProductBulkAction::AddTags(tag_ids) => {
+                    let unique_tags: BTreeSet<i32> =
+                        tag_ids.iter().copied().filter(|id| *id > 0).collect();
+                    let known_tags: i64 = tags::table
+                        .filter(tags::id.eq_any(&unique_tags))
                         .filter(tags::hub_id.eq(hub_id))
                         .count()
                         .get_result(conn)?;
-
-                    if actual_count != expected_count {
-                        return Err(diesel::result::Error::NotFound);
+                    if known_tags != unique_tags.len() as i64 {
+                        return Err(RepositoryError::NotFound);
                     }
 
                     let rows: Vec<DbNewProductTag> = unique_ids
-                        .into_iter()
-                        .map(|tag_id| {
-                            let domain = DomainNewProductTag::new(product_id, tag_id);
-                            DbNewProductTag::from(&domain)
+                        .iter()
+                        .flat_map(|product_id| {
+                            unique_tags.iter().map(move |tag_id| {
+                                let domain = DomainNewProductTag::new(*product_id, *tag_id);
+                                DbNewProductTag::from(&domain)
+                            })
                         })
                         .collect();
-
                     if !rows.is_empty() {
-                        insert_into(product_tags::table)
+                        insert_or_ignore_into(product_tags::table)
                             .values(&rows)
                             .execute(conn)?;
                     }
+                    diesel::update(target)
+                        .set(products::updated_at.eq(now))
+                        .execute(conn)?;
+                }
+            }
+
+            Ok(unique_ids.len())
+        })
+    }
+
+    fn bulk_import_products(
+        &self,
+        hub_id: i32,
+        items: &[ProductImportItem],
+        changed_by: &PriceChangeAuthor,
+    ) -> RepositoryResult<()> {
+        use crate::schema::{categories, tags};
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<(), RepositoryError, _>(|conn| {
+            // Keyed by parent and lowercase name, like the upload resolves them.
+            let mut category_ids: HashMap<(Option<i32>, String), i32> =
+                if items.iter().any(|item| !item.missing_category.is_empty()) {
+                    categories::table
+                        .filter(categories::hub_id.eq(hub_id))
+                        .select((categories::id, categories::parent_id, categories::name))
+                        .load::<(i32, Option<i32>, String)>(conn)?
+                        .into_iter()
+                        .map(|(id, parent_id, name)| ((parent_id, name.to_lowercase()), id))
+                        .collect()
+                } else {
+                    HashMap::new()
+                };
+            let mut tag_ids: HashMap<String, i32> =
+                if items.iter().any(|item| !item.missing_tags.is_empty()) {
+                    tags::table
+                        .filter(tags::hub_id.eq(hub_id))
+                        .select((tags::name, tags::id))
+                        .load::<(String, i32)>(conn)?
+                        .into_iter()
+                        .map(|(name, id)| (name.to_lowercase(), id))
+                        .collect()
+                } else {
+                    HashMap::new()
+                };
+
+            for item in items {
+                let mut category_id = None;
+                for name in &item.missing_category {
+                    let key = (category_id, name.to_lowercase());
+                    let id = match category_ids.get(&key) {
+                        Some(id) => *id,
+                        None => {
+                            let mut new_category = DomainNewCategory::new(hub_id, name.as_str());
+                            if let Some(parent_id) = category_id {
+                                new_category = new_category.with_parent_id(parent_id);
+                            }
+                            let created = diesel::insert_into(categories::table)
+                                .values(&DbNewCategory::from(&new_category))
+                                .returning(categories::id)
+                                .get_result::<i32>(conn)?;
+                            category_ids.insert(key, created);
+                            created
+                        }
+                    };
+                    category_id = Some(id);
+                }
+
+                let mut product_tag_ids = item.tag_ids.clone();
+                for name in &item.missing_tags {
+                    let id = match tag_ids.get(&name.to_lowercase()) {
+                        Some(id) => *id,
+                        None => {
+                            let created = diesel::insert_into(tags::table)
+                                .values(&DbNewTag::from(&DomainNewTag::new(hub_id, name.as_str())))
+                                .returning(tags::id)
+                                .get_result::<i32>(conn)?;
+                            tag_ids.insert(name.to_lowercase(), created);
+                            created
+                        }
+                    };
+                    product_tag_ids.push(id);
+                }
+
+                let product_id = match &item.target {
+                    ProductImportTarget::Create(new_product) => {
+                        let mut new_product = new_product.clone();
+                        new_product.category_id = category_id.or(new_product.category_id);
+                        insert_product(conn, &new_product)?.id
+                    }
+                    ProductImportTarget::Update {
+                        product_id,
+                        updates,
+                    } => {
+                        let mut updates = updates.clone();
+                        updates.category_id = category_id.or(updates.category_id);
+                        update_product_in_hub(conn, *product_id, hub_id, &updates)?;
+                        *product_id
+                    }
+                };
+
+                let rates: Vec<DomainNewProductPriceLevelRate> = item
+                    .rates
+                    .iter()
+                    .map(|rate| DomainNewProductPriceLevelRate {
+                        product_id,
+                        ..rate.clone()
+                    })
+                    .collect();
+                replace_base_rates(conn, product_id, hub_id, &rates, changed_by)?;
+
+                if !product_tag_ids.is_empty() {
+                    replace_tags(conn, product_id, hub_id, &product_tag_ids)?;
                 }
             }
 
             Ok(())
         })
-        .map_err(RepositoryError::from)
     }
+
+    fn reprice_product_price_levels(
+        &self,
+        hub_id: i32,
+        rates: &[RepricedRate],
+        changed_by: &PriceChangeAuthor,
+    ) -> RepositoryResult<usize> {
+        use crate::schema::{product_price_levels, products};
+
+        if rates.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<usize, RepositoryError, _>(|conn| {
+            let now = Local::now().naive_utc();
+            let hub_products = products::table
+                .filter(products::hub_id.eq(hub_id))
+                .select(products::id);
+
+            for rate in rates {
+                let updated = diesel::update(
+                    product_price_levels::table
+                        .filter(product_price_levels::id.eq(rate.rate_id))
+                        .filter(product_price_levels::product_id.eq(rate.product_id))
+                        .filter(product_price_levels::product_id.eq_any(hub_products))
+                        .filter(product_price_levels::price_cents.eq(rate.old_price.amount))
+                        .filter(product_price_levels::valid_from.is_null())
+                        .filter(product_price_levels::valid_to.is_null()),
+                )
+                .set((
+                    product_price_levels::price_cents.eq(rate.new_price.amount),
+                    product_price_levels::updated_at.eq(now),
+                ))
+                .execute(conn)?;
+
+                if updated == 0 {
+                    return Err(RepositoryError::ConstraintViolation(format!(
+                        "rate {} changed since it was repriced",
+                        rate.rate_id
+                    )));
+                }
+            }
+
+            let changes: Vec<DomainNewProductPriceChange> = rates
+                .iter()
+                .map(|rate| {
+                    DomainNewProductPriceChange::new(
+                        rate.product_id,
+                        rate.price_level_id,
+                        Some(rate.old_price.amount),
+                        Some(rate.new_price.amount),
+                        changed_by,
+                    )
+                    .with_min_quantity(rate.min_quantity)
+                })
+                .collect();
+            record_price_changes(conn, &changes)?;
+
+            Ok(rates.len())
+        })
+    }
+}
+
+/// Inserts a product after checking its category and tax category belong to its hub.
+fn insert_product(
+    conn: &mut SqliteConnection,
+    new_product: &DomainNewProduct,
+) -> RepositoryResult<DomainProduct> {
+    use crate::schema::products;
+
+    if let Some(category_id) = new_product.category_id {
+        use crate::schema::categories;
+        use diesel::dsl::{exists, select};
+
+        let category_exists: bool = select(exists(
+            categories::table
+                .filter(categories::id.eq(category_id))
+                .filter(categories::hub_id.eq(new_product.hub_id)),
+        ))
+        .get_result(conn)?;
+
+        if !category_exists {
+            return Err(RepositoryError::NotFound);
+        }
+    }
+
+    if let Some(tax_category_id) = new_product.tax_category_id {
+        use crate::schema::tax_categories;
+        use diesel::dsl::{exists, select};
+
+        let tax_category_exists: bool = select(exists(
+            tax_categories::table
+                .filter(tax_categories::id.eq(tax_category_id))
+                .filter(tax_categories::hub_id.eq(new_product.hub_id)),
+        ))
+        .get_result(conn)?;
+
+        if !tax_category_exists {
+            return Err(RepositoryError::NotFound);
+        }
+    }
+
+    let db_new = DbNewProduct::from(new_product);
+
+    let created = diesel::insert_into(products::table)
+        .values(&db_new)
+        .get_result::<DbProduct>(conn)?;
+
+    let mut domain: DomainProduct = created.into();
+    let mut price_levels = load_price_levels_for_products(conn, &[domain.id])?;
+    domain.price_levels = price_levels.remove(&domain.id).unwrap_or_default();
+    let mut tags = load_tags_for_products(conn, &[domain.id])?;
+    domain.tags = tags.remove(&domain.id).unwrap_or_default();
+
+    Ok(domain)
+}
+
+/// Applies `updates` to a product of the hub.
+fn update_product_in_hub(
+    conn: &mut SqliteConnection,
+    product_id: i32,
+    hub_id: i32,
+    updates: &DomainUpdateProduct,
+) -> RepositoryResult<DomainProduct> {
+    use crate::schema::products;
+
+    if let Some(category_id) = updates.category_id {
+        log::info!("{category_id}");
+
+        use crate::schema::categories;
+        use diesel::dsl::{exists, select};
+
+        let category_exists: bool = select(exists(
+            categories::table
+                .filter(categories::id.eq(category_id))
+                .filter(categories::hub_id.eq(hub_id)),
+        ))
+        .get_result(conn)?;
+
+        if !category_exists {
+            return Err(RepositoryError::NotFound);
+        }
+    }
+
+    if let Some(tax_category_id) = updates.tax_category_id {
+        use crate::schema::tax_categories;
+        use diesel::dsl::{exists, select};
+
+        let tax_category_exists: bool = select(exists(
+            tax_categories::table
+                .filter(tax_categories::id.eq(tax_category_id))
+                .filter(tax_categories::hub_id.eq(hub_id)),
+        ))
+        .get_result(conn)?;
+
+        if !tax_category_exists {
+            return Err(RepositoryError::NotFound);
+        }
+    }
+
+    let db_updates = DbUpdateProduct::from(updates);
+
+    let target = products::table
+        .filter(products::id.eq(product_id))
+        .filter(products::hub_id.eq(hub_id));
+
+    let updated = diesel::update(target)
+        .set(&db_updates)
+        .get_result::<DbProduct>(conn)?;
+
+    let mut domain: DomainProduct = updated.into();
+    let mut price_levels = load_price_levels_for_products(conn, &[domain.id])?;
+    domain.price_levels = price_levels.remove(&domain.id).unwrap_or_default();
+    let mut tags = load_tags_for_products(conn, &[domain.id])?;
+    domain.tags = tags.remove(&domain.id).unwrap_or_default();
+
+    Ok(domain)
+}
+
+/// Replaces the base rates of a product of the hub, keeping scheduled ones,
+/// and records every changed price in the history.
+fn replace_base_rates(
+    conn: &mut SqliteConnection,
+    product_id: i32,
+    hub_id: i32,
+    rates: &[DomainNewProductPriceLevelRate],
+    changed_by: &PriceChangeAuthor,
+) -> Result<(), diesel::result::Error> {
+    use crate::schema::product_price_levels;
+    use diesel::dsl::{delete, insert_into};
+
+    ensure_product_in_hub(conn, product_id, hub_id)?;
+
+    // Scheduled rates survive so edits and imports keep planned changes.
+    let base_rates = product_price_levels::table
+        .filter(product_price_levels::product_id.eq(product_id))
+        .filter(product_price_levels::valid_from.is_null())
+        .filter(product_price_levels::valid_to.is_null());
+
+    let previous: BTreeMap<(i32, i32), i64> = base_rates
+        .select((
+            product_price_levels::price_level_id,
+            product_price_levels::min_quantity,
+            product_price_levels::price_cents,
+        ))
+        .load::<(i32, i32, i64)>(conn)?
+        .into_iter()
+        .map(|(price_level_id, min_quantity, price_cents)| {
+            ((price_level_id, min_quantity), price_cents)
+        })
+        .collect();
+
+    delete(base_rates).execute(conn)?;
+
+    if !rates.is_empty() {
+        ensure_price_levels_in_hub(conn, hub_id, rates)?;
+
+        let rows: Vec<DbNewProductPriceLevel> =
+            rates.iter().map(DbNewProductPriceLevel::from).collect();
+        insert_into(product_price_levels::table)
+            .values(&rows)
+            .execute(conn)?;
+    }
+
+    let current: BTreeMap<(i32, i32), i64> = rates
+        .iter()
+        .map(|rate| ((rate.price_level_id, rate.min_quantity), rate.price.amount))
+        .collect();
+    let tiers: BTreeSet<(i32, i32)> = previous.keys().chain(current.keys()).copied().collect();
+    let changes: Vec<DomainNewProductPriceChange> = tiers
+        .into_iter()
+        .filter_map(|(price_level_id, min_quantity)| {
+            let old_price = previous.get(&(price_level_id, min_quantity)).copied();
+            let new_price = current.get(&(price_level_id, min_quantity)).copied();
+            (old_price != new_price).then(|| {
+                DomainNewProductPriceChange::new(
+                    product_id,
+                    price_level_id,
+                    old_price,
+                    new_price,
+                    changed_by,
+                )
+                .with_min_quantity(min_quantity)
+            })
+        })
+        .collect();
+    record_price_changes(conn, &changes)?;
+
+    Ok(())
+}
+
+/// Replaces the tags of a product of the hub with tags of the same hub.
+fn replace_tags(
+    conn: &mut SqliteConnection,
+    product_id: i32,
+    hub_id: i32,
+    tag_ids: &[i32],
+) -> Result<(), diesel::result::Error> {
+    use crate::schema::product_tags;
+    use crate::schema::products;
+    use crate::schema::tags;
+    use diesel::dsl::{delete, exists, insert_into, select};
+
+    let is_owned: bool = select(exists(
+        products::table
+            .filter(products::id.eq(product_id))
+            .filter(products::hub_id.eq(hub_id)),
+    ))
+    .get_result(conn)?;
+
+    if !is_owned {
+        return Err(diesel::result::Error::NotFound);
+    }
+
+    delete(product_tags::table.filter(product_tags::product_id.eq(product_id))).execute(conn)?;
+
+    if !tag_ids.is_empty() {
+        let unique_ids: std::collections::BTreeSet<i32> =
+            tag_ids.iter().copied().filter(|id| *id > 0).collect();
+
+        if !unique_ids.is_empty() {
+            let expected_count = unique_ids.len() as i64;
+            let actual_count: i64 = tags::table
+                .filter(tags::id.eq_any(&unique_ids))
+                .filter(tags::hub_id.eq(hub_id))
+                .count()
+                .get_result(conn)?;
+
+            if actual_count != expected_count {
+                return Err(diesel::result::Error::NotFound);
+            }
+
+            let rows: Vec<DbNewProductTag> = unique_ids
+                .into_iter()
+                .map(|tag_id| {
+                    let domain = DomainNewProductTag::new(product_id, tag_id);
+                    DbNewProductTag::from(&domain)
+                })
+                .collect();
+
+            if !rows.is_empty() {
+                insert_into(product_tags::table)
+                    .values(&rows)
+                    .execute(conn)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn record_price_changes(
+    conn: &mut SqliteConnection,
+    changes: &[DomainNewProductPriceChange],
+) -> Result<(), diesel::result::Error> {
+    use crate::schema::{price_levels, product_price_history, products};
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let mut product_ids: Vec<i32> = changes.iter().map(|change| change.product_id).collect();
+    product_ids.sort_unstable();
+    product_ids.dedup();
+    let product_names: HashMap<i32, String> = products::table
+        .filter(products::id.eq_any(&product_ids))
+        .select((products::id, products::name))
+        .load::<(i32, String)>(conn)?
+        .into_iter()
+        .collect();
+
+    let mut level_ids: Vec<i32> = changes.iter().map(|change| change.price_level_id).collect();
+    level_ids.sort_unstable();
+    level_ids.dedup();
+    let level_names: HashMap<i32, String> = price_levels::table
+        .filter(price_levels::id.eq_any(&level_ids))
+        .select((price_levels::id, price_levels::name))
+        .load::<(i32, String)>(conn)?
+        .into_iter()
+        .collect();
+
+    let rows: Vec<DbNewProductPriceChange> = changes
+        .iter()
+        .map(|change| {
+            DbNewProductPriceChange::from_domain(
+                change,
+                product_names
+                    .get(&change.product_id)
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+                level_names
+                    .get(&change.price_level_id)
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+            )
+        })
+        .collect();
+    diesel::insert_into(product_price_history::table)
+        .values(&rows)
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Fails with `NotFound` unless the product belongs to the hub.
+fn ensure_product_in_hub(
+    conn: &mut SqliteConnection,
+    product_id: i32,
+    hub_id: i32,
+) -> Result<(), diesel::result::Error> {
+    use crate::schema::products;
+    use diesel::dsl::{exists, select};
+
+    let is_owned: bool = select(exists(
+        products::table
+            .filter(products::id.eq(product_id))
+            .filter(products::hub_id.eq(hub_id)),
+    ))
+    .get_result(conn)?;
+
+    if !is_owned {
+        return Err(diesel::result::Error::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Fails with `NotFound` unless every rate targets a price level of the hub.
+fn ensure_price_levels_in_hub(
+    conn: &mut SqliteConnection,
+    hub_id: i32,
+    rates: &[DomainNewProductPriceLevelRate],
+) -> Result<(), diesel::result::Error> {
+    use crate::schema::price_levels;
+
+    let price_level_ids: BTreeSet<i32> = rates.iter().map(|rate| rate.price_level_id).collect();
+    let expected_count = price_level_ids.len() as i64;
+
+    if expected_count > 0 {
+        let actual_count: i64 = price_levels::table
+            .filter(price_levels::id.eq_any(price_level_ids))
+            .filter(price_levels::hub_id.eq(hub_id))
+            .count()
+            .get_result(conn)?;
+
+        if actual_count != expected_count {
+            return Err(diesel::result::Error::NotFound);
+        }
+    }
+
+    Ok(())
 }
 
 fn load_price_levels_for_products(
     conn: &mut SqliteConnection,
     product_ids: &[i32],
 ) -> RepositoryResult<HashMap<i32, Vec<DomainProductPriceLevelRate>>> {
-    use crate::schema::product_price_levels;
+    use crate::schema::{product_price_levels, products};
 
     if product_ids.is_empty() {
         return Ok(HashMap::new());
     }
 
     let rows = product_price_levels::table
+        .inner_join(products::table)
         .filter(product_price_levels::product_id.eq_any(product_ids))
-        .order(product_price_levels::created_at.asc())
-        .load::<DbProductPriceLevel>(conn)?;
+        .order((
+            product_price_levels::valid_from.asc(),
+            product_price_levels::created_at.asc(),
+        ))
+        .select((DbProductPriceLevel::as_select(), products::currency))
+        .load::<(DbProductPriceLevel, String)>(conn)?;
 
     let mut map: HashMap<i32, Vec<DomainProductPriceLevelRate>> = HashMap::new();
-    for row in rows {
-        map.entry(row.product_id).or_default().push(row.into());
+    for (row, currency) in rows {
+        map.entry(row.product_id)
+            .or_default()
+            .push(row.into_domain(&currency));
     }
 
     Ok(map)
@@ -0,0 +1,109 @@
+use diesel::prelude::*;
+use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
+
+use crate::domain::promotion::{NewPromotion as DomainNewPromotion, Promotion as DomainPromotion};
+use crate::models::promotion::{NewPromotion as DbNewPromotion, Promotion as DbPromotion};
+use crate::repository::{DieselRepository, PromotionReader, PromotionWriter};
+
+impl PromotionReader for DieselRepository {
+    fn list_promotions(&self, hub_id: i32) -> RepositoryResult<Vec<DomainPromotion>> {
+        use crate::schema::promotions;
+
+        let mut conn = self.conn()?;
+
+        let promotions = promotions::table
+            .filter(promotions::hub_id.eq(hub_id))
+            .order(promotions::code.asc())
+            .load::<DbPromotion>(&mut conn)?;
+
+        Ok(promotions.into_iter().map(DomainPromotion::from).collect())
+    }
+
+    fn get_promotion_by_code(
+        &self,
+        code: &str,
+        hub_id: i32,
+    ) -> RepositoryResult<Option<DomainPromotion>> {
+        use crate::schema::promotions;
+
+        let mut conn = self.conn()?;
+
+        let promotion = promotions::table
+            .filter(promotions::hub_id.eq(hub_id))
+            .filter(promotions::code.eq(code))
+            .first::<DbPromotion>(&mut conn)
+            .optional()?;
+
+        Ok(promotion.map(DomainPromotion::from))
+    }
+}
+
+impl PromotionWriter for DieselRepository {
+    fn create_promotion(
+        &self,
+        promotion: &DomainNewPromotion,
+    ) -> RepositoryResult<DomainPromotion> {
+        use crate::schema::{categories, promotions, tags};
+        use diesel::dsl::{exists, select};
+
+        let mut conn = self.conn()?;
+
+        if let Some(category_id) = promotion.category_id {
+            let category_exists: bool = select(exists(
+                categories::table
+                    .filter(categories::id.eq(category_id))
+                    .filter(categories::hub_id.eq(promotion.hub_id)),
+            ))
+            .get_result(&mut conn)?;
+
+            if !category_exists {
+                return Err(RepositoryError::NotFound);
+            }
+        }
+
+        if let Some(tag_id) = promotion.tag_id {
+            let tag_exists: bool = select(exists(
+                tags::table
+                    .filter(tags::id.eq(tag_id))
+                    .filter(tags::hub_id.eq(promotion.hub_id)),
+            ))
+            .get_result(&mut conn)?;
+
+            if !tag_exists {
+                return Err(RepositoryError::NotFound);
+            }
+        }
+
+        let created = diesel::insert_into(promotions::table)
+            .values(&DbNewPromotion::from(promotion))
+            .get_result::<DbPromotion>(&mut conn)?;
+
+        Ok(created.into())
+    }
+
+    fn delete_promotion(&self, promotion_id: i32, hub_id: i32) -> RepositoryResult<()> {
+        use crate::schema::{order_discounts, promotions};
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<(), RepositoryError, _>(|conn| {
+            let deleted = diesel::delete(
+                promotions::table
+                    .filter(promotions::id.eq(promotion_id))
+                    .filter(promotions::hub_id.eq(hub_id)),
+            )
+            .execute(conn)?;
+            if deleted == 0 {
+                return Err(RepositoryError::NotFound);
+            }
+
+            diesel::update(
+                order_discounts::table.filter(order_discounts::promotion_id.eq(promotion_id)),
+            )
+            .set(order_discounts::promotion_id.eq(None::<i32>))
+            .execute(conn)?;
+
+            Ok(())
+        })
+    }
+}
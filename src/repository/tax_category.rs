@@ -0,0 +1,90 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
+
+use crate::domain::tax_category::{
+    NewTaxCategory as DomainNewTaxCategory, TaxCategory as DomainTaxCategory,
+};
+use crate::models::tax_category::{
+    NewTaxCategory as DbNewTaxCategory, TaxCategory as DbTaxCategory,
+};
+use crate::repository::{DieselRepository, TaxCategoryReader, TaxCategoryWriter};
+
+impl TaxCategoryReader for DieselRepository {
+    fn list_tax_categories(&self, hub_id: i32) -> RepositoryResult<Vec<DomainTaxCategory>> {
+        use crate::schema::tax_categories;
+
+        let mut conn = self.conn()?;
+
+        let categories = tax_categories::table
+            .filter(tax_categories::hub_id.eq(hub_id))
+            .order(tax_categories::name.asc())
+            .load::<DbTaxCategory>(&mut conn)?;
+
+        Ok(categories
+            .into_iter()
+            .map(DomainTaxCategory::from)
+            .collect())
+    }
+}
+
+impl TaxCategoryWriter for DieselRepository {
+    fn save_tax_category(
+        &self,
+        tax_category: &DomainNewTaxCategory,
+    ) -> RepositoryResult<DomainTaxCategory> {
+        use crate::schema::tax_categories;
+
+        let mut conn = self.conn()?;
+        let now = Utc::now().naive_utc();
+
+        conn.transaction::<DomainTaxCategory, RepositoryError, _>(|conn| {
+            let target = tax_categories::table
+                .filter(tax_categories::hub_id.eq(tax_category.hub_id))
+                .filter(tax_categories::name.eq(&tax_category.name));
+
+            let updated = diesel::update(target)
+                .set((
+                    tax_categories::rate_basis_points.eq(tax_category.rate_basis_points),
+                    tax_categories::updated_at.eq(now),
+                ))
+                .execute(conn)?;
+
+            if updated == 0 {
+                diesel::insert_into(tax_categories::table)
+                    .values(&DbNewTaxCategory::from(tax_category))
+                    .execute(conn)?;
+            }
+
+            let saved = target.first::<DbTaxCategory>(conn)?;
+            Ok(saved.into())
+        })
+    }
+
+    fn delete_tax_category(&self, tax_category_id: i32, hub_id: i32) -> RepositoryResult<()> {
+        use crate::schema::{products, tax_categories};
+
+        let mut conn = self.conn()?;
+
+        conn.transaction::<(), RepositoryError, _>(|conn| {
+            let target = tax_categories::table
+                .filter(tax_categories::id.eq(tax_category_id))
+                .filter(tax_categories::hub_id.eq(hub_id));
+
+            let deleted = diesel::delete(target).execute(conn)?;
+            if deleted == 0 {
+                return Err(RepositoryError::NotFound);
+            }
+
+            diesel::update(
+                products::table
+                    .filter(products::hub_id.eq(hub_id))
+                    .filter(products::tax_category_id.eq(tax_category_id)),
+            )
+            .set(products::tax_category_id.eq(None::<i32>))
+            .execute(conn)?;
+
+            Ok(())
+        })
+    }
+}
@@ -1,27 +1,44 @@
-use actix_web::{HttpResponse, Responder, get, put, web};
+use actix_web::{HttpRequest, HttpResponse, Responder, get, post, put, web};
 use pushkind_common::domain::auth::AuthenticatedUser;
 
+use crate::forms::orders::PlaceOrderPayload;
 use crate::forms::price_levels::AssignClientPriceLevelPayload;
 use crate::repository::DieselRepository;
 use crate::services::main::IndexQuery;
 use crate::services::price_levels::{
     assign_price_level_to_client, load_client_price_level_assignments,
 };
-use crate::services::{ServiceError, main as main_service};
+use crate::services::products::PriceHistoryQuery;
+use crate::services::{
+    ServiceError, inventory as inventory_service, main as main_service, orders as orders_service,
+    products as products_service,
+};
 use serde_json::json;
 
 #[get("/v1/orders")]
-/// Return a JSON list of orders with optional search and pagination.
+/// Return a JSON list of orders with optional search, filters and pagination.
 ///
-/// Users without the role stored in `crate::SERVICE_ACCESS_ROLE` receive a `401 Unauthorized` response.
+/// Accepts the [`IndexQuery`] parameters; `status` may be repeated. Malformed
+/// parameters yield `400 Bad Request`. Users without the role stored in
+/// `crate::SERVICE_ACCESS_ROLE` receive a `401 Unauthorized` response.
 pub async fn api_v1_orders(
-    params: web::Query<IndexQuery>,
+    req: HttpRequest,
     user: AuthenticatedUser,
     repo: web::Data<DieselRepository>,
 ) -> impl Responder {
-    match main_service::load_index_page(repo.get_ref(), &user, params.0) {
+    let query = match serde_html_form::from_str::<IndexQuery>(req.query_string()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return HttpResponse::BadRequest().json(json!({"error": err.to_string()}));
+        }
+    };
+
+    match main_service::load_index_page(repo.get_ref(), &user, query) {
         Ok(response) => HttpResponse::Ok().json(response.orders),
         Err(ServiceError::Unauthorized) => HttpResponse::Unauthorized().finish(),
+        Err(ServiceError::Form(message)) => {
+            HttpResponse::BadRequest().json(json!({"error": message}))
+        }
         Err(err) => {
             log::error!("Failed to list orders: {err}");
             HttpResponse::InternalServerError().finish()
@@ -29,7 +46,106 @@ pub async fn api_v1_orders(
     }
 }
 
+#[post("/v1/orders")]
+/// Place a new order priced from the customer's price level.
+///
+/// Returns `201 Created` with the stored order, `404 Not Found` for unknown customers,
+/// `409 Conflict` only when the promotion code was used up concurrently and
+/// `422 Unprocessable Entity` when the payload cannot be priced or the code redeemed.
+pub async fn api_v1_create_order(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    payload: web::Json<PlaceOrderPayload>,
+) -> impl Responder {
+    match orders_service::place_order(repo.get_ref(), &user, payload.into_inner()) {
+        Ok(order) => HttpResponse::Created().json(order),
+        Err(ServiceError::Unauthorized) => HttpResponse::Unauthorized().finish(),
+        Err(ServiceError::NotFound) => HttpResponse::NotFound().finish(),
+        Err(ServiceError::Form(message)) => {
+            HttpResponse::UnprocessableEntity().json(json!({"error": message}))
+        }
+        Err(ServiceError::Conflict) => {
+            HttpResponse::Conflict().json(json!({"error": "promotion code has been used up"}))
+        }
+        Err(err) => {
+            log::error!("Failed to place order: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[get("/v1/orders/{order_id}/history")]
+/// Return the status change history of an order, oldest first.
+pub async fn api_v1_order_history(
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    let order_id = path.into_inner();
+
+    match orders_service::load_order_status_history(repo.get_ref(), &user, order_id) {
+        Ok(history) => HttpResponse::Ok().json(history),
+        Err(ServiceError::Unauthorized) => HttpResponse::Unauthorized().finish(),
+        Err(ServiceError::NotFound) => HttpResponse::NotFound().finish(),
+        Err(err) => {
+            log::error!("Failed to load history of order {order_id}: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[get("/v1/products/{product_id}/price-history")]
+/// Return the recorded price changes of a product, oldest first.
+///
+/// Accepts optional `price_level_id`, `from` and `to` (inclusive `YYYY-MM-DD`
+/// dates) parameters. Malformed parameters yield `400 Bad Request`.
+pub async fn api_v1_product_price_history(
+    req: HttpRequest,
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    let product_id = path.into_inner();
+    let query = match serde_html_form::from_str::<PriceHistoryQuery>(req.query_string()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return HttpResponse::BadRequest().json(json!({"error": err.to_string()}));
+        }
+    };
+
+    match products_service::load_product_price_history(repo.get_ref(), &user, product_id, query) {
+        Ok(history) => HttpResponse::Ok().json(history),
+        Err(ServiceError::Unauthorized) => HttpResponse::Unauthorized().finish(),
+        Err(ServiceError::NotFound) => HttpResponse::NotFound().finish(),
+        Err(err) => {
+            log::error!("Failed to load price history of product {product_id}: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[get("/v1/products/{product_id}/inventory")]
+/// Return the stock movements of a product, newest first.
+pub async fn api_v1_product_inventory(
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    let product_id = path.into_inner();
+
+    match inventory_service::load_inventory_movements(repo.get_ref(), &user, product_id) {
+        Ok(movements) => HttpResponse::Ok().json(movements),
+        Err(ServiceError::Unauthorized) => HttpResponse::Unauthorized().finish(),
+        Err(ServiceError::NotFound) => HttpResponse::NotFound().finish(),
+        Err(err) => {
+            log::error!("Failed to load inventory of product {product_id}: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
 #[get("/v1/client-price-levels")]
+/// Return the approved price level of every hub customer together with the hub default.
 pub async fn api_v1_client_price_levels(
     user: AuthenticatedUser,
     repo: web::Data<DieselRepository>,
@@ -45,6 +161,10 @@ pub async fn api_v1_client_price_levels(
 }
 
 #[put("/v1/client-price-levels")]
+/// File a client price level assignment for approval.
+///
+/// Returns `202 Accepted` with the pending request; the customer keeps its current
+/// price level until a user with the `orders_manager` role approves it.
 pub async fn api_v1_update_client_price_level(
     user: AuthenticatedUser,
     repo: web::Data<DieselRepository>,
@@ -55,7 +175,7 @@ pub async fn api_v1_update_client_price_level(
     let log_phone = payload.phone.clone();
 
     match assign_price_level_to_client(repo.get_ref(), &user, payload) {
-        Ok(()) => HttpResponse::NoContent().finish(),
+        Ok(request) => HttpResponse::Accepted().json(request),
         Err(ServiceError::Unauthorized) => HttpResponse::Unauthorized().finish(),
         Err(ServiceError::NotFound) => HttpResponse::NotFound().finish(),
         Err(ServiceError::Form(message)) => {
@@ -0,0 +1,401 @@
+use actix_multipart::form::MultipartForm;
+use actix_web::{HttpRequest, HttpResponse, Responder, get, post, web};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::models::config::CommonServerConfig;
+use pushkind_common::routes::{base_context, redirect, render_template};
+use tera::Tera;
+
+use crate::forms::customers::{
+    AddCustomerForm, EditCustomerForm, MergeCustomersForm, UploadCustomersForm,
+};
+use crate::repository::DieselRepository;
+use crate::services::ServiceError;
+use crate::services::customers::{
+    self, CustomerOrdersQuery, CustomersQuery, create_customer, customer_upload_errors,
+    import_customers, load_customer_duplicates, load_customer_page, load_customers,
+    merge_customers, preview_customers, remove_customer, update_customer,
+};
+
+#[get("/customers")]
+pub async fn show_customers(
+    params: web::Query<CustomersQuery>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    server_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+) -> impl Responder {
+    match load_customers(repo.get_ref(), &user, params.0) {
+        Ok(data) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "customers",
+                &server_config.auth_service_url,
+            );
+            context.insert("customers", &data.customers);
+            context.insert("search", &data.search);
+            context.insert("search_action", "/customers");
+            render_template(&tera, "customers/index.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(err) => {
+            log::error!("Failed to list customers: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[get("/customers/export")]
+pub async fn export_customers(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    match customers::export_customers(repo.get_ref(), &user) {
+        Ok(bytes) => HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header((
+                "Content-Disposition",
+                "attachment; filename=\"customers.csv\"",
+            ))
+            .body(bytes),
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(err) => {
+            log::error!("Failed to export customers: {err}");
+            FlashMessage::error("Не удалось выгрузить клиентов.").send();
+            redirect("/customers")
+        }
+    }
+}
+
+#[get("/customers/duplicates")]
+pub async fn show_customer_duplicates(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    server_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+) -> impl Responder {
+    match load_customer_duplicates(repo.get_ref(), &user) {
+        Ok(groups) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "customers",
+                &server_config.auth_service_url,
+            );
+            context.insert("groups", &groups);
+            context.insert("search_action", "/customers");
+            render_template(&tera, "customers/duplicates.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(err) => {
+            log::error!("Failed to find duplicate customers: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[get("/customers/{customer_id}")]
+pub async fn show_customer(
+    path: web::Path<i32>,
+    params: web::Query<CustomerOrdersQuery>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    server_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+) -> impl Responder {
+    let customer_id = path.into_inner();
+
+    match load_customer_page(repo.get_ref(), &user, customer_id, params.0) {
+        Ok(data) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "customers",
+                &server_config.auth_service_url,
+            );
+            context.insert("customer", &data.customer);
+            context.insert("orders", &data.orders);
+            context.insert("search_action", "/customers");
+            render_template(&tera, "customers/customer.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Клиент не найден.").send();
+            redirect("/customers")
+        }
+        Err(err) => {
+            log::error!("Failed to load customer {customer_id}: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[post("/customers/add")]
+pub async fn add_customer(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<AddCustomerForm>,
+) -> impl Responder {
+    match create_customer(repo.get_ref(), &user, form.into_inner()) {
+        Ok(customer) => {
+            FlashMessage::success(format!("Клиент «{}» добавлен.", customer.name)).send();
+            redirect("/customers")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/customers")
+        }
+        Err(ServiceError::Conflict) => {
+            FlashMessage::error("Клиент с таким email и телефоном уже существует.").send();
+            redirect("/customers")
+        }
+        Err(err) => {
+            log::error!("Failed to create customer: {err}");
+            FlashMessage::error("Не удалось добавить клиента.").send();
+            redirect("/customers")
+        }
+    }
+}
+
+#[post("/customers/edit")]
+pub async fn edit_customer(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<EditCustomerForm>,
+) -> impl Responder {
+    let customer_url = format!("/customers/{}", form.customer_id);
+
+    match update_customer(repo.get_ref(), &user, form.into_inner()) {
+        Ok(customer) => {
+            FlashMessage::success(format!("Клиент «{}» изменен.", customer.name)).send();
+            redirect(&customer_url)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect(&customer_url)
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Клиент не найден.").send();
+            redirect("/customers")
+        }
+        Err(ServiceError::Conflict) => {
+            FlashMessage::error("Клиент с таким email и телефоном уже существует.").send();
+            redirect(&customer_url)
+        }
+        Err(err) => {
+            log::error!("Failed to update customer: {err}");
+            FlashMessage::error("Не удалось изменить клиента.").send();
+            redirect(&customer_url)
+        }
+    }
+}
+
+#[post("/customers/upload")]
+pub async fn upload_customers(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    MultipartForm(form): MultipartForm<UploadCustomersForm>,
+) -> impl Responder {
+    match import_customers(repo.get_ref(), &user, form) {
+        Ok(summary) => {
+            let mut message = format!(
+                "Клиенты загружены: добавлено {}, обновлено {}, без изменений {}.",
+                summary.created, summary.updated, summary.unchanged
+            );
+            if summary.price_level_requests > 0 {
+                message.push_str(&format!(
+                    " Заявок на смену уровня цен: {}.",
+                    summary.price_level_requests
+                ));
+            }
+            FlashMessage::success(message).send();
+            redirect("/customers")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/customers")
+        }
+        Err(ServiceError::Conflict) => {
+            FlashMessage::error("Некоторые клиенты уже существуют.").send();
+            redirect("/customers")
+        }
+        Err(err) => {
+            log::error!("Failed to import customers: {err}");
+            FlashMessage::error("Не удалось загрузить клиентов.").send();
+            redirect("/customers")
+        }
+    }
+}
+
+#[post("/customers/upload/preview")]
+pub async fn preview_customer_upload(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    server_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+    MultipartForm(form): MultipartForm<UploadCustomersForm>,
+) -> impl Responder {
+    match preview_customers(repo.get_ref(), &user, form) {
+        Ok(preview) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "customers",
+                &server_config.auth_service_url,
+            );
+            context.insert("preview", &preview);
+            render_template(&tera, "customers/upload_preview.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/customers")
+        }
+        Err(err) => {
+            log::error!("Failed to preview customer upload: {err}");
+            FlashMessage::error("Не удалось проверить файл.").send();
+            redirect("/customers")
+        }
+    }
+}
+
+#[post("/customers/upload/errors")]
+pub async fn download_customer_upload_errors(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    MultipartForm(form): MultipartForm<UploadCustomersForm>,
+) -> impl Responder {
+    match customer_upload_errors(repo.get_ref(), &user, form) {
+        Ok(bytes) => HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header((
+                "Content-Disposition",
+                "attachment; filename=\"customer-upload-errors.csv\"",
+            ))
+            .body(bytes),
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/customers")
+        }
+        Err(err) => {
+            log::error!("Failed to build customer upload errors: {err}");
+            FlashMessage::error("Не удалось выгрузить ошибки.").send();
+            redirect("/customers")
+        }
+    }
+}
+
+#[post("/customers/merge")]
+pub async fn merge_duplicate_customers(
+    req: HttpRequest,
+    body: web::Bytes,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    // Parse with serde_qs so the `customer_ids[]` array deserializes.
+    let qs_config = serde_qs::Config::new(5, false);
+    let form = match qs_config.deserialize_bytes::<MergeCustomersForm>(body.as_ref()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            log::warn!(
+                "Failed to parse merge customers form for {}: {err}",
+                req.path()
+            );
+            FlashMessage::error("Некорректные данные формы.").send();
+            return redirect("/customers/duplicates");
+        }
+    };
+
+    match merge_customers(repo.get_ref(), &user, form) {
+        Ok(customer) => {
+            FlashMessage::success(format!("Клиенты объединены в «{}».", customer.name)).send();
+            redirect(&format!("/customers/{}", customer.id))
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/customers/duplicates")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Часть выбранных клиентов или уровень цен не найдены.").send();
+            redirect("/customers/duplicates")
+        }
+        Err(err) => {
+            log::error!("Failed to merge customers: {err}");
+            FlashMessage::error("Не удалось объединить клиентов.").send();
+            redirect("/customers/duplicates")
+        }
+    }
+}
+
+#[post("/customers/{customer_id}/delete")]
+pub async fn delete_customer(
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    let customer_id = path.into_inner();
+
+    match remove_customer(repo.get_ref(), &user, customer_id) {
+        Ok(()) => {
+            FlashMessage::success("Клиент удален.").send();
+            redirect("/customers")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Клиент не найден или уже удален.").send();
+            redirect("/customers")
+        }
+        Err(ServiceError::Conflict) => {
+            FlashMessage::error("Нельзя удалить клиента, у которого есть заказы.").send();
+            redirect(&format!("/customers/{customer_id}"))
+        }
+        Err(err) => {
+            log::error!("Failed to delete customer {customer_id}: {err}");
+            FlashMessage::error("Не удалось удалить клиента.").send();
+            redirect("/customers")
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use actix_multipart::form::MultipartForm;
+use actix_web::{HttpResponse, Responder, get, post, web};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::models::config::CommonServerConfig;
+use pushkind_common::routes::{base_context, redirect, render_template};
+use tera::Tera;
+
+use crate::forms::exchange_rates::{AddExchangeRateForm, UploadExchangeRatesForm};
+use crate::repository::DieselRepository;
+use crate::services::ServiceError;
+use crate::services::exchange_rates::{
+    import_exchange_rates, load_exchange_rates, remove_exchange_rate, save_exchange_rate,
+};
+
+#[get("/exchange-rates")]
+pub async fn show_exchange_rates(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    server_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+) -> impl Responder {
+    match load_exchange_rates(repo.get_ref(), &user) {
+        Ok(rates) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "exchange_rates",
+                &server_config.auth_service_url,
+            );
+            context.insert("rates", &rates);
+            render_template(&tera, "exchange_rates/index.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(err) => {
+            log::error!("Failed to list exchange rates: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[post("/exchange-rates/add")]
+pub async fn add_exchange_rate(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<AddExchangeRateForm>,
+) -> impl Responder {
+    match save_exchange_rate(repo.get_ref(), &user, form.into_inner()) {
+        Ok(rate) => {
+            FlashMessage::success(format!(
+                "Курс {}/{} сохранен.",
+                rate.base_currency, rate.quote_currency
+            ))
+            .send();
+            redirect("/exchange-rates")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/exchange-rates")
+        }
+        Err(err) => {
+            log::error!("Failed to save exchange rate: {err}");
+            FlashMessage::error("Не удалось сохранить курс.").send();
+            redirect("/exchange-rates")
+        }
+    }
+}
+
+#[post("/exchange-rates/upload")]
+pub async fn upload_exchange_rates(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    MultipartForm(form): MultipartForm<UploadExchangeRatesForm>,
+) -> impl Responder {
+    match import_exchange_rates(repo.get_ref(), &user, form) {
+        Ok(saved) => {
+            FlashMessage::success(format!("Загружено курсов: {saved}.")).send();
+            redirect("/exchange-rates")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/exchange-rates")
+        }
+        Err(err) => {
+            log::error!("Failed to import exchange rates: {err}");
+            FlashMessage::error("Не удалось загрузить курсы.").send();
+            redirect("/exchange-rates")
+        }
+    }
+}
+
+#[post("/exchange-rates/{exchange_rate_id}/delete")]
+pub async fn delete_exchange_rate(
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    let exchange_rate_id = path.into_inner();
+
+    match remove_exchange_rate(repo.get_ref(), &user, exchange_rate_id) {
+        Ok(()) => {
+            FlashMessage::success("Курс удален.").send();
+            redirect("/exchange-rates")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Курс не найден или уже удален.").send();
+            redirect("/exchange-rates")
+        }
+        Err(err) => {
+            log::error!("Failed to delete exchange rate {exchange_rate_id}: {err}");
+            FlashMessage::error("Не удалось удалить курс.").send();
+            redirect("/exchange-rates")
+        }
+    }
+}
@@ -1,24 +1,40 @@
-use actix_web::{HttpResponse, Responder, get, web};
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, HttpResponse, Responder, get, web};
 use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
 use pushkind_common::domain::auth::AuthenticatedUser;
 use pushkind_common::models::config::CommonServerConfig;
 use pushkind_common::routes::{base_context, redirect, render_template};
 use tera::Tera;
 
+use crate::domain::order::OrderStatus;
 use crate::repository::DieselRepository;
 use crate::services::main::IndexQuery;
 use crate::services::{ServiceError, main as main_service};
 
 #[get("/")]
 pub async fn show_index(
-    params: web::Query<IndexQuery>,
+    req: HttpRequest,
     user: AuthenticatedUser,
     repo: web::Data<DieselRepository>,
     flash_messages: IncomingFlashMessages,
     server_config: web::Data<CommonServerConfig>,
     tera: web::Data<Tera>,
 ) -> impl Responder {
-    match main_service::load_index_page(repo.get_ref(), &user, params.0) {
+    // Parse with serde_html_form so repeated `status` keys collect into a list.
+    let query = match serde_html_form::from_str::<IndexQuery>(req.query_string()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            log::warn!(
+                "Failed to parse orders filter {}: {err}",
+                req.query_string()
+            );
+            FlashMessage::error("Некорректные параметры фильтра.").send();
+            return redirect("/");
+        }
+    };
+
+    match main_service::load_index_page(repo.get_ref(), &user, query) {
         Ok(data) => {
             let mut context = base_context(
                 &flash_messages,
@@ -28,12 +44,32 @@ pub async fn show_index(
             );
             context.insert("orders", &data.orders);
             context.insert("search", &data.search);
+            context.insert("has_active_filters", &data.filters.has_filters());
+            context.insert("filters", &data.filters);
+            context.insert("statuses", &OrderStatus::ALL);
+            context.insert("export_query", req.query_string());
+            let status_transitions: HashMap<&str, Vec<&str>> = OrderStatus::ALL
+                .into_iter()
+                .map(|status| {
+                    let allowed = status
+                        .allowed_transitions()
+                        .iter()
+                        .map(|next| <&str>::from(*next))
+                        .collect();
+                    (<&str>::from(status), allowed)
+                })
+                .collect();
+            context.insert("status_transitions", &status_transitions);
             render_template(&tera, "main/index.html", &context)
         }
         Err(ServiceError::Unauthorized) => {
             FlashMessage::error("Недостаточно прав.").send();
             redirect("/na")
         }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(format!("Некорректные параметры фильтра: {message}")).send();
+            redirect("/")
+        }
         Err(err) => {
             log::error!("Failed to list orders: {err}");
             HttpResponse::InternalServerError().finish()
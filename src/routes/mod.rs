@@ -1,6 +1,34 @@
+use std::collections::HashMap;
+
+use tera::{Tera, Value};
+
+use crate::domain::money::format_amount;
+
 pub mod api;
 pub mod categories;
+pub mod customers;
+pub mod exchange_rates;
 pub mod main;
+pub mod orders;
 pub mod price_levels;
 pub mod products;
+pub mod promotions;
 pub mod tags;
+pub mod tax_categories;
+
+/// Registers the filters shared by every template.
+pub fn register_template_filters(tera: &mut Tera) {
+    tera.register_filter("money", money_filter);
+}
+
+/// Formats an amount in minor units with the precision of the `currency`
+/// argument, e.g. `{{ order.total.amount | money(currency=order.total.currency) }}`.
+fn money_filter(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let amount = value
+        .as_i64()
+        .or_else(|| value.as_f64().map(|amount| amount.round() as i64))
+        .ok_or_else(|| tera::Error::msg("`money` expects a numeric amount"))?;
+    let currency = args.get("currency").and_then(Value::as_str).unwrap_or("");
+
+    Ok(Value::String(format_amount(amount, currency)))
+}
@@ -0,0 +1,189 @@
+use actix_web::{HttpRequest, HttpResponse, Responder, get, post, web};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::models::config::CommonServerConfig;
+use pushkind_common::routes::{base_context, redirect, render_template};
+use tera::Tera;
+
+use crate::forms::orders::{EditOrderForm, TransitionOrderForm};
+use crate::repository::DieselRepository;
+use crate::services::main::IndexQuery;
+use crate::services::orders::ExportQuery;
+use crate::services::{ServiceError, orders};
+
+#[get("/orders/export")]
+pub async fn export_orders(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    // Parse with serde_html_form so repeated `status` keys collect into a list.
+    let parsed = serde_html_form::from_str::<IndexQuery>(req.query_string()).and_then(|query| {
+        serde_html_form::from_str::<ExportQuery>(req.query_string()).map(|export| (query, export))
+    });
+    let (query, export) = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            log::warn!(
+                "Failed to parse orders export {}: {err}",
+                req.query_string()
+            );
+            FlashMessage::error("Некорректные параметры фильтра.").send();
+            return redirect("/");
+        }
+    };
+
+    match orders::export_orders(repo.get_ref(), &user, query, export.format) {
+        Ok(bytes) => HttpResponse::Ok()
+            .content_type(export.format.content_type())
+            .insert_header((
+                "Content-Disposition",
+                format!(
+                    "attachment; filename=\"orders.{}\"",
+                    export.format.extension()
+                ),
+            ))
+            .body(bytes),
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(format!("Некорректные параметры фильтра: {message}")).send();
+            redirect("/")
+        }
+        Err(err) => {
+            log::error!("Failed to export orders: {err}");
+            FlashMessage::error("Не удалось выгрузить заказы.").send();
+            redirect("/")
+        }
+    }
+}
+
+#[get("/orders/{order_id}")]
+pub async fn show_order(
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    server_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+) -> impl Responder {
+    let order_id = path.into_inner();
+
+    match orders::load_order_page(repo.get_ref(), &user, order_id) {
+        Ok(data) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "index",
+                &server_config.auth_service_url,
+            );
+            context.insert("order", &data.order);
+            context.insert("history", &data.history);
+            context.insert("allowed_transitions", &data.allowed_transitions);
+            context.insert("is_editable", &data.is_editable);
+            context.insert("products", &data.products);
+            context.insert("line_totals", &data.line_totals);
+            render_template(&tera, "orders/order.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Заказ не найден.").send();
+            redirect("/")
+        }
+        Err(err) => {
+            log::error!("Failed to load order {order_id}: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[post("/orders/{order_id}/edit")]
+pub async fn edit_order(
+    req: HttpRequest,
+    path: web::Path<i32>,
+    body: web::Bytes,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    let order_id = path.into_inner();
+    let order_url = format!("/orders/{order_id}");
+
+    // Parse the URL-encoded body using serde_qs so nested arrays deserialize correctly.
+    let qs_config = serde_qs::Config::new(5, false);
+    let form = match qs_config.deserialize_bytes::<EditOrderForm>(body.as_ref()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            log::warn!("Failed to parse edit order form for {}: {err}", req.path());
+            FlashMessage::error("Некорректные данные формы.").send();
+            return redirect(&order_url);
+        }
+    };
+
+    match orders::update_order_details(repo.get_ref(), &user, order_id, form) {
+        Ok(order) => {
+            FlashMessage::success(format!("Заказ №{} обновлен.", order.id)).send();
+            redirect(&order_url)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Заказ не найден или его статус уже изменен.").send();
+            redirect("/")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect(&order_url)
+        }
+        Err(err) => {
+            log::error!("Failed to update order {order_id}: {err}");
+            FlashMessage::error("Не удалось обновить заказ.").send();
+            redirect(&order_url)
+        }
+    }
+}
+
+#[post("/orders/{order_id}/status")]
+pub async fn transition_order_status(
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<TransitionOrderForm>,
+) -> impl Responder {
+    let order_id = path.into_inner();
+    let order_url = format!("/orders/{order_id}");
+
+    match orders::transition_order(repo.get_ref(), &user, order_id, form.into_inner()) {
+        Ok(order) => {
+            FlashMessage::success(format!("Статус заказа №{} изменен.", order.id)).send();
+            redirect(&order_url)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Заказ не найден или его статус уже изменен.").send();
+            redirect(&order_url)
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect(&order_url)
+        }
+        Err(ServiceError::Conflict) => {
+            FlashMessage::error("Остатки товара не позволяют изменить статус заказа.").send();
+            redirect(&order_url)
+        }
+        Err(err) => {
+            log::error!("Failed to change status of order {order_id}: {err}");
+            FlashMessage::error("Не удалось изменить статус заказа.").send();
+            redirect(&order_url)
+        }
+    }
+}
@@ -10,9 +10,12 @@ use crate::forms::price_levels::{AddPriceLevelForm, EditPriceLevelForm, UploadPr
 use crate::models::config::ServerConfig;
 use crate::repository::DieselRepository;
 use crate::services::ServiceError;
+use crate::services::price_level_assignments::{
+    PriceLevelRequestsQuery, decide_price_level_request, load_price_level_requests,
+};
 use crate::services::price_levels::{
     PriceLevelsQuery, create_price_level, import_price_levels, load_price_levels,
-    remove_price_level, update_price_level,
+    preview_price_levels, price_level_upload_errors, remove_price_level, update_price_level,
 };
 
 #[get("/price-levels")]
@@ -150,6 +153,72 @@ pub async fn upload_price_levels(
     }
 }
 
+#[post("/price-levels/upload/preview")]
+pub async fn preview_price_level_upload(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    common_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+    MultipartForm(form): MultipartForm<UploadPriceLevelsForm>,
+) -> impl Responder {
+    match preview_price_levels(repo.get_ref(), &user, form) {
+        Ok(preview) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "price_levels",
+                &common_config.auth_service_url,
+            );
+            context.insert("preview", &preview);
+            render_template(&tera, "price_levels/upload_preview.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/price-levels")
+        }
+        Err(err) => {
+            log::error!("Failed to preview price level upload: {err}");
+            FlashMessage::error("Не удалось проверить файл.").send();
+            redirect("/price-levels")
+        }
+    }
+}
+
+#[post("/price-levels/upload/errors")]
+pub async fn download_price_level_upload_errors(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    MultipartForm(form): MultipartForm<UploadPriceLevelsForm>,
+) -> impl Responder {
+    match price_level_upload_errors(repo.get_ref(), &user, form) {
+        Ok(bytes) => HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header((
+                "Content-Disposition",
+                "attachment; filename=\"price-level-upload-errors.csv\"",
+            ))
+            .body(bytes),
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/price-levels")
+        }
+        Err(err) => {
+            log::error!("Failed to build price level upload errors: {err}");
+            FlashMessage::error("Не удалось выгрузить ошибки.").send();
+            redirect("/price-levels")
+        }
+    }
+}
+
 #[post("/price-levels/{price_level_id}/delete")]
 pub async fn delete_price_level(
     path: web::Path<i32>,
@@ -178,3 +247,84 @@ pub async fn delete_price_level(
         }
     }
 }
+
+#[get("/price-levels/requests")]
+pub async fn show_price_level_requests(
+    params: web::Query<PriceLevelRequestsQuery>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    common_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+) -> impl Responder {
+    match load_price_level_requests(repo.get_ref(), &user, params.into_inner()) {
+        Ok(data) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "price_level_requests",
+                &common_config.auth_service_url,
+            );
+            context.insert("requests", &data.requests);
+            context.insert("status", &data.status);
+            render_template(&tera, "price_levels/requests.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(err) => {
+            log::error!("Failed to list price level requests: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[post("/price-levels/requests/{request_id}/approve")]
+pub async fn approve_price_level_request(
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    decide_request(path.into_inner(), &user, repo.get_ref(), true)
+}
+
+#[post("/price-levels/requests/{request_id}/reject")]
+pub async fn reject_price_level_request(
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    decide_request(path.into_inner(), &user, repo.get_ref(), false)
+}
+
+fn decide_request(
+    request_id: i32,
+    user: &AuthenticatedUser,
+    repo: &DieselRepository,
+    approve: bool,
+) -> HttpResponse {
+    match decide_price_level_request(repo, user, request_id, approve) {
+        Ok(_) if approve => {
+            FlashMessage::success("Заявка одобрена, уровень цен назначен клиенту.").send();
+            redirect("/price-levels/requests")
+        }
+        Ok(_) => {
+            FlashMessage::success("Заявка отклонена.").send();
+            redirect("/price-levels/requests")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Заявка не найдена или уже рассмотрена.").send();
+            redirect("/price-levels/requests")
+        }
+        Err(err) => {
+            log::error!("Failed to decide price level request {request_id}: {err}");
+            FlashMessage::error("Не удалось обработать заявку.").send();
+            redirect("/price-levels/requests")
+        }
+    }
+}
@@ -7,9 +7,14 @@ use pushkind_common::routes::{base_context, redirect, render_template};
 use serde::Deserialize;
 use tera::Tera;
 
-use crate::forms::products::{AddProductForm, EditProductForm, UploadProductsForm};
+use crate::domain::product::ProductRemoval;
+use crate::forms::inventory::AdjustStockForm;
+use crate::forms::products::{
+    AddProductForm, BulkProductsForm, CancelScheduledPriceForm, DeleteProductForm, EditProductForm,
+    RepriceProductsForm, ScheduleProductPriceForm, UploadProductsForm,
+};
 use crate::repository::DieselRepository;
-use crate::services::{ServiceError, products};
+use crate::services::{ServiceError, inventory, products};
 
 #[get("/products")]
 pub async fn show_products(
@@ -39,6 +44,7 @@ pub async fn show_products(
             context.insert("search_action", "/products");
             context.insert("price_levels", &data.price_levels);
             context.insert("categories", &data.categories);
+            context.insert("tax_categories", &data.tax_categories);
             context.insert("tags", &data.tags);
             context.insert("show_archived", &data.show_archived);
             context.insert("has_active_filters", &has_active_filters);
@@ -55,6 +61,31 @@ pub async fn show_products(
     }
 }
 
+#[get("/products/export")]
+pub async fn export_products(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    match products::export_products(repo.get_ref(), &user) {
+        Ok(bytes) => HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header((
+                "Content-Disposition",
+                "attachment; filename=\"products.csv\"",
+            ))
+            .body(bytes),
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(err) => {
+            log::error!("Failed to export products: {err}");
+            FlashMessage::error("Не удалось выгрузить товары.").send();
+            redirect("/products")
+        }
+    }
+}
+
 #[post("/products/add")]
 pub async fn add_product(
     req: HttpRequest,
@@ -102,8 +133,12 @@ pub async fn upload_products(
     MultipartForm(form): MultipartForm<UploadProductsForm>,
 ) -> impl Responder {
     match products::import_products(repo.get_ref(), &user, form) {
-        Ok(created) => {
-            FlashMessage::success(format!("Загружено товаров: {created}.")).send();
+        Ok(summary) => {
+            FlashMessage::success(format!(
+                "Создано: {}, обновлено: {}, без изменений: {}.",
+                summary.created, summary.updated, summary.unchanged
+            ))
+            .send();
             redirect("/products")
         }
         Err(ServiceError::Unauthorized) => {
@@ -122,6 +157,72 @@ pub async fn upload_products(
     }
 }
 
+#[post("/products/upload/preview")]
+pub async fn preview_product_upload(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    server_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+    MultipartForm(form): MultipartForm<UploadProductsForm>,
+) -> impl Responder {
+    match products::preview_products(repo.get_ref(), &user, form) {
+        Ok(preview) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "products",
+                &server_config.auth_service_url,
+            );
+            context.insert("preview", &preview);
+            render_template(&tera, "products/upload_preview.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/products")
+        }
+        Err(err) => {
+            log::error!("Failed to preview product upload: {err}");
+            FlashMessage::error("Не удалось проверить файл.").send();
+            redirect("/products")
+        }
+    }
+}
+
+#[post("/products/upload/errors")]
+pub async fn download_product_upload_errors(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    MultipartForm(form): MultipartForm<UploadProductsForm>,
+) -> impl Responder {
+    match products::product_upload_errors(repo.get_ref(), &user, form) {
+        Ok(bytes) => HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header((
+                "Content-Disposition",
+                "attachment; filename=\"product-upload-errors.csv\"",
+            ))
+            .body(bytes),
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/products")
+        }
+        Err(err) => {
+            log::error!("Failed to build product upload errors: {err}");
+            FlashMessage::error("Не удалось выгрузить ошибки.").send();
+            redirect("/products")
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct EditProductPayload {
     product_id: i32,
@@ -176,3 +277,256 @@ pub async fn edit_product(
         }
     }
 }
+
+#[post("/products/delete")]
+pub async fn delete_product(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<DeleteProductForm>,
+) -> impl Responder {
+    let product_id = form.product_id;
+
+    match products::remove_product(repo.get_ref(), &user, product_id) {
+        Ok(ProductRemoval::Deleted) => {
+            FlashMessage::success("Товар удалён.").send();
+            redirect("/products")
+        }
+        Ok(ProductRemoval::Archived) => {
+            FlashMessage::success("Товар используется в заказах и перенесён в архив.").send();
+            redirect("/products")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Товар не найден или уже удалён.").send();
+            redirect("/products")
+        }
+        Err(err) => {
+            log::error!("Failed to delete product {product_id}: {err}");
+            FlashMessage::error("Не удалось удалить товар.").send();
+            redirect("/products")
+        }
+    }
+}
+
+#[post("/products/prices/schedule")]
+pub async fn schedule_product_price(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<ScheduleProductPriceForm>,
+) -> impl Responder {
+    let product_id = form.product_id;
+
+    match products::schedule_product_price(repo.get_ref(), &user, form.into_inner()) {
+        Ok(product) => {
+            FlashMessage::success(format!("Цена товара «{}» запланирована.", product.name)).send();
+            redirect("/products")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/products")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Товар или уровень цен не найден.").send();
+            redirect("/products")
+        }
+        Err(err) => {
+            log::error!("Failed to schedule price for product {product_id}: {err}");
+            FlashMessage::error("Не удалось запланировать цену.").send();
+            redirect("/products")
+        }
+    }
+}
+
+#[post("/products/prices/reprice/preview")]
+pub async fn preview_product_repricing(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    server_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+    form: web::Form<RepriceProductsForm>,
+) -> impl Responder {
+    let form = form.into_inner();
+
+    match products::preview_repricing(repo.get_ref(), &user, form.clone()) {
+        Ok(preview) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "products",
+                &server_config.auth_service_url,
+            );
+            context.insert("preview", &preview);
+            context.insert("form", &form);
+            render_template(&tera, "products/reprice_preview.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/products")
+        }
+        Err(err) => {
+            log::error!("Failed to preview repricing: {err}");
+            FlashMessage::error("Не удалось рассчитать новые цены.").send();
+            redirect("/products")
+        }
+    }
+}
+
+#[post("/products/prices/reprice")]
+pub async fn reprice_products(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<RepriceProductsForm>,
+) -> impl Responder {
+    match products::reprice_products(repo.get_ref(), &user, form.into_inner()) {
+        Ok(count) => {
+            FlashMessage::success(format!("Изменено цен: {count}.")).send();
+            redirect("/products")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/products")
+        }
+        Err(ServiceError::Conflict) => {
+            FlashMessage::error("Цены изменились после проверки, повторите переоценку.").send();
+            redirect("/products")
+        }
+        Err(err) => {
+            log::error!("Failed to reprice products: {err}");
+            FlashMessage::error("Не удалось изменить цены.").send();
+            redirect("/products")
+        }
+    }
+}
+
+#[post("/products/prices/cancel")]
+pub async fn cancel_scheduled_product_price(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<CancelScheduledPriceForm>,
+) -> impl Responder {
+    let rate_id = form.rate_id;
+
+    match products::cancel_scheduled_product_price(repo.get_ref(), &user, form.into_inner()) {
+        Ok(()) => {
+            FlashMessage::success("Запланированная цена отменена.").send();
+            redirect("/products")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Запланированная цена не найдена или уже отменена.").send();
+            redirect("/products")
+        }
+        Err(err) => {
+            log::error!("Failed to cancel scheduled price {rate_id}: {err}");
+            FlashMessage::error("Не удалось отменить цену.").send();
+            redirect("/products")
+        }
+    }
+}
+
+#[post("/products/stock")]
+pub async fn adjust_product_stock(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<AdjustStockForm>,
+) -> impl Responder {
+    let product_id = form.product_id;
+
+    match inventory::adjust_product_stock(repo.get_ref(), &user, form.into_inner()) {
+        Ok(movement) => {
+            FlashMessage::success(format!(
+                "Остаток изменен: на складе {}, доступно {}.",
+                movement.stock.on_hand,
+                movement.stock.available()
+            ))
+            .send();
+            redirect("/products")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/products")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Товар не найден.").send();
+            redirect("/products")
+        }
+        Err(ServiceError::Conflict) => {
+            FlashMessage::error("Остаток на складе не может стать отрицательным.").send();
+            redirect("/products")
+        }
+        Err(err) => {
+            log::error!("Failed to adjust stock of product {product_id}: {err}");
+            FlashMessage::error("Не удалось изменить остаток.").send();
+            redirect("/products")
+        }
+    }
+}
+
+#[post("/products/bulk")]
+pub async fn bulk_update_products(
+    req: HttpRequest,
+    body: web::Bytes,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    // Parse with serde_qs so the `product_ids[]` and `tag_ids[]` arrays deserialize.
+    let qs_config = serde_qs::Config::new(5, false);
+    let form = match qs_config.deserialize_bytes::<BulkProductsForm>(body.as_ref()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            log::warn!(
+                "Failed to parse bulk products form for {}: {err}",
+                req.path()
+            );
+            FlashMessage::error("Некорректные данные формы.").send();
+            return redirect("/products");
+        }
+    };
+
+    match products::bulk_update_products(repo.get_ref(), &user, form) {
+        Ok(count) => {
+            FlashMessage::success(format!("Обновлено товаров: {count}.")).send();
+            redirect("/products")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/products")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Часть выбранных товаров, категория или теги не найдены.").send();
+            redirect("/products")
+        }
+        Err(err) => {
+            log::error!("Failed to apply bulk product action: {err}");
+            FlashMessage::error("Не удалось обновить товары.").send();
+            redirect("/products")
+        }
+    }
+}
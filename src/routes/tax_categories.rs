@@ -0,0 +1,99 @@
+use actix_web::{HttpResponse, Responder, get, post, web};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::models::config::CommonServerConfig;
+use pushkind_common::routes::{base_context, redirect, render_template};
+use tera::Tera;
+
+use crate::forms::tax_categories::AddTaxCategoryForm;
+use crate::repository::DieselRepository;
+use crate::services::ServiceError;
+use crate::services::tax_categories::{
+    load_tax_categories, remove_tax_category, save_tax_category,
+};
+
+#[get("/tax-categories")]
+pub async fn show_tax_categories(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    flash_messages: IncomingFlashMessages,
+    server_config: web::Data<CommonServerConfig>,
+    tera: web::Data<Tera>,
+) -> impl Responder {
+    match load_tax_categories(repo.get_ref(), &user) {
+        Ok(tax_categories) => {
+            let mut context = base_context(
+                &flash_messages,
+                &user,
+                "tax_categories",
+                &server_config.auth_service_url,
+            );
+            context.insert("tax_categories", &tax_categories);
+            render_template(&tera, "tax_categories/index.html", &context)
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(err) => {
+            log::error!("Failed to list tax categories: {err}");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[post("/tax-categories/add")]
+pub async fn add_tax_category(
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+    form: web::Form<AddTaxCategoryForm>,
+) -> impl Responder {
+    match save_tax_category(repo.get_ref(), &user, form.into_inner()) {
+        Ok(category) => {
+            FlashMessage::success(format!("Налог «{}» сохранен.", category.name)).send();
+            redirect("/tax-categories")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::Form(message)) => {
+            FlashMessage::error(message).send();
+            redirect("/tax-categories")
+        }
+        Err(err) => {
+            log::error!("Failed to save tax category: {err}");
+            FlashMessage::error("Не удалось сохранить налог.").send();
+            redirect("/tax-categories")
+        }
+    }
+}
+
+#[post("/tax-categories/{tax_category_id}/delete")]
+pub async fn delete_tax_category(
+    path: web::Path<i32>,
+    user: AuthenticatedUser,
+    repo: web::Data<DieselRepository>,
+) -> impl Responder {
+    let tax_category_id = path.into_inner();
+
+    match remove_tax_category(repo.get_ref(), &user, tax_category_id) {
+        Ok(()) => {
+            FlashMessage::success("Налог удален.").send();
+            redirect("/tax-categories")
+        }
+        Err(ServiceError::Unauthorized) => {
+            FlashMessage::error("Недостаточно прав.").send();
+            redirect("/na")
+        }
+        Err(ServiceError::NotFound) => {
+            FlashMessage::error("Налог не найден или уже удален.").send();
+            redirect("/tax-categories")
+        }
+        Err(err) => {
+            log::error!("Failed to delete tax category {tax_category_id}: {err}");
+            FlashMessage::error("Не удалось удалить налог.").send();
+            redirect("/tax-categories")
+        }
+    }
+}
@@ -26,6 +26,54 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    exchange_rates (id) {
+        id -> Integer,
+        hub_id -> Integer,
+        base_currency -> Text,
+        quote_currency -> Text,
+        rate_micros -> BigInt,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    inventory_movements (id) {
+        id -> Integer,
+        product_id -> Integer,
+        order_id -> Nullable<Integer>,
+        kind -> Text,
+        quantity -> Integer,
+        stock_on_hand -> Integer,
+        stock_reserved -> Integer,
+        note -> Nullable<Text>,
+        changed_by_email -> Text,
+        changed_by_name -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    order_discounts (id) {
+        id -> Integer,
+        order_id -> Integer,
+        promotion_id -> Nullable<Integer>,
+        code -> Text,
+        kind -> Text,
+        value -> BigInt,
+        currency -> Text,
+        category_id -> Nullable<Integer>,
+        tag_id -> Nullable<Integer>,
+        min_order_cents -> Nullable<BigInt>,
+        net_cents -> BigInt,
+        tax_cents -> BigInt,
+        gross_cents -> BigInt,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     order_products (id) {
         id -> Integer,
@@ -34,11 +82,29 @@ diesel::table! {
         name -> Text,
         sku -> Nullable<Text>,
         description -> Nullable<Text>,
-        price_cents -> Integer,
         currency -> Text,
         quantity -> Integer,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        exchange_rate_micros -> BigInt,
+        price_cents -> BigInt,
+        tax_rate_basis_points -> Integer,
+        prices_include_tax -> Bool,
+        net_cents -> BigInt,
+        tax_cents -> BigInt,
+        gross_cents -> BigInt,
+    }
+}
+
+diesel::table! {
+    order_status_history (id) {
+        id -> Integer,
+        order_id -> Integer,
+        from_status -> Text,
+        to_status -> Text,
+        changed_by_email -> Text,
+        changed_by_name -> Text,
+        created_at -> Timestamp,
     }
 }
 
@@ -50,10 +116,28 @@ diesel::table! {
         reference -> Nullable<Text>,
         status -> Text,
         notes -> Nullable<Text>,
-        total_cents -> Integer,
         currency -> Text,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        total_cents -> BigInt,
+        net_cents -> BigInt,
+        tax_cents -> BigInt,
+    }
+}
+
+diesel::table! {
+    price_level_assignment_requests (id) {
+        id -> Integer,
+        hub_id -> Integer,
+        customer_id -> Integer,
+        price_level_id -> Nullable<Integer>,
+        status -> Text,
+        requested_by_email -> Text,
+        requested_by_name -> Text,
+        decided_by_email -> Nullable<Text>,
+        decided_by_name -> Nullable<Text>,
+        created_at -> Timestamp,
+        decided_at -> Nullable<Timestamp>,
     }
 }
 
@@ -65,6 +149,29 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         is_default -> Bool,
+        base_price_level_id -> Nullable<Integer>,
+        adjustment_kind -> Text,
+        rounding -> Text,
+        adjustment_value -> BigInt,
+        prices_include_tax -> Bool,
+    }
+}
+
+diesel::table! {
+    product_price_history (id) {
+        id -> Integer,
+        product_id -> Nullable<Integer>,
+        price_level_id -> Nullable<Integer>,
+        product_name -> Text,
+        price_level_name -> Text,
+        valid_from -> Nullable<Timestamp>,
+        valid_to -> Nullable<Timestamp>,
+        changed_by_email -> Text,
+        changed_by_name -> Text,
+        created_at -> Timestamp,
+        min_quantity -> Integer,
+        old_price_cents -> Nullable<BigInt>,
+        new_price_cents -> Nullable<BigInt>,
     }
 }
 
@@ -73,9 +180,12 @@ diesel::table! {
         id -> Integer,
         product_id -> Integer,
         price_level_id -> Integer,
-        price_cents -> Integer,
+        valid_from -> Nullable<Timestamp>,
+        valid_to -> Nullable<Timestamp>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        min_quantity -> Integer,
+        price_cents -> BigInt,
     }
 }
 
@@ -102,6 +212,30 @@ diesel::table! {
         updated_at -> Timestamp,
         units -> Nullable<Text>,
         category_id -> Nullable<Integer>,
+        tax_category_id -> Nullable<Integer>,
+        stock_on_hand -> Integer,
+        stock_reserved -> Integer,
+        allow_oversell -> Bool,
+    }
+}
+
+diesel::table! {
+    promotions (id) {
+        id -> Integer,
+        hub_id -> Integer,
+        code -> Text,
+        kind -> Text,
+        value -> BigInt,
+        currency -> Text,
+        min_order_cents -> Nullable<BigInt>,
+        valid_from -> Nullable<Timestamp>,
+        valid_to -> Nullable<Timestamp>,
+        usage_limit -> Nullable<Integer>,
+        usage_count -> Integer,
+        category_id -> Nullable<Integer>,
+        tag_id -> Nullable<Integer>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -115,6 +249,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    tax_categories (id) {
+        id -> Integer,
+        hub_id -> Integer,
+        name -> Text,
+        rate_basis_points -> Integer,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     users (id) {
         id -> Integer,
@@ -127,23 +272,43 @@ diesel::table! {
 }
 
 diesel::joinable!(customers -> price_levels (price_level_id));
+diesel::joinable!(inventory_movements -> orders (order_id));
+diesel::joinable!(inventory_movements -> products (product_id));
+diesel::joinable!(order_discounts -> orders (order_id));
+diesel::joinable!(order_discounts -> promotions (promotion_id));
 diesel::joinable!(order_products -> orders (order_id));
+diesel::joinable!(order_status_history -> orders (order_id));
 diesel::joinable!(orders -> customers (customer_id));
+diesel::joinable!(price_level_assignment_requests -> customers (customer_id));
+diesel::joinable!(price_level_assignment_requests -> price_levels (price_level_id));
+diesel::joinable!(product_price_history -> price_levels (price_level_id));
+diesel::joinable!(product_price_history -> products (product_id));
 diesel::joinable!(product_price_levels -> price_levels (price_level_id));
 diesel::joinable!(product_price_levels -> products (product_id));
 diesel::joinable!(product_tags -> products (product_id));
 diesel::joinable!(product_tags -> tags (tag_id));
 diesel::joinable!(products -> categories (category_id));
+diesel::joinable!(promotions -> categories (category_id));
+diesel::joinable!(promotions -> tags (tag_id));
+diesel::joinable!(products -> tax_categories (tax_category_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     categories,
     customers,
+    exchange_rates,
+    inventory_movements,
+    order_discounts,
     order_products,
+    order_status_history,
     orders,
+    price_level_assignment_requests,
     price_levels,
+    product_price_history,
     product_price_levels,
     product_tags,
     products,
+    promotions,
     tags,
+    tax_categories,
     users,
 );
@@ -0,0 +1,1279 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::Utc;
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::pagination::{DEFAULT_ITEMS_PER_PAGE, Paginated};
+use pushkind_common::routes::check_role;
+use serde::{Deserialize, Serialize};
+
+use crate::SERVICE_ACCESS_ROLE;
+use crate::domain::customer::{
+    Customer, CustomerImportItem, CustomerImportTarget, CustomerListQuery, UpdateCustomer,
+};
+use crate::domain::order::{Order, OrderListQuery};
+use crate::domain::phone::normalize_phone;
+use crate::domain::price_level::{PriceLevel, PriceLevelListQuery};
+use crate::domain::price_level_assignment::NewPriceLevelAssignmentRequest;
+use crate::forms::customers::{
+    AddCustomerForm, CUSTOMER_CSV_HEADERS, CustomerFormError, CustomerUploadRow, EditCustomerForm,
+    MergeCustomersForm, UploadCustomersForm, UploadCustomersFormError,
+};
+use crate::repository::{CustomerReader, CustomerWriter, OrderReader, PriceLevelReader};
+use crate::services::uploads::{UploadRowError, write_upload_errors_csv};
+use crate::services::{ServiceError, ServiceResult};
+
+/// Query parameters accepted by the customers index page.
+#[derive(Debug, Default, Deserialize)]
+pub struct CustomersQuery {
+    /// Optional search applied to customer names and emails.
+    pub search: Option<String>,
+    /// Page number requested by the UI (1-based).
+    pub page: Option<usize>,
+}
+
+/// Query parameters accepted by the customer page.
+#[derive(Debug, Default, Deserialize)]
+pub struct CustomerOrdersQuery {
+    /// Page of the customer's orders requested by the UI (1-based).
+    pub page: Option<usize>,
+}
+
+/// Customer together with the price level that applies to its orders.
+#[derive(Debug, Serialize)]
+pub struct CustomerView {
+    #[serde(flatten)]
+    pub customer: Customer,
+    /// Name of the effective price level, `None` when the hub has no default.
+    pub price_level_name: Option<String>,
+    /// Whether the effective level is the hub default rather than an assignment.
+    pub uses_default_price_level: bool,
+}
+
+impl CustomerView {
+    fn new(customer: Customer, price_levels: &[PriceLevel]) -> Self {
+        let assigned = customer
+            .price_level_id
+            .and_then(|id| price_levels.iter().find(|level| level.id == id));
+        let effective = assigned.or_else(|| price_levels.iter().find(|level| level.is_default));
+
+        Self {
+            price_level_name: effective.map(|level| level.name.clone()),
+            uses_default_price_level: assigned.is_none(),
+            customer,
+        }
+    }
+}
+
+/// Data required to render the customers index template.
+pub struct CustomersPageData {
+    /// Paginated list of customers displayed in the table.
+    pub customers: Paginated<CustomerView>,
+    /// Search query echoed back to the template when present.
+    pub search: Option<String>,
+}
+
+/// Data required to render a single customer page.
+pub struct CustomerPageData {
+    pub customer: CustomerView,
+    /// Paginated orders placed by the customer, newest first.
+    pub orders: Paginated<Order>,
+}
+
+/// Fetches paginated customers for the authenticated user's hub.
+pub fn load_customers<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    query: CustomersQuery,
+) -> ServiceResult<CustomersPageData>
+where
+    R: CustomerReader + PriceLevelReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let CustomersQuery { search, page } = query;
+    let page = page.unwrap_or(1);
+
+    let mut list_query = CustomerListQuery::new(user.hub_id).paginate(page, DEFAULT_ITEMS_PER_PAGE);
+    if let Some(term) = search.as_ref() {
+        list_query = list_query.search(term);
+    }
+
+    let (total, customers) = repo
+        .list_customers(list_query)
+        .map_err(ServiceError::from)?;
+    let (_, price_levels) = repo
+        .list_price_levels(PriceLevelListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+
+    let items = customers
+        .into_iter()
+        .map(|customer| CustomerView::new(customer, &price_levels))
+        .collect();
+    let total_pages = total.div_ceil(DEFAULT_ITEMS_PER_PAGE);
+
+    Ok(CustomersPageData {
+        customers: Paginated::new(items, page, total_pages),
+        search,
+    })
+}
+
+/// Loads a customer with its effective price level and order history.
+pub fn load_customer_page<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    customer_id: i32,
+    query: CustomerOrdersQuery,
+) -> ServiceResult<CustomerPageData>
+where
+    R: CustomerReader + OrderReader + PriceLevelReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let customer = repo
+        .get_customer_by_id(customer_id, user.hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or(ServiceError::NotFound)?;
+
+    let (_, price_levels) = repo
+        .list_price_levels(PriceLevelListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+
+    let page = query.page.unwrap_or(1);
+    let (total, orders) = repo
+        .list_orders(
+            OrderListQuery::new(user.hub_id)
+                .customer_id(customer.id)
+                .paginate(page, DEFAULT_ITEMS_PER_PAGE),
+        )
+        .map_err(ServiceError::from)?;
+    let total_pages = total.div_ceil(DEFAULT_ITEMS_PER_PAGE);
+
+    Ok(CustomerPageData {
+        customer: CustomerView::new(customer, &price_levels),
+        orders: Paginated::new(orders, page, total_pages),
+    })
+}
+
+/// Creates a new customer for the authenticated user's hub.
+pub fn create_customer<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: AddCustomerForm,
+) -> ServiceResult<Customer>
+where
+    R: CustomerWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let new_customer = form
+        .into_new_customer(user.hub_id)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    repo.create_customer(&new_customer)
+        .map_err(ServiceError::from)
+}
+
+/// Updates the contact details of an existing customer.
+pub fn update_customer<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: EditCustomerForm,
+) -> ServiceResult<Customer>
+where
+    R: CustomerWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let customer_id = form.customer_id;
+    let updates = form
+        .into_update_customer(Utc::now().naive_utc())
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    repo.update_customer(customer_id, user.hub_id, &updates)
+        .map_err(ServiceError::from)
+}
+
+/// Deletes a customer that has no orders.
+///
+/// Returns [`ServiceError::Conflict`] while the customer still has orders.
+pub fn remove_customer<R>(repo: &R, user: &AuthenticatedUser, customer_id: i32) -> ServiceResult<()>
+where
+    R: CustomerWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    repo.delete_customer(customer_id, user.hub_id)
+        .map_err(ServiceError::from)
+}
+
+/// Why the customers of a duplicate group look like the same client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum DuplicateReason {
+    /// Same email address.
+    Email,
+    /// Same phone number once formatting is ignored.
+    Phone,
+    /// Same words in the name, ignoring case, punctuation and word order.
+    Name,
+}
+
+/// Price level one of the customers of a duplicate group is assigned to.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DuplicatePriceLevelOption {
+    /// Assigned level, `None` for customers on the hub default.
+    pub price_level_id: Option<i32>,
+    /// Name of the assigned level, `None` for customers on the hub default.
+    pub name: Option<String>,
+}
+
+/// Customers that likely describe the same client.
+#[derive(Debug, Serialize)]
+pub struct CustomerDuplicateGroup {
+    /// Criteria that matched within the group.
+    pub reasons: Vec<DuplicateReason>,
+    /// Customers of the group ordered by id.
+    pub customers: Vec<CustomerView>,
+    /// Distinct price levels of the group the merge can keep.
+    pub price_levels: Vec<DuplicatePriceLevelOption>,
+}
+
+/// Loads groups of likely duplicate customers within the hub.
+///
+/// Customers are grouped when they share a normalised email, phone or name,
+/// transitively, so a group may combine several reasons.
+pub fn load_customer_duplicates<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+) -> ServiceResult<Vec<CustomerDuplicateGroup>>
+where
+    R: CustomerReader + PriceLevelReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let (_, customers) = repo
+        .list_customers(CustomerListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+    let (_, price_levels) = repo
+        .list_price_levels(PriceLevelListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+
+    let groups = group_duplicate_customers(customers)
+        .into_iter()
+        .map(|(reasons, customers)| {
+            let mut price_levels_seen = Vec::new();
+            for customer in &customers {
+                if !price_levels_seen.contains(&customer.price_level_id) {
+                    price_levels_seen.push(customer.price_level_id);
+                }
+            }
+            let price_level_options = price_levels_seen
+                .into_iter()
+                .map(|price_level_id| DuplicatePriceLevelOption {
+                    price_level_id,
+                    name: price_level_id.and_then(|id| {
+                        price_levels
+                            .iter()
+                            .find(|level| level.id == id)
+                            .map(|level| level.name.clone())
+                    }),
+                })
+                .collect();
+
+            CustomerDuplicateGroup {
+                reasons,
+                customers: customers
+                    .into_iter()
+                    .map(|customer| CustomerView::new(customer, &price_levels))
+                    .collect(),
+                price_levels: price_level_options,
+            }
+        })
+        .collect();
+
+    Ok(groups)
+}
+
+/// Merges duplicate customers into the selected survivor.
+///
+/// The kept price level has to be one already assigned to a merged customer,
+/// so merging cannot grant a level that was never approved.
+pub fn merge_customers<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: MergeCustomersForm,
+) -> ServiceResult<Customer>
+where
+    R: CustomerReader + CustomerWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let merge = form
+        .into_customer_merge(user.hub_id, Utc::now().naive_utc())
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    let mut assigned_levels = Vec::with_capacity(merge.merged_ids.len() + 1);
+    for customer_id in merge.merged_ids.iter().chain([&merge.survivor_id]) {
+        let customer = repo
+            .get_customer_by_id(*customer_id, user.hub_id)
+            .map_err(ServiceError::from)?
+            .ok_or(ServiceError::NotFound)?;
+        assigned_levels.push(customer.price_level_id);
+    }
+    if !assigned_levels.contains(&merge.price_level_id) {
+        return Err(ServiceError::Form(
+            CustomerFormError::PriceLevelNotInMerge.to_string(),
+        ));
+    }
+
+    repo.merge_customers(&merge).map_err(ServiceError::from)
+}
+
+/// Groups customers sharing a normalised email, phone or name.
+fn group_duplicate_customers(
+    customers: Vec<Customer>,
+) -> Vec<(Vec<DuplicateReason>, Vec<Customer>)> {
+    fn find(parents: &mut [usize], index: usize) -> usize {
+        let mut root = index;
+        while parents[root] != root {
+            root = parents[root];
+        }
+        parents[index] = root;
+        root
+    }
+
+    let mut buckets: HashMap<(DuplicateReason, String), Vec<usize>> = HashMap::new();
+    for (index, customer) in customers.iter().enumerate() {
+        let keys = [
+            (DuplicateReason::Email, customer.email.trim().to_lowercase()),
+            (
+                DuplicateReason::Phone,
+                customer.phone.as_deref().map(phone_key).unwrap_or_default(),
+            ),
+            (DuplicateReason::Name, normalize_name(&customer.name)),
+        ];
+        for key in keys.into_iter().filter(|(_, value)| !value.is_empty()) {
+            buckets.entry(key).or_default().push(index);
+        }
+    }
+    let buckets: Vec<(DuplicateReason, Vec<usize>)> = buckets
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|((reason, _), members)| (reason, members))
+        .collect();
+
+    let mut parents: Vec<usize> = (0..customers.len()).collect();
+    for (_, members) in &buckets {
+        for member in &members[1..] {
+            let left = find(&mut parents, members[0]);
+            let right = find(&mut parents, *member);
+            parents[right] = left;
+        }
+    }
+
+    let mut reasons: HashMap<usize, BTreeSet<DuplicateReason>> = HashMap::new();
+    for (reason, members) in &buckets {
+        let root = find(&mut parents, members[0]);
+        reasons.entry(root).or_default().insert(*reason);
+    }
+
+    let mut grouped: HashMap<usize, Vec<Customer>> = HashMap::new();
+    for (index, customer) in customers.into_iter().enumerate() {
+        let root = find(&mut parents, index);
+        if reasons.contains_key(&root) {
+            grouped.entry(root).or_default().push(customer);
+        }
+    }
+
+    let mut groups: Vec<(Vec<DuplicateReason>, Vec<Customer>)> = grouped
+        .into_iter()
+        .map(|(root, mut members)| {
+            members.sort_by_key(|customer| customer.id);
+            let reasons = reasons.remove(&root).unwrap_or_default();
+            (reasons.into_iter().collect(), members)
+        })
+        .collect();
+    groups.sort_by_key(|(_, members)| members[0].id);
+    groups
+}
+
+fn phone_key(phone: &str) -> String {
+    normalize_phone(phone).unwrap_or_else(|| phone.chars().filter(char::is_ascii_digit).collect())
+}
+
+fn normalize_name(name: &str) -> String {
+    let lowercase = name.to_lowercase();
+    let mut words: Vec<&str> = lowercase
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.sort_unstable();
+    words.join(" ")
+}
+
+/// Outcome of a customer CSV import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CustomerImportSummary {
+    /// Rows that created a new customer.
+    pub created: usize,
+    /// Rows that renamed an existing customer or requested a new price level.
+    pub updated: usize,
+    /// Rows that matched an existing customer without any difference.
+    pub unchanged: usize,
+    /// Price level changes filed for approval.
+    pub price_level_requests: usize,
+}
+
+/// What importing an uploaded customer row does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CustomerImportAction {
+    Create,
+    Update,
+    Unchanged,
+}
+
+/// Uploaded customer row as listed in the import preview.
+#[derive(Debug, Serialize)]
+pub struct CustomerPreviewRow {
+    /// Line of the uploaded CSV.
+    pub row: usize,
+    pub action: CustomerImportAction,
+    pub name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    /// Price level requested for the customer, when it differs from the current one.
+    pub requested_price_level: Option<String>,
+}
+
+/// Data required to render the customer import preview.
+#[derive(Debug, Serialize)]
+pub struct CustomerUploadPreview {
+    /// Rows that would be imported, in file order.
+    pub rows: Vec<CustomerPreviewRow>,
+    /// Every row error; the import is rejected while any are present.
+    pub errors: Vec<UploadRowError>,
+    /// Counts of the actions listed in `rows`.
+    pub summary: CustomerImportSummary,
+    /// Uploaded CSV, re-submitted when the operator confirms the import.
+    pub content: String,
+}
+
+/// Upload row with its price level resolved and the action importing it takes.
+struct PlannedCustomer {
+    upload: CustomerUploadRow,
+    action: CustomerImportAction,
+    /// Existing customer matched by email and phone.
+    target: Option<Customer>,
+    /// Price level to request when it differs from the customer's current one.
+    requested_price_level_id: Option<i32>,
+}
+
+/// Imports customers from an uploaded CSV file.
+///
+/// Rows are matched to existing customers by email and phone, and a row
+/// repeating the email and phone of an earlier one is rejected. Matches are
+/// renamed when the name differs and the others are created. A non-empty
+/// `price_level` column must name one of the hub's price levels; when it
+/// differs from the customer's current level a pending assignment request is
+/// filed instead of changing the level directly. Nothing is written when any
+/// row is invalid, and the rows are written in one transaction.
+pub fn import_customers<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    mut form: UploadCustomersForm,
+) -> ServiceResult<CustomerImportSummary>
+where
+    R: CustomerReader + CustomerWriter + PriceLevelReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let report = form
+        .into_upload_report(user.hub_id)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+    let (plan, errors) = plan_customer_import(repo, user.hub_id, report.rows)?;
+    if let Some(err) = report.errors.first().or(errors.first()) {
+        return Err(ServiceError::Form(err.to_string()));
+    }
+
+    let mut summary = CustomerImportSummary::default();
+    let mut items = Vec::with_capacity(plan.len());
+    for planned in plan {
+        let target = match (planned.action, planned.target) {
+            (CustomerImportAction::Unchanged, Some(customer)) => {
+                summary.unchanged += 1;
+                CustomerImportTarget::Existing(customer.id)
+            }
+            (CustomerImportAction::Update, Some(customer)) => {
+                summary.updated += 1;
+                if customer.name == planned.upload.customer.name {
+                    CustomerImportTarget::Existing(customer.id)
+                } else {
+                    let mut updates = UpdateCustomer::new(
+                        planned.upload.customer.name,
+                        &customer.email,
+                        Utc::now().naive_utc(),
+                    );
+                    if let Some(phone) = customer.phone.as_ref() {
+                        updates = updates.with_phone(phone.clone());
+                    }
+                    CustomerImportTarget::Update {
+                        customer_id: customer.id,
+                        updates,
+                    }
+                }
+            }
+            _ => {
+                summary.created += 1;
+                CustomerImportTarget::Create(planned.upload.customer)
+            }
+        };
+
+        let price_level_request = planned.requested_price_level_id.map(|price_level_id| {
+            summary.price_level_requests += 1;
+            NewPriceLevelAssignmentRequest::new(
+                user.hub_id,
+                0,
+                Some(price_level_id),
+                &user.email,
+                &user.name,
+            )
+        });
+        items.push(CustomerImportItem {
+            target,
+            price_level_request,
+        });
+    }
+
+    repo.bulk_import_customers(user.hub_id, &items)
+        .map_err(ServiceError::from)?;
+
+    Ok(summary)
+}
+
+/// Parses an uploaded customer CSV and reports what [`import_customers`]
+/// would do with every row, together with all row errors, without writing
+/// anything.
+pub fn preview_customers<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    mut form: UploadCustomersForm,
+) -> ServiceResult<CustomerUploadPreview>
+where
+    R: CustomerReader + PriceLevelReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let content = form
+        .content()
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+    let report = form
+        .into_upload_report(user.hub_id)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+    let price_level_names = price_level_names(repo, user.hub_id)?;
+    let (plan, plan_errors) = plan_customer_import(repo, user.hub_id, report.rows)?;
+
+    let mut row_errors = report.errors;
+    row_errors.extend(plan_errors);
+    row_errors.sort_by_key(UploadCustomersFormError::row);
+
+    let mut summary = CustomerImportSummary::default();
+    let rows = plan
+        .into_iter()
+        .map(|planned| {
+            match planned.action {
+                CustomerImportAction::Create => summary.created += 1,
+                CustomerImportAction::Update => summary.updated += 1,
+                CustomerImportAction::Unchanged => summary.unchanged += 1,
+            }
+            if planned.requested_price_level_id.is_some() {
+                summary.price_level_requests += 1;
+            }
+            CustomerPreviewRow {
+                row: planned.upload.row,
+                action: planned.action,
+                name: planned.upload.customer.name,
+                email: planned.upload.customer.email,
+                phone: planned.upload.customer.phone,
+                requested_price_level: planned
+                    .requested_price_level_id
+                    .and_then(|id| price_level_names.get(&id).cloned()),
+            }
+        })
+        .collect();
+
+    Ok(CustomerUploadPreview {
+        rows,
+        errors: row_errors
+            .iter()
+            .map(|err| UploadRowError::new(err.row(), err.to_string()))
+            .collect(),
+        summary,
+        content,
+    })
+}
+
+/// Returns the row errors of an uploaded customer CSV as a CSV report.
+pub fn customer_upload_errors<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: UploadCustomersForm,
+) -> ServiceResult<Vec<u8>>
+where
+    R: CustomerReader + PriceLevelReader + ?Sized,
+{
+    let preview = preview_customers(repo, user, form)?;
+    write_upload_errors_csv(&preview.errors)
+}
+
+/// Exports the hub's customers as CSV in the format read by
+/// [`import_customers`]. The `price_level` column holds the name of the
+/// assigned level and stays empty for customers on the hub default.
+pub fn export_customers<R>(repo: &R, user: &AuthenticatedUser) -> ServiceResult<Vec<u8>>
+where
+    R: CustomerReader + PriceLevelReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let price_level_names = price_level_names(repo, user.hub_id)?;
+    let (_, customers) = repo
+        .list_customers(CustomerListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let write_error = |err: csv::Error| {
+        log::error!("Failed to write customers CSV: {err}");
+        ServiceError::Internal
+    };
+    writer
+        .write_record(CUSTOMER_CSV_HEADERS)
+        .map_err(write_error)?;
+
+    for customer in &customers {
+        let price_level = customer
+            .price_level_id
+            .and_then(|id| price_level_names.get(&id))
+            .map(String::as_str)
+            .unwrap_or_default();
+        writer
+            .write_record([
+                customer.name.as_str(),
+                customer.email.as_str(),
+                customer.phone.as_deref().unwrap_or_default(),
+                price_level,
+            ])
+            .map_err(write_error)?;
+    }
+
+    writer.into_inner().map_err(|err| {
+        log::error!("Failed to flush customers CSV: {err}");
+        ServiceError::Internal
+    })
+}
+
+fn price_level_names<R>(repo: &R, hub_id: i32) -> ServiceResult<HashMap<i32, String>>
+where
+    R: PriceLevelReader + ?Sized,
+{
+    let (_, price_levels) = repo
+        .list_price_levels(PriceLevelListQuery::new(hub_id))
+        .map_err(ServiceError::from)?;
+
+    Ok(price_levels
+        .into_iter()
+        .map(|level| (level.id, level.name))
+        .collect())
+}
+
+fn plan_customer_import<R>(
+    repo: &R,
+    hub_id: i32,
+    rows: Vec<CustomerUploadRow>,
+) -> ServiceResult<(Vec<PlannedCustomer>, Vec<UploadCustomersFormError>)>
+where
+    R: CustomerReader + PriceLevelReader + ?Sized,
+{
+    let level_ids: HashMap<String, i32> = price_level_names(repo, hub_id)?
+        .into_iter()
+        .map(|(id, name)| (name.to_lowercase(), id))
+        .collect();
+
+    let mut plan = Vec::with_capacity(rows.len());
+    let mut errors = Vec::new();
+    let mut first_rows: HashMap<(String, Option<String>), usize> = HashMap::new();
+    for upload in rows {
+        let key = (upload.customer.email.clone(), upload.customer.phone.clone());
+        if let Some(first_row) = first_rows.get(&key) {
+            errors.push(UploadCustomersFormError::DuplicateCustomer {
+                row: upload.row,
+                email: upload.customer.email,
+                first_row: *first_row,
+            });
+            continue;
+        }
+        first_rows.insert(key, upload.row);
+
+        let price_level_id = match upload.price_level.as_deref() {
+            Some(name) => match level_ids.get(&name.to_lowercase()) {
+                Some(id) => Some(*id),
+                None => {
+                    errors.push(UploadCustomersFormError::UnknownPriceLevel {
+                        row: upload.row,
+                        name: name.to_string(),
+                    });
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        let target = repo
+            .get_customer_by_email_and_phone(
+                &upload.customer.email,
+                upload.customer.phone.as_deref(),
+                hub_id,
+            )
+            .map_err(ServiceError::from)?;
+
+        let current_price_level_id = target.as_ref().and_then(|customer| customer.price_level_id);
+        let requested_price_level_id =
+            price_level_id.filter(|id| Some(*id) != current_price_level_id);
+
+        let action = match target.as_ref() {
+            None => CustomerImportAction::Create,
+            Some(customer)
+                if customer.name == upload.customer.name && requested_price_level_id.is_none() =>
+            {
+                CustomerImportAction::Unchanged
+            }
+            Some(_) => CustomerImportAction::Update,
+        };
+
+        plan.push(PlannedCustomer {
+            upload,
+            action,
+            target,
+            requested_price_level_id,
+        });
+    }
+
+    Ok((plan, errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_multipart::form::tempfile::TempFile;
+    use chrono::NaiveDateTime;
+    use std::io::{Seek, SeekFrom, Write};
+    use tempfile::NamedTempFile;
+
+    use crate::repository::mock::{MockCustomerReader, MockCustomerWriter, MockPriceLevelReader};
+    use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
+
+    struct FakeRepo {
+        customer_reader: MockCustomerReader,
+        price_level_reader: MockPriceLevelReader,
+    }
+
+    impl CustomerReader for FakeRepo {
+        fn get_customer_by_id(&self, id: i32, hub_id: i32) -> RepositoryResult<Option<Customer>> {
+            self.customer_reader.get_customer_by_id(id, hub_id)
+        }
+
+        fn get_customer_by_email(
+            &self,
+            email: &str,
+            hub_id: i32,
+        ) -> RepositoryResult<Option<Customer>> {
+            self.customer_reader.get_customer_by_email(email, hub_id)
+        }
+
+        fn get_customer_by_email_and_phone(
+            &self,
+            email: &str,
+            phone: Option<&str>,
+            hub_id: i32,
+        ) -> RepositoryResult<Option<Customer>> {
+            self.customer_reader
+                .get_customer_by_email_and_phone(email, phone, hub_id)
+        }
+
+        fn list_customers(
+            &self,
+            query: CustomerListQuery,
+        ) -> RepositoryResult<(usize, Vec<Customer>)> {
+            self.customer_reader.list_customers(query)
+        }
+    }
+
+    impl PriceLevelReader for FakeRepo {
+        fn get_price_level_by_id(
+            &self,
+            id: i32,
+            hub_id: i32,
+        ) -> RepositoryResult<Option<PriceLevel>> {
+            self.price_level_reader.get_price_level_by_id(id, hub_id)
+        }
+
+        fn list_price_levels(
+            &self,
+            query: PriceLevelListQuery,
+        ) -> RepositoryResult<(usize, Vec<PriceLevel>)> {
+            self.price_level_reader.list_price_levels(query)
+        }
+    }
+
+    fn user_with_roles(roles: &[&str]) -> AuthenticatedUser {
+        AuthenticatedUser {
+            sub: "user-1".to_string(),
+            email: "user@example.com".to_string(),
+            hub_id: 5,
+            name: "Tester".to_string(),
+            roles: roles.iter().map(|role| (*role).to_string()).collect(),
+            exp: 0,
+        }
+    }
+
+    fn sample_customer(id: i32, price_level_id: Option<i32>) -> Customer {
+        Customer {
+            id,
+            hub_id: 5,
+            name: format!("Customer {id}"),
+            email: format!("customer{id}@example.com"),
+            phone: None,
+            price_level_id,
+        }
+    }
+
+    fn sample_level(id: i32, name: &str, is_default: bool) -> PriceLevel {
+        PriceLevel {
+            id,
+            hub_id: 5,
+            name: name.to_string(),
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+            is_default,
+            derivation: None,
+            prices_include_tax: true,
+        }
+    }
+
+    #[test]
+    fn load_customers_resolves_effective_price_levels() {
+        let mut repo = FakeRepo {
+            customer_reader: MockCustomerReader::new(),
+            price_level_reader: MockPriceLevelReader::new(),
+        };
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.customer_reader
+            .expect_list_customers()
+            .times(1)
+            .withf(|query| query.hub_id == 5 && query.search.as_deref() == Some("acme"))
+            .returning(|_| {
+                Ok((
+                    2,
+                    vec![sample_customer(1, Some(2)), sample_customer(2, None)],
+                ))
+            });
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .times(1)
+            .returning(|_| {
+                Ok((
+                    2,
+                    vec![
+                        sample_level(1, "Retail", true),
+                        sample_level(2, "Wholesale", false),
+                    ],
+                ))
+            });
+
+        let data = load_customers(
+            &repo,
+            &user,
+            CustomersQuery {
+                search: Some("acme".to_string()),
+                page: None,
+            },
+        )
+        .expect("expected customers page");
+
+        let serialized =
+            serde_json::to_value(&data.customers).expect("serialization should succeed");
+        let items = serialized["items"].as_array().expect("expected items");
+        assert_eq!(items[0]["price_level_name"], "Wholesale");
+        assert_eq!(items[0]["uses_default_price_level"], false);
+        assert_eq!(items[1]["price_level_name"], "Retail");
+        assert_eq!(items[1]["uses_default_price_level"], true);
+    }
+
+    #[test]
+    fn remove_customer_reports_conflict_while_orders_exist() {
+        let mut repo = MockCustomerWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.expect_delete_customer()
+            .times(1)
+            .withf(|customer_id, hub_id| *customer_id == 3 && *hub_id == 5)
+            .returning(|_, _| {
+                Err(RepositoryError::ConstraintViolation(
+                    "customer has orders".to_string(),
+                ))
+            });
+
+        let result = remove_customer(&repo, &user, 3);
+
+        assert!(matches!(result, Err(ServiceError::Conflict)));
+    }
+
+    #[test]
+    fn create_customer_requires_role() {
+        let repo = MockCustomerWriter::new();
+        let user = user_with_roles(&[]);
+        let form = AddCustomerForm {
+            name: "Acme".to_string(),
+            email: "acme@example.com".to_string(),
+            phone: None,
+        };
+
+        let result = create_customer(&repo, &user, form);
+
+        assert!(matches!(result, Err(ServiceError::Unauthorized)));
+    }
+
+    #[test]
+    fn preview_customers_plans_rows_and_reports_unknown_levels() {
+        let mut repo = FakeRepo {
+            customer_reader: MockCustomerReader::new(),
+            price_level_reader: MockPriceLevelReader::new(),
+        };
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| {
+                Ok((
+                    2,
+                    vec![
+                        sample_level(1, "Retail", true),
+                        sample_level(2, "Wholesale", false),
+                    ],
+                ))
+            });
+        repo.customer_reader
+            .expect_get_customer_by_email_and_phone()
+            .returning(|email, _, _| {
+                Ok(match email {
+                    "customer1@example.com" => Some(sample_customer(1, Some(2))),
+                    "customer2@example.com" => Some(sample_customer(2, None)),
+                    _ => None,
+                })
+            });
+
+        let form = build_upload_form(
+            "name,email,phone,price_level\n\
+             Customer 1,customer1@example.com,,wholesale\n\
+             Customer 2,customer2@example.com,,Wholesale\n\
+             New,new@example.com,,\n\
+             Other,other@example.com,,Gold\n\
+             Customer 1 again,Customer1@example.com,,\n",
+        );
+
+        let preview = preview_customers(&repo, &user, form).expect("expected preview");
+
+        let actions: Vec<CustomerImportAction> =
+            preview.rows.iter().map(|row| row.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                CustomerImportAction::Unchanged,
+                CustomerImportAction::Update,
+                CustomerImportAction::Create,
+            ]
+        );
+        assert_eq!(
+            preview.rows[1].requested_price_level.as_deref(),
+            Some("Wholesale")
+        );
+        assert_eq!(preview.summary.price_level_requests, 1);
+        let errors: Vec<(Option<usize>, &str)> = preview
+            .errors
+            .iter()
+            .map(|error| (error.row, error.message.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (Some(5), "row 5: price level `Gold` does not exist"),
+                (
+                    Some(6),
+                    "row 6 repeats customer `customer1@example.com` from row 2"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn export_customers_writes_assigned_level_names() {
+        let mut repo = FakeRepo {
+            customer_reader: MockCustomerReader::new(),
+            price_level_reader: MockPriceLevelReader::new(),
+        };
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![sample_level(2, "Wholesale", false)])));
+        repo.customer_reader.expect_list_customers().returning(|_| {
+            Ok((
+                2,
+                vec![sample_customer(1, Some(2)), sample_customer(2, None)],
+            ))
+        });
+
+        let bytes = export_customers(&repo, &user).expect("expected export");
+
+        assert_eq!(
+            String::from_utf8(bytes).expect("expected utf-8"),
+            "name,email,phone,price_level\n\
+             Customer 1,customer1@example.com,,Wholesale\n\
+             Customer 2,customer2@example.com,,\n"
+        );
+    }
+
+    #[test]
+    fn load_customer_duplicates_groups_transitively() {
+        let mut repo = FakeRepo {
+            customer_reader: MockCustomerReader::new(),
+            price_level_reader: MockPriceLevelReader::new(),
+        };
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.customer_reader.expect_list_customers().returning(|_| {
+            let mut first = sample_customer(1, Some(2));
+            first.email = "shop@example.com".to_string();
+            first.phone = Some("+7 (900) 111-22-33".to_string());
+            let mut second = sample_customer(2, None);
+            second.email = "shop@example.com".to_string();
+            let mut third = sample_customer(3, None);
+            third.phone = Some("79001112233".to_string());
+            let mut fourth = sample_customer(4, None);
+            fourth.name = "Acme, Ltd".to_string();
+            let mut fifth = sample_customer(5, None);
+            fifth.name = "ltd ACME".to_string();
+            Ok((
+                6,
+                vec![
+                    first,
+                    second,
+                    third,
+                    fourth,
+                    fifth,
+                    sample_customer(6, None),
+                ],
+            ))
+        });
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![sample_level(2, "Wholesale", false)])));
+
+        let groups = load_customer_duplicates(&repo, &user).expect("expected duplicates");
+
+        assert_eq!(groups.len(), 2);
+        let ids: Vec<i32> = groups[0]
+            .customers
+            .iter()
+            .map(|view| view.customer.id)
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(
+            groups[0].reasons,
+            vec![DuplicateReason::Email, DuplicateReason::Phone]
+        );
+        assert_eq!(
+            groups[0].price_levels,
+            vec![
+                DuplicatePriceLevelOption {
+                    price_level_id: Some(2),
+                    name: Some("Wholesale".to_string()),
+                },
+                DuplicatePriceLevelOption {
+                    price_level_id: None,
+                    name: None,
+                },
+            ]
+        );
+        let ids: Vec<i32> = groups[1]
+            .customers
+            .iter()
+            .map(|view| view.customer.id)
+            .collect();
+        assert_eq!(ids, vec![4, 5]);
+        assert_eq!(groups[1].reasons, vec![DuplicateReason::Name]);
+    }
+
+    #[test]
+    fn merge_customers_rejects_price_level_outside_the_group() {
+        struct MergeRepo {
+            reader: MockCustomerReader,
+            writer: MockCustomerWriter,
+        }
+
+        impl CustomerReader for MergeRepo {
+            fn get_customer_by_id(
+                &self,
+                id: i32,
+                hub_id: i32,
+            ) -> RepositoryResult<Option<Customer>> {
+                self.reader.get_customer_by_id(id, hub_id)
+            }
+
+            fn get_customer_by_email(
+                &self,
+                email: &str,
+                hub_id: i32,
+            ) -> RepositoryResult<Option<Customer>> {
+                self.reader.get_customer_by_email(email, hub_id)
+            }
+
+            fn get_customer_by_email_and_phone(
+                &self,
+                email: &str,
+                phone: Option<&str>,
+                hub_id: i32,
+            ) -> RepositoryResult<Option<Customer>> {
+                self.reader
+                    .get_customer_by_email_and_phone(email, phone, hub_id)
+            }
+
+            fn list_customers(
+                &self,
+                query: CustomerListQuery,
+            ) -> RepositoryResult<(usize, Vec<Customer>)> {
+                self.reader.list_customers(query)
+            }
+        }
+
+        impl CustomerWriter for MergeRepo {
+            fn create_customer(
+                &self,
+                new_customer: &crate::domain::customer::NewCustomer,
+            ) -> RepositoryResult<Customer> {
+                self.writer.create_customer(new_customer)
+            }
+
+            fn assign_price_level_to_customers(
+                &self,
+                hub_id: i32,
+                customer_ids: &[i32],
+                price_level_id: Option<i32>,
+            ) -> RepositoryResult<()> {
+                self.writer
+                    .assign_price_level_to_customers(hub_id, customer_ids, price_level_id)
+            }
+
+            fn update_customer(
+                &self,
+                customer_id: i32,
+                hub_id: i32,
+                updates: &UpdateCustomer,
+            ) -> RepositoryResult<Customer> {
+                self.writer.update_customer(customer_id, hub_id, updates)
+            }
+
+            fn delete_customer(&self, customer_id: i32, hub_id: i32) -> RepositoryResult<()> {
+                self.writer.delete_customer(customer_id, hub_id)
+            }
+
+            fn merge_customers(
+                &self,
+                merge: &crate::domain::customer::CustomerMerge,
+            ) -> RepositoryResult<Customer> {
+                self.writer.merge_customers(merge)
+            }
+
+            fn bulk_import_customers(
+                &self,
+                hub_id: i32,
+                items: &[crate::domain::customer::CustomerImportItem],
+            ) -> RepositoryResult<()> {
+                self.writer.bulk_import_customers(hub_id, items)
+            }
+        }
+
+        let mut repo = MergeRepo {
+            reader: MockCustomerReader::new(),
+            writer: MockCustomerWriter::new(),
+        };
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.reader
+            .expect_get_customer_by_id()
+            .returning(|id, _| Ok(Some(sample_customer(id, Some(id)))));
+        repo.writer
+            .expect_merge_customers()
+            .times(1)
+            .withf(|merge| merge.survivor_id == 1 && merge.merged_ids == vec![2])
+            .returning(|merge| Ok(sample_customer(merge.survivor_id, merge.price_level_id)));
+
+        let rejected = merge_customers(
+            &repo,
+            &user,
+            MergeCustomersForm {
+                survivor_id: 1,
+                customer_ids: vec![1, 2],
+                price_level_id: Some("7".to_string()),
+            },
+        );
+        assert!(matches!(rejected, Err(ServiceError::Form(_))));
+
+        let merged = merge_customers(
+            &repo,
+            &user,
+            MergeCustomersForm {
+                survivor_id: 1,
+                customer_ids: vec![1, 2],
+                price_level_id: Some("2".to_string()),
+            },
+        )
+        .expect("expected merge");
+        assert_eq!(merged.price_level_id, Some(2));
+    }
+
+    fn build_upload_form(csv: &str) -> UploadCustomersForm {
+        let mut file = NamedTempFile::new().expect("create temp file");
+        file.write_all(csv.as_bytes()).expect("write csv file");
+        file.as_file_mut()
+            .seek(SeekFrom::Start(0))
+            .expect("seek to start");
+
+        UploadCustomersForm {
+            csv: TempFile {
+                file,
+                content_type: None,
+                file_name: Some("customers.csv".to_string()),
+                size: csv.len(),
+            },
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use chrono::NaiveDateTime;
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::routes::check_role;
+use serde::Serialize;
+
+use crate::SERVICE_ACCESS_ROLE;
+use crate::domain::exchange_rate::{NewExchangeRate, format_rate};
+use crate::forms::exchange_rates::{AddExchangeRateForm, UploadExchangeRatesForm};
+use crate::repository::{ExchangeRateReader, ExchangeRateWriter};
+use crate::services::{ServiceError, ServiceResult};
+
+/// Exchange rate row rendered on the rates page.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct ExchangeRateView {
+    pub id: i32,
+    pub base_currency: String,
+    pub quote_currency: String,
+    /// Rate as a decimal, e.g. `92.5`.
+    pub rate: String,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Lists the exchange rates of the authenticated user's hub.
+pub fn load_exchange_rates<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+) -> ServiceResult<Vec<ExchangeRateView>>
+where
+    R: ExchangeRateReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let rates = repo
+        .list_exchange_rates(user.hub_id)
+        .map_err(ServiceError::from)?;
+
+    Ok(rates
+        .into_iter()
+        .map(|rate| ExchangeRateView {
+            id: rate.id,
+            rate: format_rate(rate.rate_micros),
+            base_currency: rate.base_currency,
+            quote_currency: rate.quote_currency,
+            updated_at: rate.updated_at,
+        })
+        .collect())
+}
+
+/// Stores a rate entered by hand, replacing the current rate of the pair.
+pub fn save_exchange_rate<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: AddExchangeRateForm,
+) -> ServiceResult<NewExchangeRate>
+where
+    R: ExchangeRateWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let rate = form
+        .into_new_exchange_rate(user.hub_id)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    repo.save_exchange_rates(user.hub_id, std::slice::from_ref(&rate))
+        .map_err(ServiceError::from)?;
+
+    Ok(rate)
+}
+
+/// Stores every rate of an uploaded CSV in one transaction.
+///
+/// The whole file is rejected with [`ServiceError::Form`] when any row is invalid.
+pub fn import_exchange_rates<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    mut form: UploadExchangeRatesForm,
+) -> ServiceResult<usize>
+where
+    R: ExchangeRateWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let rates = form
+        .into_new_exchange_rates(user.hub_id)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    if rates.is_empty() {
+        return Ok(0);
+    }
+
+    repo.save_exchange_rates(user.hub_id, &rates)
+        .map_err(ServiceError::from)
+}
+
+/// Deletes an exchange rate of the authenticated user's hub.
+pub fn remove_exchange_rate<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    exchange_rate_id: i32,
+) -> ServiceResult<()>
+where
+    R: ExchangeRateWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    repo.delete_exchange_rate(exchange_rate_id, user.hub_id)
+        .map_err(ServiceError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pushkind_common::repository::errors::RepositoryError;
+
+    use crate::domain::exchange_rate::ExchangeRate;
+    use crate::repository::mock::{MockExchangeRateReader, MockExchangeRateWriter};
+
+    fn user_with_roles(roles: &[&str]) -> AuthenticatedUser {
+        AuthenticatedUser {
+            sub: "user-1".to_string(),
+            email: "user@example.com".to_string(),
+            hub_id: 7,
+            name: "Tester".to_string(),
+            roles: roles.iter().map(|role| (*role).to_string()).collect(),
+            exp: 0,
+        }
+    }
+
+    #[test]
+    fn load_exchange_rates_formats_rates() {
+        let mut repo = MockExchangeRateReader::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.expect_list_exchange_rates()
+            .withf(|hub_id| *hub_id == 7)
+            .return_once(|hub_id| {
+                Ok(vec![ExchangeRate {
+                    id: 1,
+                    hub_id,
+                    base_currency: "USD".to_string(),
+                    quote_currency: "RUB".to_string(),
+                    rate_micros: 92_500_000,
+                    created_at: NaiveDateTime::default(),
+                    updated_at: NaiveDateTime::default(),
+                }])
+            });
+
+        let rates = load_exchange_rates(&repo, &user).expect("expected rates");
+
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].rate, "92.5");
+    }
+
+    #[test]
+    fn save_exchange_rate_requires_role() {
+        let repo = MockExchangeRateWriter::new();
+        let user = user_with_roles(&[]);
+        let form = AddExchangeRateForm {
+            base_currency: "USD".to_string(),
+            quote_currency: "RUB".to_string(),
+            rate: "92.5".to_string(),
+        };
+
+        let result = save_exchange_rate(&repo, &user, form);
+
+        assert!(matches!(result, Err(ServiceError::Unauthorized)));
+    }
+
+    #[test]
+    fn save_exchange_rate_stores_pair_for_hub() {
+        let mut repo = MockExchangeRateWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+        let form = AddExchangeRateForm {
+            base_currency: "eur".to_string(),
+            quote_currency: "rub".to_string(),
+            rate: "100".to_string(),
+        };
+
+        repo.expect_save_exchange_rates()
+            .withf(|hub_id, rates| {
+                *hub_id == 7 && rates == [NewExchangeRate::new(7, "EUR", "RUB", 100_000_000)]
+            })
+            .return_once(|_, rates| Ok(rates.len()));
+
+        let rate = save_exchange_rate(&repo, &user, form).expect("expected success");
+
+        assert_eq!(rate.base_currency, "EUR");
+    }
+
+    #[test]
+    fn remove_exchange_rate_bubbles_not_found() {
+        let mut repo = MockExchangeRateWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.expect_delete_exchange_rate()
+            .withf(|id, hub_id| *id == 3 && *hub_id == 7)
+            .return_once(|_, _| Err(RepositoryError::NotFound));
+
+        let result = remove_exchange_rate(&repo, &user, 3);
+
+        assert!(matches!(result, Err(ServiceError::NotFound)));
+    }
+}
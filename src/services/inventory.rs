@@ -0,0 +1,141 @@
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::routes::check_role;
+
+use crate::SERVICE_ACCESS_ROLE;
+use crate::domain::inventory_movement::InventoryMovement;
+use crate::forms::inventory::AdjustStockForm;
+use crate::repository::{InventoryReader, InventoryWriter};
+use crate::services::{ServiceError, ServiceResult};
+
+/// Lists the stock movements of a product of the authenticated user's hub,
+/// newest first.
+pub fn load_inventory_movements<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    product_id: i32,
+) -> ServiceResult<Vec<InventoryMovement>>
+where
+    R: InventoryReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    repo.list_inventory_movements(product_id, user.hub_id)
+        .map_err(ServiceError::from)
+}
+
+/// Changes the quantity on hand of a product and records who did it.
+///
+/// Adjustments that would leave a negative quantity on hand fail with
+/// [`ServiceError::Conflict`].
+pub fn adjust_product_stock<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: AdjustStockForm,
+) -> ServiceResult<InventoryMovement>
+where
+    R: InventoryWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let adjustment = form
+        .into_new_stock_adjustment(user.hub_id, &user.email, &user.name)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    repo.adjust_stock(&adjustment).map_err(ServiceError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use pushkind_common::repository::errors::RepositoryError;
+
+    use crate::domain::inventory_movement::{InventoryMovementKind, StockLevel};
+    use crate::repository::mock::{MockInventoryReader, MockInventoryWriter};
+
+    fn user_with_roles(roles: &[&str]) -> AuthenticatedUser {
+        AuthenticatedUser {
+            sub: "user-1".to_string(),
+            email: "user@example.com".to_string(),
+            hub_id: 7,
+            name: "Tester".to_string(),
+            roles: roles.iter().map(|role| (*role).to_string()).collect(),
+            exp: 0,
+        }
+    }
+
+    fn form(quantity: &str) -> AdjustStockForm {
+        AdjustStockForm {
+            product_id: 3,
+            quantity: quantity.to_string(),
+            note: Some("Delivery".to_string()),
+        }
+    }
+
+    #[test]
+    fn adjust_product_stock_records_author() {
+        let mut repo = MockInventoryWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.expect_adjust_stock()
+            .withf(|adjustment| {
+                adjustment.hub_id == 7
+                    && adjustment.product_id == 3
+                    && adjustment.quantity == 12
+                    && adjustment.changed_by_email == "user@example.com"
+                    && adjustment.changed_by_name == "Tester"
+            })
+            .return_once(|adjustment| {
+                Ok(InventoryMovement {
+                    id: 1,
+                    product_id: adjustment.product_id,
+                    order_id: None,
+                    kind: InventoryMovementKind::Adjustment,
+                    quantity: adjustment.quantity,
+                    stock: StockLevel {
+                        on_hand: 12,
+                        reserved: 0,
+                    },
+                    note: adjustment.note.clone(),
+                    changed_by_email: adjustment.changed_by_email.clone(),
+                    changed_by_name: adjustment.changed_by_name.clone(),
+                    created_at: NaiveDateTime::default(),
+                })
+            });
+
+        let movement = adjust_product_stock(&repo, &user, form("12")).expect("expected success");
+
+        assert_eq!(movement.stock.available(), 12);
+    }
+
+    #[test]
+    fn adjust_product_stock_rejects_negative_stock_as_conflict() {
+        let mut repo = MockInventoryWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.expect_adjust_stock().return_once(|_| {
+            Err(RepositoryError::ConstraintViolation(
+                "product 3 has only 2 units on hand".to_string(),
+            ))
+        });
+
+        let result = adjust_product_stock(&repo, &user, form("-5"));
+
+        assert!(matches!(result, Err(ServiceError::Conflict)));
+    }
+
+    #[test]
+    fn load_inventory_movements_requires_role() {
+        let mut repo = MockInventoryReader::new();
+        let user = user_with_roles(&[]);
+        repo.expect_list_inventory_movements().never();
+
+        let result = load_inventory_movements(&repo, &user, 3);
+
+        assert!(matches!(result, Err(ServiceError::Unauthorized)));
+    }
+}
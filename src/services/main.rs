@@ -1,20 +1,95 @@
+use chrono::{Days, NaiveDate, NaiveTime};
 use pushkind_common::domain::auth::AuthenticatedUser;
 use pushkind_common::pagination::{DEFAULT_ITEMS_PER_PAGE, Paginated};
 use pushkind_common::routes::check_role;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::SERVICE_ACCESS_ROLE;
-use crate::domain::order::{Order, OrderListQuery};
+use crate::domain::money::Money;
+use crate::domain::order::{Order, OrderListQuery, OrderStatus};
 use crate::repository::OrderReader;
 use crate::services::{ServiceError, ServiceResult};
 
 /// Query parameters accepted by the index page service.
-#[derive(Debug, Default, Deserialize)]
+///
+/// `status` may be repeated to match several statuses. Dates are inclusive
+/// calendar days and totals are decimal amounts in `currency`; they only
+/// match orders in that currency.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct IndexQuery {
     /// Optional search string entered by the user.
     pub search: Option<String>,
     /// Page number requested by the user interface.
     pub page: Option<usize>,
+    /// Statuses to include; empty matches every status.
+    #[serde(default)]
+    pub status: Vec<OrderStatus>,
+    /// Optional customer the orders belong to.
+    pub customer_id: Option<i32>,
+    /// First day of the creation date range.
+    pub created_from: Option<NaiveDate>,
+    /// Last day of the creation date range.
+    pub created_to: Option<NaiveDate>,
+    /// ISO 4217 currency code of the order totals.
+    pub currency: Option<String>,
+    /// Minimum order total, e.g. `10.50`.
+    pub min_total: Option<String>,
+    /// Maximum order total, e.g. `99.99`.
+    pub max_total: Option<String>,
+}
+
+impl IndexQuery {
+    /// Returns `true` when any filter besides search and paging is set.
+    pub fn has_filters(&self) -> bool {
+        !self.status.is_empty()
+            || self.customer_id.is_some()
+            || self.created_from.is_some()
+            || self.created_to.is_some()
+            || self.currency.is_some()
+            || self.min_total.is_some()
+            || self.max_total.is_some()
+    }
+
+    /// Builds the unpaginated [`OrderListQuery`] matching the search and filters.
+    ///
+    /// Fails with [`ServiceError::Form`] when a total is not a valid amount or
+    /// is given without a currency.
+    pub fn to_list_query(&self, hub_id: i32) -> ServiceResult<OrderListQuery> {
+        let mut list_query = OrderListQuery::new(hub_id).statuses(self.status.iter().copied());
+
+        if let Some(value) = self.search.as_ref() {
+            list_query = list_query.search(value);
+        }
+        if let Some(customer_id) = self.customer_id {
+            list_query = list_query.customer_id(customer_id);
+        }
+        if let Some(from) = self.created_from {
+            list_query = list_query.created_from(from.and_time(NaiveTime::MIN));
+        }
+        if let Some(next_day) = self
+            .created_to
+            .and_then(|to| to.checked_add_days(Days::new(1)))
+        {
+            list_query = list_query.created_before(next_day.and_time(NaiveTime::MIN));
+        }
+        if let Some(min_total) = self.min_total.as_deref() {
+            list_query = list_query.min_total(self.parse_total(min_total)?);
+        }
+        if let Some(max_total) = self.max_total.as_deref() {
+            list_query = list_query.max_total(self.parse_total(max_total)?);
+        }
+
+        Ok(list_query)
+    }
+
+    /// Parses a total filter in the selected currency.
+    fn parse_total(&self, value: &str) -> ServiceResult<Money> {
+        let currency = self
+            .currency
+            .as_deref()
+            .ok_or_else(|| ServiceError::Form("order totals need a currency".to_string()))?;
+        Money::parse(value, currency).map_err(|err| ServiceError::Form(err.to_string()))
+    }
 }
 
 /// Data required to render the main index template.
@@ -23,6 +98,8 @@ pub struct IndexPageData {
     pub orders: Paginated<Order>,
     /// Search query echoed back to the template when present.
     pub search: Option<String>,
+    /// Filters echoed back to the template.
+    pub filters: IndexQuery,
 }
 
 /// Loads the orders list for the main index page.
@@ -39,11 +116,9 @@ where
     }
 
     let page = query.page.unwrap_or(1);
-    let mut list_query = OrderListQuery::new(user.hub_id).paginate(page, DEFAULT_ITEMS_PER_PAGE);
-
-    if let Some(value) = query.search.as_ref() {
-        list_query = list_query.search(value);
-    }
+    let list_query = query
+        .to_list_query(user.hub_id)?
+        .paginate(page, DEFAULT_ITEMS_PER_PAGE);
 
     let (total, orders) = repo.list_orders(list_query).map_err(ServiceError::from)?;
 
@@ -52,7 +127,8 @@ where
 
     Ok(IndexPageData {
         orders,
-        search: query.search,
+        search: query.search.clone(),
+        filters: query,
     })
 }
 
@@ -81,9 +157,12 @@ mod tests {
             reference: Some(reference.to_string()),
             status: OrderStatus::Pending,
             notes: None,
-            total_cents: 1000,
+            total: Money::new(1000, "RUB"),
+            net: Money::new(1000, "RUB"),
+            tax: Money::zero("RUB"),
             currency: "RUB".to_string(),
             products: Vec::new(),
+            discount: None,
             created_at: fixed_datetime(),
             updated_at: fixed_datetime(),
         }
@@ -117,6 +196,7 @@ mod tests {
         let query = IndexQuery {
             search: Some("alp".to_string()),
             page: Some(2),
+            ..IndexQuery::default()
         };
 
         let expected_hub = user.hub_id;
@@ -181,4 +261,54 @@ mod tests {
             .and_then(Value::as_str);
         assert_eq!(first_reference, Some("alpha-ref"));
     }
+
+    #[test]
+    fn load_index_page_maps_filters_to_list_query() {
+        let mut repo = MockOrderReader::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+        let query = IndexQuery {
+            status: vec![OrderStatus::Pending, OrderStatus::Processing],
+            customer_id: Some(5),
+            created_from: NaiveDate::from_ymd_opt(2024, 1, 1),
+            created_to: NaiveDate::from_ymd_opt(2024, 1, 31),
+            currency: Some("usd".to_string()),
+            min_total: Some("10.5".to_string()),
+            max_total: Some("99,99".to_string()),
+            ..IndexQuery::default()
+        };
+
+        repo.expect_list_orders()
+            .times(1)
+            .withf(|query| {
+                query.statuses == vec![OrderStatus::Pending, OrderStatus::Processing]
+                    && query.customer_id == Some(5)
+                    && query.created_from
+                        == NaiveDate::from_ymd_opt(2024, 1, 1)
+                            .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    && query.created_before
+                        == NaiveDate::from_ymd_opt(2024, 2, 1)
+                            .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    && query.min_total == Some(Money::new(1050, "USD"))
+                    && query.max_total == Some(Money::new(9999, "USD"))
+            })
+            .returning(|_| Ok((0, Vec::new())));
+
+        let data = load_index_page(&repo, &user, query).expect("expected index page");
+
+        assert!(data.filters.has_filters());
+    }
+
+    #[test]
+    fn load_index_page_rejects_totals_without_currency() {
+        let repo = MockOrderReader::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+        let query = IndexQuery {
+            min_total: Some("10".to_string()),
+            ..IndexQuery::default()
+        };
+
+        let result = load_index_page(&repo, &user, query);
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
 }
@@ -1,7 +1,15 @@
 pub use pushkind_common::services::errors::{ServiceError, ServiceResult};
 
 pub mod categories;
+pub mod customers;
+pub mod exchange_rates;
+pub mod inventory;
 pub mod main;
+pub mod orders;
+pub mod price_level_assignments;
 pub mod price_levels;
 pub mod products;
+pub mod promotions;
 pub mod tags;
+pub mod tax_categories;
+pub mod uploads;
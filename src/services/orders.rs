@@ -0,0 +1,2464 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use chrono::Utc;
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::repository::errors::RepositoryError;
+use pushkind_common::routes::check_role;
+use rust_xlsxwriter::{Workbook, Worksheet, XlsxError};
+use serde::{Deserialize, Serialize};
+
+use crate::SERVICE_ACCESS_ROLE;
+use crate::domain::customer::Customer;
+use crate::domain::exchange_rate::{EXCHANGE_RATE_SCALE, conversion_rate, format_rate};
+use crate::domain::money::{Money, minor_units};
+use crate::domain::order::{
+    NewOrder, Order, OrderDiscount, OrderProduct, OrderStatus, UpdateOrder,
+};
+use crate::domain::order_status_history::{NewOrderStatusChange, OrderStatusChange};
+use crate::domain::price_level::{PriceLevel, PriceLevelListQuery};
+use crate::domain::product::{Product, ProductListQuery};
+use crate::domain::promotion::PROMOTION_USED_UP;
+use crate::domain::tax_category::{TaxAmounts, format_tax_rate};
+use crate::forms::orders::{
+    EditOrderForm, PlaceOrderItemInput, PlaceOrderPayload, TransitionOrderForm,
+};
+use crate::repository::{
+    CategoryReader, CustomerReader, ExchangeRateReader, OrderReader, OrderWriter, PriceLevelReader,
+    ProductReader, PromotionReader, TaxCategoryReader,
+};
+use crate::services::main::IndexQuery;
+use crate::services::products::category_subtree;
+use crate::services::{ServiceError, ServiceResult};
+
+/// Places a new order for the authenticated user's hub.
+///
+/// Line items are priced on the server from the customer's price level, or the
+/// hub default level when the customer has none assigned. The order is kept in
+/// the requested currency, or the currency of the first line, and lines in
+/// other currencies are converted with the hub's exchange rates. Each line is
+/// taxed at the rate of its product's tax category, treating prices as gross
+/// or net as configured on the price level. A promotion code adds a discount
+/// line subtracted from the line totals. Archived products, products without
+/// a rate at the resolved level, quantities beyond the available stock of
+/// products that do not allow oversell, currencies without an exchange rate
+/// and codes that cannot be redeemed are rejected with [`ServiceError::Form`].
+/// A code used up by another order in the meantime is reported as
+/// [`ServiceError::Conflict`]; other constraint violations are not.
+pub fn place_order<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    payload: PlaceOrderPayload,
+) -> ServiceResult<Order>
+where
+    R: OrderWriter
+        + ProductReader
+        + PriceLevelReader
+        + CustomerReader
+        + ExchangeRateReader
+        + TaxCategoryReader
+        + PromotionReader
+        + CategoryReader
+        + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let request = payload
+        .into_place_order_request()
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    let customer = match request.customer_id {
+        Some(customer_id) => Some(
+            repo.get_customer_by_id(customer_id, user.hub_id)
+                .map_err(ServiceError::from)?
+                .ok_or(ServiceError::NotFound)?,
+        ),
+        None => None,
+    };
+
+    let price_level = resolve_price_level(repo, user.hub_id, customer.as_ref())?;
+
+    let mut products = Vec::with_capacity(request.items.len());
+    for item in &request.items {
+        products.push(price_order_line(repo, user.hub_id, &price_level, item)?);
+    }
+
+    let currency = match request.currency {
+        Some(currency) => currency,
+        None => products
+            .first()
+            .map(|line| line.price.currency.clone())
+            .ok_or_else(|| {
+                ServiceError::Form("order must contain at least one product".to_string())
+            })?,
+    };
+    apply_exchange_rates(repo, user.hub_id, &currency, &mut products)?;
+    let mut amounts = order_amounts(&currency, &mut products)?;
+
+    let discount = match request.promo_code {
+        Some(code) => {
+            let discount =
+                redeem_promotion(repo, user.hub_id, &code, &currency, &products, &amounts)?;
+            amounts = discounted_amounts(&amounts, &discount)?;
+            Some(discount)
+        }
+        None => None,
+    };
+
+    let mut new_order = NewOrder::new(user.hub_id, amounts.gross.clone())
+        .with_amounts(amounts)
+        .with_status(OrderStatus::Pending)
+        .with_products(products);
+
+    if let Some(discount) = discount {
+        new_order = new_order.with_discount(discount);
+    }
+    if let Some(customer) = customer.as_ref() {
+        new_order = new_order.with_customer_id(customer.id);
+    }
+    if let Some(reference) = request.reference {
+        new_order = new_order.with_reference(reference);
+    }
+    if let Some(notes) = request.notes {
+        new_order = new_order.with_notes(notes);
+    }
+
+    repo.create_order(&new_order).map_err(|err| match err {
+        RepositoryError::ConstraintViolation(message) if message == PROMOTION_USED_UP => {
+            ServiceError::Conflict
+        }
+        RepositoryError::ConstraintViolation(_) => ServiceError::Repository(err),
+        err => ServiceError::from(err),
+    })
+}
+
+/// Data required to render the order detail template.
+pub struct OrderPageData {
+    /// Order being viewed.
+    pub order: Order,
+    /// Status changes of the order, oldest first.
+    pub history: Vec<OrderStatusChange>,
+    /// Statuses the order may move to next.
+    pub allowed_transitions: Vec<OrderStatus>,
+    /// Whether the order lines, notes and reference may still be edited.
+    pub is_editable: bool,
+    /// Active products that can be appended to the order.
+    pub products: Vec<Product>,
+    /// Amounts of each order line, in the order of `order.products`.
+    pub line_totals: Vec<OrderLineTotals>,
+}
+
+/// Amounts of an order line shown on the order page.
+#[derive(Debug, Serialize)]
+pub struct OrderLineTotals {
+    /// Line total in the line currency.
+    pub total: Money,
+    /// Line total converted into the order currency with the captured rate.
+    pub order_total: Money,
+    /// Captured exchange rate as a decimal, e.g. `92.5`.
+    pub exchange_rate: String,
+    /// Captured tax rate as a percentage, e.g. `20`.
+    pub tax_rate: String,
+}
+
+impl OrderLineTotals {
+    fn from_line(line: &OrderProduct, order_currency: &str) -> Self {
+        Self {
+            total: line
+                .total()
+                .unwrap_or_else(|_| Money::zero(line.price.currency.as_str())),
+            order_total: line
+                .total_in_order_currency(order_currency)
+                .unwrap_or_else(|_| Money::zero(order_currency)),
+            exchange_rate: format_rate(line.exchange_rate_micros),
+            tax_rate: format_tax_rate(line.tax_rate_basis_points),
+        }
+    }
+}
+
+/// Loads an order together with the data needed by its detail page.
+pub fn load_order_page<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    order_id: i32,
+) -> ServiceResult<OrderPageData>
+where
+    R: OrderReader + ProductReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let order = repo
+        .get_order_by_id(order_id, user.hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or(ServiceError::NotFound)?;
+
+    let history = repo
+        .list_order_status_history(order.id, user.hub_id)
+        .map_err(ServiceError::from)?;
+
+    let is_editable = order.status.is_editable();
+    let products = if is_editable {
+        let (_, products) = repo
+            .list_products(ProductListQuery::new(user.hub_id))
+            .map_err(ServiceError::from)?;
+        products
+    } else {
+        Vec::new()
+    };
+
+    let line_totals = order
+        .products
+        .iter()
+        .map(|line| OrderLineTotals::from_line(line, &order.currency))
+        .collect();
+
+    Ok(OrderPageData {
+        allowed_transitions: order.status.allowed_transitions().to_vec(),
+        is_editable,
+        line_totals,
+        order,
+        history,
+        products,
+    })
+}
+
+/// Updates the reference, notes and lines of an editable order.
+///
+/// Lines whose quantity changes are re-priced for the new quantity, so they
+/// move into or out of quantity breaks, and converted at the current exchange
+/// rates; other lines keep the price and exchange rate captured when they
+/// were added. Lines keep their captured tax rate. Appended products are
+/// priced from the customer's price level, or the hub default level, and
+/// converted and taxed at the current rates. The order net, tax and gross
+/// totals are recalculated from the resulting lines, and a discount is
+/// recalculated from the promotion terms captured when its code was redeemed.
+/// Edits that take the order below the promotion's minimum order amount, or
+/// that raise a changed line above the available stock of a product that does
+/// not allow oversell, are rejected with [`ServiceError::Form`].
+pub fn update_order_details<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    order_id: i32,
+    form: EditOrderForm,
+) -> ServiceResult<Order>
+where
+    R: OrderReader
+        + OrderWriter
+        + ProductReader
+        + PriceLevelReader
+        + CustomerReader
+        + ExchangeRateReader
+        + TaxCategoryReader
+        + CategoryReader
+        + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let request = form
+        .into_edit_request()
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    let order = repo
+        .get_order_by_id(order_id, user.hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or(ServiceError::NotFound)?;
+
+    if !order.status.is_editable() {
+        return Err(ServiceError::Form(format!(
+            "order in status {} cannot be edited",
+            <&str>::from(order.status)
+        )));
+    }
+
+    let mut products = order.products.clone();
+    let mut changed = vec![false; products.len()];
+    for (index, quantity) in request.quantities {
+        let line = products
+            .get_mut(index)
+            .ok_or_else(|| ServiceError::Form(format!("order line {index} not found")))?;
+        if line.quantity != quantity {
+            line.quantity = quantity;
+            changed[index] = true;
+        }
+    }
+
+    let mut new_item = None;
+    if let Some(item) = request.add_product {
+        let existing = products
+            .iter()
+            .position(|line| line.product_id == Some(item.product_id));
+        match existing {
+            Some(index) => {
+                let line = &mut products[index];
+                line.quantity = line
+                    .quantity
+                    .checked_add(item.quantity)
+                    .ok_or_else(|| ServiceError::Form("quantity is too large".to_string()))?;
+                changed[index] = true;
+            }
+            None => new_item = Some(item),
+        }
+    }
+
+    let needs_pricing = new_item.is_some()
+        || products
+            .iter()
+            .zip(&changed)
+            .any(|(line, is_changed)| *is_changed && line.quantity > 0);
+    if needs_pricing {
+        let customer = match order.customer_id {
+            Some(customer_id) => repo
+                .get_customer_by_id(customer_id, user.hub_id)
+                .map_err(ServiceError::from)?,
+            None => None,
+        };
+        let price_level = resolve_price_level(repo, user.hub_id, customer.as_ref())?;
+
+        for (line, _) in products
+            .iter_mut()
+            .zip(&changed)
+            .filter(|(line, is_changed)| **is_changed && line.quantity > 0)
+        {
+            reprice_order_line(repo, user.hub_id, &price_level, &order.currency, line)?;
+        }
+
+        if let Some(item) = new_item {
+            let mut line = price_order_line(repo, user.hub_id, &price_level, &item)?;
+            apply_exchange_rates(
+                repo,
+                user.hub_id,
+                &order.currency,
+                std::slice::from_mut(&mut line),
+            )?;
+            products.push(line);
+        }
+    }
+    products.retain(|line| line.quantity > 0);
+
+    if products.is_empty() {
+        return Err(ServiceError::Form(
+            "order must contain at least one product".to_string(),
+        ));
+    }
+
+    let mut amounts = order_amounts(&order.currency, &mut products)?;
+    let discount = match order.discount.clone() {
+        Some(mut discount) => {
+            discount
+                .check_minimum(&amounts.gross)
+                .map_err(|err| ServiceError::Form(err.to_string()))?;
+            let eligible =
+                eligible_amounts(repo, user.hub_id, &order.currency, &discount, &products)?;
+            discount
+                .calculate_amounts(&eligible)
+                .map_err(|_| ServiceError::Form("order total is too large".to_string()))?;
+            amounts = discounted_amounts(&amounts, &discount)?;
+            Some(discount)
+        }
+        None => None,
+    };
+
+    let updates = UpdateOrder {
+        expected_status: order.status,
+        notes: request.notes,
+        total: amounts.gross,
+        net: amounts.net,
+        tax: amounts.tax,
+        currency: order.currency.clone(),
+        customer_id: order.customer_id,
+        reference: request.reference,
+        products: Some(products),
+        discount,
+        updated_at: Utc::now().naive_utc(),
+    };
+
+    repo.update_order(order.id, user.hub_id, &updates)
+        .map_err(ServiceError::from)
+}
+
+/// Moves an order to another status and records who made the change.
+///
+/// Transitions not allowed by [`OrderStatus::allowed_transitions`] are rejected
+/// with [`ServiceError::Form`]. Moving to processing fails with
+/// [`ServiceError::Conflict`] when a product that does not allow oversell lacks
+/// the units to reserve, and releasing or completing fails the same way when a
+/// product holds fewer reserved units than the order.
+pub fn transition_order<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    order_id: i32,
+    form: TransitionOrderForm,
+) -> ServiceResult<Order>
+where
+    R: OrderReader + OrderWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let next_status = form
+        .into_status()
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    let order = repo
+        .get_order_by_id(order_id, user.hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or(ServiceError::NotFound)?;
+
+    if !order.status.can_transition_to(next_status) {
+        return Err(ServiceError::Form(format!(
+            "order cannot move from {} to {}",
+            <&str>::from(order.status),
+            <&str>::from(next_status)
+        )));
+    }
+
+    let change =
+        NewOrderStatusChange::new(order.id, order.status, next_status, &user.email, &user.name);
+
+    repo.transition_order_status(user.hub_id, &change)
+        .map_err(ServiceError::from)
+}
+
+/// Loads the status change history of an order, oldest first.
+pub fn load_order_status_history<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    order_id: i32,
+) -> ServiceResult<Vec<OrderStatusChange>>
+where
+    R: OrderReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    repo.get_order_by_id(order_id, user.hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or(ServiceError::NotFound)?;
+
+    repo.list_order_status_history(order_id, user.hub_id)
+        .map_err(ServiceError::from)
+}
+
+/// File formats offered by the orders export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Comma-separated values.
+    #[default]
+    Csv,
+    /// Excel workbook.
+    Xlsx,
+}
+
+impl ExportFormat {
+    /// MIME type of the exported file.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+
+    /// File extension used in the download name.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+}
+
+/// Query parameters selecting the export format.
+#[derive(Debug, Default, Deserialize)]
+pub struct ExportQuery {
+    /// Requested file format, CSV when omitted.
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Column headers of the orders export.
+const ORDER_EXPORT_HEADERS: [&str; 15] = [
+    "order_id",
+    "reference",
+    "status",
+    "created_at",
+    "customer_name",
+    "customer_email",
+    "sku",
+    "product_name",
+    "quantity",
+    "unit_price",
+    "line_total",
+    "currency",
+    "exchange_rate",
+    "order_line_total",
+    "order_currency",
+];
+
+/// Single exported line, one per ordered product.
+struct OrderExportRow {
+    order_id: i32,
+    reference: String,
+    status: &'static str,
+    created_at: String,
+    customer_name: String,
+    customer_email: String,
+    sku: String,
+    product_name: String,
+    quantity: i32,
+    unit_price: Money,
+    line_total: Money,
+    exchange_rate: String,
+    order_line_total: Money,
+}
+
+/// Number of orders loaded per page while exporting.
+const ORDER_EXPORT_PAGE_SIZE: usize = 500;
+
+/// Exports every order matching the index filters, one row per order line.
+///
+/// A discount is exported as a trailing row with a negative amount, so the
+/// order line totals of an order add up to its total.
+///
+/// The index pagination is ignored; orders are instead loaded and written
+/// [`ORDER_EXPORT_PAGE_SIZE`] at a time, so memory use does not grow with the
+/// number of matching orders beyond the file itself. Orders placed while the
+/// export runs are left out so they cannot shift the pages.
+pub fn export_orders<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    query: IndexQuery,
+    format: ExportFormat,
+) -> ServiceResult<Vec<u8>>
+where
+    R: OrderReader + CustomerReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let mut list_query = query.to_list_query(user.hub_id)?;
+    let started_at = Utc::now().naive_utc();
+    list_query.created_before = Some(
+        list_query
+            .created_before
+            .map_or(started_at, |before| before.min(started_at)),
+    );
+
+    let mut customers: HashMap<i32, Option<Customer>> = HashMap::new();
+    let mut writer = OrderExportWriter::new(format)?;
+
+    for page in 1.. {
+        let (_, orders) = repo
+            .list_orders(list_query.clone().paginate(page, ORDER_EXPORT_PAGE_SIZE))
+            .map_err(ServiceError::from)?;
+
+        for order in &orders {
+            let customer = match order.customer_id {
+                Some(customer_id) => match customers.entry(customer_id) {
+                    Entry::Occupied(entry) => entry.into_mut().as_ref(),
+                    Entry::Vacant(entry) => entry
+                        .insert(
+                            repo.get_customer_by_id(customer_id, user.hub_id)
+                                .map_err(ServiceError::from)?,
+                        )
+                        .as_ref(),
+                },
+                None => None,
+            };
+
+            for row in order_export_rows(order, customer) {
+                writer.write_row(&row)?;
+            }
+        }
+
+        if orders.len() < ORDER_EXPORT_PAGE_SIZE {
+            break;
+        }
+    }
+
+    writer.finish()
+}
+
+/// Rows of `order`: one per ordered product plus one for its discount.
+fn order_export_rows(order: &Order, customer: Option<&Customer>) -> Vec<OrderExportRow> {
+    let mut rows = Vec::with_capacity(order.products.len() + 1);
+
+    for line in &order.products {
+        let totals = OrderLineTotals::from_line(line, &order.currency);
+        rows.push(OrderExportRow {
+            order_id: order.id,
+            reference: order.reference.clone().unwrap_or_default(),
+            status: order.status.into(),
+            created_at: order.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            customer_name: customer.map(|c| c.name.clone()).unwrap_or_default(),
+            customer_email: customer.map(|c| c.email.clone()).unwrap_or_default(),
+            sku: line.sku.clone().unwrap_or_default(),
+            product_name: line.name.clone(),
+            quantity: line.quantity,
+            unit_price: line.price.clone(),
+            line_total: totals.total,
+            exchange_rate: totals.exchange_rate,
+            order_line_total: totals.order_total,
+        });
+    }
+
+    if let Some(discount) = order.discount.as_ref() {
+        let amount = Money::new(-discount.gross.amount, discount.gross.currency.as_str());
+        rows.push(OrderExportRow {
+            order_id: order.id,
+            reference: order.reference.clone().unwrap_or_default(),
+            status: order.status.into(),
+            created_at: order.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            customer_name: customer.map(|c| c.name.clone()).unwrap_or_default(),
+            customer_email: customer.map(|c| c.email.clone()).unwrap_or_default(),
+            sku: String::new(),
+            product_name: format!("Скидка {}", discount.code),
+            quantity: 1,
+            unit_price: amount.clone(),
+            line_total: amount.clone(),
+            exchange_rate: format_rate(EXCHANGE_RATE_SCALE),
+            order_line_total: amount,
+        });
+    }
+
+    rows
+}
+
+/// Export file being written row by row.
+enum OrderExportWriter {
+    Csv(Box<csv::Writer<Vec<u8>>>),
+    Xlsx {
+        worksheet: Box<Worksheet>,
+        next_row: u32,
+    },
+}
+
+impl OrderExportWriter {
+    /// Starts a file in `format` with the header row written.
+    fn new(format: ExportFormat) -> ServiceResult<Self> {
+        match format {
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                writer
+                    .write_record(ORDER_EXPORT_HEADERS)
+                    .map_err(csv_export_error)?;
+                Ok(Self::Csv(Box::new(writer)))
+            }
+            ExportFormat::Xlsx => {
+                let mut worksheet = Worksheet::new();
+                for (col, header) in ORDER_EXPORT_HEADERS.iter().enumerate() {
+                    worksheet
+                        .write_string(0, col as u16, *header)
+                        .map_err(xlsx_export_error)?;
+                }
+                Ok(Self::Xlsx {
+                    worksheet: Box::new(worksheet),
+                    next_row: 1,
+                })
+            }
+        }
+    }
+
+    /// Appends `row` to the file.
+    fn write_row(&mut self, row: &OrderExportRow) -> ServiceResult<()> {
+        match self {
+            Self::Csv(writer) => writer
+                .write_record([
+                    row.order_id.to_string(),
+                    row.reference.clone(),
+                    row.status.to_string(),
+                    row.created_at.clone(),
+                    row.customer_name.clone(),
+                    row.customer_email.clone(),
+                    row.sku.clone(),
+                    row.product_name.clone(),
+                    row.quantity.to_string(),
+                    row.unit_price.format_amount(),
+                    row.line_total.format_amount(),
+                    row.unit_price.currency.clone(),
+                    row.exchange_rate.clone(),
+                    row.order_line_total.format_amount(),
+                    row.order_line_total.currency.clone(),
+                ])
+                .map_err(csv_export_error),
+            Self::Xlsx {
+                worksheet,
+                next_row,
+                ..
+            } => {
+                write_xlsx_row(worksheet, *next_row, row).map_err(xlsx_export_error)?;
+                *next_row += 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Completes the file and returns its bytes.
+    fn finish(self) -> ServiceResult<Vec<u8>> {
+        match self {
+            Self::Csv(writer) => writer.into_inner().map_err(|err| {
+                log::error!("Failed to flush orders CSV: {err}");
+                ServiceError::Internal
+            }),
+            Self::Xlsx { worksheet, .. } => {
+                let mut workbook = Workbook::new();
+                workbook.push_worksheet(*worksheet);
+                workbook.save_to_buffer().map_err(xlsx_export_error)
+            }
+        }
+    }
+}
+
+fn write_xlsx_row(
+    worksheet: &mut Worksheet,
+    line: u32,
+    row: &OrderExportRow,
+) -> Result<(), XlsxError> {
+    worksheet.write_number(line, 0, row.order_id)?;
+    worksheet.write_string(line, 1, &row.reference)?;
+    worksheet.write_string(line, 2, row.status)?;
+    worksheet.write_string(line, 3, &row.created_at)?;
+    worksheet.write_string(line, 4, &row.customer_name)?;
+    worksheet.write_string(line, 5, &row.customer_email)?;
+    worksheet.write_string(line, 6, &row.sku)?;
+    worksheet.write_string(line, 7, &row.product_name)?;
+    worksheet.write_number(line, 8, row.quantity)?;
+    worksheet.write_number(line, 9, major_units(&row.unit_price))?;
+    worksheet.write_number(line, 10, major_units(&row.line_total))?;
+    worksheet.write_string(line, 11, &row.unit_price.currency)?;
+    worksheet.write_string(line, 12, &row.exchange_rate)?;
+    worksheet.write_number(line, 13, major_units(&row.order_line_total))?;
+    worksheet.write_string(line, 14, &row.order_line_total.currency)?;
+    Ok(())
+}
+
+fn csv_export_error(err: csv::Error) -> ServiceError {
+    log::error!("Failed to write orders CSV: {err}");
+    ServiceError::Internal
+}
+
+fn xlsx_export_error(err: XlsxError) -> ServiceError {
+    log::error!("Failed to build orders workbook: {err}");
+    ServiceError::Internal
+}
+
+/// Amount in whole currency units for numeric spreadsheet cells.
+fn major_units(money: &Money) -> f64 {
+    money.amount as f64 / 10f64.powi(minor_units(&money.currency) as i32)
+}
+
+/// Builds an order line for `item`, priced at `price_level`.
+///
+/// The rate in effect now is used, so scheduled prices apply automatically.
+/// Products without a rate of their own at a derived level are priced from
+/// the base level; the hub's levels are only loaded in that case. The line
+/// captures the rate of the product's tax category, loaded only for taxed
+/// products. Missing and archived products, quantities beyond the available
+/// stock of products that do not allow oversell, and products without any
+/// price at the level, are rejected with [`ServiceError::Form`].
+fn price_order_line<R>(
+    repo: &R,
+    hub_id: i32,
+    price_level: &PriceLevel,
+    item: &PlaceOrderItemInput,
+) -> ServiceResult<OrderProduct>
+where
+    R: ProductReader + PriceLevelReader + TaxCategoryReader + ?Sized,
+{
+    let price_level_id = price_level.id;
+    let product = repo
+        .get_product_by_id(item.product_id, hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or_else(|| ServiceError::Form(format!("product {} not found", item.product_id)))?;
+
+    if product.is_archived {
+        return Err(ServiceError::Form(format!(
+            "product `{}` is archived",
+            product.name
+        )));
+    }
+
+    check_stock(&product, item.quantity)?;
+
+    let price = unit_price(repo, hub_id, price_level_id, &product, item.quantity)?;
+
+    let tax_rate_basis_points = match product.tax_category_id {
+        Some(tax_category_id) => repo
+            .list_tax_categories(hub_id)
+            .map_err(ServiceError::from)?
+            .into_iter()
+            .find(|category| category.id == tax_category_id)
+            .map_or(0, |category| category.rate_basis_points),
+        None => 0,
+    };
+
+    let mut snapshot = OrderProduct::new(&product.name, price, item.quantity)
+        .with_product_id(product.id)
+        .with_tax(tax_rate_basis_points, price_level.prices_include_tax);
+    if let Some(sku) = product.sku.as_ref() {
+        snapshot = snapshot.with_sku(sku);
+    }
+    if let Some(description) = product.description.as_ref() {
+        snapshot = snapshot.with_description(description);
+    }
+    Ok(snapshot)
+}
+
+/// Re-prices an existing order line for its current quantity at
+/// `price_level` and converts it into `currency` at the current rate.
+///
+/// Lines of deleted products keep their captured price. Products without any
+/// price at the level, or without the units available when they do not allow
+/// oversell, are rejected with [`ServiceError::Form`].
+fn reprice_order_line<R>(
+    repo: &R,
+    hub_id: i32,
+    price_level: &PriceLevel,
+    currency: &str,
+    line: &mut OrderProduct,
+) -> ServiceResult<()>
+where
+    R: ProductReader + PriceLevelReader + ExchangeRateReader + ?Sized,
+{
+    let Some(product_id) = line.product_id else {
+        return Ok(());
+    };
+    let Some(product) = repo
+        .get_product_by_id(product_id, hub_id)
+        .map_err(ServiceError::from)?
+    else {
+        return Ok(());
+    };
+
+    check_stock(&product, line.quantity)?;
+
+    line.price = unit_price(repo, hub_id, price_level.id, &product, line.quantity)?;
+    apply_exchange_rates(repo, hub_id, currency, std::slice::from_mut(line))
+}
+
+/// Rejects `quantity` units of a product that does not allow oversell when
+/// more than its available stock is requested.
+fn check_stock(product: &Product, quantity: i32) -> ServiceResult<()> {
+    let available = product.stock.available();
+    if !product.allow_oversell && quantity > available {
+        return Err(ServiceError::Form(format!(
+            "product `{}` has only {} units available",
+            product.name,
+            available.max(0)
+        )));
+    }
+
+    Ok(())
+}
+
+/// Unit price of `quantity` units of `product` at `price_level_id` in effect
+/// now, including the quantity break the line falls into.
+///
+/// Products without a rate of their own at a derived level are priced from
+/// the base level; the hub's levels are only loaded in that case.
+fn unit_price<R>(
+    repo: &R,
+    hub_id: i32,
+    price_level_id: i32,
+    product: &Product,
+    quantity: i32,
+) -> ServiceResult<Money>
+where
+    R: PriceLevelReader + ?Sized,
+{
+    let now = Utc::now().naive_utc();
+    match product.rate_for_quantity(price_level_id, now, quantity) {
+        Some(rate) => Some(rate.price.clone()),
+        None => {
+            let (_, price_levels) = repo
+                .list_price_levels(PriceLevelListQuery::new(hub_id))
+                .map_err(ServiceError::from)?;
+            product
+                .price_for_quantity(&price_levels, price_level_id, now, quantity)
+                .map(|price| price.price)
+        }
+    }
+    .ok_or_else(|| {
+        ServiceError::Form(format!(
+            "product `{}` has no price at the selected price level",
+            product.name
+        ))
+    })
+}
+
+/// Captures on each line the rate converting its currency into `currency`.
+///
+/// The hub's rates are only loaded when a line is in another currency; a
+/// missing rate is rejected with [`ServiceError::Form`].
+fn apply_exchange_rates<R>(
+    repo: &R,
+    hub_id: i32,
+    currency: &str,
+    products: &mut [OrderProduct],
+) -> ServiceResult<()>
+where
+    R: ExchangeRateReader + ?Sized,
+{
+    let rates = if products.iter().any(|line| line.price.currency != currency) {
+        repo.list_exchange_rates(hub_id)
+            .map_err(ServiceError::from)?
+    } else {
+        Vec::new()
+    };
+
+    for line in products.iter_mut() {
+        line.exchange_rate_micros = if line.price.currency == currency {
+            EXCHANGE_RATE_SCALE
+        } else {
+            conversion_rate(&rates, &line.price.currency, currency).ok_or_else(|| {
+                ServiceError::Form(format!(
+                    "no exchange rate from {} to {currency}",
+                    line.price.currency
+                ))
+            })?
+        };
+    }
+
+    Ok(())
+}
+
+/// Stores the net, tax and gross amounts of each line in the order currency
+/// and returns their sum, rejecting overflowing amounts.
+fn order_amounts(currency: &str, products: &mut [OrderProduct]) -> ServiceResult<TaxAmounts> {
+    products
+        .iter_mut()
+        .try_fold(TaxAmounts::zero(currency), |total, line| {
+            total.checked_add(&line.calculate_amounts(currency)?)
+        })
+        .map_err(|_| ServiceError::Form("order total is too large".to_string()))
+}
+
+/// Looks up the promotion `code` and builds the discount it gives on
+/// `products`, whose line totals sum to `subtotal`.
+///
+/// Unknown codes, codes that cannot be redeemed on the order and codes that
+/// do not apply to any ordered product are rejected with
+/// [`ServiceError::Form`].
+fn redeem_promotion<R>(
+    repo: &R,
+    hub_id: i32,
+    code: &str,
+    currency: &str,
+    products: &[OrderProduct],
+    subtotal: &TaxAmounts,
+) -> ServiceResult<OrderDiscount>
+where
+    R: PromotionReader + ProductReader + CategoryReader + ?Sized,
+{
+    let promotion = repo
+        .get_promotion_by_code(code, hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or_else(|| ServiceError::Form(format!("promotion code {code} not found")))?;
+
+    promotion
+        .check_redeemable(Utc::now().naive_utc(), &subtotal.gross)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    let mut discount = OrderDiscount::from_promotion(&promotion, currency);
+    let eligible = eligible_amounts(repo, hub_id, currency, &discount, products)?;
+    if eligible.gross.amount == 0 {
+        return Err(ServiceError::Form(format!(
+            "promotion code {} does not apply to the ordered products",
+            promotion.code
+        )));
+    }
+
+    discount
+        .calculate_amounts(&eligible)
+        .map_err(|_| ServiceError::Form("order total is too large".to_string()))?;
+    Ok(discount)
+}
+
+/// Sums the amounts of the lines `discount` applies to in the order
+/// `currency`.
+///
+/// Lines are matched against the category subtree and tag of a restricted
+/// discount through their current product; lines of deleted products never
+/// match.
+fn eligible_amounts<R>(
+    repo: &R,
+    hub_id: i32,
+    currency: &str,
+    discount: &OrderDiscount,
+    products: &[OrderProduct],
+) -> ServiceResult<TaxAmounts>
+where
+    R: ProductReader + CategoryReader + ?Sized,
+{
+    let categories = match discount.category_id {
+        Some(category_id) => Some(category_subtree(repo, hub_id, category_id)?),
+        None => None,
+    };
+
+    let mut eligible = TaxAmounts::zero(currency);
+    for line in products {
+        if discount.is_restricted() {
+            let product = match line.product_id {
+                Some(product_id) => repo
+                    .get_product_by_id(product_id, hub_id)
+                    .map_err(ServiceError::from)?,
+                None => None,
+            };
+            let Some(product) = product else {
+                continue;
+            };
+            if let Some(categories) = categories.as_ref()
+                && !product
+                    .category_id
+                    .is_some_and(|category_id| categories.contains(&category_id))
+            {
+                continue;
+            }
+            if let Some(tag_id) = discount.tag_id
+                && !product.tags.iter().any(|tag| tag.id == tag_id)
+            {
+                continue;
+            }
+        }
+
+        eligible = eligible
+            .checked_add(&line.amounts())
+            .map_err(|_| ServiceError::Form("order total is too large".to_string()))?;
+    }
+
+    Ok(eligible)
+}
+
+/// Subtracts the discount from the line totals of an order.
+fn discounted_amounts(amounts: &TaxAmounts, discount: &OrderDiscount) -> ServiceResult<TaxAmounts> {
+    amounts
+        .checked_sub(&discount.amounts())
+        .map_err(|_| ServiceError::Form("order total is too large".to_string()))
+}
+
+/// Returns the customer's price level, or the hub's default level when the
+/// customer has none assigned.
+fn resolve_price_level<R>(
+    repo: &R,
+    hub_id: i32,
+    customer: Option<&Customer>,
+) -> ServiceResult<PriceLevel>
+where
+    R: PriceLevelReader + ?Sized,
+{
+    if let Some(price_level_id) = customer.and_then(|c| c.price_level_id) {
+        return repo
+            .get_price_level_by_id(price_level_id, hub_id)
+            .map_err(ServiceError::from)?
+            .ok_or_else(|| ServiceError::Form(format!("price level {price_level_id} not found")));
+    }
+
+    let (_, price_levels) = repo
+        .list_price_levels(PriceLevelListQuery::new(hub_id))
+        .map_err(ServiceError::from)?;
+
+    price_levels
+        .into_iter()
+        .find(|level| level.is_default)
+        .ok_or_else(|| ServiceError::Form("no default price level configured".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use std::sync::{Arc, Mutex};
+
+    use crate::domain::category::{Category, CategoryTreeQuery};
+    use crate::domain::customer::{Customer, CustomerListQuery};
+    use crate::domain::exchange_rate::ExchangeRate;
+    use crate::domain::inventory_movement::StockLevel;
+    use crate::domain::order::OrderListQuery;
+    use crate::domain::price_level::{
+        PriceAdjustmentKind, PriceDerivation, PriceLevel, PriceRounding,
+    };
+    use crate::domain::product_price_history::{ProductPriceChange, ProductPriceHistoryQuery};
+    use crate::domain::product_price_level::ProductPriceLevelRate;
+    use crate::domain::promotion::Promotion;
+    use crate::domain::tax_category::TaxCategory;
+    use crate::forms::orders::{EditOrderLineForm, PlaceOrderItemPayload};
+    use crate::repository::mock::{
+        MockCategoryReader, MockCustomerReader, MockExchangeRateReader, MockOrderReader,
+        MockOrderWriter, MockPriceLevelReader, MockProductReader, MockPromotionReader,
+        MockTaxCategoryReader,
+    };
+    use pushkind_common::repository::errors::RepositoryResult;
+
+    struct FakeRepo {
+        order_reader: MockOrderReader,
+        order_writer: MockOrderWriter,
+        product_reader: MockProductReader,
+        price_level_reader: MockPriceLevelReader,
+        customer_reader: MockCustomerReader,
+        exchange_rate_reader: MockExchangeRateReader,
+        tax_category_reader: MockTaxCategoryReader,
+        promotion_reader: MockPromotionReader,
+        category_reader: MockCategoryReader,
+    }
+
+    impl FakeRepo {
+        fn new() -> Self {
+            Self {
+                order_reader: MockOrderReader::new(),
+                order_writer: MockOrderWriter::new(),
+                product_reader: MockProductReader::new(),
+                price_level_reader: MockPriceLevelReader::new(),
+                customer_reader: MockCustomerReader::new(),
+                exchange_rate_reader: MockExchangeRateReader::new(),
+                tax_category_reader: MockTaxCategoryReader::new(),
+                promotion_reader: MockPromotionReader::new(),
+                category_reader: MockCategoryReader::new(),
+            }
+        }
+    }
+
+    impl OrderReader for FakeRepo {
+        fn get_order_by_id(&self, id: i32, hub_id: i32) -> RepositoryResult<Option<Order>> {
+            self.order_reader.get_order_by_id(id, hub_id)
+        }
+
+        fn list_orders(&self, query: OrderListQuery) -> RepositoryResult<(usize, Vec<Order>)> {
+            self.order_reader.list_orders(query)
+        }
+
+        fn list_order_status_history(
+            &self,
+            order_id: i32,
+            hub_id: i32,
+        ) -> RepositoryResult<Vec<OrderStatusChange>> {
+            self.order_reader
+                .list_order_status_history(order_id, hub_id)
+        }
+    }
+
+    impl OrderWriter for FakeRepo {
+        fn create_order(&self, new_order: &NewOrder) -> RepositoryResult<Order> {
+            self.order_writer.create_order(new_order)
+        }
+
+        fn update_order(
+            &self,
+            order_id: i32,
+            hub_id: i32,
+            updates: &UpdateOrder,
+        ) -> RepositoryResult<Order> {
+            self.order_writer.update_order(order_id, hub_id, updates)
+        }
+
+        fn delete_order(&self, order_id: i32, hub_id: i32) -> RepositoryResult<()> {
+            self.order_writer.delete_order(order_id, hub_id)
+        }
+
+        fn transition_order_status(
+            &self,
+            hub_id: i32,
+            change: &NewOrderStatusChange,
+        ) -> RepositoryResult<Order> {
+            self.order_writer.transition_order_status(hub_id, change)
+        }
+    }
+
+    impl ProductReader for FakeRepo {
+        fn get_product_by_id(&self, id: i32, hub_id: i32) -> RepositoryResult<Option<Product>> {
+            self.product_reader.get_product_by_id(id, hub_id)
+        }
+
+        fn list_products(
+            &self,
+            query: ProductListQuery,
+        ) -> RepositoryResult<(usize, Vec<Product>)> {
+            self.product_reader.list_products(query)
+        }
+
+        fn list_product_price_history(
+            &self,
+            query: ProductPriceHistoryQuery,
+        ) -> RepositoryResult<Vec<ProductPriceChange>> {
+            self.product_reader.list_product_price_history(query)
+        }
+    }
+
+    impl PriceLevelReader for FakeRepo {
+        fn get_price_level_by_id(
+            &self,
+            id: i32,
+            hub_id: i32,
+        ) -> RepositoryResult<Option<PriceLevel>> {
+            self.price_level_reader.get_price_level_by_id(id, hub_id)
+        }
+
+        fn list_price_levels(
+            &self,
+            query: PriceLevelListQuery,
+        ) -> RepositoryResult<(usize, Vec<PriceLevel>)> {
+            self.price_level_reader.list_price_levels(query)
+        }
+    }
+
+    impl CustomerReader for FakeRepo {
+        fn get_customer_by_id(&self, id: i32, hub_id: i32) -> RepositoryResult<Option<Customer>> {
+            self.customer_reader.get_customer_by_id(id, hub_id)
+        }
+
+        fn get_customer_by_email(
+            &self,
+            email: &str,
+            hub_id: i32,
+        ) -> RepositoryResult<Option<Customer>> {
+            self.customer_reader.get_customer_by_email(email, hub_id)
+        }
+
+        fn get_customer_by_email_and_phone(
+            &self,
+            email: &str,
+            phone: Option<&str>,
+            hub_id: i32,
+        ) -> RepositoryResult<Option<Customer>> {
+            self.customer_reader
+                .get_customer_by_email_and_phone(email, phone, hub_id)
+        }
+
+        fn list_customers(
+            &self,
+            query: CustomerListQuery,
+        ) -> RepositoryResult<(usize, Vec<Customer>)> {
+            self.customer_reader.list_customers(query)
+        }
+    }
+
+    impl ExchangeRateReader for FakeRepo {
+        fn list_exchange_rates(&self, hub_id: i32) -> RepositoryResult<Vec<ExchangeRate>> {
+            self.exchange_rate_reader.list_exchange_rates(hub_id)
+        }
+    }
+
+    impl TaxCategoryReader for FakeRepo {
+        fn list_tax_categories(&self, hub_id: i32) -> RepositoryResult<Vec<TaxCategory>> {
+            self.tax_category_reader.list_tax_categories(hub_id)
+        }
+    }
+
+    impl PromotionReader for FakeRepo {
+        fn list_promotions(&self, hub_id: i32) -> RepositoryResult<Vec<Promotion>> {
+            self.promotion_reader.list_promotions(hub_id)
+        }
+
+        fn get_promotion_by_code(
+            &self,
+            code: &str,
+            hub_id: i32,
+        ) -> RepositoryResult<Option<Promotion>> {
+            self.promotion_reader.get_promotion_by_code(code, hub_id)
+        }
+    }
+
+    impl CategoryReader for FakeRepo {
+        fn list_categories(
+            &self,
+            query: CategoryTreeQuery,
+        ) -> RepositoryResult<(usize, Vec<Category>)> {
+            self.category_reader.list_categories(query)
+        }
+
+        fn get_category_by_id(
+            &self,
+            category_id: i32,
+            hub_id: i32,
+        ) -> RepositoryResult<Option<Category>> {
+            self.category_reader.get_category_by_id(category_id, hub_id)
+        }
+    }
+
+    fn datetime() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .unwrap_or_default()
+    }
+
+    fn user_with_role(role: &str) -> AuthenticatedUser {
+        AuthenticatedUser {
+            sub: "user".to_string(),
+            email: "user@example.com".to_string(),
+            hub_id: 7,
+            name: "User".to_string(),
+            roles: vec![role.to_string()],
+            exp: 0,
+        }
+    }
+
+    fn rate(product_id: i32, price_level_id: i32, price_cents: i64) -> ProductPriceLevelRate {
+        ProductPriceLevelRate {
+            id: product_id * 100 + price_level_id,
+            product_id,
+            price_level_id,
+            price: Money::new(price_cents, "USD"),
+            min_quantity: 1,
+            valid_from: None,
+            valid_to: None,
+            created_at: datetime(),
+            updated_at: datetime(),
+        }
+    }
+
+    fn product(id: i32, name: &str, rates: Vec<ProductPriceLevelRate>) -> Product {
+        Product {
+            id,
+            hub_id: 7,
+            name: name.to_string(),
+            sku: Some(format!("SKU-{id}")),
+            description: None,
+            units: None,
+            currency: "USD".to_string(),
+            is_archived: false,
+            category_id: None,
+            tax_category_id: None,
+            stock: StockLevel::default(),
+            allow_oversell: true,
+            price_levels: rates,
+            tags: Vec::new(),
+            created_at: datetime(),
+            updated_at: datetime(),
+        }
+    }
+
+    fn price_level(id: i32, is_default: bool) -> PriceLevel {
+        PriceLevel {
+            id,
+            hub_id: 7,
+            name: format!("Level {id}"),
+            created_at: datetime(),
+            updated_at: datetime(),
+            is_default,
+            derivation: None,
+            prices_include_tax: true,
+        }
+    }
+
+    fn payload(customer_id: Option<i32>, items: &[(i32, i32)]) -> PlaceOrderPayload {
+        PlaceOrderPayload {
+            customer_id,
+            reference: None,
+            notes: None,
+            currency: None,
+            items: items
+                .iter()
+                .map(|(product_id, quantity)| PlaceOrderItemPayload {
+                    product_id: *product_id,
+                    quantity: *quantity,
+                })
+                .collect(),
+            promo_code: None,
+        }
+    }
+
+    fn echo_order(new_order: &NewOrder) -> Order {
+        Order {
+            id: 1,
+            hub_id: new_order.hub_id,
+            customer_id: new_order.customer_id,
+            reference: new_order.reference.clone(),
+            status: new_order.status,
+            notes: new_order.notes.clone(),
+            total: new_order.total.clone(),
+            net: new_order.net.clone(),
+            tax: new_order.tax.clone(),
+            currency: new_order.currency.clone(),
+            products: new_order.products.clone(),
+            discount: new_order.discount.clone(),
+            created_at: datetime(),
+            updated_at: datetime(),
+        }
+    }
+
+    #[test]
+    fn place_order_requires_role() {
+        let repo = FakeRepo::new();
+        let user = user_with_role("viewer");
+
+        let result = place_order(&repo, &user, payload(None, &[(1, 1)]));
+
+        assert!(matches!(result, Err(ServiceError::Unauthorized)));
+    }
+
+    #[test]
+    fn place_order_prices_items_from_customer_level() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.customer_reader
+            .expect_get_customer_by_id()
+            .returning(|id, hub_id| {
+                Ok(Some(Customer {
+                    id,
+                    hub_id,
+                    name: "Alice".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                    price_level_id: Some(2),
+                }))
+            });
+        repo.price_level_reader.expect_list_price_levels().never();
+        repo.price_level_reader
+            .expect_get_price_level_by_id()
+            .withf(|id, hub_id| *id == 2 && *hub_id == 7)
+            .returning(|id, _| Ok(Some(price_level(id, false))));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| {
+                Ok(Some(match id {
+                    1 => product(1, "Coffee", vec![rate(1, 1, 500), rate(1, 2, 400)]),
+                    _ => product(2, "Tea", vec![rate(2, 2, 250)]),
+                }))
+            });
+
+        let captured = Arc::new(Mutex::new(None));
+        let captured_clone = Arc::clone(&captured);
+        repo.order_writer
+            .expect_create_order()
+            .times(1)
+            .returning(move |new_order| {
+                *captured_clone.lock().expect("mutex poisoned") = Some(new_order.clone());
+                Ok(echo_order(new_order))
+            });
+
+        let order = place_order(&repo, &user, payload(Some(5), &[(1, 2), (2, 3)]))
+            .expect("expected order to be placed");
+
+        assert_eq!(order.total.amount, 400 * 2 + 250 * 3);
+        assert_eq!(order.customer_id, Some(5));
+        assert_eq!(order.currency, "USD");
+        assert_eq!(order.status, OrderStatus::Pending);
+
+        let new_order = captured
+            .lock()
+            .expect("mutex poisoned")
+            .clone()
+            .expect("order captured");
+        assert_eq!(new_order.products.len(), 2);
+        assert_eq!(new_order.products[0].product_id, Some(1));
+        assert_eq!(new_order.products[0].price.amount, 400);
+        assert_eq!(new_order.products[0].sku.as_deref(), Some("SKU-1"));
+        assert_eq!(new_order.products[1].quantity, 3);
+    }
+
+    #[test]
+    fn place_order_falls_back_to_default_level() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((2, vec![price_level(1, false), price_level(3, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|_, _| Ok(Some(product(1, "Coffee", vec![rate(1, 3, 990)]))));
+        repo.order_writer
+            .expect_create_order()
+            .returning(|new_order| Ok(echo_order(new_order)));
+
+        let order = place_order(&repo, &user, payload(None, &[(1, 1)]))
+            .expect("expected order to be placed");
+
+        assert_eq!(order.total.amount, 990);
+        assert!(order.customer_id.is_none());
+    }
+
+    #[test]
+    fn place_order_adds_tax_on_top_of_net_prices() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| {
+                let mut level = price_level(1, true);
+                level.prices_include_tax = false;
+                Ok((1, vec![level]))
+            });
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| {
+                let mut taxed = product(id, "Coffee", vec![rate(id, 1, 1_000)]);
+                if id == 1 {
+                    taxed.tax_category_id = Some(4);
+                }
+                Ok(Some(taxed))
+            });
+        repo.tax_category_reader
+            .expect_list_tax_categories()
+            .times(1)
+            .returning(|hub_id| {
+                Ok(vec![TaxCategory {
+                    id: 4,
+                    hub_id,
+                    name: "VAT".to_string(),
+                    rate_basis_points: 2_000,
+                    created_at: datetime(),
+                    updated_at: datetime(),
+                }])
+            });
+        repo.order_writer
+            .expect_create_order()
+            .returning(|new_order| Ok(echo_order(new_order)));
+
+        let order = place_order(&repo, &user, payload(None, &[(1, 2), (2, 1)]))
+            .expect("expected order to be placed");
+
+        assert_eq!(order.net.amount, 3_000);
+        assert_eq!(order.tax.amount, 400);
+        assert_eq!(order.total.amount, 3_400);
+        assert_eq!(order.products[0].tax_rate_basis_points, 2_000);
+        assert!(!order.products[0].prices_include_tax);
+        assert_eq!(order.products[0].gross.amount, 2_400);
+        assert_eq!(order.products[1].tax.amount, 0);
+    }
+
+    #[test]
+    fn place_order_uses_rate_in_effect_now() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|_, _| {
+                let now = Utc::now().naive_utc();
+                let mut running = rate(1, 1, 450);
+                running.id = 2;
+                running.valid_from = Some(now - Duration::days(1));
+                running.valid_to = Some(now + Duration::days(1));
+                let mut upcoming = rate(1, 1, 300);
+                upcoming.id = 3;
+                upcoming.valid_from = Some(now + Duration::days(2));
+                Ok(Some(product(
+                    1,
+                    "Coffee",
+                    vec![rate(1, 1, 500), running, upcoming],
+                )))
+            });
+        repo.order_writer
+            .expect_create_order()
+            .returning(|new_order| Ok(echo_order(new_order)));
+
+        let order = place_order(&repo, &user, payload(None, &[(1, 2)]))
+            .expect("expected order to be placed");
+
+        assert_eq!(order.total.amount, 450 * 2);
+    }
+
+    #[test]
+    fn place_order_prices_derived_level_from_base_rate() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| {
+                let mut wholesale = price_level(2, true);
+                wholesale.derivation = Some(
+                    PriceDerivation::new(1)
+                        .with_percent(-1500)
+                        .with_rounding(PriceRounding::NinetyNine),
+                );
+                Ok((2, vec![price_level(1, false), wholesale]))
+            });
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| {
+                Ok(Some(match id {
+                    1 => product(1, "Coffee", vec![rate(1, 1, 1000)]),
+                    _ => product(2, "Tea", vec![rate(2, 1, 1000), rate(2, 2, 700)]),
+                }))
+            });
+        repo.order_writer
+            .expect_create_order()
+            .returning(|new_order| Ok(echo_order(new_order)));
+
+        let order = place_order(&repo, &user, payload(None, &[(1, 1), (2, 1)]))
+            .expect("expected order to be placed");
+
+        assert_eq!(order.products[0].price.amount, 899);
+        assert_eq!(order.products[1].price.amount, 700);
+    }
+
+    #[test]
+    fn place_order_applies_quantity_breaks() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| {
+                let mut case = rate(id, 1, 900);
+                case.id += 10;
+                case.min_quantity = 10;
+                Ok(Some(product(id, "Coffee", vec![rate(id, 1, 1000), case])))
+            });
+        repo.order_writer
+            .expect_create_order()
+            .returning(|new_order| Ok(echo_order(new_order)));
+
+        let order = place_order(&repo, &user, payload(None, &[(1, 9), (2, 12)]))
+            .expect("expected order to be placed");
+
+        assert_eq!(order.products[0].price.amount, 1000);
+        assert_eq!(order.products[1].price.amount, 900);
+    }
+
+    #[test]
+    fn place_order_converts_lines_into_order_currency() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| {
+                Ok(Some(match id {
+                    1 => product(1, "Coffee", vec![rate(1, 1, 500)]),
+                    _ => {
+                        let mut tea_rate = rate(2, 1, 1000);
+                        tea_rate.price = Money::new(1000, "EUR");
+                        let mut tea = product(2, "Tea", vec![tea_rate]);
+                        tea.currency = "EUR".to_string();
+                        tea
+                    }
+                }))
+            });
+        repo.exchange_rate_reader
+            .expect_list_exchange_rates()
+            .times(1)
+            .returning(|hub_id| {
+                Ok(vec![ExchangeRate {
+                    id: 1,
+                    hub_id,
+                    base_currency: "EUR".to_string(),
+                    quote_currency: "USD".to_string(),
+                    rate_micros: 1_100_000,
+                    created_at: datetime(),
+                    updated_at: datetime(),
+                }])
+            });
+        repo.order_writer
+            .expect_create_order()
+            .returning(|new_order| Ok(echo_order(new_order)));
+
+        let mut request = payload(None, &[(1, 1), (2, 2)]);
+        request.currency = Some("usd".to_string());
+        let order = place_order(&repo, &user, request).expect("expected order to be placed");
+
+        assert_eq!(order.currency, "USD");
+        assert_eq!(order.total.amount, 500 + 2200);
+        assert_eq!(order.products[0].exchange_rate_micros, 1_000_000);
+        assert_eq!(order.products[1].price.currency, "EUR");
+        assert_eq!(order.products[1].price.amount, 1000);
+        assert_eq!(order.products[1].exchange_rate_micros, 1_100_000);
+    }
+
+    #[test]
+    fn place_order_rejects_currency_without_exchange_rate() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|_, _| Ok(Some(product(1, "Coffee", vec![rate(1, 1, 500)]))));
+        repo.exchange_rate_reader
+            .expect_list_exchange_rates()
+            .returning(|_| Ok(Vec::new()));
+        repo.order_writer.expect_create_order().never();
+
+        let mut request = payload(None, &[(1, 1)]);
+        request.currency = Some("RUB".to_string());
+        let result = place_order(&repo, &user, request);
+
+        assert!(matches!(result, Err(ServiceError::Form(message)) if message.contains("RUB")));
+    }
+
+    #[test]
+    fn place_order_rejects_archived_products() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|_, _| {
+                let mut archived = product(1, "Coffee", vec![rate(1, 1, 500)]);
+                archived.is_archived = true;
+                Ok(Some(archived))
+            });
+        repo.order_writer.expect_create_order().never();
+
+        let result = place_order(&repo, &user, payload(None, &[(1, 1)]));
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
+
+    #[test]
+    fn place_order_rejects_quantities_above_available_stock() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|_, _| {
+                let mut limited = product(1, "Coffee", vec![rate(1, 1, 500)]);
+                limited.allow_oversell = false;
+                limited.stock = StockLevel {
+                    on_hand: 5,
+                    reserved: 2,
+                };
+                Ok(Some(limited))
+            });
+        repo.order_writer.expect_create_order().never();
+
+        let result = place_order(&repo, &user, payload(None, &[(1, 4)]));
+
+        match result {
+            Err(ServiceError::Form(message)) => {
+                assert!(message.contains("only 3 units available"), "{message}")
+            }
+            other => panic!("expected form error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn place_order_rejects_products_without_rate() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|_, _| Ok(Some(product(1, "Coffee", vec![rate(1, 2, 500)]))));
+        repo.order_writer.expect_create_order().never();
+
+        let result = place_order(&repo, &user, payload(None, &[(1, 1)]));
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
+
+    #[test]
+    fn place_order_returns_not_found_for_unknown_customer() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.customer_reader
+            .expect_get_customer_by_id()
+            .returning(|_, _| Ok(None));
+        repo.order_writer.expect_create_order().never();
+
+        let result = place_order(&repo, &user, payload(Some(9), &[(1, 1)]));
+
+        assert!(matches!(result, Err(ServiceError::NotFound)));
+    }
+
+    fn promotion(code: &str, kind: PriceAdjustmentKind, value: i64) -> Promotion {
+        Promotion {
+            id: 11,
+            hub_id: 7,
+            code: code.to_string(),
+            kind,
+            value,
+            currency: "USD".to_string(),
+            min_order_cents: None,
+            valid_from: None,
+            valid_to: None,
+            usage_limit: None,
+            usage_count: 0,
+            category_id: None,
+            tag_id: None,
+            created_at: datetime(),
+            updated_at: datetime(),
+        }
+    }
+
+    #[test]
+    fn place_order_applies_percent_promotion_with_tax_share() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| {
+                let mut level = price_level(1, true);
+                level.prices_include_tax = false;
+                Ok((1, vec![level]))
+            });
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| {
+                let mut taxed = product(id, "Coffee", vec![rate(id, 1, 1_000)]);
+                taxed.tax_category_id = Some(4);
+                Ok(Some(taxed))
+            });
+        repo.tax_category_reader
+            .expect_list_tax_categories()
+            .returning(|hub_id| {
+                Ok(vec![TaxCategory {
+                    id: 4,
+                    hub_id,
+                    name: "VAT".to_string(),
+                    rate_basis_points: 2_000,
+                    created_at: datetime(),
+                    updated_at: datetime(),
+                }])
+            });
+        repo.promotion_reader
+            .expect_get_promotion_by_code()
+            .withf(|code, hub_id| code == "SALE10" && *hub_id == 7)
+            .returning(|code, _| Ok(Some(promotion(code, PriceAdjustmentKind::Percent, 1_000))));
+        repo.order_writer
+            .expect_create_order()
+            .returning(|new_order| Ok(echo_order(new_order)));
+
+        let mut order_payload = payload(None, &[(1, 2)]);
+        order_payload.promo_code = Some(" sale10 ".to_string());
+        let order = place_order(&repo, &user, order_payload).expect("expected order to be placed");
+
+        let discount = order.discount.expect("discount applied");
+        assert_eq!(discount.promotion_id, Some(11));
+        assert_eq!(discount.code, "SALE10");
+        assert_eq!(discount.gross.amount, 240);
+        assert_eq!(discount.tax.amount, 40);
+        assert_eq!(discount.net.amount, 200);
+        assert_eq!(order.total.amount, 2_160);
+        assert_eq!(order.tax.amount, 360);
+        assert_eq!(order.net.amount, 1_800);
+    }
+
+    #[test]
+    fn place_order_rejects_unredeemable_promotions() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| Ok(Some(product(id, "Coffee", vec![rate(id, 1, 1_000)]))));
+        repo.category_reader
+            .expect_list_categories()
+            .returning(|_| Ok((0, Vec::new())));
+        repo.promotion_reader
+            .expect_get_promotion_by_code()
+            .returning(|code, _| {
+                let mut found = promotion(code, PriceAdjustmentKind::Fixed, 500);
+                match code {
+                    "OLD" => found.valid_to = Some(datetime()),
+                    "TEA" => found.category_id = Some(9),
+                    _ => return Ok(None),
+                }
+                Ok(Some(found))
+            });
+        repo.order_writer.expect_create_order().never();
+
+        for code in ["OLD", "TEA", "MISSING"] {
+            let mut order_payload = payload(None, &[(1, 1)]);
+            order_payload.promo_code = Some(code.to_string());
+
+            let result = place_order(&repo, &user, order_payload);
+
+            assert!(
+                matches!(&result, Err(ServiceError::Form(message)) if message.contains(code)),
+                "unexpected result for {code}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn place_order_reports_only_used_up_promotions_as_conflict() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| Ok(Some(product(id, "Coffee", vec![rate(id, 1, 1_000)]))));
+        repo.order_writer
+            .expect_create_order()
+            .returning(|new_order| {
+                let message = match new_order.reference.as_deref() {
+                    Some("USED-UP") => PROMOTION_USED_UP,
+                    _ => "UNIQUE constraint failed: orders.reference",
+                };
+                Err(RepositoryError::ConstraintViolation(message.to_string()))
+            });
+
+        let mut used_up = payload(None, &[(1, 1)]);
+        used_up.reference = Some("USED-UP".to_string());
+        assert!(matches!(
+            place_order(&repo, &user, used_up),
+            Err(ServiceError::Conflict)
+        ));
+
+        let mut duplicate = payload(None, &[(1, 1)]);
+        duplicate.reference = Some("ORD-1".to_string());
+        assert!(matches!(
+            place_order(&repo, &user, duplicate),
+            Err(ServiceError::Repository(
+                RepositoryError::ConstraintViolation(_)
+            ))
+        ));
+    }
+
+    fn stored_order(id: i32, status: OrderStatus) -> Order {
+        Order {
+            id,
+            hub_id: 7,
+            customer_id: None,
+            reference: None,
+            status,
+            notes: None,
+            total: Money::zero("USD"),
+            net: Money::zero("USD"),
+            tax: Money::zero("USD"),
+            currency: "USD".to_string(),
+            products: Vec::new(),
+            discount: None,
+            created_at: datetime(),
+            updated_at: datetime(),
+        }
+    }
+
+    #[test]
+    fn transition_order_records_change_author() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| Ok(Some(stored_order(id, OrderStatus::Pending))));
+        repo.order_writer
+            .expect_transition_order_status()
+            .times(1)
+            .withf(|hub_id, change| {
+                *hub_id == 7
+                    && change.order_id == 3
+                    && change.from_status == OrderStatus::Pending
+                    && change.to_status == OrderStatus::Processing
+                    && change.changed_by_email == "user@example.com"
+            })
+            .returning(|_, change| Ok(stored_order(change.order_id, change.to_status)));
+
+        let form = TransitionOrderForm {
+            status: "Processing".to_string(),
+        };
+        let order = transition_order(&repo, &user, 3, form).expect("expected transition");
+
+        assert_eq!(order.status, OrderStatus::Processing);
+    }
+
+    #[test]
+    fn transition_order_rejects_leaving_terminal_status() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| Ok(Some(stored_order(id, OrderStatus::Cancelled))));
+        repo.order_writer.expect_transition_order_status().never();
+
+        let form = TransitionOrderForm {
+            status: "Pending".to_string(),
+        };
+        let result = transition_order(&repo, &user, 3, form);
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
+
+    #[test]
+    fn transition_order_rejects_completed_to_draft() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| Ok(Some(stored_order(id, OrderStatus::Completed))));
+        repo.order_writer.expect_transition_order_status().never();
+
+        let form = TransitionOrderForm {
+            status: "Draft".to_string(),
+        };
+        let result = transition_order(&repo, &user, 3, form);
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
+
+    #[test]
+    fn load_order_status_history_returns_not_found_for_foreign_order() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|_, _| Ok(None));
+        repo.order_reader.expect_list_order_status_history().never();
+
+        let result = load_order_status_history(&repo, &user, 3);
+
+        assert!(matches!(result, Err(ServiceError::NotFound)));
+    }
+
+    fn edit_form(lines: &[(usize, &str)], add_product_id: Option<&str>) -> EditOrderForm {
+        EditOrderForm {
+            reference: Some("ORD-1".to_string()),
+            notes: None,
+            lines: lines
+                .iter()
+                .map(|(index, quantity)| EditOrderLineForm {
+                    index: *index,
+                    quantity: quantity.to_string(),
+                })
+                .collect(),
+            add_product_id: add_product_id.map(str::to_string),
+            add_quantity: Some("2".to_string()),
+        }
+    }
+
+    #[test]
+    fn update_order_details_recalculates_total() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| {
+                let mut order = stored_order(id, OrderStatus::Pending);
+                order.products = vec![
+                    OrderProduct::new("Coffee", Money::new(500, "USD"), 1).with_product_id(1),
+                    OrderProduct::new("Tea", Money::new(250, "USD"), 4).with_product_id(2),
+                ];
+                order.total.amount = 1500;
+                Ok(Some(order))
+            });
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| {
+                Ok(Some(match id {
+                    1 => product(1, "Coffee", vec![rate(1, 1, 500)]),
+                    _ => product(3, "Cocoa", vec![rate(3, 1, 300)]),
+                }))
+            });
+        repo.order_writer
+            .expect_update_order()
+            .times(1)
+            .withf(|order_id, hub_id, updates| {
+                let products = updates.products.as_deref().unwrap_or_default();
+                *order_id == 4
+                    && *hub_id == 7
+                    && updates.expected_status == OrderStatus::Pending
+                    && updates.reference.as_deref() == Some("ORD-1")
+                    && updates.total.amount == 500 * 3 + 300 * 2
+                    && products.len() == 2
+                    && products[0].price.amount == 500
+                    && products[1].product_id == Some(3)
+            })
+            .returning(|order_id, _, updates| {
+                let mut order = stored_order(order_id, OrderStatus::Pending);
+                order.total.amount = updates.total.amount;
+                Ok(order)
+            });
+
+        let order =
+            update_order_details(&repo, &user, 4, edit_form(&[(0, "3"), (1, "0")], Some("3")))
+                .expect("expected order to be updated");
+
+        assert_eq!(order.total.amount, 2100);
+    }
+
+    #[test]
+    fn update_order_details_reprices_changed_quantities() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| {
+                let mut order = stored_order(id, OrderStatus::Pending);
+                order.products = vec![
+                    OrderProduct::new("Coffee", Money::new(1000, "USD"), 5).with_product_id(1),
+                    OrderProduct::new("Tea", Money::new(900, "USD"), 12).with_product_id(2),
+                    OrderProduct::new("Cocoa", Money::new(700, "USD"), 1).with_product_id(3),
+                ];
+                Ok(Some(order))
+            });
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|id, _| {
+                let mut case = rate(id, 1, 900);
+                case.id += 10;
+                case.min_quantity = 10;
+                Ok(Some(product(id, "Coffee", vec![rate(id, 1, 1000), case])))
+            });
+        repo.order_writer
+            .expect_update_order()
+            .times(1)
+            .withf(|_, _, updates| {
+                let prices: Vec<_> = updates
+                    .products
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|line| (line.quantity, line.price.amount))
+                    .collect();
+                // Cocoa is unchanged and keeps its captured price.
+                prices == [(10, 900), (9, 1000), (1, 700)]
+            })
+            .returning(|order_id, _, updates| {
+                let mut order = stored_order(order_id, OrderStatus::Pending);
+                order.total.amount = updates.total.amount;
+                Ok(order)
+            });
+
+        let order = update_order_details(
+            &repo,
+            &user,
+            4,
+            edit_form(&[(0, "10"), (1, "9"), (2, "1")], None),
+        )
+        .expect("expected order to be updated");
+
+        assert_eq!(order.total.amount, 10 * 900 + 9 * 1000 + 700);
+    }
+
+    #[test]
+    fn update_order_details_rechecks_stock_of_changed_lines() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| {
+                let mut order = stored_order(id, OrderStatus::Pending);
+                order.products =
+                    vec![OrderProduct::new("Coffee", Money::new(500, "USD"), 2).with_product_id(1)];
+                Ok(Some(order))
+            });
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|_, _| {
+                let mut limited = product(1, "Coffee", vec![rate(1, 1, 500)]);
+                limited.allow_oversell = false;
+                limited.stock = StockLevel {
+                    on_hand: 5,
+                    reserved: 2,
+                };
+                Ok(Some(limited))
+            });
+        repo.order_writer.expect_update_order().never();
+
+        // Raising the quantity directly and merging an added product both
+        // exceed the 3 available units.
+        for form in [
+            edit_form(&[(0, "4")], None),
+            edit_form(&[(0, "2")], Some("1")),
+        ] {
+            match update_order_details(&repo, &user, 4, form) {
+                Err(ServiceError::Form(message)) => {
+                    assert!(message.contains("only 3 units available"), "{message}")
+                }
+                other => panic!("expected form error, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn update_order_details_recalculates_discount() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| {
+                let mut order = stored_order(id, OrderStatus::Pending);
+                order.products =
+                    vec![OrderProduct::new("Coffee", Money::new(500, "USD"), 2).with_product_id(1)];
+                let mut discount = OrderDiscount::from_promotion(
+                    &promotion("SALE10", PriceAdjustmentKind::Percent, 1_000),
+                    "USD",
+                );
+                discount.gross.amount = 100;
+                discount.net.amount = 100;
+                order.discount = Some(discount);
+                order.total.amount = 900;
+                Ok(Some(order))
+            });
+        repo.promotion_reader.expect_get_promotion_by_code().never();
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|_, _| Ok(Some(product(1, "Coffee", vec![rate(1, 1, 500)]))));
+        repo.order_writer
+            .expect_update_order()
+            .times(1)
+            .withf(|_, _, updates| {
+                updates.total.amount == 1_350
+                    && updates
+                        .discount
+                        .as_ref()
+                        .is_some_and(|discount| discount.gross.amount == 150)
+            })
+            .returning(|order_id, _, updates| {
+                let mut order = stored_order(order_id, OrderStatus::Pending);
+                order.total.amount = updates.total.amount;
+                order.discount = updates.discount.clone();
+                Ok(order)
+            });
+
+        let order = update_order_details(&repo, &user, 4, edit_form(&[(0, "3")], None))
+            .expect("expected order to be updated");
+
+        assert_eq!(order.total.amount, 1_350);
+    }
+
+    #[test]
+    fn update_order_details_rejects_totals_below_promotion_minimum() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| {
+                let mut order = stored_order(id, OrderStatus::Pending);
+                order.products = vec![
+                    OrderProduct::new("Coffee", Money::new(500, "USD"), 20).with_product_id(1),
+                ];
+                let mut promotion = promotion("BIG10", PriceAdjustmentKind::Percent, 1_000);
+                promotion.min_order_cents = Some(10_000);
+                order.discount = Some(OrderDiscount::from_promotion(&promotion, "USD"));
+                Ok(Some(order))
+            });
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((1, vec![price_level(1, true)])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(|_, _| Ok(Some(product(1, "Coffee", vec![rate(1, 1, 500)]))));
+        repo.order_writer.expect_update_order().never();
+
+        let result = update_order_details(&repo, &user, 4, edit_form(&[(0, "19")], None));
+
+        match result {
+            Err(ServiceError::Form(message)) => {
+                assert!(message.contains("at least 100.00 USD"), "{message}")
+            }
+            other => panic!("expected form error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn update_order_details_rejects_completed_orders() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| Ok(Some(stored_order(id, OrderStatus::Completed))));
+        repo.order_writer.expect_update_order().never();
+
+        let result = update_order_details(&repo, &user, 4, edit_form(&[], None));
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
+
+    #[test]
+    fn update_order_details_rejects_removing_every_line() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| {
+                let mut order = stored_order(id, OrderStatus::Draft);
+                order.products = vec![OrderProduct::new("Coffee", Money::new(500, "USD"), 1)];
+                Ok(Some(order))
+            });
+        repo.order_writer.expect_update_order().never();
+
+        let result = update_order_details(&repo, &user, 4, edit_form(&[(0, "0")], None));
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
+
+    #[test]
+    fn load_order_page_skips_products_for_closed_orders() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_get_order_by_id()
+            .returning(|id, _| Ok(Some(stored_order(id, OrderStatus::Processing))));
+        repo.order_reader
+            .expect_list_order_status_history()
+            .returning(|_, _| Ok(Vec::new()));
+        repo.product_reader.expect_list_products().never();
+
+        let data = load_order_page(&repo, &user, 4).expect("expected order page");
+
+        assert!(!data.is_editable);
+        assert_eq!(
+            data.allowed_transitions,
+            vec![
+                OrderStatus::Pending,
+                OrderStatus::Completed,
+                OrderStatus::Cancelled
+            ]
+        );
+    }
+
+    #[test]
+    fn export_orders_requires_role() {
+        let repo = FakeRepo::new();
+        let user = user_with_role("viewer");
+
+        let result = export_orders(&repo, &user, IndexQuery::default(), ExportFormat::Csv);
+
+        assert!(matches!(result, Err(ServiceError::Unauthorized)));
+    }
+
+    #[test]
+    fn export_orders_writes_one_csv_row_per_line() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_list_orders()
+            .times(1)
+            .withf(|query| {
+                query.hub_id == 7
+                    && query.created_before.is_some()
+                    && query.pagination.as_ref().is_some_and(|pagination| {
+                        pagination.page == 1 && pagination.per_page == ORDER_EXPORT_PAGE_SIZE
+                    })
+            })
+            .returning(|_| {
+                let mut order = stored_order(3, OrderStatus::Pending);
+                order.reference = Some("ORD-3".to_string());
+                order.customer_id = Some(5);
+                order.products = vec![
+                    OrderProduct::new("Coffee", Money::new(450, "USD"), 2).with_sku("CF-1"),
+                    OrderProduct::new("Tea", Money::new(1205, "USD"), 1),
+                ];
+                Ok((1, vec![order]))
+            });
+        repo.customer_reader
+            .expect_get_customer_by_id()
+            .times(1)
+            .returning(|id, hub_id| {
+                Ok(Some(Customer {
+                    id,
+                    hub_id,
+                    name: "Alice".to_string(),
+                    email: "alice@example.com".to_string(),
+                    phone: None,
+                    price_level_id: None,
+                }))
+            });
+
+        let bytes = export_orders(&repo, &user, IndexQuery::default(), ExportFormat::Csv)
+            .expect("expected export");
+        let content = String::from_utf8(bytes).expect("utf-8 csv");
+        let lines: Vec<&str> = content.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], ORDER_EXPORT_HEADERS.join(","));
+        assert_eq!(
+            lines[1],
+            "3,ORD-3,Pending,2024-01-01 00:00:00,Alice,alice@example.com,CF-1,Coffee,2,4.50,9.00,USD,1,9.00,USD"
+        );
+        assert!(lines[2].ends_with(",Tea,1,12.05,12.05,USD,1,12.05,USD"));
+    }
+
+    #[test]
+    fn export_orders_builds_xlsx_workbook() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader.expect_list_orders().returning(|_| {
+            let mut order = stored_order(3, OrderStatus::Completed);
+            order.products = vec![OrderProduct::new("Coffee", Money::new(450, "USD"), 2)];
+            Ok((1, vec![order]))
+        });
+
+        let bytes = export_orders(&repo, &user, IndexQuery::default(), ExportFormat::Xlsx)
+            .expect("expected export");
+
+        assert!(bytes.starts_with(b"PK"));
+    }
+
+    #[test]
+    fn export_orders_pages_through_all_orders() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.order_reader
+            .expect_list_orders()
+            .times(2)
+            .returning(|query| {
+                let page = query.pagination.expect("export pages through orders").page;
+                let count = if page == 1 { ORDER_EXPORT_PAGE_SIZE } else { 1 };
+                let orders = (0..count)
+                    .map(|index| {
+                        let mut order = stored_order(index as i32 + 1, OrderStatus::Pending);
+                        order.products =
+                            vec![OrderProduct::new("Coffee", Money::new(450, "USD"), 1)];
+                        order
+                    })
+                    .collect();
+                Ok((ORDER_EXPORT_PAGE_SIZE + 1, orders))
+            });
+
+        let bytes = export_orders(&repo, &user, IndexQuery::default(), ExportFormat::Csv)
+            .expect("expected export");
+        let content = String::from_utf8(bytes).expect("utf-8 csv");
+
+        assert_eq!(content.lines().count(), ORDER_EXPORT_PAGE_SIZE + 2);
+    }
+}
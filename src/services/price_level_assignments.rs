@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::pagination::{DEFAULT_ITEMS_PER_PAGE, Paginated};
+use pushkind_common::routes::check_role;
+use serde::{Deserialize, Serialize};
+
+use crate::PRICE_LEVEL_APPROVER_ROLE;
+use crate::domain::customer::{Customer, CustomerListQuery};
+use crate::domain::price_level::PriceLevelListQuery;
+use crate::domain::price_level_assignment::{
+    PriceLevelAssignmentDecision, PriceLevelAssignmentListQuery, PriceLevelAssignmentRequest,
+    PriceLevelAssignmentStatus,
+};
+use crate::repository::{
+    CustomerReader, PriceLevelAssignmentReader, PriceLevelAssignmentWriter, PriceLevelReader,
+};
+use crate::services::{ServiceError, ServiceResult};
+
+/// Query parameters accepted by the assignment approval queue.
+#[derive(Debug, Default, Deserialize)]
+pub struct PriceLevelRequestsQuery {
+    /// Review status to show; pending requests by default.
+    pub status: Option<PriceLevelAssignmentStatus>,
+    /// Page requested by the UI (1-based).
+    pub page: Option<usize>,
+}
+
+/// Assignment request enriched with the customer and price level names.
+#[derive(Debug, Serialize)]
+pub struct PriceLevelRequestView {
+    #[serde(flatten)]
+    pub request: PriceLevelAssignmentRequest,
+    /// Display name of the customer.
+    pub customer_name: String,
+    /// Email of the customer.
+    pub customer_email: String,
+    /// Phone of the customer when known.
+    pub customer_phone: Option<String>,
+    /// Name of the level currently assigned to the customer, `None` for the hub default.
+    pub current_price_level_name: Option<String>,
+    /// Name of the requested level, `None` for the hub default.
+    pub requested_price_level_name: Option<String>,
+}
+
+/// Data required to render the approval queue template.
+pub struct PriceLevelRequestsPageData {
+    /// Paginated requests with the selected status.
+    pub requests: Paginated<PriceLevelRequestView>,
+    /// Status the queue is filtered by.
+    pub status: PriceLevelAssignmentStatus,
+}
+
+/// Loads client price level assignment requests for the approval queue.
+pub fn load_price_level_requests<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    query: PriceLevelRequestsQuery,
+) -> ServiceResult<PriceLevelRequestsPageData>
+where
+    R: PriceLevelAssignmentReader + CustomerReader + PriceLevelReader + ?Sized,
+{
+    if !check_role(PRICE_LEVEL_APPROVER_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let status = query.status.unwrap_or_default();
+    let page = query.page.unwrap_or(1);
+
+    let (total, requests) = repo
+        .list_price_level_assignment_requests(
+            PriceLevelAssignmentListQuery::new(user.hub_id)
+                .status(status)
+                .paginate(page, DEFAULT_ITEMS_PER_PAGE),
+        )
+        .map_err(ServiceError::from)?;
+
+    let (_, customers) = repo
+        .list_customers(CustomerListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+    let customers: HashMap<i32, Customer> = customers
+        .into_iter()
+        .map(|customer| (customer.id, customer))
+        .collect();
+
+    let (_, price_levels) = repo
+        .list_price_levels(PriceLevelListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+    let level_names: HashMap<i32, String> = price_levels
+        .into_iter()
+        .map(|level| (level.id, level.name))
+        .collect();
+    let level_name = |id: Option<i32>| id.and_then(|id| level_names.get(&id).cloned());
+
+    let items = requests
+        .into_iter()
+        .map(|request| {
+            let customer = customers.get(&request.customer_id);
+            PriceLevelRequestView {
+                customer_name: customer.map(|c| c.name.clone()).unwrap_or_default(),
+                customer_email: customer.map(|c| c.email.clone()).unwrap_or_default(),
+                customer_phone: customer.and_then(|c| c.phone.clone()),
+                current_price_level_name: level_name(customer.and_then(|c| c.price_level_id)),
+                requested_price_level_name: level_name(request.price_level_id),
+                request,
+            }
+        })
+        .collect();
+
+    let total_pages = total.div_ceil(DEFAULT_ITEMS_PER_PAGE);
+
+    Ok(PriceLevelRequestsPageData {
+        requests: Paginated::new(items, page, total_pages),
+        status,
+    })
+}
+
+/// Approves or rejects a pending assignment request.
+///
+/// Approval applies the requested level to the customer.
+pub fn decide_price_level_request<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    request_id: i32,
+    approve: bool,
+) -> ServiceResult<PriceLevelAssignmentRequest>
+where
+    R: PriceLevelAssignmentWriter + ?Sized,
+{
+    if !check_role(PRICE_LEVEL_APPROVER_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let decision = PriceLevelAssignmentDecision::new(request_id, approve, &user.email, &user.name);
+
+    repo.decide_price_level_assignment_request(user.hub_id, &decision)
+        .map_err(ServiceError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    use crate::SERVICE_ACCESS_ROLE;
+    use crate::repository::mock::MockPriceLevelAssignmentWriter;
+
+    fn user_with_roles(roles: &[&str]) -> AuthenticatedUser {
+        AuthenticatedUser {
+            sub: "approver".to_string(),
+            email: "approver@example.com".to_string(),
+            hub_id: 3,
+            name: "Approver".to_string(),
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+            exp: 0,
+        }
+    }
+
+    #[test]
+    fn decide_price_level_request_requires_approver_role() {
+        let repo = MockPriceLevelAssignmentWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        let result = decide_price_level_request(&repo, &user, 1, true);
+
+        assert!(matches!(result, Err(ServiceError::Unauthorized)));
+    }
+
+    #[test]
+    fn decide_price_level_request_records_approver() {
+        let mut repo = MockPriceLevelAssignmentWriter::new();
+        let user = user_with_roles(&[PRICE_LEVEL_APPROVER_ROLE]);
+
+        repo.expect_decide_price_level_assignment_request()
+            .times(1)
+            .withf(|hub_id, decision| {
+                *hub_id == 3
+                    && decision.request_id == 9
+                    && !decision.approve
+                    && decision.decided_by_email == "approver@example.com"
+            })
+            .returning(|hub_id, decision| {
+                Ok(PriceLevelAssignmentRequest {
+                    id: decision.request_id,
+                    hub_id,
+                    customer_id: 4,
+                    price_level_id: Some(2),
+                    status: decision.status(),
+                    requested_by_email: "admin@example.com".to_string(),
+                    requested_by_name: "Admin".to_string(),
+                    decided_by_email: Some(decision.decided_by_email.clone()),
+                    decided_by_name: Some(decision.decided_by_name.clone()),
+                    created_at: NaiveDateTime::default(),
+                    decided_at: Some(NaiveDateTime::default()),
+                })
+            });
+
+        let request =
+            decide_price_level_request(&repo, &user, 9, false).expect("expected rejection");
+
+        assert_eq!(request.status, PriceLevelAssignmentStatus::Rejected);
+    }
+}
@@ -1,14 +1,25 @@
+use std::collections::HashSet;
+
 use pushkind_common::domain::auth::AuthenticatedUser;
 use pushkind_common::routes::check_role;
 use serde::{Deserialize, Serialize};
 
 use crate::SERVICE_ACCESS_ROLE;
 use crate::domain::customer::{CustomerListQuery, NewCustomer};
-use crate::domain::price_level::{PriceLevel, PriceLevelListQuery};
+use crate::domain::price_level::{
+    PriceDerivation, PriceLevel, PriceLevelListQuery, derivation_forms_cycle,
+};
+use crate::domain::price_level_assignment::{
+    NewPriceLevelAssignmentRequest, PriceLevelAssignmentRequest,
+};
 use crate::forms::price_levels::{
     AddPriceLevelForm, AssignClientPriceLevelPayload, EditPriceLevelForm, UploadPriceLevelsForm,
+    UploadPriceLevelsFormError,
 };
-use crate::repository::{CustomerReader, CustomerWriter, PriceLevelReader, PriceLevelWriter};
+use crate::repository::{
+    CustomerReader, CustomerWriter, PriceLevelAssignmentWriter, PriceLevelReader, PriceLevelWriter,
+};
+use crate::services::uploads::{UploadRowError, write_upload_errors_csv};
 use crate::services::{ServiceError, ServiceResult};
 
 /// Query parameters accepted by the price levels index page.
@@ -125,7 +136,7 @@ pub fn create_price_level<R>(
     form: AddPriceLevelForm,
 ) -> ServiceResult<PriceLevel>
 where
-    R: PriceLevelWriter + ?Sized,
+    R: PriceLevelReader + PriceLevelWriter + ?Sized,
 {
     if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
         return Err(ServiceError::Unauthorized);
@@ -135,6 +146,8 @@ where
         .into_new_price_level(user.hub_id)
         .map_err(|err| ServiceError::Form(err.to_string()))?;
 
+    ensure_valid_derivation(repo, user.hub_id, None, new_price_level.derivation.as_ref())?;
+
     repo.create_price_level(&new_price_level)
         .map_err(ServiceError::from)
 }
@@ -147,7 +160,7 @@ pub fn update_price_level<R>(
     form: EditPriceLevelForm,
 ) -> ServiceResult<PriceLevel>
 where
-    R: PriceLevelWriter + ?Sized,
+    R: PriceLevelReader + PriceLevelWriter + ?Sized,
 {
     if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
         return Err(ServiceError::Unauthorized);
@@ -157,10 +170,55 @@ where
         .into_update_price_level()
         .map_err(|err| ServiceError::Form(err.to_string()))?;
 
+    ensure_valid_derivation(
+        repo,
+        user.hub_id,
+        Some(price_level_id),
+        updates.derivation.as_ref(),
+    )?;
+
     repo.update_price_level(price_level_id, user.hub_id, &updates)
         .map_err(ServiceError::from)
 }
 
+/// Rejects derivations from levels of other hubs and derivation cycles.
+fn ensure_valid_derivation<R>(
+    repo: &R,
+    hub_id: i32,
+    price_level_id: Option<i32>,
+    derivation: Option<&PriceDerivation>,
+) -> ServiceResult<()>
+where
+    R: PriceLevelReader + ?Sized,
+{
+    let Some(derivation) = derivation else {
+        return Ok(());
+    };
+
+    let (_, price_levels) = repo
+        .list_price_levels(PriceLevelListQuery::new(hub_id))
+        .map_err(ServiceError::from)?;
+
+    if !price_levels
+        .iter()
+        .any(|level| level.id == derivation.base_price_level_id)
+    {
+        return Err(ServiceError::Form("base price level not found".to_string()));
+    }
+
+    if derivation_forms_cycle(
+        &price_levels,
+        price_level_id,
+        derivation.base_price_level_id,
+    ) {
+        return Err(ServiceError::Form(
+            "price level cannot be derived from itself".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 /// Imports price levels from an uploaded CSV file.
 pub fn import_price_levels<R>(
     repo: &R,
@@ -187,6 +245,95 @@ where
     Ok(count)
 }
 
+/// Price level listed in the import preview.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct PriceLevelPreviewRow {
+    /// Line of the uploaded CSV.
+    pub row: usize,
+    pub name: String,
+}
+
+/// Data required to render the price level import preview.
+#[derive(Debug, Serialize)]
+pub struct PriceLevelUploadPreview {
+    /// Price levels that would be created, in file order.
+    pub rows: Vec<PriceLevelPreviewRow>,
+    /// Every row error; the import is rejected while any are present.
+    pub errors: Vec<UploadRowError>,
+    /// Uploaded CSV, re-submitted when the operator confirms the import.
+    pub content: String,
+}
+
+/// Parses an uploaded price level CSV and lists the levels
+/// [`import_price_levels`] would create, together with all row errors, such
+/// as names that already exist in the hub.
+pub fn preview_price_levels<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    mut form: UploadPriceLevelsForm,
+) -> ServiceResult<PriceLevelUploadPreview>
+where
+    R: PriceLevelReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let content = form
+        .content()
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+    let report = form
+        .into_upload_report(user.hub_id)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    let (_, existing) = repo
+        .list_price_levels(PriceLevelListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+    let existing: HashSet<String> = existing
+        .into_iter()
+        .map(|level| level.name.to_lowercase())
+        .collect();
+
+    let mut row_errors = report.errors;
+    let mut rows = Vec::with_capacity(report.rows.len());
+    for (row, level) in report.rows {
+        if existing.contains(&level.name.to_lowercase()) {
+            row_errors.push(UploadPriceLevelsFormError::AlreadyExists {
+                row,
+                name: level.name,
+            });
+        } else {
+            rows.push(PriceLevelPreviewRow {
+                row,
+                name: level.name,
+            });
+        }
+    }
+    row_errors.sort_by_key(UploadPriceLevelsFormError::row);
+
+    Ok(PriceLevelUploadPreview {
+        rows,
+        errors: row_errors
+            .iter()
+            .map(|err| UploadRowError::new(err.row(), err.to_string()))
+            .collect(),
+        content,
+    })
+}
+
+/// Returns the row errors of an uploaded price level CSV as a CSV report.
+pub fn price_level_upload_errors<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: UploadPriceLevelsForm,
+) -> ServiceResult<Vec<u8>>
+where
+    R: PriceLevelReader + ?Sized,
+{
+    let preview = preview_price_levels(repo, user, form)?;
+    write_upload_errors_csv(&preview.errors)
+}
+
 /// Deletes a price level for the authenticated user's hub.
 pub fn remove_price_level<R>(
     repo: &R,
@@ -204,14 +351,17 @@ where
         .map_err(ServiceError::from)
 }
 
-/// Persists a price level assignment for a single customer.
+/// Files a price level assignment for a single customer as a pending request.
+///
+/// The customer is created when missing, but its price level only changes once
+/// a user with [`crate::PRICE_LEVEL_APPROVER_ROLE`] approves the request.
 pub fn assign_price_level_to_client<R>(
     repo: &R,
     user: &AuthenticatedUser,
     payload: AssignClientPriceLevelPayload,
-) -> ServiceResult<()>
+) -> ServiceResult<PriceLevelAssignmentRequest>
 where
-    R: CustomerReader + CustomerWriter + ?Sized,
+    R: CustomerReader + CustomerWriter + PriceLevelAssignmentWriter + ?Sized,
 {
     if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
         return Err(ServiceError::Unauthorized);
@@ -245,16 +395,20 @@ where
                 new_customer = new_customer.with_phone(phone.clone());
             }
 
-            if let Some(price_level_id) = assignment.price_level_id {
-                new_customer = new_customer.with_price_level_id(price_level_id);
-            }
-
             repo.create_customer(&new_customer)
                 .map_err(ServiceError::from)?
         }
     };
 
-    repo.assign_price_level_to_customers(user.hub_id, &[customer.id], assignment.price_level_id)
+    let request = NewPriceLevelAssignmentRequest::new(
+        user.hub_id,
+        customer.id,
+        assignment.price_level_id,
+        &user.email,
+        &user.name,
+    );
+
+    repo.create_price_level_assignment_request(&request)
         .map_err(ServiceError::from)
 }
 
@@ -268,13 +422,19 @@ mod tests {
     use actix_multipart::form::tempfile::TempFile;
     use tempfile::NamedTempFile;
 
-    use crate::domain::customer::{Customer, CustomerListQuery, NewCustomer};
-    use crate::domain::price_level::PriceLevel;
+    use crate::domain::customer::{
+        Customer, CustomerImportItem, CustomerListQuery, CustomerMerge, NewCustomer, UpdateCustomer,
+    };
+    use crate::domain::price_level::{NewPriceLevel, PriceLevel, UpdatePriceLevel};
+    use crate::domain::price_level_assignment::{
+        PriceLevelAssignmentDecision, PriceLevelAssignmentStatus,
+    };
     use crate::forms::price_levels::{
         AddPriceLevelForm, AssignClientPriceLevelPayload, UploadPriceLevelsForm,
     };
     use crate::repository::mock::{
-        MockCustomerReader, MockCustomerWriter, MockPriceLevelReader, MockPriceLevelWriter,
+        MockCustomerReader, MockCustomerWriter, MockPriceLevelAssignmentWriter,
+        MockPriceLevelReader, MockPriceLevelWriter,
     };
     use crate::repository::{CustomerReader, CustomerWriter};
     use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
@@ -282,11 +442,16 @@ mod tests {
     struct CombinedCustomerRepo {
         reader: MockCustomerReader,
         writer: MockCustomerWriter,
+        requests: MockPriceLevelAssignmentWriter,
     }
 
     impl CombinedCustomerRepo {
         fn new(reader: MockCustomerReader, writer: MockCustomerWriter) -> Self {
-            Self { reader, writer }
+            Self {
+                reader,
+                writer,
+                requests: MockPriceLevelAssignmentWriter::new(),
+            }
         }
     }
 
@@ -335,6 +500,68 @@ mod tests {
             self.writer
                 .assign_price_level_to_customers(hub_id, customer_ids, price_level_id)
         }
+
+        fn update_customer(
+            &self,
+            customer_id: i32,
+            hub_id: i32,
+            updates: &UpdateCustomer,
+        ) -> RepositoryResult<Customer> {
+            self.writer.update_customer(customer_id, hub_id, updates)
+        }
+
+        fn delete_customer(&self, customer_id: i32, hub_id: i32) -> RepositoryResult<()> {
+            self.writer.delete_customer(customer_id, hub_id)
+        }
+
+        fn merge_customers(&self, merge: &CustomerMerge) -> RepositoryResult<Customer> {
+            self.writer.merge_customers(merge)
+        }
+
+        fn bulk_import_customers(
+            &self,
+            hub_id: i32,
+            items: &[CustomerImportItem],
+        ) -> RepositoryResult<()> {
+            self.writer.bulk_import_customers(hub_id, items)
+        }
+    }
+
+    impl PriceLevelAssignmentWriter for CombinedCustomerRepo {
+        fn create_price_level_assignment_request(
+            &self,
+            new_request: &NewPriceLevelAssignmentRequest,
+        ) -> RepositoryResult<PriceLevelAssignmentRequest> {
+            self.requests
+                .create_price_level_assignment_request(new_request)
+        }
+
+        fn decide_price_level_assignment_request(
+            &self,
+            hub_id: i32,
+            decision: &PriceLevelAssignmentDecision,
+        ) -> RepositoryResult<PriceLevelAssignmentRequest> {
+            self.requests
+                .decide_price_level_assignment_request(hub_id, decision)
+        }
+    }
+
+    fn pending_request(
+        new_request: &NewPriceLevelAssignmentRequest,
+    ) -> PriceLevelAssignmentRequest {
+        PriceLevelAssignmentRequest {
+            id: 1,
+            hub_id: new_request.hub_id,
+            customer_id: new_request.customer_id,
+            price_level_id: new_request.price_level_id,
+            status: PriceLevelAssignmentStatus::Pending,
+            requested_by_email: new_request.requested_by_email.clone(),
+            requested_by_name: new_request.requested_by_name.clone(),
+            decided_by_email: None,
+            decided_by_name: None,
+            created_at: fixed_datetime(),
+            decided_at: None,
+        }
     }
 
     fn fixed_datetime() -> NaiveDateTime {
@@ -344,6 +571,53 @@ mod tests {
         }
     }
 
+    /// Price level repository combining the reader and writer mocks.
+    #[derive(Default)]
+    struct PriceLevelRepo {
+        reader: MockPriceLevelReader,
+        writer: MockPriceLevelWriter,
+    }
+
+    impl PriceLevelReader for PriceLevelRepo {
+        fn get_price_level_by_id(
+            &self,
+            id: i32,
+            hub_id: i32,
+        ) -> RepositoryResult<Option<PriceLevel>> {
+            self.reader.get_price_level_by_id(id, hub_id)
+        }
+
+        fn list_price_levels(
+            &self,
+            query: PriceLevelListQuery,
+        ) -> RepositoryResult<(usize, Vec<PriceLevel>)> {
+            self.reader.list_price_levels(query)
+        }
+    }
+
+    impl PriceLevelWriter for PriceLevelRepo {
+        fn create_price_level(
+            &self,
+            new_price_level: &NewPriceLevel,
+        ) -> RepositoryResult<PriceLevel> {
+            self.writer.create_price_level(new_price_level)
+        }
+
+        fn update_price_level(
+            &self,
+            price_level_id: i32,
+            hub_id: i32,
+            updates: &UpdatePriceLevel,
+        ) -> RepositoryResult<PriceLevel> {
+            self.writer
+                .update_price_level(price_level_id, hub_id, updates)
+        }
+
+        fn delete_price_level(&self, price_level_id: i32, hub_id: i32) -> RepositoryResult<()> {
+            self.writer.delete_price_level(price_level_id, hub_id)
+        }
+    }
+
     fn sample_level(id: i32, hub_id: i32, name: &str) -> PriceLevel {
         PriceLevel {
             id,
@@ -352,6 +626,8 @@ mod tests {
             created_at: fixed_datetime(),
             updated_at: fixed_datetime(),
             is_default: false,
+            derivation: None,
+            prices_include_tax: true,
         }
     }
 
@@ -427,11 +703,12 @@ mod tests {
 
     #[test]
     fn create_price_level_requires_role() {
-        let repo = MockPriceLevelWriter::new();
+        let repo = PriceLevelRepo::default();
         let user = user_with_roles(&[]);
         let form = AddPriceLevelForm {
             name: "Retail".to_string(),
             default: false,
+            ..Default::default()
         };
 
         let result = create_price_level(&repo, &user, form);
@@ -441,15 +718,17 @@ mod tests {
 
     #[test]
     fn create_price_level_persists_price_level() {
-        let mut repo = MockPriceLevelWriter::new();
+        let mut repo = PriceLevelRepo::default();
         let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
         let form = AddPriceLevelForm {
             name: "Retail".to_string(),
             default: false,
+            ..Default::default()
         };
 
         let expected_hub = user.hub_id;
-        repo.expect_create_price_level()
+        repo.writer
+            .expect_create_price_level()
             .times(1)
             .withf(move |payload| payload.hub_id == expected_hub && payload.name == "Retail")
             .returning(move |_| Ok(sample_level(5, expected_hub, "Retail")));
@@ -463,11 +742,12 @@ mod tests {
 
     #[test]
     fn create_price_level_propagates_form_errors() {
-        let repo = MockPriceLevelWriter::new();
+        let repo = PriceLevelRepo::default();
         let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
         let form = AddPriceLevelForm {
             name: "   ".to_string(),
             default: false,
+            ..Default::default()
         };
 
         let result = create_price_level(&repo, &user, form);
@@ -485,11 +765,12 @@ mod tests {
 
     #[test]
     fn update_price_level_requires_role() {
-        let repo = MockPriceLevelWriter::new();
+        let repo = PriceLevelRepo::default();
         let user = user_with_roles(&[]);
         let form = EditPriceLevelForm {
             name: "Retail".to_string(),
             default: false,
+            ..Default::default()
         };
 
         let result = update_price_level(&repo, &user, 7, form);
@@ -499,15 +780,17 @@ mod tests {
 
     #[test]
     fn update_price_level_updates_record() {
-        let mut repo = MockPriceLevelWriter::new();
+        let mut repo = PriceLevelRepo::default();
         let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
         let form = EditPriceLevelForm {
             name: "  Retail Plus  ".to_string(),
             default: true,
+            ..Default::default()
         };
 
         let expected_hub = user.hub_id;
-        repo.expect_update_price_level()
+        repo.writer
+            .expect_update_price_level()
             .times(1)
             .withf(move |id, hub, updates| {
                 *id == 7
@@ -525,11 +808,12 @@ mod tests {
 
     #[test]
     fn update_price_level_propagates_form_errors() {
-        let repo = MockPriceLevelWriter::new();
+        let repo = PriceLevelRepo::default();
         let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
         let form = EditPriceLevelForm {
             name: "   ".to_string(),
             default: false,
+            ..Default::default()
         };
 
         let result = update_price_level(&repo, &user, 3, form);
@@ -547,14 +831,16 @@ mod tests {
 
     #[test]
     fn update_price_level_bubbles_not_found() {
-        let mut repo = MockPriceLevelWriter::new();
+        let mut repo = PriceLevelRepo::default();
         let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
         let form = EditPriceLevelForm {
             name: "Retail".to_string(),
             default: false,
+            ..Default::default()
         };
 
-        repo.expect_update_price_level()
+        repo.writer
+            .expect_update_price_level()
             .times(1)
             .return_once(|_, _, _| Err(RepositoryError::NotFound));
 
@@ -563,6 +849,31 @@ mod tests {
         assert!(matches!(result, Err(ServiceError::NotFound)));
     }
 
+    #[test]
+    fn update_price_level_rejects_derivation_cycles() {
+        let mut repo = PriceLevelRepo::default();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+        let hub_id = user.hub_id;
+        let form = EditPriceLevelForm {
+            name: "Retail".to_string(),
+            base_price_level_id: Some("2".to_string()),
+            adjustment: Some("10".to_string()),
+            ..Default::default()
+        };
+
+        repo.reader.expect_list_price_levels().returning(move |_| {
+            let retail = sample_level(1, hub_id, "Retail");
+            let mut wholesale = sample_level(2, hub_id, "Wholesale");
+            wholesale.derivation = Some(PriceDerivation::new(1).with_percent(-1500));
+            Ok((2, vec![retail, wholesale]))
+        });
+        repo.writer.expect_update_price_level().never();
+
+        let result = update_price_level(&repo, &user, 1, form);
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
+
     struct ClientAssignmentRepo {
         customer_reader: MockCustomerReader,
         price_level_reader: MockPriceLevelReader,
@@ -734,15 +1045,18 @@ mod tests {
                 Ok(Some(sample_customer(expected_customer_id, hub_id, None)))
             });
 
-        writer
-            .expect_assign_price_level_to_customers()
+        writer.expect_assign_price_level_to_customers().never();
+
+        let mut repo = CombinedCustomerRepo::new(reader, writer);
+        repo.requests
+            .expect_create_price_level_assignment_request()
             .times(1)
-            .withf(move |target_hub, ids, price_level_id| {
-                *target_hub == hub_id && ids == [expected_customer_id] && price_level_id == &Some(8)
+            .withf(move |request| {
+                request.hub_id == hub_id
+                    && request.customer_id == expected_customer_id
+                    && request.price_level_id == Some(8)
             })
-            .returning(|_, _, _| Ok(()));
-
-        let repo = CombinedCustomerRepo::new(reader, writer);
+            .returning(|request| Ok(pending_request(request)));
         let payload = AssignClientPriceLevelPayload {
             hub_id,
             name: "Customer Seven".to_string(),
@@ -751,13 +1065,15 @@ mod tests {
             price_level_id: Some(8),
         };
 
-        assign_price_level_to_client(&repo, &user, payload).expect("expected success");
+        let request =
+            assign_price_level_to_client(&repo, &user, payload).expect("expected success");
+        assert_eq!(request.status, PriceLevelAssignmentStatus::Pending);
     }
 
     #[test]
     fn assign_price_level_to_client_clears_assignment_without_phone() {
         let mut reader = MockCustomerReader::new();
-        let mut writer = MockCustomerWriter::new();
+        let writer = MockCustomerWriter::new();
         let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
         let hub_id = user.hub_id;
         let expected_customer = Customer {
@@ -778,15 +1094,14 @@ mod tests {
             })
             .returning(move |_, _, _| Ok(Some(expected_customer.clone())));
 
-        writer
-            .expect_assign_price_level_to_customers()
+        let mut repo = CombinedCustomerRepo::new(reader, writer);
+        repo.requests
+            .expect_create_price_level_assignment_request()
             .times(1)
-            .withf(move |target_hub, ids, price_level_id| {
-                *target_hub == hub_id && ids == [expected_customer_id] && price_level_id.is_none()
+            .withf(move |request| {
+                request.customer_id == expected_customer_id && request.price_level_id.is_none()
             })
-            .returning(|_, _, _| Ok(()));
-
-        let repo = CombinedCustomerRepo::new(reader, writer);
+            .returning(|request| Ok(pending_request(request)));
         let payload = AssignClientPriceLevelPayload {
             hub_id,
             name: "Client 55".to_string(),
@@ -823,7 +1138,7 @@ mod tests {
                         .as_ref()
                         .map(|value| value == "+1999000")
                         .unwrap_or(false)
-                    && new_customer.price_level_id == Some(1)
+                    && new_customer.price_level_id.is_none()
             })
             .returning(move |new_customer| {
                 Ok(Customer {
@@ -836,15 +1151,14 @@ mod tests {
                 })
             });
 
-        writer
-            .expect_assign_price_level_to_customers()
+        let mut repo = CombinedCustomerRepo::new(reader, writer);
+        repo.requests
+            .expect_create_price_level_assignment_request()
             .times(1)
-            .withf(move |target_hub, ids, price_level_id| {
-                *target_hub == hub_id && ids == [expected_customer_id] && price_level_id == &Some(1)
+            .withf(move |request| {
+                request.customer_id == expected_customer_id && request.price_level_id == Some(1)
             })
-            .return_once(|_, _, _| Ok(()));
-
-        let repo = CombinedCustomerRepo::new(reader, writer);
+            .returning(|request| Ok(pending_request(request)));
         let payload = AssignClientPriceLevelPayload {
             hub_id,
             name: "  Missing User  ".to_string(),
@@ -922,6 +1236,38 @@ mod tests {
         assert!(stored.contains(&"Wholesale".to_string()));
     }
 
+    #[test]
+    fn preview_price_levels_flags_existing_names() {
+        let mut repo = MockPriceLevelReader::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+        let hub_id = user.hub_id;
+
+        repo.expect_list_price_levels()
+            .times(1)
+            .returning(move |_| Ok((1, vec![sample_level(1, hub_id, "Retail")])));
+
+        let form = build_upload_form("name\nretail\nWholesale\n\"\"\n");
+
+        let preview = preview_price_levels(&repo, &user, form).expect("expected preview");
+
+        assert_eq!(
+            preview.rows,
+            vec![PriceLevelPreviewRow {
+                row: 3,
+                name: "Wholesale".to_string(),
+            }]
+        );
+        assert_eq!(
+            preview
+                .errors
+                .iter()
+                .map(|error| error.row)
+                .collect::<Vec<_>>(),
+            vec![Some(2), Some(4)]
+        );
+        assert!(preview.content.starts_with("name\nretail"));
+    }
+
     #[test]
     fn import_price_levels_handles_empty_upload() {
         let repo = MockPriceLevelWriter::new();
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use pushkind_common::domain::auth::AuthenticatedUser;
 use pushkind_common::pagination::{DEFAULT_ITEMS_PER_PAGE, Paginated};
 use pushkind_common::routes::check_role;
@@ -8,17 +9,27 @@ use serde::{Deserialize, Serialize};
 use crate::SERVICE_ACCESS_ROLE;
 use crate::domain::{
     category::{Category, CategoryTreeQuery},
+    money::Money,
     price_level::{PriceLevel, PriceLevelListQuery},
-    product::{Product, ProductListQuery},
-    product_price_level::{NewProductPriceLevelRate, ProductPriceLevelRate},
+    product::{
+        Product, ProductImportItem, ProductImportTarget, ProductListQuery, ProductPrice,
+        ProductRemoval, UpdateProduct,
+    },
+    product_price_history::{PriceChangeAuthor, ProductPriceChange, ProductPriceHistoryQuery},
+    product_price_level::{NewProductPriceLevelRate, ProductPriceLevelRate, RepricedRate},
     tag::{Tag, TagListQuery},
+    tax_category::TaxCategory,
 };
 use crate::forms::products::{
-    AddProductForm, EditProductForm, NewProductUpload, UploadProductsForm,
+    AddProductForm, BulkProductsForm, CATEGORY_PATH_SEPARATOR, CancelScheduledPriceForm,
+    DERIVED_PRICES_HEADER, EditProductForm, NewProductUpload, ProductFormError, ProductImportMode,
+    ProductUploadRow, RepriceProductsForm, ScheduleProductPriceForm, TAG_SEPARATOR,
+    UploadProductsForm, price_tier_header,
 };
 use crate::repository::{
-    CategoryReader, PriceLevelReader, ProductReader, ProductWriter, TagReader,
+    CategoryReader, PriceLevelReader, ProductReader, ProductWriter, TagReader, TaxCategoryReader,
 };
+use crate::services::uploads::{UploadRowError, write_upload_errors_csv};
 use crate::services::{ServiceError, ServiceResult};
 
 /// Query parameters accepted by the products index page.
@@ -43,6 +54,8 @@ pub struct ProductsPageData {
     pub price_levels: Vec<PriceLevel>,
     /// All available categories for the add product form.
     pub categories: Vec<Category>,
+    /// Tax categories of the hub for the product forms.
+    pub tax_categories: Vec<TaxCategory>,
     /// All available tags for the edit product modal.
     pub tags: Vec<Tag>,
     /// Whether archived items were requested.
@@ -56,7 +69,7 @@ pub fn load_products_page<R>(
     query: ProductsQuery,
 ) -> ServiceResult<ProductsPageData>
 where
-    R: ProductReader + PriceLevelReader + CategoryReader + TagReader + ?Sized,
+    R: ProductReader + PriceLevelReader + CategoryReader + TagReader + TaxCategoryReader + ?Sized,
 {
     if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
         return Err(ServiceError::Unauthorized);
@@ -99,12 +112,19 @@ where
         .map_err(ServiceError::from)?;
     tags.sort_by(|a, b| a.name.cmp(&b.name));
 
+    let tax_categories = repo
+        .list_tax_categories(user.hub_id)
+        .map_err(ServiceError::from)?;
+
     let level_lookup: HashMap<i32, &PriceLevel> =
         price_levels.iter().map(|level| (level.id, level)).collect();
 
+    let now = Utc::now().naive_utc();
     let view_items: Vec<ProductView> = items
         .into_iter()
-        .map(|product| ProductView::from_product(product, &level_lookup, &category_lookup))
+        .map(|product| {
+            ProductView::from_product(product, &price_levels, &level_lookup, &category_lookup, now)
+        })
         .collect();
 
     let total_pages = total.div_ceil(DEFAULT_ITEMS_PER_PAGE);
@@ -115,6 +135,7 @@ where
         search,
         price_levels,
         categories,
+        tax_categories,
         tags,
         show_archived,
     })
@@ -139,35 +160,605 @@ where
         .into_new_product_with_prices(user.hub_id, &price_levels)
         .map_err(|err| ServiceError::Form(err.to_string()))?;
 
-    persist_new_product(repo, user.hub_id, payload)
+    persist_new_product(repo, user, payload)
+}
+
+/// Outcome of a product CSV import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ProductImportSummary {
+    /// Rows that created a new product.
+    pub created: usize,
+    /// Rows that updated an existing product matched by SKU.
+    pub updated: usize,
+    /// Rows that matched an existing product without any difference.
+    pub unchanged: usize,
+}
+
+/// What importing an uploaded row does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ProductImportAction {
+    Create,
+    Update,
+    Unchanged,
+}
+
+/// Uploaded row as listed in the import preview.
+#[derive(Debug, Serialize)]
+pub struct ProductPreviewRow {
+    /// Line of the uploaded CSV.
+    pub row: usize,
+    pub action: ProductImportAction,
+    pub name: String,
+    pub sku: Option<String>,
+    pub units: Option<String>,
+    pub currency: String,
+    pub category: Option<String>,
+    /// Whether the category does not exist yet and is created by the import.
+    pub new_category: bool,
+    pub tags: Vec<String>,
+    /// Tags that do not exist yet and are created by the import.
+    pub new_tags: Vec<String>,
+    pub prices: Vec<ProductPreviewPrice>,
+}
+
+/// Price level rate of a [`ProductPreviewRow`].
+#[derive(Debug, Serialize)]
+pub struct ProductPreviewPrice {
+    pub price_level: String,
+    pub price: Money,
+}
+
+/// Data required to render the product import preview.
+#[derive(Debug, Serialize)]
+pub struct ProductUploadPreview {
+    /// Rows that would be imported, in file order.
+    pub rows: Vec<ProductPreviewRow>,
+    /// Every row error; the import is rejected while any are present.
+    pub errors: Vec<UploadRowError>,
+    /// Counts of the actions listed in `rows`.
+    pub summary: ProductImportSummary,
+    /// Import mode the preview was computed for.
+    pub mode: ProductImportMode,
+    /// Whether unknown categories and tags are created by the import.
+    pub create_missing: bool,
+    /// Uploaded CSV, re-submitted when the operator confirms the import.
+    pub content: String,
+}
+
+/// Upload row with its references resolved and the action importing it takes.
+struct PlannedUpload {
+    row: usize,
+    upload: NewProductUpload,
+    tag_ids: Vec<i32>,
+    /// Category path created before the row is imported.
+    missing_category: Option<String>,
+    /// Tag names created before the row is imported.
+    missing_tags: Vec<String>,
+    action: ProductImportAction,
+    /// Product updated by the row in upsert mode.
+    target: Option<Product>,
 }
 
 /// Imports products from an uploaded CSV file.
+///
+/// The `category` column holds a category name or a `/`-separated path and
+/// the `tags` column a comma-separated list of tag names. Unknown categories
+/// and tags are rejected unless the form asks to create them. A row refers to
+/// the product named in its `id` column or, without one, to the products
+/// sharing its SKU; an unknown id is a row error. Rows whose product has
+/// identical fields, rates, category and tags are skipped, so re-importing a
+/// file from [`export_products`] is a no-op. In [`ProductImportMode::Upsert`]
+/// the other matches are updated in place, including their price level rates;
+/// an empty category or tag cell keeps the current value. Nothing is written
+/// when any row is invalid.
 pub fn import_products<R>(
     repo: &R,
     user: &AuthenticatedUser,
     mut form: UploadProductsForm,
-) -> ServiceResult<usize>
+) -> ServiceResult<ProductImportSummary>
 where
-    R: ProductWriter + PriceLevelReader + ?Sized,
+    R: ProductReader + ProductWriter + PriceLevelReader + CategoryReader + TagReader + ?Sized,
 {
     if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
         return Err(ServiceError::Unauthorized);
     }
 
+    let mode = form.mode();
+    let create_missing = form.create_missing();
     let price_levels = fetch_all_price_levels(repo, user.hub_id)?;
 
-    let uploads = form
-        .into_new_products(user.hub_id, &price_levels)
+    let report = form
+        .into_upload_report(user.hub_id, &price_levels)
         .map_err(|err| ServiceError::Form(err.to_string()))?;
+    if let Some(err) = report.errors.first() {
+        return Err(ServiceError::Form(err.to_string()));
+    }
 
-    let mut created = 0usize;
-    for upload in uploads {
-        persist_new_product(repo, user.hub_id, upload)?;
-        created += 1;
+    let lookups = ImportLookups::load(repo, user.hub_id, &report.rows)?;
+    let (plan, errors) = plan_product_import(
+        repo,
+        user.hub_id,
+        mode,
+        create_missing,
+        &lookups,
+        report.rows,
+    )?;
+    if let Some(err) = errors.first() {
+        return Err(ServiceError::Form(err.to_string()));
     }
 
-    Ok(created)
+    let mut summary = ProductImportSummary::default();
+    let mut items = Vec::with_capacity(plan.len());
+    for planned in plan {
+        let target = match (planned.action, planned.target) {
+            (ProductImportAction::Unchanged, _) => {
+                summary.unchanged += 1;
+                continue;
+            }
+            (ProductImportAction::Update, Some(product)) => {
+                summary.updated += 1;
+                upload_update_target(&product, &planned.upload)
+            }
+            _ => {
+                summary.created += 1;
+                ProductImportTarget::Create(planned.upload.product)
+            }
+        };
+
+        let rates = planned
+            .upload
+            .price_levels
+            .iter()
+            .map(|rate| {
+                NewProductPriceLevelRate::new(0, rate.price_level_id, rate.price.clone())
+                    .with_min_quantity(rate.min_quantity)
+            })
+            .collect();
+        let mut item = ProductImportItem::new(target, rates);
+        item.tag_ids = planned.tag_ids;
+        item.missing_category = planned
+            .missing_category
+            .map(|path| {
+                path.split(CATEGORY_PATH_SEPARATOR)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        item.missing_tags = planned.missing_tags;
+        items.push(item);
+    }
+
+    if !items.is_empty() {
+        repo.bulk_import_products(user.hub_id, &items, &price_change_author(user))
+            .map_err(ServiceError::from)?;
+    }
+
+    Ok(summary)
+}
+
+/// Parses an uploaded product CSV and reports what [`import_products`] would
+/// do with every row, together with all row errors, without writing anything.
+pub fn preview_products<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    mut form: UploadProductsForm,
+) -> ServiceResult<ProductUploadPreview>
+where
+    R: ProductReader + PriceLevelReader + CategoryReader + TagReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let mode = form.mode();
+    let create_missing = form.create_missing();
+    let content = form
+        .content()
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+    let price_levels = fetch_all_price_levels(repo, user.hub_id)?;
+
+    let report = form
+        .into_upload_report(user.hub_id, &price_levels)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+    let lookups = ImportLookups::load(repo, user.hub_id, &report.rows)?;
+    let (plan, plan_errors) = plan_product_import(
+        repo,
+        user.hub_id,
+        mode,
+        create_missing,
+        &lookups,
+        report.rows,
+    )?;
+
+    let mut row_errors = report.errors;
+    row_errors.extend(plan_errors);
+    row_errors.sort_by_key(ProductFormError::row);
+    let errors = row_errors
+        .iter()
+        .map(|err| UploadRowError::new(err.row(), err.to_string()))
+        .collect();
+
+    let level_names: HashMap<i32, &str> = price_levels
+        .iter()
+        .map(|level| (level.id, level.name.as_str()))
+        .collect();
+
+    let mut summary = ProductImportSummary::default();
+    let rows = plan
+        .into_iter()
+        .map(|planned| {
+            match planned.action {
+                ProductImportAction::Create => summary.created += 1,
+                ProductImportAction::Update => summary.updated += 1,
+                ProductImportAction::Unchanged => summary.unchanged += 1,
+            }
+            let NewProductUpload {
+                product,
+                price_levels,
+                category,
+                tags,
+                ..
+            } = planned.upload;
+            ProductPreviewRow {
+                row: planned.row,
+                action: planned.action,
+                name: product.name,
+                sku: product.sku,
+                units: product.units,
+                currency: product.currency,
+                category,
+                new_category: planned.missing_category.is_some(),
+                tags,
+                new_tags: planned.missing_tags,
+                prices: price_levels
+                    .iter()
+                    .map(|rate| ProductPreviewPrice {
+                        price_level: price_tier_header(
+                            level_names
+                                .get(&rate.price_level_id)
+                                .copied()
+                                .unwrap_or_default(),
+                            rate.min_quantity,
+                        ),
+                        price: rate.price.clone(),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(ProductUploadPreview {
+        rows,
+        errors,
+        summary,
+        mode,
+        create_missing,
+        content,
+    })
+}
+
+/// Returns the row errors of an uploaded product CSV as a CSV report.
+pub fn product_upload_errors<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: UploadProductsForm,
+) -> ServiceResult<Vec<u8>>
+where
+    R: ProductReader + PriceLevelReader + CategoryReader + TagReader + ?Sized,
+{
+    let preview = preview_products(repo, user, form)?;
+    write_upload_errors_csv(&preview.errors)
+}
+
+/// Categories and tags of the hub used to resolve the upload columns.
+struct ImportLookups {
+    /// Lowercase `/`-separated category path to id.
+    category_paths: HashMap<String, i32>,
+    /// Lowercase category name to the ids of every category with that name.
+    category_names: HashMap<String, Vec<i32>>,
+    /// Lowercase tag name to id.
+    tags: HashMap<String, i32>,
+}
+
+/// Outcome of resolving a `category` cell.
+enum CategoryMatch {
+    Found(i32),
+    Ambiguous,
+    Missing,
+}
+
+impl ImportLookups {
+    /// Loads the categories and tags referenced by `rows`, skipping the
+    /// queries when no row needs them.
+    fn load<R>(repo: &R, hub_id: i32, rows: &[ProductUploadRow]) -> ServiceResult<Self>
+    where
+        R: CategoryReader + TagReader + ?Sized,
+    {
+        let mut lookups = Self {
+            category_paths: HashMap::new(),
+            category_names: HashMap::new(),
+            tags: HashMap::new(),
+        };
+
+        if rows.iter().any(|row| row.upload.category.is_some()) {
+            for (id, path) in category_paths(repo, hub_id)? {
+                lookups.insert_category(id, &path);
+            }
+        }
+
+        if rows.iter().any(|row| !row.upload.tags.is_empty()) {
+            let (_, tags) = repo
+                .list_tags(TagListQuery::new(hub_id))
+                .map_err(ServiceError::from)?;
+            lookups.tags = tags
+                .into_iter()
+                .map(|tag| (tag.name.to_lowercase(), tag.id))
+                .collect();
+        }
+
+        Ok(lookups)
+    }
+
+    fn insert_category(&mut self, id: i32, path: &str) {
+        let path = path.to_lowercase();
+        let name = path
+            .rsplit(CATEGORY_PATH_SEPARATOR)
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        self.category_names.entry(name).or_default().push(id);
+        self.category_paths.insert(path, id);
+    }
+
+    /// Resolves a category by its full path, or by its name when the name is
+    /// unique within the hub.
+    fn resolve_category(&self, value: &str) -> CategoryMatch {
+        let key = value.to_lowercase();
+        if let Some(id) = self.category_paths.get(&key) {
+            return CategoryMatch::Found(*id);
+        }
+        if key.contains(CATEGORY_PATH_SEPARATOR) {
+            return CategoryMatch::Missing;
+        }
+        match self.category_names.get(&key).map(Vec::as_slice) {
+            Some([id]) => CategoryMatch::Found(*id),
+            Some([_, _, ..]) => CategoryMatch::Ambiguous,
+            _ => CategoryMatch::Missing,
+        }
+    }
+
+    fn tag_id(&self, name: &str) -> Option<i32> {
+        self.tags.get(&name.to_lowercase()).copied()
+    }
+}
+
+/// Resolves categories and tags of the parsed rows and decides what importing
+/// each of them does. Unknown categories and tags are returned as row errors
+/// unless `create_missing` is set, in which case they are recorded on the
+/// planned row for the import to create.
+fn plan_product_import<R>(
+    repo: &R,
+    hub_id: i32,
+    mode: ProductImportMode,
+    create_missing: bool,
+    lookups: &ImportLookups,
+    rows: Vec<ProductUploadRow>,
+) -> ServiceResult<(Vec<PlannedUpload>, Vec<ProductFormError>)>
+where
+    R: ProductReader + ?Sized,
+{
+    let mut plan = Vec::with_capacity(rows.len());
+    let mut errors = Vec::new();
+    for ProductUploadRow { row, mut upload } in rows {
+        let mut missing_category = None;
+        if let Some(value) = upload.category.as_ref() {
+            match lookups.resolve_category(value) {
+                CategoryMatch::Found(category_id) => {
+                    upload.product = upload.product.with_category_id(category_id);
+                }
+                CategoryMatch::Ambiguous => {
+                    errors.push(ProductFormError::UploadAmbiguousCategory {
+                        row,
+                        name: value.clone(),
+                    });
+                    continue;
+                }
+                CategoryMatch::Missing if create_missing => {
+                    missing_category = Some(value.clone());
+                }
+                CategoryMatch::Missing => {
+                    errors.push(ProductFormError::UploadUnknownCategory {
+                        row,
+                        path: value.clone(),
+                    });
+                    continue;
+                }
+            }
+        }
+
+        let mut tag_ids = Vec::with_capacity(upload.tags.len());
+        let mut missing_tags = Vec::new();
+        for name in &upload.tags {
+            match lookups.tag_id(name) {
+                Some(tag_id) => tag_ids.push(tag_id),
+                None => missing_tags.push(name.clone()),
+            }
+        }
+        if !create_missing && let Some(name) = missing_tags.first() {
+            errors.push(ProductFormError::UploadUnknownTag {
+                row,
+                name: name.clone(),
+            });
+            continue;
+        }
+
+        let existing = match (upload.product_id, upload.product.sku.as_ref()) {
+            (Some(product_id), _) => match repo
+                .get_product_by_id(product_id, hub_id)
+                .map_err(ServiceError::from)?
+            {
+                Some(product) => vec![product],
+                None => {
+                    errors.push(ProductFormError::UploadUnknownProduct { row, product_id });
+                    continue;
+                }
+            },
+            (None, Some(sku)) => {
+                repo.list_products(
+                    ProductListQuery::new(hub_id)
+                        .sku(sku.as_str())
+                        .include_archived(),
+                )
+                .map_err(ServiceError::from)?
+                .1
+            }
+            (None, None) => Vec::new(),
+        };
+
+        let creates_references = missing_category.is_some() || !missing_tags.is_empty();
+        let (action, target) = if !creates_references
+            && existing
+                .iter()
+                .any(|product| upload_matches_product(&upload, &tag_ids, product))
+        {
+            (ProductImportAction::Unchanged, None)
+        } else {
+            match existing.into_iter().next() {
+                Some(product) if mode == ProductImportMode::Upsert => {
+                    (ProductImportAction::Update, Some(product))
+                }
+                _ => (ProductImportAction::Create, None),
+            }
+        };
+
+        plan.push(PlannedUpload {
+            row,
+            upload,
+            tag_ids,
+            missing_category,
+            missing_tags,
+            action,
+            target,
+        });
+    }
+
+    Ok((plan, errors))
+}
+
+/// Exports the hub's active products as CSV in the format read by
+/// [`import_products`]: the product id, one column per product field, the
+/// category path, the tag list and one column per price level with the base
+/// rate.
+///
+/// Levels without an explicit rate show the price derived from the base rate
+/// of their base level, so scheduled rates never leak into the export, and the
+/// trailing `derived_prices` column lists those levels so the
+/// importer leaves them derived.
+pub fn export_products<R>(repo: &R, user: &AuthenticatedUser) -> ServiceResult<Vec<u8>>
+where
+    R: ProductReader + PriceLevelReader + CategoryReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let price_levels = fetch_all_price_levels(repo, user.hub_id)?;
+    let categories = category_paths(repo, user.hub_id)?;
+    let (_, products) = repo
+        .list_products(ProductListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+
+    let mut headers = vec![
+        "id".to_string(),
+        "name".to_string(),
+        "sku".to_string(),
+        "description".to_string(),
+        "units".to_string(),
+        "currency".to_string(),
+        "category".to_string(),
+        "tags".to_string(),
+    ];
+    headers.extend(price_levels.iter().map(|level| level.name.clone()));
+    let mut tier_columns: Vec<(i32, i32)> = products
+        .iter()
+        .flat_map(|product| product.quantity_breaks())
+        .map(|rate| (rate.price_level_id, rate.min_quantity))
+        .collect();
+    tier_columns.sort_unstable_by_key(|&(price_level_id, min_quantity)| {
+        (
+            price_levels
+                .iter()
+                .position(|level| level.id == price_level_id),
+            min_quantity,
+        )
+    });
+    tier_columns.dedup();
+    tier_columns
+        .retain(|(price_level_id, _)| price_levels.iter().any(|level| level.id == *price_level_id));
+    let level_names: HashMap<i32, &str> = price_levels
+        .iter()
+        .map(|level| (level.id, level.name.as_str()))
+        .collect();
+    headers.extend(tier_columns.iter().map(|(price_level_id, min_quantity)| {
+        price_tier_header(level_names[price_level_id], *min_quantity)
+    }));
+    headers.push(DERIVED_PRICES_HEADER.to_string());
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let write_error = |err: csv::Error| {
+        log::error!("Failed to write products CSV: {err}");
+        ServiceError::Internal
+    };
+    writer.write_record(&headers).map_err(write_error)?;
+
+    for product in &products {
+        let mut record = vec![
+            product.id.to_string(),
+            product.name.clone(),
+            product.sku.clone().unwrap_or_default(),
+            product.description.clone().unwrap_or_default(),
+            product.units.clone().unwrap_or_default(),
+            product.currency.clone(),
+            product
+                .category_id
+                .and_then(|id| categories.get(&id).cloned())
+                .unwrap_or_default(),
+            product
+                .tags
+                .iter()
+                .map(|tag| tag.name.as_str())
+                .collect::<Vec<_>>()
+                .join(&TAG_SEPARATOR.to_string()),
+        ];
+        let mut derived = Vec::new();
+        record.extend(price_levels.iter().map(|level| {
+            match product.base_price(&price_levels, level.id) {
+                Some(price) => {
+                    if price.is_derived {
+                        derived.push(level.name.as_str());
+                    }
+                    price.price.format_amount()
+                }
+                None => String::new(),
+            }
+        }));
+        record.extend(tier_columns.iter().map(|&(price_level_id, min_quantity)| {
+            product
+                .quantity_breaks()
+                .find(|rate| {
+                    rate.price_level_id == price_level_id && rate.min_quantity == min_quantity
+                })
+                .map(|rate| rate.price.format_amount())
+                .unwrap_or_default()
+        }));
+        record.push(derived.join(&TAG_SEPARATOR.to_string()));
+        writer.write_record(&record).map_err(write_error)?;
+    }
+
+    writer.into_inner().map_err(|err| {
+        log::error!("Failed to flush products CSV: {err}");
+        ServiceError::Internal
+    })
 }
 
 /// Updates an existing product for the authenticated user's hub.
@@ -206,6 +797,413 @@ where
         .map_err(ServiceError::from)
 }
 
+/// Deletes a product that was never ordered, archiving it otherwise so order
+/// history keeps its reference.
+pub fn remove_product<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    product_id: i32,
+) -> ServiceResult<ProductRemoval>
+where
+    R: ProductWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    repo.remove_product(product_id, user.hub_id)
+        .map_err(ServiceError::from)
+}
+
+/// Applies a bulk action to the selected products in a single transaction.
+///
+/// Returns the number of products affected.
+pub fn bulk_update_products<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: BulkProductsForm,
+) -> ServiceResult<usize>
+where
+    R: ProductWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let update = form
+        .into_bulk_update()
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    repo.bulk_update_products(user.hub_id, &update.product_ids, &update.action)
+        .map_err(ServiceError::from)
+}
+
+/// Repricing of a price level planned from a [`RepriceProductsForm`].
+struct RepricingPlan {
+    price_level: PriceLevel,
+    products: Vec<Product>,
+    rates: Vec<RepricedRate>,
+}
+
+/// Old and new prices of a bulk repricing, shown before it is applied.
+#[derive(Debug, Serialize)]
+pub struct RepricingPreview {
+    /// Name of the repriced price level.
+    pub price_level_name: String,
+    /// Number of products in the selected scope.
+    pub product_count: usize,
+    /// Rates whose price changes, in product order.
+    pub rows: Vec<RepricingPreviewRow>,
+}
+
+/// Price change of one rate in a [`RepricingPreview`].
+#[derive(Debug, Serialize)]
+pub struct RepricingPreviewRow {
+    pub product_id: i32,
+    pub name: String,
+    pub sku: Option<String>,
+    /// Smallest order line quantity the rate applies to.
+    pub min_quantity: i32,
+    pub old_price: Money,
+    pub new_price: Money,
+}
+
+/// Lists the price changes a bulk repricing would make without applying them.
+pub fn preview_repricing<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: RepriceProductsForm,
+) -> ServiceResult<RepricingPreview>
+where
+    R: ProductReader + PriceLevelReader + CategoryReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let plan = plan_repricing(repo, user.hub_id, form)?;
+    let products: HashMap<i32, &Product> = plan
+        .products
+        .iter()
+        .map(|product| (product.id, product))
+        .collect();
+
+    let rows = plan
+        .rates
+        .iter()
+        .filter_map(|rate| {
+            let product = products.get(&rate.product_id)?;
+            Some(RepricingPreviewRow {
+                product_id: product.id,
+                name: product.name.clone(),
+                sku: product.sku.clone(),
+                min_quantity: rate.min_quantity,
+                old_price: rate.old_price.clone(),
+                new_price: rate.new_price.clone(),
+            })
+        })
+        .collect();
+
+    Ok(RepricingPreview {
+        price_level_name: plan.price_level.name,
+        product_count: plan.products.len(),
+        rows,
+    })
+}
+
+/// Changes the base rates of a price level for every product in the scope
+/// of the form, in a single transaction. Returns the number of rates changed.
+///
+/// Prices are computed again from the stored rates, so the result matches the
+/// preview unless prices were edited in between.
+pub fn reprice_products<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: RepriceProductsForm,
+) -> ServiceResult<usize>
+where
+    R: ProductReader + ProductWriter + PriceLevelReader + CategoryReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let plan = plan_repricing(repo, user.hub_id, form)?;
+    repo.reprice_product_price_levels(user.hub_id, &plan.rates, &price_change_author(user))
+        .map_err(ServiceError::from)
+}
+
+fn plan_repricing<R>(
+    repo: &R,
+    hub_id: i32,
+    form: RepriceProductsForm,
+) -> ServiceResult<RepricingPlan>
+where
+    R: ProductReader + PriceLevelReader + CategoryReader + ?Sized,
+{
+    let request = form
+        .into_repricing()
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    let price_level = repo
+        .get_price_level_by_id(request.repricing.price_level_id, hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or_else(|| ServiceError::Form("Уровень цен не найден.".to_string()))?;
+
+    let mut query = ProductListQuery::new(hub_id);
+    if let Some(category_id) = request.category_id {
+        query = query.categories(category_subtree(repo, hub_id, category_id)?);
+    }
+    if let Some(tag_id) = request.tag_id {
+        query = query.tag(tag_id);
+    }
+    if let Some(search) = request.search {
+        query = query.search(search);
+    }
+
+    let (_, products) = repo.list_products(query).map_err(ServiceError::from)?;
+    let rates = products
+        .iter()
+        .flat_map(|product| request.repricing.reprice(&product.price_levels))
+        .collect();
+
+    Ok(RepricingPlan {
+        price_level,
+        products,
+        rates,
+    })
+}
+
+/// Returns `category_id` together with the ids of all its subcategories.
+pub(crate) fn category_subtree<R>(
+    repo: &R,
+    hub_id: i32,
+    category_id: i32,
+) -> ServiceResult<Vec<i32>>
+where
+    R: CategoryReader + ?Sized,
+{
+    let (_, categories) = repo
+        .list_categories(CategoryTreeQuery::new(hub_id).include_archived())
+        .map_err(ServiceError::from)?;
+
+    let mut subtree = vec![category_id];
+    let mut index = 0;
+    while let Some(parent_id) = subtree.get(index).copied() {
+        subtree.extend(
+            categories
+                .iter()
+                .filter(|category| category.parent_id == Some(parent_id))
+                .map(|category| category.id)
+                .filter(|id| !subtree.contains(id))
+                .collect::<Vec<_>>(),
+        );
+        index += 1;
+    }
+    Ok(subtree)
+}
+
+/// Schedules a price change or promotion for a product.
+///
+/// The rate is added next to the existing ones and applies from its start
+/// date, overriding the base rate until its optional end date.
+pub fn schedule_product_price<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: ScheduleProductPriceForm,
+) -> ServiceResult<Product>
+where
+    R: ProductReader + ProductWriter + PriceLevelReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let product = repo
+        .get_product_by_id(form.product_id, user.hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or(ServiceError::NotFound)?;
+
+    let price_levels = fetch_all_price_levels(repo, user.hub_id)?;
+    let rate = form
+        .into_new_rate(&price_levels, &product.currency)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    repo.schedule_product_price_levels(
+        product.id,
+        user.hub_id,
+        &[rate],
+        &price_change_author(user),
+    )
+    .map_err(ServiceError::from)?;
+
+    Ok(product)
+}
+
+/// Cancels a scheduled price of a product; base rates cannot be cancelled.
+pub fn cancel_scheduled_product_price<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: CancelScheduledPriceForm,
+) -> ServiceResult<()>
+where
+    R: ProductWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    repo.delete_scheduled_product_price_level(
+        form.product_id,
+        user.hub_id,
+        form.rate_id,
+        &price_change_author(user),
+    )
+    .map_err(ServiceError::from)
+}
+
+/// Query parameters accepted by the product price history endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct PriceHistoryQuery {
+    /// Only list changes of this price level.
+    pub price_level_id: Option<i32>,
+    /// First day (inclusive, UTC) of the period to list.
+    pub from: Option<NaiveDate>,
+    /// Last day (inclusive, UTC) of the period to list.
+    pub to: Option<NaiveDate>,
+}
+
+/// Lists the recorded price changes of a product, oldest first.
+pub fn load_product_price_history<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    product_id: i32,
+    query: PriceHistoryQuery,
+) -> ServiceResult<Vec<ProductPriceChange>>
+where
+    R: ProductReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    repo.get_product_by_id(product_id, user.hub_id)
+        .map_err(ServiceError::from)?
+        .ok_or(ServiceError::NotFound)?;
+
+    let mut history_query = ProductPriceHistoryQuery::new(product_id, user.hub_id);
+    if let Some(price_level_id) = query.price_level_id {
+        history_query = history_query.price_level(price_level_id);
+    }
+    if let Some(from) = query.from {
+        history_query = history_query.changed_from(from.and_time(NaiveTime::MIN));
+    }
+    if let Some(to) = query.to.and_then(|to| to.succ_opt()) {
+        history_query = history_query.changed_to(to.and_time(NaiveTime::MIN));
+    }
+
+    repo.list_product_price_history(history_query)
+        .map_err(ServiceError::from)
+}
+
+/// Returns the `/`-separated path of every category of the hub, keyed by id.
+fn category_paths<R>(repo: &R, hub_id: i32) -> ServiceResult<HashMap<i32, String>>
+where
+    R: CategoryReader + ?Sized,
+{
+    let (_, categories) = repo
+        .list_categories(CategoryTreeQuery::new(hub_id).include_archived())
+        .map_err(ServiceError::from)?;
+    let by_id: HashMap<i32, &Category> = categories
+        .iter()
+        .map(|category| (category.id, category))
+        .collect();
+
+    let mut paths = HashMap::with_capacity(categories.len());
+    for category in &categories {
+        let mut segments = vec![category.name.as_str()];
+        let mut parent_id = category.parent_id;
+        // Guard against cycles by never walking more steps than there are categories.
+        while let Some(parent) = parent_id.and_then(|id| by_id.get(&id)) {
+            if segments.len() > categories.len() {
+                break;
+            }
+            segments.push(parent.name.as_str());
+            parent_id = parent.parent_id;
+        }
+        segments.reverse();
+        paths.insert(
+            category.id,
+            segments.join(&CATEGORY_PATH_SEPARATOR.to_string()),
+        );
+    }
+
+    Ok(paths)
+}
+
+/// Builds the update overwriting `product` with the fields of an upload row.
+///
+/// The category is only replaced when the row supplies one.
+fn upload_update_target(product: &Product, upload: &NewProductUpload) -> ProductImportTarget {
+    let new_product = &upload.product;
+    let mut updates = UpdateProduct::new(new_product.name.as_str(), new_product.currency.as_str());
+    updates.sku = new_product.sku.clone();
+    updates.description = new_product.description.clone();
+    updates.units = new_product.units.clone();
+    updates.is_archived = product.is_archived;
+    updates.category_id = new_product.category_id.or(product.category_id);
+    updates.tax_category_id = product.tax_category_id;
+    updates.allow_oversell = product.allow_oversell;
+
+    ProductImportTarget::Update {
+        product_id: product.id,
+        updates,
+    }
+}
+
+/// Returns `true` when importing `upload` would not change `product`.
+///
+/// A row without a category or tags leaves those of the product untouched.
+/// Only base rates are compared since imports keep scheduled rates.
+fn upload_matches_product(upload: &NewProductUpload, tag_ids: &[i32], product: &Product) -> bool {
+    let new_product = &upload.product;
+
+    let mut uploaded_rates: Vec<(i32, i32, i64)> = upload
+        .price_levels
+        .iter()
+        .map(|rate| (rate.price_level_id, rate.min_quantity, rate.price.amount))
+        .collect();
+    let mut existing_rates: Vec<(i32, i32, i64)> = product
+        .base_rates()
+        .map(|rate| (rate.price_level_id, rate.min_quantity, rate.price.amount))
+        .collect();
+    uploaded_rates.sort_unstable();
+    existing_rates.sort_unstable();
+
+    let mut existing_tags: Vec<i32> = product.tags.iter().map(|tag| tag.id).collect();
+    let mut uploaded_tags = if tag_ids.is_empty() {
+        existing_tags.clone()
+    } else {
+        tag_ids.to_vec()
+    };
+    uploaded_tags.sort_unstable();
+    existing_tags.sort_unstable();
+
+    new_product.name == product.name
+        && new_product.sku == product.sku
+        && new_product.description == product.description
+        && new_product.units == product.units
+        && new_product.currency == product.currency
+        && new_product.category_id.or(product.category_id) == product.category_id
+        && uploaded_rates == existing_rates
+        && uploaded_tags == existing_tags
+}
+
+/// Records the authenticated user as the author of price changes.
+fn price_change_author(user: &AuthenticatedUser) -> PriceChangeAuthor {
+    PriceChangeAuthor::new(&user.email, &user.name)
+}
+
 fn fetch_all_price_levels<R>(repo: &R, hub_id: i32) -> ServiceResult<Vec<PriceLevel>>
 where
     R: PriceLevelReader + ?Sized,
@@ -217,12 +1215,13 @@ where
 
 fn persist_new_product<R>(
     repo: &R,
-    hub_id: i32,
+    user: &AuthenticatedUser,
     payload: NewProductUpload,
 ) -> ServiceResult<Product>
 where
     R: ProductWriter + ?Sized,
 {
+    let hub_id = user.hub_id;
     let created = repo
         .create_product(&payload.product)
         .map_err(ServiceError::from)?;
@@ -235,11 +1234,14 @@ where
         .price_levels
         .iter()
         .map(|rate| {
-            NewProductPriceLevelRate::new(created.id, rate.price_level_id, rate.price_cents)
+            NewProductPriceLevelRate::new(created.id, rate.price_level_id, rate.price.clone())
+                .with_min_quantity(rate.min_quantity)
         })
         .collect();
 
-    if let Err(err) = repo.replace_product_price_levels(created.id, hub_id, &rates) {
+    if let Err(err) =
+        repo.replace_product_price_levels(created.id, hub_id, &rates, &price_change_author(user))
+    {
         log::error!(
             "Failed to attach price levels to product {}: {err}",
             created.id
@@ -269,17 +1271,51 @@ pub struct ProductView {
     pub is_archived: bool,
     pub category_id: Option<i32>,
     pub category_name: Option<String>,
+    pub tax_category_id: Option<i32>,
+    /// Units in stock, including reserved ones.
+    pub stock_on_hand: i32,
+    /// Units held for processing orders.
+    pub stock_reserved: i32,
+    /// Units that can still be ordered.
+    pub stock_available: i32,
+    pub allow_oversell: bool,
     pub updated_at: chrono::NaiveDateTime,
+    /// Prices in effect when the page was rendered, explicit or derived.
     pub price_levels: Vec<ProductPriceLevelView>,
+    /// Quantity breaks, ordered by price level and quantity.
+    pub price_tiers: Vec<ProductPriceTierView>,
+    /// Scheduled rates that are running or have yet to start.
+    pub scheduled_prices: Vec<ScheduledPriceView>,
     pub tags: Vec<ProductTagView>,
 }
 
 impl ProductView {
     fn from_product(
         product: crate::domain::product::Product,
+        price_levels: &[PriceLevel],
         level_lookup: &HashMap<i32, &PriceLevel>,
         category_lookup: &HashMap<i32, String>,
+        now: NaiveDateTime,
     ) -> Self {
+        let price_levels = product
+            .prices_at(price_levels, now)
+            .into_iter()
+            .filter_map(|price| ProductPriceLevelView::from_price(price, level_lookup))
+            .collect();
+
+        let mut price_tiers: Vec<ProductPriceTierView> = product
+            .quantity_breaks()
+            .filter_map(|rate| ProductPriceTierView::from_rate(rate, level_lookup))
+            .collect();
+        price_tiers.sort_by_key(|tier| (tier.price_level_id, tier.min_quantity));
+
+        let scheduled_prices = product
+            .price_levels
+            .iter()
+            .filter(|rate| rate.is_scheduled() && !rate.is_expired_at(now))
+            .filter_map(|rate| ScheduledPriceView::from_rate(rate, level_lookup, now))
+            .collect();
+
         let crate::domain::product::Product {
             id,
             hub_id,
@@ -290,18 +1326,15 @@ impl ProductView {
             currency,
             is_archived,
             category_id,
-            price_levels,
+            tax_category_id,
+            stock,
+            allow_oversell,
             tags,
             created_at: _,
             updated_at,
             ..
         } = product;
 
-        let price_levels = price_levels
-            .into_iter()
-            .flat_map(|rate| ProductPriceLevelView::from_rate(rate, level_lookup))
-            .collect();
-
         let tags = tags.into_iter().map(ProductTagView::from_tag).collect();
 
         Self {
@@ -315,8 +1348,15 @@ impl ProductView {
             is_archived,
             category_id,
             category_name: category_id.and_then(|id| category_lookup.get(&id).cloned()),
+            tax_category_id,
+            stock_on_hand: stock.on_hand,
+            stock_reserved: stock.reserved,
+            stock_available: stock.available(),
+            allow_oversell,
             updated_at,
             price_levels,
+            price_tiers,
+            scheduled_prices,
             tags,
         }
     }
@@ -327,8 +1367,51 @@ impl ProductView {
 pub struct ProductPriceLevelView {
     pub price_level_id: i32,
     pub price_level_name: String,
-    pub price_cents: i32,
+    pub price: Money,
     pub price_formatted: String,
+    /// Whether the price is computed from the level's base level.
+    pub is_derived: bool,
+}
+
+/// View model for a quantity break of a product.
+#[derive(Debug, Serialize)]
+pub struct ProductPriceTierView {
+    pub price_level_id: i32,
+    pub price_level_name: String,
+    pub min_quantity: i32,
+    pub price: Money,
+    pub price_formatted: String,
+}
+
+impl ProductPriceTierView {
+    fn from_rate(
+        rate: &ProductPriceLevelRate,
+        level_lookup: &HashMap<i32, &PriceLevel>,
+    ) -> Option<Self> {
+        let level = level_lookup.get(&rate.price_level_id)?;
+
+        Some(Self {
+            price_level_id: rate.price_level_id,
+            price_level_name: level.name.clone(),
+            min_quantity: rate.min_quantity,
+            price_formatted: rate.price.format_amount(),
+            price: rate.price.clone(),
+        })
+    }
+}
+
+/// View model for a scheduled price of a product.
+#[derive(Debug, Serialize)]
+pub struct ScheduledPriceView {
+    pub id: i32,
+    pub price_level_id: i32,
+    pub price_level_name: String,
+    pub price: Money,
+    pub price_formatted: String,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_to: Option<NaiveDateTime>,
+    /// Whether the rate already applies.
+    pub is_active: bool,
 }
 
 /// View model for a product tag entry.
@@ -348,18 +1431,36 @@ impl ProductTagView {
 }
 
 impl ProductPriceLevelView {
+    fn from_price(price: ProductPrice, level_lookup: &HashMap<i32, &PriceLevel>) -> Option<Self> {
+        let level = level_lookup.get(&price.price_level_id)?;
+
+        Some(Self {
+            price_level_id: price.price_level_id,
+            price_level_name: level.name.clone(),
+            price_formatted: price.price.format_amount(),
+            price: price.price,
+            is_derived: price.is_derived,
+        })
+    }
+}
+
+impl ScheduledPriceView {
     fn from_rate(
-        rate: ProductPriceLevelRate,
+        rate: &ProductPriceLevelRate,
         level_lookup: &HashMap<i32, &PriceLevel>,
+        now: NaiveDateTime,
     ) -> Option<Self> {
         let level = level_lookup.get(&rate.price_level_id)?;
-        let price_formatted = format!("{:.2}", rate.price_cents as f64 / 100.0);
 
         Some(Self {
+            id: rate.id,
             price_level_id: rate.price_level_id,
             price_level_name: level.name.clone(),
-            price_cents: rate.price_cents,
-            price_formatted,
+            price_formatted: rate.price.format_amount(),
+            price: rate.price.clone(),
+            valid_from: rate.valid_from,
+            valid_to: rate.valid_to,
+            is_active: rate.is_effective_at(now),
         })
     }
 }
@@ -373,17 +1474,22 @@ mod tests {
     use std::sync::{Arc, Mutex};
 
     use crate::domain::{
-        category::Category, price_level::PriceLevel, product::Product,
+        category::Category,
+        inventory_movement::StockLevel,
+        price_level::{PriceDerivation, PriceLevel},
+        product::{Product, ProductBulkAction},
         product_price_level::ProductPriceLevelRate,
+        tax_category::TaxCategory,
     };
     use crate::forms::products::{
-        AddProductForm, AddProductPriceLevelForm, EditProductForm, UploadProductsForm,
+        AddProductForm, AddProductPriceLevelForm, EditProductForm, RepriceProductsForm,
+        UploadProductsForm,
     };
     use crate::repository::mock::{
         MockCategoryReader, MockPriceLevelReader, MockProductReader, MockProductWriter,
-        MockTagReader,
+        MockTagReader, MockTaxCategoryReader,
     };
-    use actix_multipart::form::tempfile::TempFile;
+    use actix_multipart::form::{tempfile::TempFile, text::Text};
     use pushkind_common::repository::errors::{RepositoryError, RepositoryResult};
     use tempfile::NamedTempFile;
 
@@ -409,6 +1515,9 @@ mod tests {
             currency: "USD".to_string(),
             is_archived: false,
             category_id: None,
+            tax_category_id: None,
+            stock: StockLevel::default(),
+            allow_oversell: true,
             price_levels,
             tags: Vec::new(),
             created_at: datetime(),
@@ -496,7 +1605,10 @@ mod tests {
                         id: 1,
                         product_id: 1,
                         price_level_id: 10,
-                        price_cents: 1299,
+                        price: Money::new(1299, "USD"),
+                        min_quantity: 1,
+                        valid_from: None,
+                        valid_to: None,
                         created_at: datetime(),
                         updated_at: datetime(),
                     }],
@@ -512,7 +1624,10 @@ mod tests {
                         id: 2,
                         product_id: 2,
                         price_level_id: 11,
-                        price_cents: 1599,
+                        price: Money::new(1599, "USD"),
+                        min_quantity: 1,
+                        valid_from: None,
+                        valid_to: None,
                         created_at: datetime(),
                         updated_at: datetime(),
                     }],
@@ -554,6 +1669,21 @@ mod tests {
             })
             .returning(move |_| Ok((tags_len, tags_response.clone())));
 
+        repo.tax_category_reader
+            .expect_list_tax_categories()
+            .times(1)
+            .withf(move |hub_id| *hub_id == expected_hub)
+            .returning(|hub_id| {
+                Ok(vec![TaxCategory {
+                    id: 4,
+                    hub_id,
+                    name: "VAT".to_string(),
+                    rate_basis_points: 2_000,
+                    created_at: datetime(),
+                    updated_at: datetime(),
+                }])
+            });
+
         let result = load_products_page(&repo, &user, query);
 
         let data = result.expect("expected success");
@@ -562,6 +1692,7 @@ mod tests {
         assert_eq!(data.price_levels.len(), 2);
         assert_eq!(data.categories.len(), 2);
         assert_eq!(data.tags.len(), 2);
+        assert_eq!(data.tax_categories.len(), 1);
         let category_names: Vec<&str> = data
             .categories
             .iter()
@@ -606,6 +1737,12 @@ mod tests {
                 .and_then(Value::as_str),
             Some("12.99")
         );
+        assert_eq!(
+            first_price_levels[0]
+                .get("is_derived")
+                .and_then(Value::as_bool),
+            Some(false)
+        );
 
         let first_tags = items[0]
             .get("tags")
@@ -649,6 +1786,11 @@ mod tests {
             .times(1)
             .returning(move |_| Ok((0, Vec::new())));
 
+        repo.tax_category_reader
+            .expect_list_tax_categories()
+            .times(1)
+            .returning(|_| Ok(Vec::new()));
+
         let result = load_products_page(
             &repo,
             &user,
@@ -684,6 +1826,8 @@ mod tests {
             units: None,
             currency: "USD".to_string(),
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             price_levels: Vec::new(),
         };
 
@@ -720,15 +1864,15 @@ mod tests {
         repo.product_writer
             .expect_replace_product_price_levels()
             .times(1)
-            .withf(move |product_id, scope_hub, rates| {
+            .withf(move |product_id, scope_hub, rates, _| {
                 assert_eq!(*product_id, 101);
                 assert_eq!(*scope_hub, expected_hub);
                 assert_eq!(rates.len(), 1);
                 assert_eq!(rates[0].price_level_id, 10);
-                assert_eq!(rates[0].price_cents, 1234);
+                assert_eq!(rates[0].price.amount, 1234);
                 true
             })
-            .returning(|_, _, _| Ok(()));
+            .returning(|_, _, _, _| Ok(()));
 
         let form = AddProductForm {
             name: " Widget ".to_string(),
@@ -737,9 +1881,12 @@ mod tests {
             units: Some(" Each ".to_string()),
             currency: "usd".to_string(),
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             price_levels: vec![AddProductPriceLevelForm {
                 price_level_id: 10,
                 price: Some("12.34".to_string()),
+                min_quantity: None,
             }],
         };
 
@@ -766,7 +1913,7 @@ mod tests {
 
         repo.product_writer
             .expect_replace_product_price_levels()
-            .returning(|_, _, _| Err(RepositoryError::NotFound));
+            .returning(|_, _, _, _| Err(RepositoryError::NotFound));
 
         let expected_hub_id = hub_id;
         repo.product_writer
@@ -786,9 +1933,12 @@ mod tests {
             units: Some("Each".to_string()),
             currency: "USD".to_string(),
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             price_levels: vec![AddProductPriceLevelForm {
                 price_level_id: 5,
                 price: Some("10.00".to_string()),
+                min_quantity: None,
             }],
         };
 
@@ -812,52 +1962,22 @@ mod tests {
             .expect_list_price_levels()
             .returning(move |_| Ok((levels.len(), levels.clone())));
 
-        let create_counter = Arc::new(Mutex::new(0));
-        let create_counter_clone = create_counter.clone();
-
-        repo.product_writer
-            .expect_create_product()
-            .times(2)
-            .returning(move |new_product| {
-                let mut counter = create_counter_clone.lock().unwrap();
-                *counter += 1;
-                let id = *counter;
-                Ok(sample_product(
-                    id,
-                    new_product.hub_id,
-                    new_product.name.as_str(),
-                    Vec::new(),
-                ))
-            });
-
-        let rate_counter = Arc::new(Mutex::new(0));
-        let rate_counter_clone = rate_counter.clone();
-
         repo.product_writer
-            .expect_replace_product_price_levels()
-            .times(2)
-            .returning(move |product_id, scope_hub, rates| {
-                let mut idx = rate_counter_clone.lock().unwrap();
-                match *idx {
-                    0 => {
-                        assert_eq!(product_id, 1);
-                        assert_eq!(scope_hub, hub_id);
-                        assert_eq!(rates.len(), 2);
-                        assert_eq!(rates[0].price_level_id, 1);
-                        assert_eq!(rates[0].price_cents, 1234);
-                        assert_eq!(rates[1].price_level_id, 2);
-                        assert_eq!(rates[1].price_cents, 990);
-                    }
-                    1 => {
-                        assert_eq!(product_id, 2);
-                        assert_eq!(scope_hub, hub_id);
-                        assert_eq!(rates.len(), 1);
-                        assert_eq!(rates[0].price_level_id, 1);
-                        assert_eq!(rates[0].price_cents, 750);
-                    }
-                    _ => panic!("unexpected additional rate call"),
-                }
-                *idx += 1;
+            .expect_bulk_import_products()
+            .times(1)
+            .returning(move |scope_hub, items, _| {
+                assert_eq!(scope_hub, hub_id);
+                let rates: Vec<Vec<(i32, i64)>> = items
+                    .iter()
+                    .map(|item| {
+                        assert!(matches!(item.target, ProductImportTarget::Create(_)));
+                        item.rates
+                            .iter()
+                            .map(|rate| (rate.price_level_id, rate.price.amount))
+                            .collect()
+                    })
+                    .collect();
+                assert_eq!(rates, vec![vec![(1, 1234), (2, 990)], vec![(1, 750)]]);
                 Ok(())
             });
 
@@ -870,7 +1990,7 @@ Banana,USD,7.50,
 
         let result = import_products(&repo, &user, form).expect("expected success");
 
-        assert_eq!(result, 2);
+        assert_eq!(result.created, 2);
     }
 
     #[test]
@@ -912,6 +2032,8 @@ Banana,USD,7.50,
             currency: None,
             is_archived: None,
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             tag_ids: Vec::new(),
         };
 
@@ -941,6 +2063,8 @@ Banana,USD,7.50,
             currency: Some("usd".to_string()),
             is_archived: Some(false),
             category_id: None,
+            tax_category_id: None,
+            allow_oversell: None,
             tag_ids: vec!["3".to_string(), "5".to_string()],
         };
 
@@ -953,6 +2077,58 @@ Banana,USD,7.50,
         ));
     }
 
+    #[test]
+    fn load_product_price_history_filters_whole_days() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let hub_id = user.hub_id;
+
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(move |id, _| Ok(Some(sample_product(id, hub_id, "Coffee", Vec::new()))));
+        repo.product_reader
+            .expect_list_product_price_history()
+            .times(1)
+            .withf(move |query| {
+                query.product_id == 5
+                    && query.hub_id == hub_id
+                    && query.price_level_id == Some(2)
+                    && query.changed_from.map(|at| at.to_string()).as_deref()
+                        == Some("2025-03-01 00:00:00")
+                    && query.changed_to.map(|at| at.to_string()).as_deref()
+                        == Some("2025-04-01 00:00:00")
+            })
+            .returning(|query| {
+                Ok(vec![ProductPriceChange {
+                    id: 1,
+                    product_id: Some(query.product_id),
+                    price_level_id: Some(2),
+                    product_name: "Coffee".to_string(),
+                    price_level_name: "Wholesale".to_string(),
+                    min_quantity: 1,
+                    old_price_cents: Some(1000),
+                    new_price_cents: Some(1200),
+                    valid_from: None,
+                    valid_to: None,
+                    changed_by_email: "user@example.com".to_string(),
+                    changed_by_name: "User".to_string(),
+                    created_at: datetime(),
+                }])
+            });
+
+        let query = PriceHistoryQuery {
+            price_level_id: Some(2),
+            from: NaiveDate::from_ymd_opt(2025, 3, 1),
+            to: NaiveDate::from_ymd_opt(2025, 3, 31),
+        };
+        let history =
+            load_product_price_history(&repo, &user, 5, query).expect("expected price history");
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].price_level_name, "Wholesale");
+        assert_eq!(history[0].new_price_cents, Some(1200));
+    }
+
     #[test]
     fn update_product_applies_changes() {
         let mut repo = FakeRepo::new();
@@ -1044,6 +2220,8 @@ Banana,USD,7.50,
             is_archived: Some(true),
             category_id: Some("0".to_string()), // clears category
             tag_ids: vec!["42".to_string(), "99".to_string()],
+            tax_category_id: None,
+            allow_oversell: None,
         };
 
         let result =
@@ -1066,6 +2244,7 @@ Banana,USD,7.50,
         price_level_reader: MockPriceLevelReader,
         category_reader: MockCategoryReader,
         tag_reader: MockTagReader,
+        tax_category_reader: MockTaxCategoryReader,
     }
 
     impl FakeRepo {
@@ -1076,6 +2255,7 @@ Banana,USD,7.50,
                 price_level_reader: MockPriceLevelReader::new(),
                 category_reader: MockCategoryReader::new(),
                 tag_reader: MockTagReader::new(),
+                tax_category_reader: MockTaxCategoryReader::new(),
             }
         }
     }
@@ -1091,6 +2271,13 @@ Banana,USD,7.50,
         ) -> RepositoryResult<(usize, Vec<Product>)> {
             self.product_reader.list_products(query)
         }
+
+        fn list_product_price_history(
+            &self,
+            query: ProductPriceHistoryQuery,
+        ) -> RepositoryResult<Vec<ProductPriceChange>> {
+            self.product_reader.list_product_price_history(query)
+        }
     }
 
     impl PriceLevelReader for FakeRepo {
@@ -1133,6 +2320,12 @@ Banana,USD,7.50,
         }
     }
 
+    impl TaxCategoryReader for FakeRepo {
+        fn list_tax_categories(&self, hub_id: i32) -> RepositoryResult<Vec<TaxCategory>> {
+            self.tax_category_reader.list_tax_categories(hub_id)
+        }
+    }
+
     impl ProductWriter for FakeRepo {
         fn create_product(
             &self,
@@ -1160,9 +2353,32 @@ Banana,USD,7.50,
             product_id: i32,
             hub_id: i32,
             rates: &[NewProductPriceLevelRate],
+            changed_by: &PriceChangeAuthor,
+        ) -> RepositoryResult<()> {
+            self.product_writer
+                .replace_product_price_levels(product_id, hub_id, rates, changed_by)
+        }
+
+        fn schedule_product_price_levels(
+            &self,
+            product_id: i32,
+            hub_id: i32,
+            rates: &[NewProductPriceLevelRate],
+            changed_by: &PriceChangeAuthor,
+        ) -> RepositoryResult<()> {
+            self.product_writer
+                .schedule_product_price_levels(product_id, hub_id, rates, changed_by)
+        }
+
+        fn delete_scheduled_product_price_level(
+            &self,
+            product_id: i32,
+            hub_id: i32,
+            rate_id: i32,
+            changed_by: &PriceChangeAuthor,
         ) -> RepositoryResult<()> {
             self.product_writer
-                .replace_product_price_levels(product_id, hub_id, rates)
+                .delete_scheduled_product_price_level(product_id, hub_id, rate_id, changed_by)
         }
 
         fn replace_product_tags(
@@ -1174,6 +2390,383 @@ Banana,USD,7.50,
             self.product_writer
                 .replace_product_tags(product_id, hub_id, tag_ids)
         }
+
+        fn remove_product(&self, product_id: i32, hub_id: i32) -> RepositoryResult<ProductRemoval> {
+            self.product_writer.remove_product(product_id, hub_id)
+        }
+
+        fn bulk_import_products(
+            &self,
+            hub_id: i32,
+            items: &[ProductImportItem],
+            changed_by: &PriceChangeAuthor,
+        ) -> RepositoryResult<()> {
+            self.product_writer
+                .bulk_import_products(hub_id, items, changed_by)
+        }
+
+        fn bulk_update_products(
+            &self,
+            hub_id: i32,
+            product_ids: &[i32],
+            action: &ProductBulkAction,
+        ) -> RepositoryResult<usize> {
+            self.product_writer
+                .bulk_update_products(hub_id, product_ids, action)
+        }
+
+        fn reprice_product_price_levels(
+            &self,
+            hub_id: i32,
+            rates: &[RepricedRate],
+            changed_by: &PriceChangeAuthor,
+        ) -> RepositoryResult<usize> {
+            self.product_writer
+                .reprice_product_price_levels(hub_id, rates, changed_by)
+        }
+    }
+
+    fn catalog_product(hub_id: i32) -> Product {
+        let mut product = sample_product(
+            1,
+            hub_id,
+            "Cold Brew",
+            vec![ProductPriceLevelRate {
+                id: 1,
+                product_id: 1,
+                price_level_id: 1,
+                price: Money::new(450, "USD"),
+                min_quantity: 1,
+                valid_from: None,
+                valid_to: None,
+                created_at: datetime(),
+                updated_at: datetime(),
+            }],
+        );
+        product.sku = Some("CB-1".to_string());
+        product.description = Some("Line one\nLine two, with comma".to_string());
+        product.units = Some("Bottle".to_string());
+        product.category_id = Some(2);
+        product.tags = vec![tag(5, hub_id, "Seasonal"), tag(6, hub_id, "Popular")];
+        product
+    }
+
+    fn exported_product(hub_id: i32) -> Product {
+        let mut product = catalog_product(hub_id);
+        let mut case = product.price_levels[0].clone();
+        case.id = 2;
+        case.min_quantity = 10;
+        case.price.amount = 400;
+        product.price_levels.push(case);
+        product
+    }
+
+    fn export_catalog_repo(hub_id: i32, product: Product) -> FakeRepo {
+        let mut repo = FakeRepo::new();
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(move |_| {
+                let mut wholesale = price_level(2, hub_id, "Wholesale");
+                wholesale.derivation = Some(PriceDerivation::new(1).with_percent(-1000));
+                Ok((2, vec![price_level(1, hub_id, "Retail"), wholesale]))
+            });
+        repo.category_reader
+            .expect_list_categories()
+            .returning(move |_| {
+                let mut coffee = category(2, hub_id, "Coffee", false);
+                coffee.parent_id = Some(1);
+                Ok((2, vec![category(1, hub_id, "Drinks", false), coffee]))
+            });
+        repo.tag_reader.expect_list_tags().returning(move |_| {
+            Ok((
+                2,
+                vec![tag(5, hub_id, "Seasonal"), tag(6, hub_id, "Popular")],
+            ))
+        });
+        let listed = product.clone();
+        repo.product_reader
+            .expect_list_products()
+            .returning(move |_| Ok((1, vec![listed.clone()])));
+        repo.product_reader
+            .expect_get_product_by_id()
+            .returning(move |id, _| Ok((id == product.id).then(|| product.clone())));
+        repo.product_writer.expect_bulk_import_products().never();
+        repo
+    }
+
+    #[test]
+    fn exported_products_reimport_as_no_op() {
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let repo = export_catalog_repo(user.hub_id, exported_product(user.hub_id));
+
+        let bytes = export_products(&repo, &user).expect("expected export");
+        let content = String::from_utf8(bytes).expect("utf-8 csv");
+
+        assert!(content.starts_with(
+            "id,name,sku,description,units,currency,category,tags,Retail,Wholesale,Retail 10+,derived_prices\n1,Cold Brew,CB-1,"
+        ));
+        assert!(content.contains(",Drinks/Coffee,\"Seasonal,Popular\",4.50,4.05,4.00,Wholesale\n"));
+
+        let summary = import_products(&repo, &user, build_upload_form(&content))
+            .expect("expected import to succeed");
+
+        assert_eq!(
+            summary,
+            ProductImportSummary {
+                created: 0,
+                updated: 0,
+                unchanged: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn exported_derived_prices_ignore_scheduled_rates() {
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let mut product = exported_product(user.hub_id);
+        let mut promotion = product.price_levels[0].clone();
+        promotion.id = 3;
+        promotion.price.amount = 300;
+        promotion.valid_from = Some(datetime() - chrono::Duration::days(1));
+        product.price_levels.push(promotion);
+        let repo = export_catalog_repo(user.hub_id, product);
+
+        let bytes = export_products(&repo, &user).expect("expected export");
+        let content = String::from_utf8(bytes).expect("utf-8 csv");
+
+        assert!(content.contains(",Drinks/Coffee,\"Seasonal,Popular\",4.50,4.05,4.00,Wholesale\n"));
+    }
+
+    #[test]
+    fn exported_products_without_sku_match_by_id() {
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let mut product = exported_product(user.hub_id);
+        product.sku = None;
+        let repo = export_catalog_repo(user.hub_id, product);
+
+        let bytes = export_products(&repo, &user).expect("expected export");
+        let content = String::from_utf8(bytes).expect("utf-8 csv");
+
+        let summary = import_products(&repo, &user, build_upload_form(&content))
+            .expect("expected import to succeed");
+        assert_eq!(summary.unchanged, 1);
+        assert_eq!(summary.created, 0);
+
+        let unknown = content.replacen("\n1,Cold Brew,", "\n99,Cold Brew,", 1);
+        let err = import_products(&repo, &user, build_upload_form(&unknown))
+            .expect_err("expected unknown product id to be rejected");
+        assert!(
+            matches!(err, ServiceError::Form(message) if message == "row 2 references unknown product 99")
+        );
+    }
+
+    #[test]
+    fn preview_products_reports_actions_and_row_errors() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let hub_id = user.hub_id;
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(move |_| Ok((1, vec![price_level(1, hub_id, "Retail")])));
+        repo.category_reader
+            .expect_list_categories()
+            .returning(move |_| Ok((1, vec![category(2, hub_id, "Coffee", false)])));
+        repo.product_reader
+            .expect_list_products()
+            .returning(move |query| match query.sku.as_deref() {
+                Some("CB-1") => Ok((1, vec![catalog_product(hub_id)])),
+                _ => Ok((0, Vec::new())),
+            });
+        repo.product_writer.expect_create_product().never();
+        repo.product_writer.expect_update_product().never();
+
+        let mut form = build_upload_form(
+            "name,sku,currency,category,Retail\n\
+             Cold Brew XL,CB-1,USD,Coffee,5.00\n\
+             Latte,LT-1,USD,Coffee,oops\n\
+             Tea,TE-1,USD,Tea,2.00\n\
+             Mocha,MO-1,USD,,3.00\n",
+        );
+        form.mode = Some(Text(ProductImportMode::Upsert));
+
+        let preview = preview_products(&repo, &user, form).expect("expected preview");
+
+        let actions: Vec<(usize, ProductImportAction)> = preview
+            .rows
+            .iter()
+            .map(|row| (row.row, row.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (2, ProductImportAction::Update),
+                (5, ProductImportAction::Create),
+            ]
+        );
+        assert_eq!(preview.rows[0].prices[0].price_level, "Retail");
+        assert_eq!(
+            preview
+                .errors
+                .iter()
+                .map(|error| error.row)
+                .collect::<Vec<_>>(),
+            vec![Some(3), Some(4)]
+        );
+        assert_eq!(preview.summary.updated, 1);
+        assert_eq!(preview.summary.created, 1);
+        assert_eq!(preview.mode, ProductImportMode::Upsert);
+    }
+
+    #[test]
+    fn import_products_resolves_names_and_creates_missing_references() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let hub_id = user.hub_id;
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((0, Vec::new())));
+        repo.category_reader
+            .expect_list_categories()
+            .returning(move |_| {
+                let mut coffee = category(2, hub_id, "Coffee", false);
+                coffee.parent_id = Some(1);
+                Ok((2, vec![category(1, hub_id, "Drinks", false), coffee]))
+            });
+        repo.tag_reader
+            .expect_list_tags()
+            .returning(|_| Ok((0, Vec::new())));
+        repo.product_reader
+            .expect_list_products()
+            .returning(|_| Ok((0, Vec::new())));
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let written_clone = written.clone();
+        repo.product_writer
+            .expect_bulk_import_products()
+            .times(1)
+            .returning(move |_, items, _| {
+                written_clone
+                    .lock()
+                    .unwrap()
+                    .extend(items.iter().map(|item| {
+                        let category_id = match &item.target {
+                            ProductImportTarget::Create(new_product) => new_product.category_id,
+                            ProductImportTarget::Update { .. } => panic!("expected a new product"),
+                        };
+                        (
+                            category_id,
+                            item.missing_category.clone(),
+                            item.missing_tags.clone(),
+                        )
+                    }));
+                Ok(())
+            });
+
+        let mut form = build_upload_form(
+            "name,sku,currency,category,tags\n\
+             Latte,LT-1,USD,coffee,Seasonal\n\
+             Tea,TE-1,USD,Drinks/Tea,seasonal\n",
+        );
+        form.create_missing = Some(Text(true));
+
+        let summary = import_products(&repo, &user, form).expect("expected import to succeed");
+
+        assert_eq!(summary.created, 2);
+        assert_eq!(
+            *written.lock().unwrap(),
+            vec![
+                (Some(2), Vec::new(), vec!["Seasonal".to_string()]),
+                (
+                    None,
+                    vec!["Drinks".to_string(), "Tea".to_string()],
+                    vec!["seasonal".to_string()],
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn import_products_upsert_updates_matching_sku() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let hub_id = user.hub_id;
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(move |_| Ok((1, vec![price_level(1, hub_id, "Retail")])));
+        repo.product_reader
+            .expect_list_products()
+            .times(2)
+            .returning(move |query| match query.sku.as_deref() {
+                Some("CB-1") => Ok((1, vec![catalog_product(hub_id)])),
+                _ => Ok((0, Vec::new())),
+            });
+        repo.product_writer
+            .expect_bulk_import_products()
+            .times(1)
+            .returning(move |_, items, _| {
+                assert_eq!(items.len(), 2);
+                match &items[0].target {
+                    ProductImportTarget::Update {
+                        product_id,
+                        updates,
+                    } => {
+                        assert_eq!(*product_id, 1);
+                        assert_eq!(updates.name, "Cold Brew XL");
+                        assert_eq!(updates.units.as_deref(), Some("Can"));
+                        assert_eq!(updates.category_id, Some(2));
+                    }
+                    ProductImportTarget::Create(_) => panic!("expected an update"),
+                }
+                assert!(matches!(
+                    &items[1].target,
+                    ProductImportTarget::Create(new_product) if new_product.name == "Latte"
+                ));
+                let prices: Vec<i64> = items
+                    .iter()
+                    .flat_map(|item| item.rates.iter().map(|rate| rate.price.amount))
+                    .collect();
+                assert_eq!(prices, vec![500, 300]);
+                assert!(items.iter().all(|item| item.tag_ids.is_empty()));
+                Ok(())
+            });
+
+        let mut form = build_upload_form(
+            "name,sku,units,currency,Retail\nCold Brew XL,CB-1,Can,USD,5.00\nLatte,LT-1,Cup,USD,3.00\n",
+        );
+        form.mode = Some(Text(ProductImportMode::Upsert));
+
+        let summary = import_products(&repo, &user, form).expect("expected import to succeed");
+
+        assert_eq!(
+            summary,
+            ProductImportSummary {
+                created: 1,
+                updated: 1,
+                unchanged: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn import_products_rejects_unknown_category() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        repo.price_level_reader
+            .expect_list_price_levels()
+            .returning(|_| Ok((0, Vec::new())));
+        repo.category_reader
+            .expect_list_categories()
+            .returning(|_| Ok((0, Vec::new())));
+        repo.product_writer.expect_bulk_import_products().never();
+
+        let form = build_upload_form("name,currency,category\nWidget,USD,Tools\n");
+
+        let result = import_products(&repo, &user, form);
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
     }
 
     fn build_upload_form(csv: &str) -> UploadProductsForm {
@@ -1190,6 +2783,8 @@ Banana,USD,7.50,
                 file_name: Some("products.csv".to_string()),
                 size: csv.len(),
             },
+            mode: None,
+            create_missing: None,
         }
     }
 
@@ -1201,6 +2796,8 @@ Banana,USD,7.50,
             created_at: datetime(),
             updated_at: datetime(),
             is_default: false,
+            derivation: None,
+            prices_include_tax: true,
         }
     }
 
@@ -1226,4 +2823,148 @@ Banana,USD,7.50,
             updated_at: datetime(),
         }
     }
+
+    #[test]
+    fn remove_product_reports_archived_products() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let hub_id = user.hub_id;
+
+        repo.product_writer
+            .expect_remove_product()
+            .times(1)
+            .withf(move |id, hub| *id == 5 && *hub == hub_id)
+            .returning(|_, _| Ok(ProductRemoval::Archived));
+
+        let result = remove_product(&repo, &user, 5);
+
+        assert!(matches!(result, Ok(ProductRemoval::Archived)));
+    }
+
+    #[test]
+    fn bulk_update_products_applies_action_to_selection() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let hub_id = user.hub_id;
+
+        repo.product_writer
+            .expect_bulk_update_products()
+            .times(1)
+            .withf(move |hub, ids, action| {
+                *hub == hub_id
+                    && ids == [2, 4]
+                    && *action == ProductBulkAction::SetCategory(Some(9))
+            })
+            .returning(|_, ids, _| Ok(ids.len()));
+
+        let form = BulkProductsForm {
+            product_ids: vec![4, 2],
+            action: "categorize".to_string(),
+            category_id: Some("9".to_string()),
+            tag_ids: Vec::new(),
+        };
+
+        let result = bulk_update_products(&repo, &user, form);
+
+        assert!(matches!(result, Ok(2)));
+    }
+
+    #[test]
+    fn reprice_products_scopes_to_category_subtree_and_tag() {
+        let mut repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+        let hub_id = user.hub_id;
+
+        repo.price_level_reader
+            .expect_get_price_level_by_id()
+            .returning(move |id, _| Ok(Some(price_level(id, hub_id, "Wholesale"))));
+        repo.category_reader
+            .expect_list_categories()
+            .returning(move |_| {
+                let mut juice = category(2, hub_id, "Juice", false);
+                juice.parent_id = Some(1);
+                let mut apple = category(3, hub_id, "Apple", false);
+                apple.parent_id = Some(2);
+                Ok((
+                    4,
+                    vec![
+                        category(1, hub_id, "Drinks", false),
+                        juice,
+                        apple,
+                        category(4, hub_id, "Snacks", false),
+                    ],
+                ))
+            });
+        repo.product_reader
+            .expect_list_products()
+            .withf(|query| {
+                query.category_ids == Some(vec![1, 2, 3])
+                    && query.tag_id == Some(5)
+                    && query.search.is_none()
+            })
+            .returning(move |_| {
+                let rates = vec![
+                    ProductPriceLevelRate {
+                        price_level_id: 2,
+                        ..catalog_product(hub_id).price_levels[0].clone()
+                    },
+                    ProductPriceLevelRate {
+                        id: 2,
+                        price_level_id: 2,
+                        price: Money::new(400, "USD"),
+                        min_quantity: 10,
+                        ..catalog_product(hub_id).price_levels[0].clone()
+                    },
+                    catalog_product(hub_id).price_levels[0].clone(),
+                ];
+                Ok((1, vec![sample_product(1, hub_id, "Cold Brew", rates)]))
+            });
+        repo.product_writer
+            .expect_reprice_product_price_levels()
+            .times(1)
+            .withf(move |hub, rates, author| {
+                let changes: Vec<(i32, i64, i64)> = rates
+                    .iter()
+                    .map(|rate| (rate.rate_id, rate.old_price.amount, rate.new_price.amount))
+                    .collect();
+                *hub == hub_id
+                    && changes == [(1, 450, 495), (2, 400, 440)]
+                    && author.email == "user@example.com"
+            })
+            .returning(|_, rates, _| Ok(rates.len()));
+
+        let form = RepriceProductsForm {
+            price_level_id: 2,
+            adjustment: "10".to_string(),
+            category_id: Some("1".to_string()),
+            tag_id: Some("5".to_string()),
+            ..RepriceProductsForm::default()
+        };
+
+        let preview = preview_repricing(&repo, &user, form.clone()).expect("expected preview");
+        assert_eq!(preview.price_level_name, "Wholesale");
+        assert_eq!(preview.product_count, 1);
+        assert_eq!(preview.rows.len(), 2);
+        assert_eq!(preview.rows[1].min_quantity, 10);
+
+        let changed = reprice_products(&repo, &user, form).expect("expected repricing");
+        assert_eq!(changed, 2);
+    }
+
+    #[test]
+    fn bulk_update_products_rejects_empty_selection() {
+        let repo = FakeRepo::new();
+        let user = user_with_role(SERVICE_ACCESS_ROLE);
+
+        let form = BulkProductsForm {
+            product_ids: Vec::new(),
+            action: "archive".to_string(),
+            category_id: None,
+            tag_ids: Vec::new(),
+        };
+
+        let result = bulk_update_products(&repo, &user, form);
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
 }
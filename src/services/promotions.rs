@@ -0,0 +1,300 @@
+use chrono::{NaiveDateTime, Utc};
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::routes::check_role;
+use serde::Serialize;
+
+use crate::SERVICE_ACCESS_ROLE;
+use crate::domain::category::{Category, CategoryTreeQuery};
+use crate::domain::money::Money;
+use crate::domain::price_level::PriceAdjustmentKind;
+use crate::domain::promotion::Promotion;
+use crate::domain::tag::{Tag, TagListQuery};
+use crate::domain::tax_category::format_tax_rate;
+use crate::forms::promotions::AddPromotionForm;
+use crate::repository::{CategoryReader, PromotionReader, PromotionWriter, TagReader};
+use crate::services::{ServiceError, ServiceResult};
+
+/// Data required to render the promotions page.
+pub struct PromotionsPageData {
+    /// Promotions of the hub ordered by code.
+    pub promotions: Vec<PromotionView>,
+    /// Active categories offered as a restriction.
+    pub categories: Vec<Category>,
+    /// Tags offered as a restriction.
+    pub tags: Vec<Tag>,
+}
+
+/// Promotion row rendered on the promotions page.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct PromotionView {
+    pub id: i32,
+    pub code: String,
+    /// Discount such as `10%` or `500.00 RUB`.
+    pub discount: String,
+    /// Minimum order total with its currency, if any.
+    pub min_order: Option<String>,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_to: Option<NaiveDateTime>,
+    pub usage_count: i32,
+    pub usage_limit: Option<i32>,
+    /// Name of the restricting category, if any.
+    pub category_name: Option<String>,
+    /// Name of the restricting tag, if any.
+    pub tag_name: Option<String>,
+    /// Whether the code can be redeemed now, ignoring the order requirements.
+    pub is_active: bool,
+}
+
+impl PromotionView {
+    fn new(
+        promotion: Promotion,
+        categories: &[Category],
+        tags: &[Tag],
+        now: NaiveDateTime,
+    ) -> Self {
+        let discount = match promotion.kind {
+            PriceAdjustmentKind::Percent => {
+                format!("{}%", format_tax_rate(promotion.value as i32))
+            }
+            PriceAdjustmentKind::Fixed => {
+                Money::new(promotion.value, promotion.currency.as_str()).to_string()
+            }
+        };
+        let is_active = promotion.valid_from.is_none_or(|from| from <= now)
+            && promotion.valid_to.is_none_or(|to| now < to)
+            && promotion
+                .usage_limit
+                .is_none_or(|limit| promotion.usage_count < limit);
+
+        Self {
+            id: promotion.id,
+            discount,
+            min_order: promotion
+                .min_order_cents
+                .map(|cents| Money::new(cents, promotion.currency.as_str()).to_string()),
+            valid_from: promotion.valid_from,
+            valid_to: promotion.valid_to,
+            usage_count: promotion.usage_count,
+            usage_limit: promotion.usage_limit,
+            category_name: promotion.category_id.and_then(|id| {
+                categories
+                    .iter()
+                    .find(|category| category.id == id)
+                    .map(|category| category.name.clone())
+            }),
+            tag_name: promotion.tag_id.and_then(|id| {
+                tags.iter()
+                    .find(|tag| tag.id == id)
+                    .map(|tag| tag.name.clone())
+            }),
+            is_active,
+            code: promotion.code,
+        }
+    }
+}
+
+/// Lists the promotions of the authenticated user's hub with the categories
+/// and tags that can restrict new ones.
+pub fn load_promotions_page<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+) -> ServiceResult<PromotionsPageData>
+where
+    R: PromotionReader + CategoryReader + TagReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let promotions = repo
+        .list_promotions(user.hub_id)
+        .map_err(ServiceError::from)?;
+    let (_, mut categories) = repo
+        .list_categories(CategoryTreeQuery::new(user.hub_id).include_archived())
+        .map_err(ServiceError::from)?;
+    let (_, mut tags) = repo
+        .list_tags(TagListQuery::new(user.hub_id))
+        .map_err(ServiceError::from)?;
+    tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let now = Utc::now().naive_utc();
+    let promotions = promotions
+        .into_iter()
+        .map(|promotion| PromotionView::new(promotion, &categories, &tags, now))
+        .collect();
+
+    categories.retain(|category| !category.is_archived);
+    categories.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(PromotionsPageData {
+        promotions,
+        categories,
+        tags,
+    })
+}
+
+/// Creates a promotion for the authenticated user's hub.
+///
+/// Codes are unique within the hub, so reusing one fails with
+/// [`ServiceError::Conflict`].
+pub fn create_promotion<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: AddPromotionForm,
+) -> ServiceResult<Promotion>
+where
+    R: PromotionWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let promotion = form
+        .into_new_promotion(user.hub_id)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    repo.create_promotion(&promotion)
+        .map_err(ServiceError::from)
+}
+
+/// Deletes a promotion of the authenticated user's hub.
+///
+/// Orders that redeemed the code keep their discount.
+pub fn remove_promotion<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    promotion_id: i32,
+) -> ServiceResult<()>
+where
+    R: PromotionWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    repo.delete_promotion(promotion_id, user.hub_id)
+        .map_err(ServiceError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use pushkind_common::repository::errors::RepositoryError;
+
+    use crate::domain::promotion::NewPromotion;
+    use crate::repository::mock::MockPromotionWriter;
+
+    fn user_with_roles(roles: &[&str]) -> AuthenticatedUser {
+        AuthenticatedUser {
+            sub: "user-1".to_string(),
+            email: "user@example.com".to_string(),
+            hub_id: 7,
+            name: "Tester".to_string(),
+            roles: roles.iter().map(|role| (*role).to_string()).collect(),
+            exp: 0,
+        }
+    }
+
+    fn form(value: &str) -> AddPromotionForm {
+        AddPromotionForm {
+            code: "sale".to_string(),
+            kind: Some("Percent".to_string()),
+            value: value.to_string(),
+            currency: "RUB".to_string(),
+            min_order: None,
+            valid_from: None,
+            valid_to: None,
+            usage_limit: None,
+            category_id: None,
+            tag_id: None,
+        }
+    }
+
+    fn promotion(new_promotion: &NewPromotion) -> Promotion {
+        Promotion {
+            id: 1,
+            hub_id: new_promotion.hub_id,
+            code: new_promotion.code.clone(),
+            kind: new_promotion.kind,
+            value: new_promotion.value,
+            currency: new_promotion.currency.clone(),
+            min_order_cents: new_promotion.min_order_cents,
+            valid_from: new_promotion.valid_from,
+            valid_to: new_promotion.valid_to,
+            usage_limit: new_promotion.usage_limit,
+            usage_count: 0,
+            category_id: new_promotion.category_id,
+            tag_id: new_promotion.tag_id,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn create_promotion_requires_role() {
+        let repo = MockPromotionWriter::new();
+        let user = user_with_roles(&[]);
+
+        let result = create_promotion(&repo, &user, form("10"));
+
+        assert!(matches!(result, Err(ServiceError::Unauthorized)));
+    }
+
+    #[test]
+    fn create_promotion_stores_normalized_code_for_hub() {
+        let mut repo = MockPromotionWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.expect_create_promotion()
+            .withf(|promotion| {
+                promotion.hub_id == 7 && promotion.code == "SALE" && promotion.value == 1_000
+            })
+            .return_once(|new_promotion| Ok(promotion(new_promotion)));
+
+        let created = create_promotion(&repo, &user, form("10")).expect("expected success");
+
+        assert_eq!(created.code, "SALE");
+    }
+
+    #[test]
+    fn create_promotion_rejects_invalid_form() {
+        let mut repo = MockPromotionWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+        repo.expect_create_promotion().never();
+
+        let result = create_promotion(&repo, &user, form("150"));
+
+        assert!(matches!(result, Err(ServiceError::Form(_))));
+    }
+
+    #[test]
+    fn remove_promotion_bubbles_not_found() {
+        let mut repo = MockPromotionWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.expect_delete_promotion()
+            .withf(|id, hub_id| *id == 3 && *hub_id == 7)
+            .return_once(|_, _| Err(RepositoryError::NotFound));
+
+        let result = remove_promotion(&repo, &user, 3);
+
+        assert!(matches!(result, Err(ServiceError::NotFound)));
+    }
+
+    #[test]
+    fn promotion_view_formats_terms() {
+        let now = NaiveDateTime::default() + Duration::days(10);
+        let new_promotion = NewPromotion::new(7, "gift", PriceAdjustmentKind::Fixed, 50_000, "RUB")
+            .with_min_order_cents(200_000)
+            .with_usage_limit(1);
+        let mut redeemed = promotion(&new_promotion);
+        redeemed.usage_count = 1;
+
+        let view = PromotionView::new(redeemed, &[], &[], now);
+
+        assert_eq!(view.discount, "500.00 RUB");
+        assert_eq!(view.min_order.as_deref(), Some("2000.00 RUB"));
+        assert!(!view.is_active);
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::NaiveDateTime;
+use pushkind_common::domain::auth::AuthenticatedUser;
+use pushkind_common::routes::check_role;
+use serde::Serialize;
+
+use crate::SERVICE_ACCESS_ROLE;
+use crate::domain::tax_category::{TaxCategory, format_tax_rate};
+use crate::forms::tax_categories::AddTaxCategoryForm;
+use crate::repository::{TaxCategoryReader, TaxCategoryWriter};
+use crate::services::{ServiceError, ServiceResult};
+
+/// Tax category row rendered on the tax categories page.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct TaxCategoryView {
+    pub id: i32,
+    pub name: String,
+    /// Rate as a percentage, e.g. `7.5`.
+    pub rate: String,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<TaxCategory> for TaxCategoryView {
+    fn from(category: TaxCategory) -> Self {
+        Self {
+            id: category.id,
+            rate: format_tax_rate(category.rate_basis_points),
+            name: category.name,
+            updated_at: category.updated_at,
+        }
+    }
+}
+
+/// Lists the tax categories of the authenticated user's hub.
+pub fn load_tax_categories<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+) -> ServiceResult<Vec<TaxCategoryView>>
+where
+    R: TaxCategoryReader + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let categories = repo
+        .list_tax_categories(user.hub_id)
+        .map_err(ServiceError::from)?;
+
+    Ok(categories.into_iter().map(TaxCategoryView::from).collect())
+}
+
+/// Stores a tax category, replacing the rate of a category with the same name.
+pub fn save_tax_category<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    form: AddTaxCategoryForm,
+) -> ServiceResult<TaxCategory>
+where
+    R: TaxCategoryWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    let category = form
+        .into_new_tax_category(user.hub_id)
+        .map_err(|err| ServiceError::Form(err.to_string()))?;
+
+    repo.save_tax_category(&category)
+        .map_err(ServiceError::from)
+}
+
+/// Deletes a tax category of the authenticated user's hub.
+///
+/// Products of the category become untaxed; placed orders keep their amounts.
+pub fn remove_tax_category<R>(
+    repo: &R,
+    user: &AuthenticatedUser,
+    tax_category_id: i32,
+) -> ServiceResult<()>
+where
+    R: TaxCategoryWriter + ?Sized,
+{
+    if !check_role(SERVICE_ACCESS_ROLE, &user.roles) {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    repo.delete_tax_category(tax_category_id, user.hub_id)
+        .map_err(ServiceError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pushkind_common::repository::errors::RepositoryError;
+
+    use crate::domain::tax_category::NewTaxCategory;
+    use crate::repository::mock::{MockTaxCategoryReader, MockTaxCategoryWriter};
+
+    fn user_with_roles(roles: &[&str]) -> AuthenticatedUser {
+        AuthenticatedUser {
+            sub: "user-1".to_string(),
+            email: "user@example.com".to_string(),
+            hub_id: 7,
+            name: "Tester".to_string(),
+            roles: roles.iter().map(|role| (*role).to_string()).collect(),
+            exp: 0,
+        }
+    }
+
+    fn tax_category(id: i32, name: &str, rate_basis_points: i32) -> TaxCategory {
+        TaxCategory {
+            id,
+            hub_id: 7,
+            name: name.to_string(),
+            rate_basis_points,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn load_tax_categories_formats_rates() {
+        let mut repo = MockTaxCategoryReader::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.expect_list_tax_categories()
+            .withf(|hub_id| *hub_id == 7)
+            .return_once(|_| Ok(vec![tax_category(1, "Reduced", 750)]));
+
+        let categories = load_tax_categories(&repo, &user).expect("expected categories");
+
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].rate, "7.5");
+    }
+
+    #[test]
+    fn save_tax_category_requires_role() {
+        let repo = MockTaxCategoryWriter::new();
+        let user = user_with_roles(&[]);
+        let form = AddTaxCategoryForm {
+            name: "VAT".to_string(),
+            rate: "20".to_string(),
+        };
+
+        let result = save_tax_category(&repo, &user, form);
+
+        assert!(matches!(result, Err(ServiceError::Unauthorized)));
+    }
+
+    #[test]
+    fn save_tax_category_stores_rate_for_hub() {
+        let mut repo = MockTaxCategoryWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+        let form = AddTaxCategoryForm {
+            name: "VAT".to_string(),
+            rate: "20".to_string(),
+        };
+
+        repo.expect_save_tax_category()
+            .withf(|category| *category == NewTaxCategory::new(7, "VAT", 2_000))
+            .return_once(|category| {
+                Ok(tax_category(1, &category.name, category.rate_basis_points))
+            });
+
+        let category = save_tax_category(&repo, &user, form).expect("expected success");
+
+        assert_eq!(category.rate_basis_points, 2_000);
+    }
+
+    #[test]
+    fn remove_tax_category_bubbles_not_found() {
+        let mut repo = MockTaxCategoryWriter::new();
+        let user = user_with_roles(&[SERVICE_ACCESS_ROLE]);
+
+        repo.expect_delete_tax_category()
+            .withf(|id, hub_id| *id == 3 && *hub_id == 7)
+            .return_once(|_, _| Err(RepositoryError::NotFound));
+
+        let result = remove_tax_category(&repo, &user, 3);
+
+        assert!(matches!(result, Err(ServiceError::NotFound)));
+    }
+}
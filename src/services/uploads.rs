@@ -0,0 +1,44 @@
+use serde::Serialize;
+
+use crate::services::{ServiceError, ServiceResult};
+
+/// Problem found in an uploaded CSV, shown in import previews.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UploadRowError {
+    /// Line of the file the error refers to, `None` for file-wide problems.
+    pub row: Option<usize>,
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+impl UploadRowError {
+    /// Create an error for the given line of the upload.
+    pub fn new(row: Option<usize>, message: impl Into<String>) -> Self {
+        Self {
+            row,
+            message: message.into(),
+        }
+    }
+}
+
+/// Writes upload errors as a CSV with `row` and `error` columns.
+pub fn write_upload_errors_csv(errors: &[UploadRowError]) -> ServiceResult<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let write_error = |err: csv::Error| {
+        log::error!("Failed to write upload errors CSV: {err}");
+        ServiceError::Internal
+    };
+
+    writer.write_record(["row", "error"]).map_err(write_error)?;
+    for error in errors {
+        let row = error.row.map(|row| row.to_string()).unwrap_or_default();
+        writer
+            .write_record([row.as_str(), error.message.as_str()])
+            .map_err(write_error)?;
+    }
+
+    writer.into_inner().map_err(|err| {
+        log::error!("Failed to flush upload errors CSV: {err}");
+        ServiceError::Internal
+    })
+}
@@ -15,3 +15,108 @@ fn test_creates_and_removes_db_files() {
     assert!(!std::path::Path::new(&format!("{base}-shm")).exists());
     assert!(!std::path::Path::new(&format!("{base}-wal")).exists());
 }
+
+#[test]
+fn inventory_migration_reserves_orders_already_in_processing() {
+    use diesel::connection::SimpleConnection;
+    use diesel::migration::MigrationSource;
+    use diesel::sqlite::Sqlite;
+    use diesel_migrations::MigrationHarness;
+    use pushkind_common::db::establish_connection_pool;
+    use pushkind_orders::domain::inventory_movement::InventoryMovementKind;
+    use pushkind_orders::domain::order::OrderStatus;
+    use pushkind_orders::domain::order_status_history::NewOrderStatusChange;
+    use pushkind_orders::repository::{
+        DieselRepository, InventoryReader, OrderWriter, ProductReader,
+    };
+
+    let base = "test_inventory_backfill.db";
+    std::fs::remove_file(base).ok();
+    let pool = establish_connection_pool(base).expect("failed to open database");
+    let mut conn = pool.get().expect("failed to get connection");
+
+    let migrations = MigrationSource::<Sqlite>::migrations(&common::MIGRATIONS)
+        .expect("failed to load migrations");
+    let (before, after): (Vec<_>, Vec<_>) = migrations
+        .into_iter()
+        .partition(|migration| migration.name().to_string().as_str() < "2025-11-13");
+    conn.applied_migrations()
+        .expect("failed to set up migrations table");
+    for migration in &before {
+        conn.run_migration(migration.as_ref())
+            .expect("failed to run migration");
+    }
+
+    conn.batch_execute(
+        "INSERT INTO products (id, hub_id, name, currency) VALUES (1, 1, 'Coffee', 'USD');
+         INSERT INTO orders (id, hub_id, status, total_cents, currency) VALUES
+             (1, 1, 'Processing', 0, 'USD'),
+             (2, 1, 'Processing', 0, 'USD'),
+             (3, 1, 'Pending', 0, 'USD'),
+             (4, 2, 'Processing', 0, 'USD');
+         INSERT INTO order_products (order_id, product_id, name, price_cents, currency, quantity)
+         VALUES
+             (1, 1, 'Coffee', 100, 'USD', 2),
+             (1, 1, 'Coffee', 100, 'USD', 1),
+             (2, 1, 'Coffee', 100, 'USD', 4),
+             (3, 1, 'Coffee', 100, 'USD', 5),
+             (4, 1, 'Coffee', 100, 'USD', 6);",
+    )
+    .expect("failed to seed orders");
+
+    for migration in &after {
+        conn.run_migration(migration.as_ref())
+            .expect("failed to run migration");
+    }
+    drop(conn);
+
+    let repo = DieselRepository::new(pool);
+    let stock = || {
+        repo.get_product_by_id(1, 1)
+            .expect("failed to fetch product")
+            .expect("product should exist")
+            .stock
+    };
+    assert_eq!((stock().on_hand, stock().reserved), (0, 7));
+
+    let movements = repo
+        .list_inventory_movements(1, 1)
+        .expect("failed to list movements");
+    let reservations: Vec<_> = movements
+        .iter()
+        .map(|movement| {
+            (
+                movement.kind,
+                movement.order_id,
+                movement.quantity,
+                movement.stock.reserved,
+            )
+        })
+        .collect();
+    assert_eq!(
+        reservations,
+        vec![
+            (InventoryMovementKind::Reservation, Some(2), 4, 7),
+            (InventoryMovementKind::Reservation, Some(1), 3, 3),
+        ]
+    );
+
+    // Cancelling a backfilled order releases only its own units.
+    repo.transition_order_status(
+        1,
+        &NewOrderStatusChange::new(
+            2,
+            OrderStatus::Processing,
+            OrderStatus::Cancelled,
+            "m@b.c",
+            "M",
+        ),
+    )
+    .expect("failed to cancel order");
+    assert_eq!((stock().on_hand, stock().reserved), (0, 3));
+
+    drop(repo);
+    std::fs::remove_file(base).ok();
+    std::fs::remove_file(format!("{base}-shm")).ok();
+    std::fs::remove_file(format!("{base}-wal")).ok();
+}
@@ -1,23 +1,48 @@
+use chrono::{Duration, NaiveDate};
 use diesel::prelude::*;
 use pushkind_common::repository::errors::RepositoryError;
 use pushkind_orders::domain::{
     category::NewCategory as DomainNewCategory,
     customer::CustomerListQuery,
+    customer::CustomerMerge,
     customer::NewCustomer,
-    order::{NewOrder, OrderListQuery, OrderProduct, OrderStatus, UpdateOrder},
-    price_level::{NewPriceLevel, PriceLevelListQuery, UpdatePriceLevel},
-    product::{NewProduct, ProductListQuery, UpdateProduct},
-    product_price_level::NewProductPriceLevelRate,
+    customer::UpdateCustomer,
+    customer::{CustomerImportItem, CustomerImportTarget},
+    exchange_rate::NewExchangeRate,
+    inventory_movement::{InventoryMovementKind, NewStockAdjustment},
+    money::Money,
+    order::{NewOrder, OrderDiscount, OrderListQuery, OrderProduct, OrderStatus, UpdateOrder},
+    order_status_history::NewOrderStatusChange,
+    price_level::{
+        NewPriceLevel, PriceAdjustmentKind, PriceDerivation, PriceLevelListQuery, PriceRounding,
+        UpdatePriceLevel,
+    },
+    price_level_assignment::{
+        NewPriceLevelAssignmentRequest, PriceLevelAssignmentDecision,
+        PriceLevelAssignmentListQuery, PriceLevelAssignmentStatus,
+    },
+    product::{
+        NewProduct, ProductBulkAction, ProductImportItem, ProductImportTarget, ProductListQuery,
+        ProductRemoval, UpdateProduct,
+    },
+    product_price_history::{PriceChangeAuthor, ProductPriceHistoryQuery},
+    product_price_level::{NewProductPriceLevelRate, PriceLevelRepricing},
+    promotion::NewPromotion,
+    tag::NewTag,
+    tax_category::NewTaxCategory,
     user::{NewUser, UpdateUser},
 };
 use pushkind_orders::models::category::NewCategory as DbNewCategory;
 use pushkind_orders::models::product_price_level::NewProductPriceLevel as DbNewProductPriceLevel;
 use pushkind_orders::repository::DieselRepository;
 use pushkind_orders::repository::{
-    CustomerReader, CustomerWriter, OrderReader, OrderWriter, PriceLevelReader, PriceLevelWriter,
-    ProductReader, ProductWriter, UserListQuery, UserReader, UserWriter,
+    CategoryWriter, CustomerReader, CustomerWriter, ExchangeRateReader, ExchangeRateWriter,
+    InventoryReader, InventoryWriter, OrderReader, OrderWriter, PriceLevelAssignmentReader,
+    PriceLevelAssignmentWriter, PriceLevelReader, PriceLevelWriter, ProductReader, ProductWriter,
+    PromotionReader, PromotionWriter, TagWriter, TaxCategoryReader, TaxCategoryWriter,
+    UserListQuery, UserReader, UserWriter,
 };
-use pushkind_orders::schema::categories;
+use pushkind_orders::schema::{categories, tags};
 
 mod common;
 
@@ -226,6 +251,293 @@ fn test_customer_repository_crud() {
     assert!(matches!(err, RepositoryError::NotFound));
 }
 
+#[test]
+fn test_customer_lookup_normalizes_phone() {
+    let test_db = common::TestDb::new("test_customer_lookup_normalizes_phone.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let customer = repo
+        .create_customer(
+            &NewCustomer::new(1, "Alice", "alice@example.com").with_phone("+79001234567"),
+        )
+        .expect("failed to create customer");
+
+    for phone in ["+7 (900) 123-45-67", "89001234567", "9001234567"] {
+        let found = repo
+            .get_customer_by_email_and_phone("alice@example.com", Some(phone), 1)
+            .expect("failed to look up customer");
+        assert_eq!(found.map(|c| c.id), Some(customer.id), "{phone}");
+    }
+    assert!(
+        repo.get_customer_by_email_and_phone("alice@example.com", Some("89001234568"), 1)
+            .expect("failed to look up customer")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_update_and_delete_customer() {
+    let test_db = common::TestDb::new("test_update_and_delete_customer.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let alice = repo
+        .create_customer(&NewCustomer::new(1, "Alice", "alice@example.com"))
+        .expect("failed to create customer");
+    let bob = repo
+        .create_customer(&NewCustomer::new(1, "Bob", "bob@example.com"))
+        .expect("failed to create customer");
+
+    let now = chrono::Utc::now().naive_utc();
+    let updated = repo
+        .update_customer(
+            alice.id,
+            1,
+            &UpdateCustomer::new("Alice Smith", "Alice@Example.com", now).with_phone("+100"),
+        )
+        .expect("failed to update customer");
+    assert_eq!(updated.name, "Alice Smith");
+    assert_eq!(updated.email, "alice@example.com");
+    assert_eq!(updated.phone.as_deref(), Some("+100"));
+
+    let foreign = repo.update_customer(alice.id, 2, &UpdateCustomer::new("X", "x@x.com", now));
+    assert!(matches!(foreign, Err(RepositoryError::NotFound)));
+
+    repo.create_order(&NewOrder::new(1, Money::new(500, "USD")).with_customer_id(alice.id))
+        .expect("failed to create order");
+
+    let blocked = repo.delete_customer(alice.id, 1);
+    assert!(matches!(
+        blocked,
+        Err(RepositoryError::ConstraintViolation(_))
+    ));
+
+    repo.delete_customer(bob.id, 1)
+        .expect("failed to delete customer");
+    assert!(
+        repo.get_customer_by_id(bob.id, 1)
+            .expect("failed to load customer")
+            .is_none()
+    );
+    assert!(matches!(
+        repo.delete_customer(bob.id, 1),
+        Err(RepositoryError::NotFound)
+    ));
+}
+
+#[test]
+fn test_merge_customers() {
+    let test_db = common::TestDb::new("test_merge_customers.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let level = repo
+        .create_price_level(&NewPriceLevel::new(1, "Wholesale", false))
+        .expect("failed to create price level");
+    let survivor = repo
+        .create_customer(&NewCustomer::new(1, "Alice", "alice@example.com"))
+        .expect("failed to create customer");
+    let duplicate = repo
+        .create_customer(
+            &NewCustomer::new(1, "Alice", "alice@example.com")
+                .with_phone("+100")
+                .with_price_level_id(level.id),
+        )
+        .expect("failed to create customer");
+    let foreign = repo
+        .create_customer(&NewCustomer::new(2, "Alice", "alice@example.com"))
+        .expect("failed to create customer");
+
+    let order = repo
+        .create_order(&NewOrder::new(1, Money::new(500, "USD")).with_customer_id(duplicate.id))
+        .expect("failed to create order");
+    repo.create_price_level_assignment_request(&NewPriceLevelAssignmentRequest::new(
+        1,
+        duplicate.id,
+        None,
+        "admin@example.com",
+        "Admin",
+    ))
+    .expect("failed to create request");
+
+    let now = chrono::Utc::now().naive_utc();
+    let cross_hub =
+        repo.merge_customers(&CustomerMerge::new(1, survivor.id, [foreign.id], None, now));
+    assert!(matches!(cross_hub, Err(RepositoryError::NotFound)));
+
+    let merged = repo
+        .merge_customers(&CustomerMerge::new(
+            1,
+            survivor.id,
+            [duplicate.id],
+            Some(level.id),
+            now,
+        ))
+        .expect("failed to merge customers");
+    assert_eq!(merged.id, survivor.id);
+    assert_eq!(merged.price_level_id, Some(level.id));
+
+    assert!(
+        repo.get_customer_by_id(duplicate.id, 1)
+            .expect("failed to load customer")
+            .is_none()
+    );
+    let moved = repo
+        .get_order_by_id(order.id, 1)
+        .expect("failed to load order")
+        .expect("order should exist");
+    assert_eq!(moved.customer_id, Some(survivor.id));
+    let (_, requests) = repo
+        .list_price_level_assignment_requests(PriceLevelAssignmentListQuery::new(1))
+        .expect("failed to list requests");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].customer_id, survivor.id);
+}
+
+#[test]
+fn bulk_import_customers_writes_all_rows_or_none() {
+    let test_db = common::TestDb::new("bulk_import_customers.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let level = repo
+        .create_price_level(&NewPriceLevel::new(1, "Wholesale", false))
+        .expect("failed to create price level");
+    let foreign_level = repo
+        .create_price_level(&NewPriceLevel::new(2, "Wholesale", false))
+        .expect("failed to create foreign price level");
+    let alice = repo
+        .create_customer(&NewCustomer::new(1, "Alice", "alice@example.com"))
+        .expect("failed to create customer");
+    let now = chrono::Utc::now().naive_utc();
+
+    let rename = CustomerImportItem {
+        target: CustomerImportTarget::Update {
+            customer_id: alice.id,
+            updates: UpdateCustomer::new("Alice Smith", "alice@example.com", now),
+        },
+        price_level_request: None,
+    };
+    let create = |price_level_id| CustomerImportItem {
+        target: CustomerImportTarget::Create(NewCustomer::new(1, "Bob", "bob@example.com")),
+        price_level_request: Some(NewPriceLevelAssignmentRequest::new(
+            1,
+            0,
+            Some(price_level_id),
+            "admin@example.com",
+            "Admin",
+        )),
+    };
+
+    // The request for a foreign level fails, so the rename is rolled back too.
+    let err = repo
+        .bulk_import_customers(1, &[rename.clone(), create(foreign_level.id)])
+        .expect_err("expected a foreign price level to fail the import");
+    assert!(matches!(err, RepositoryError::NotFound));
+    let (total, customers) = repo
+        .list_customers(CustomerListQuery::new(1))
+        .expect("failed to list customers");
+    assert_eq!(total, 1);
+    assert_eq!(customers[0].name, "Alice");
+
+    repo.bulk_import_customers(1, &[rename, create(level.id)])
+        .expect("failed to import customers");
+
+    let (_, customers) = repo
+        .list_customers(CustomerListQuery::new(1))
+        .expect("failed to list customers");
+    let mut names: Vec<&str> = customers
+        .iter()
+        .map(|customer| customer.name.as_str())
+        .collect();
+    names.sort_unstable();
+    assert_eq!(names, vec!["Alice Smith", "Bob"]);
+    let bob = customers
+        .iter()
+        .find(|customer| customer.name == "Bob")
+        .expect("Bob should be created");
+    let (_, requests) = repo
+        .list_price_level_assignment_requests(PriceLevelAssignmentListQuery::new(1))
+        .expect("failed to list requests");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].customer_id, bob.id);
+    assert_eq!(requests[0].price_level_id, Some(level.id));
+}
+
+#[test]
+fn test_price_level_assignment_requests() {
+    let test_db = common::TestDb::new("test_price_level_assignment_requests.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let vip_level = repo
+        .create_price_level(&NewPriceLevel::new(1, "VIP", false))
+        .expect("failed to create price level");
+    let customer = repo
+        .create_customer(&NewCustomer::new(1, "Alice", "alice@example.com"))
+        .expect("failed to create customer");
+
+    let new_request = NewPriceLevelAssignmentRequest::new(
+        1,
+        customer.id,
+        Some(vip_level.id),
+        "admin@example.com",
+        "Admin",
+    );
+    let request = repo
+        .create_price_level_assignment_request(&new_request)
+        .expect("failed to create request");
+    assert_eq!(request.status, PriceLevelAssignmentStatus::Pending);
+
+    let repeated = repo
+        .create_price_level_assignment_request(&new_request)
+        .expect("failed to repeat request");
+    assert_eq!(repeated.id, request.id);
+
+    let unchanged = repo
+        .get_customer_by_id(customer.id, 1)
+        .expect("failed to fetch customer")
+        .expect("customer should exist");
+    assert_eq!(unchanged.price_level_id, None);
+
+    let err = repo
+        .decide_price_level_assignment_request(
+            2,
+            &PriceLevelAssignmentDecision::new(request.id, true, "boss@example.com", "Boss"),
+        )
+        .expect_err("expected cross-hub decision to fail");
+    assert!(matches!(err, RepositoryError::NotFound));
+
+    let approved = repo
+        .decide_price_level_assignment_request(
+            1,
+            &PriceLevelAssignmentDecision::new(request.id, true, "boss@example.com", "Boss"),
+        )
+        .expect("failed to approve request");
+    assert_eq!(approved.status, PriceLevelAssignmentStatus::Approved);
+    assert_eq!(
+        approved.decided_by_email.as_deref(),
+        Some("boss@example.com")
+    );
+
+    let assigned = repo
+        .get_customer_by_id(customer.id, 1)
+        .expect("failed to fetch customer")
+        .expect("customer should exist");
+    assert_eq!(assigned.price_level_id, Some(vip_level.id));
+
+    let err = repo
+        .decide_price_level_assignment_request(
+            1,
+            &PriceLevelAssignmentDecision::new(request.id, false, "boss@example.com", "Boss"),
+        )
+        .expect_err("expected decided request to stay decided");
+    assert!(matches!(err, RepositoryError::NotFound));
+
+    let (pending_total, _) = repo
+        .list_price_level_assignment_requests(
+            PriceLevelAssignmentListQuery::new(1).status(PriceLevelAssignmentStatus::Pending),
+        )
+        .expect("failed to list requests");
+    assert_eq!(pending_total, 0);
+}
+
 #[test]
 fn test_product_repository_crud() {
     let test_db = common::TestDb::new("test_product_repository_crud.db");
@@ -373,11 +685,12 @@ fn test_replace_product_price_levels() {
         .expect("failed to create product");
 
     let rates = vec![
-        NewProductPriceLevelRate::new(product.id, retail_level.id, 1250),
-        NewProductPriceLevelRate::new(product.id, wholesale_level.id, 990),
+        NewProductPriceLevelRate::new(product.id, retail_level.id, Money::new(1250, "USD")),
+        NewProductPriceLevelRate::new(product.id, wholesale_level.id, Money::new(990, "USD")),
     ];
 
-    repo.replace_product_price_levels(product.id, 1, &rates)
+    let author = PriceChangeAuthor::new("admin@example.com", "Admin");
+    repo.replace_product_price_levels(product.id, 1, &rates, &author)
         .expect("failed to replace product price levels");
 
     let mut fetched = repo
@@ -389,14 +702,493 @@ fn test_replace_product_price_levels() {
 
     assert_eq!(fetched.price_levels.len(), 2);
     assert_eq!(fetched.price_levels[0].price_level_id, retail_level.id);
-    assert_eq!(fetched.price_levels[0].price_cents, 1250);
+    assert_eq!(fetched.price_levels[0].price.amount, 1250);
     assert_eq!(fetched.price_levels[1].price_level_id, wholesale_level.id);
-    assert_eq!(fetched.price_levels[1].price_cents, 990);
+    assert_eq!(fetched.price_levels[1].price.amount, 990);
 
     let err = repo
-        .replace_product_price_levels(product.id, 2, &rates)
+        .replace_product_price_levels(product.id, 2, &rates, &author)
         .expect_err("expected cross-hub update to fail");
     assert!(matches!(err, RepositoryError::NotFound));
+
+    repo.replace_product_price_levels(
+        product.id,
+        1,
+        &[NewProductPriceLevelRate::new(
+            product.id,
+            retail_level.id,
+            Money::new(1300, "USD"),
+        )],
+        &author,
+    )
+    .expect("failed to reprice product");
+
+    let history = repo
+        .list_product_price_history(ProductPriceHistoryQuery::new(product.id, 1))
+        .expect("failed to list price history");
+    let entries: Vec<(Option<i32>, Option<i64>, Option<i64>)> = history
+        .iter()
+        .map(|change| {
+            (
+                change.price_level_id,
+                change.old_price_cents,
+                change.new_price_cents,
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (Some(retail_level.id), None, Some(1250)),
+            (Some(wholesale_level.id), None, Some(990)),
+            (Some(retail_level.id), Some(1250), Some(1300)),
+            (Some(wholesale_level.id), Some(990), None),
+        ]
+    );
+    assert!(
+        history
+            .iter()
+            .all(|change| change.changed_by_email == "admin@example.com")
+    );
+
+    let wholesale_history = repo
+        .list_product_price_history(
+            ProductPriceHistoryQuery::new(product.id, 1).price_level(wholesale_level.id),
+        )
+        .expect("failed to list wholesale history");
+    assert_eq!(wholesale_history.len(), 2);
+
+    let foreign = repo
+        .list_product_price_history(ProductPriceHistoryQuery::new(product.id, 2))
+        .expect("failed to list foreign history");
+    assert!(foreign.is_empty());
+
+    repo.delete_price_level(wholesale_level.id, 1)
+        .expect("failed to delete wholesale level");
+    let history = repo
+        .list_product_price_history(ProductPriceHistoryQuery::new(product.id, 1))
+        .expect("failed to list price history after deleting a level");
+    assert_eq!(history.len(), 4);
+    let orphaned: Vec<(Option<i32>, &str)> = history
+        .iter()
+        .filter(|change| change.price_level_name == "Wholesale")
+        .map(|change| (change.price_level_id, change.product_name.as_str()))
+        .collect();
+    assert_eq!(
+        orphaned,
+        vec![(None, product.name.as_str()), (None, product.name.as_str())]
+    );
+}
+
+#[test]
+fn test_scheduled_product_price_levels() {
+    let test_db = common::TestDb::new("test_scheduled_product_price_levels.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let retail_level = repo
+        .create_price_level(&NewPriceLevel::new(1, "Retail", false))
+        .expect("failed to create price level");
+    let product = repo
+        .create_product(&NewProduct::new(1, "Coffee", "USD"))
+        .expect("failed to create product");
+    let author = PriceChangeAuthor::new("admin@example.com", "Admin");
+
+    repo.replace_product_price_levels(
+        product.id,
+        1,
+        &[NewProductPriceLevelRate::new(
+            product.id,
+            retail_level.id,
+            Money::new(1250, "USD"),
+        )],
+        &author,
+    )
+    .expect("failed to set base rate");
+
+    let start = NaiveDate::from_ymd_opt(2030, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("valid date");
+    let promotion =
+        NewProductPriceLevelRate::new(product.id, retail_level.id, Money::new(990, "USD"))
+            .with_valid_from(start)
+            .with_valid_to(start + Duration::days(7));
+    repo.schedule_product_price_levels(product.id, 1, &[promotion], &author)
+        .expect("failed to schedule promotion");
+
+    // Replacing base rates keeps the scheduled promotion.
+    repo.replace_product_price_levels(
+        product.id,
+        1,
+        &[NewProductPriceLevelRate::new(
+            product.id,
+            retail_level.id,
+            Money::new(1300, "USD"),
+        )],
+        &author,
+    )
+    .expect("failed to replace base rate");
+
+    let fetched = repo
+        .get_product_by_id(product.id, 1)
+        .expect("failed to fetch product")
+        .expect("product should exist");
+    assert_eq!(fetched.price_levels.len(), 2);
+    let price_at = |at| {
+        fetched
+            .rate_at(retail_level.id, at)
+            .map(|rate| rate.price.amount)
+    };
+    assert_eq!(price_at(start - Duration::days(1)), Some(1300));
+    assert_eq!(price_at(start + Duration::days(1)), Some(990));
+    assert_eq!(price_at(start + Duration::days(7)), Some(1300));
+
+    let scheduled = fetched
+        .price_levels
+        .iter()
+        .find(|rate| rate.is_scheduled())
+        .expect("scheduled rate should exist");
+    let base = fetched.base_rates().next().expect("base rate should exist");
+
+    let err = repo
+        .delete_scheduled_product_price_level(product.id, 1, base.id, &author)
+        .expect_err("expected base rate to be protected");
+    assert!(matches!(err, RepositoryError::NotFound));
+
+    repo.delete_scheduled_product_price_level(product.id, 1, scheduled.id, &author)
+        .expect("failed to cancel scheduled rate");
+    let fetched = repo
+        .get_product_by_id(product.id, 1)
+        .expect("failed to fetch product")
+        .expect("product should exist");
+    assert_eq!(fetched.price_levels.len(), 1);
+    assert_eq!(fetched.price_levels[0].price.amount, 1300);
+}
+
+#[test]
+fn quantity_breaks_are_stored_and_tracked_per_tier() {
+    let test_db = common::TestDb::new("quantity_breaks_per_tier.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let retail_level = repo
+        .create_price_level(&NewPriceLevel::new(1, "Retail", false))
+        .expect("failed to create price level");
+    let product = repo
+        .create_product(&NewProduct::new(1, "Coffee", "USD"))
+        .expect("failed to create product");
+    let author = PriceChangeAuthor::new("admin@example.com", "Admin");
+
+    let tiers = |case_price| {
+        vec![
+            NewProductPriceLevelRate::new(product.id, retail_level.id, Money::new(1000, "USD")),
+            NewProductPriceLevelRate::new(
+                product.id,
+                retail_level.id,
+                Money::new(case_price, "USD"),
+            )
+            .with_min_quantity(10),
+            NewProductPriceLevelRate::new(product.id, retail_level.id, Money::new(800, "USD"))
+                .with_min_quantity(100),
+        ]
+    };
+    repo.replace_product_price_levels(product.id, 1, &tiers(900), &author)
+        .expect("failed to set quantity breaks");
+    repo.replace_product_price_levels(product.id, 1, &tiers(850), &author)
+        .expect("failed to reprice quantity break");
+
+    let fetched = repo
+        .get_product_by_id(product.id, 1)
+        .expect("failed to fetch product")
+        .expect("product should exist");
+    assert_eq!(fetched.price_levels.len(), 3);
+    let at = NaiveDate::from_ymd_opt(2030, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("valid date");
+    let price_for = |quantity| {
+        fetched
+            .rate_for_quantity(retail_level.id, at, quantity)
+            .map(|rate| rate.price.amount)
+    };
+    assert_eq!(price_for(1), Some(1000));
+    assert_eq!(price_for(10), Some(850));
+    assert_eq!(price_for(500), Some(800));
+
+    let history = repo
+        .list_product_price_history(ProductPriceHistoryQuery::new(product.id, 1))
+        .expect("failed to list price history");
+    let entries: Vec<(i32, Option<i64>, Option<i64>)> = history
+        .iter()
+        .map(|change| {
+            (
+                change.min_quantity,
+                change.old_price_cents,
+                change.new_price_cents,
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (1, None, Some(1000)),
+            (10, None, Some(900)),
+            (100, None, Some(800)),
+            (10, Some(900), Some(850)),
+        ]
+    );
+}
+
+#[test]
+fn repricing_updates_scoped_rates_in_one_transaction() {
+    let test_db = common::TestDb::new("repricing_scoped_rates.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let level = repo
+        .create_price_level(&NewPriceLevel::new(1, "Wholesale", false))
+        .expect("failed to create price level");
+    let drinks = repo
+        .create_category(&DomainNewCategory::new(1, "Drinks"))
+        .expect("failed to create category");
+    let seasonal = repo
+        .create_tag(&NewTag::new(1, "Seasonal"))
+        .expect("failed to create tag");
+    let author = PriceChangeAuthor::new("admin@example.com", "Admin");
+
+    let mut products = Vec::new();
+    for (name, in_category) in [("Coffee", true), ("Tea", true), ("Chips", false)] {
+        let mut new_product = NewProduct::new(1, name, "USD");
+        if in_category {
+            new_product = new_product.with_category_id(drinks.id);
+        }
+        let product = repo
+            .create_product(&new_product)
+            .expect("failed to create product");
+        repo.replace_product_price_levels(
+            product.id,
+            1,
+            &[NewProductPriceLevelRate::new(
+                product.id,
+                level.id,
+                Money::new(1000, "USD"),
+            )],
+            &author,
+        )
+        .expect("failed to set price");
+        products.push(product);
+    }
+    repo.replace_product_tags(products[0].id, 1, &[seasonal.id])
+        .expect("failed to tag product");
+
+    let (_, in_category) = repo
+        .list_products(ProductListQuery::new(1).categories(vec![drinks.id]))
+        .expect("failed to filter by category");
+    assert_eq!(in_category.len(), 2);
+    let (_, scoped) = repo
+        .list_products(
+            ProductListQuery::new(1)
+                .categories(vec![drinks.id])
+                .tag(seasonal.id),
+        )
+        .expect("failed to filter by tag");
+    assert_eq!(scoped.len(), 1);
+    assert_eq!(scoped[0].id, products[0].id);
+
+    let repricing = PriceLevelRepricing::new(level.id).with_percent(-1000);
+    let rates: Vec<_> = in_category
+        .iter()
+        .flat_map(|product| repricing.reprice(&product.price_levels))
+        .collect();
+    assert_eq!(
+        repo.reprice_product_price_levels(1, &rates, &author)
+            .expect("failed to reprice"),
+        2
+    );
+
+    // The same plan is stale now, so nothing is changed a second time.
+    let err = repo
+        .reprice_product_price_levels(1, &rates, &author)
+        .expect_err("expected stale repricing to fail");
+    assert!(matches!(err, RepositoryError::ConstraintViolation(_)));
+
+    let prices: Vec<i64> = products
+        .iter()
+        .map(|product| {
+            repo.get_product_by_id(product.id, 1)
+                .expect("failed to fetch product")
+                .expect("product should exist")
+                .price_levels[0]
+                .price
+                .amount
+        })
+        .collect();
+    assert_eq!(prices, vec![900, 900, 1000]);
+
+    let history = repo
+        .list_product_price_history(ProductPriceHistoryQuery::new(products[0].id, 1))
+        .expect("failed to list price history");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].old_price_cents, Some(1000));
+    assert_eq!(history[1].new_price_cents, Some(900));
+}
+
+#[test]
+fn bulk_import_products_writes_all_rows_or_none() {
+    let test_db = common::TestDb::new("bulk_import_products.db");
+    let repo = DieselRepository::new(test_db.pool());
+    let mut conn = test_db.pool().get().expect("obtain connection");
+
+    let level = repo
+        .create_price_level(&NewPriceLevel::new(1, "Retail", false))
+        .expect("failed to create price level");
+    let foreign_level = repo
+        .create_price_level(&NewPriceLevel::new(2, "Retail", false))
+        .expect("failed to create foreign price level");
+    let drinks = repo
+        .create_category(&DomainNewCategory::new(1, "Drinks"))
+        .expect("failed to create category");
+    let existing = repo
+        .create_product(&NewProduct::new(1, "Coffee", "USD").with_sku("CF-1"))
+        .expect("failed to create product");
+    let author = PriceChangeAuthor::new("admin@example.com", "Admin");
+
+    let path = vec!["drinks".to_string(), "Tea".to_string()];
+    let mut tea = ProductImportItem::new(
+        ProductImportTarget::Create(NewProduct::new(1, "Green tea", "USD")),
+        vec![NewProductPriceLevelRate::new(
+            0,
+            level.id,
+            Money::new(300, "USD"),
+        )],
+    );
+    tea.missing_category = path.clone();
+    tea.missing_tags = vec!["Seasonal".to_string()];
+    let mut coffee = ProductImportItem::new(
+        ProductImportTarget::Update {
+            product_id: existing.id,
+            updates: UpdateProduct::new("Coffee XL", "USD"),
+        },
+        vec![NewProductPriceLevelRate::new(
+            0,
+            level.id,
+            Money::new(500, "USD"),
+        )],
+    );
+    coffee.missing_category = path.clone();
+    coffee.missing_tags = vec!["seasonal".to_string()];
+
+    // The foreign price level fails the last row, so the first is rolled back too.
+    let mut failing = tea.clone();
+    failing.rates = vec![NewProductPriceLevelRate::new(
+        0,
+        foreign_level.id,
+        Money::new(300, "USD"),
+    )];
+    let err = repo
+        .bulk_import_products(1, &[tea.clone(), failing], &author)
+        .expect_err("expected a foreign price level to fail the import");
+    assert!(matches!(err, RepositoryError::NotFound));
+    let (total, _) = repo
+        .list_products(ProductListQuery::new(1))
+        .expect("failed to list products");
+    assert_eq!(total, 1);
+    let category_count: i64 = categories::table
+        .count()
+        .get_result(&mut conn)
+        .expect("failed to count categories");
+    assert_eq!(category_count, 1);
+
+    repo.bulk_import_products(1, &[tea, coffee], &author)
+        .expect("failed to import products");
+
+    let tea_category: i32 = categories::table
+        .filter(categories::parent_id.eq(drinks.id))
+        .filter(categories::name.eq("Tea"))
+        .select(categories::id)
+        .get_result(&mut conn)
+        .expect("missing path should be created under the existing parent");
+    let tag_ids: Vec<i32> = tags::table
+        .select(tags::id)
+        .load(&mut conn)
+        .expect("failed to list tags");
+    assert_eq!(tag_ids.len(), 1);
+
+    let (_, products) = repo
+        .list_products(ProductListQuery::new(1))
+        .expect("failed to list products");
+    let imported: Vec<(&str, Option<i32>, Vec<i32>, i64)> = products
+        .iter()
+        .map(|product| {
+            (
+                product.name.as_str(),
+                product.category_id,
+                product.tags.iter().map(|tag| tag.id).collect(),
+                product.price_levels[0].price.amount,
+            )
+        })
+        .collect();
+    assert_eq!(
+        imported,
+        vec![
+            ("Coffee XL", Some(tea_category), tag_ids.clone(), 500),
+            ("Green tea", Some(tea_category), tag_ids.clone(), 300),
+        ]
+    );
+}
+
+#[test]
+fn test_remove_and_bulk_update_products() {
+    let test_db = common::TestDb::new("test_remove_and_bulk_update_products.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let ordered = repo
+        .create_product(&NewProduct::new(1, "Coffee", "USD"))
+        .expect("failed to create product");
+    let unused = repo
+        .create_product(&NewProduct::new(1, "Tea", "USD"))
+        .expect("failed to create product");
+
+    repo.create_order(
+        &NewOrder::new(1, Money::new(500, "USD")).with_products(vec![
+            OrderProduct::new("Coffee", Money::new(500, "USD"), 1).with_product_id(ordered.id),
+        ]),
+    )
+    .expect("failed to create order");
+
+    let archived = repo
+        .bulk_update_products(1, &[ordered.id, unused.id], &ProductBulkAction::Archive)
+        .expect("failed to archive products");
+    assert_eq!(archived, 2);
+
+    let restored = repo
+        .bulk_update_products(1, &[ordered.id, unused.id], &ProductBulkAction::Restore)
+        .expect("failed to restore products");
+    assert_eq!(restored, 2);
+
+    let err = repo
+        .bulk_update_products(2, &[ordered.id], &ProductBulkAction::Archive)
+        .expect_err("expected cross-hub bulk update to fail");
+    assert!(matches!(err, RepositoryError::NotFound));
+
+    assert_eq!(
+        repo.remove_product(ordered.id, 1)
+            .expect("failed to remove ordered product"),
+        ProductRemoval::Archived
+    );
+    assert!(
+        repo.get_product_by_id(ordered.id, 1)
+            .expect("failed to fetch product")
+            .expect("product should exist")
+            .is_archived
+    );
+
+    assert_eq!(
+        repo.remove_product(unused.id, 1)
+            .expect("failed to remove unused product"),
+        ProductRemoval::Deleted
+    );
+    assert!(
+        repo.get_product_by_id(unused.id, 1)
+            .expect("failed to fetch product")
+            .is_none()
+    );
 }
 
 #[test]
@@ -454,12 +1246,15 @@ fn test_price_level_repository_crud() {
         name: "Gold".to_string(),
         updated_at: chrono::Utc::now().naive_utc(),
         is_default: false,
+        derivation: None,
+        prices_include_tax: false,
     };
 
     let updated = repo
         .update_price_level(bronze.id, 1, &updates)
         .expect("failed to update price level");
     assert_eq!(updated.name, "Gold");
+    assert!(!updated.prices_include_tax);
 
     let mut cross_hub_updates = updates.clone();
     cross_hub_updates.name = "Intruder".to_string();
@@ -514,6 +1309,8 @@ fn updating_price_level_default_resets_previous_default() {
         name: secondary.name.clone(),
         updated_at: chrono::Utc::now().naive_utc(),
         is_default: true,
+        derivation: None,
+        prices_include_tax: true,
     };
 
     let updated = repo
@@ -533,6 +1330,41 @@ fn updating_price_level_default_resets_previous_default() {
     );
 }
 
+#[test]
+fn derived_price_levels_round_trip_and_survive_base_deletion() {
+    let test_db = common::TestDb::new("derived_price_levels_round_trip.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let retail = repo
+        .create_price_level(&NewPriceLevel::new(1, "Retail", false))
+        .expect("failed to create retail level");
+    let derivation = PriceDerivation::new(retail.id)
+        .with_percent(-1500)
+        .with_rounding(PriceRounding::NinetyNine);
+    let wholesale = repo
+        .create_price_level(&NewPriceLevel::new(1, "Wholesale", false).with_derivation(derivation))
+        .expect("failed to create wholesale level");
+    assert_eq!(wholesale.derivation, Some(derivation));
+
+    let fixed = PriceDerivation::new(retail.id).with_fixed(250);
+    let updated = repo
+        .update_price_level(
+            wholesale.id,
+            1,
+            &UpdatePriceLevel::new("Wholesale", false).with_derivation(fixed),
+        )
+        .expect("failed to update derivation");
+    assert_eq!(updated.derivation, Some(fixed));
+
+    repo.delete_price_level(retail.id, 1)
+        .expect("failed to delete base level");
+    let orphan = repo
+        .get_price_level_by_id(wholesale.id, 1)
+        .expect("failed to fetch derived level")
+        .expect("derived level should remain");
+    assert_eq!(orphan.derivation, None);
+}
+
 #[test]
 fn deleting_price_level_removes_product_rates() {
     use diesel::prelude::*;
@@ -558,6 +1390,9 @@ fn deleting_price_level_removes_product_rates() {
             product_id: product.id,
             price_level_id: price_level.id,
             price_cents: 1950,
+            min_quantity: 1,
+            valid_from: None,
+            valid_to: None,
         };
 
         diesel::insert_into(product_rates::product_price_levels)
@@ -599,16 +1434,283 @@ fn deleting_price_level_removes_product_rates() {
     );
 }
 
+#[test]
+fn exchange_rates_replace_pairs_and_are_captured_on_order_lines() {
+    let test_db = common::TestDb::new("exchange_rates.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let saved = repo
+        .save_exchange_rates(
+            1,
+            &[
+                NewExchangeRate::new(1, "USD", "RUB", 90_000_000),
+                NewExchangeRate::new(1, "EUR", "RUB", 100_000_000),
+            ],
+        )
+        .expect("failed to save rates");
+    assert_eq!(saved, 2);
+    repo.save_exchange_rates(1, &[NewExchangeRate::new(1, "USD", "RUB", 92_500_000)])
+        .expect("failed to replace rate");
+    repo.save_exchange_rates(2, &[NewExchangeRate::new(2, "USD", "RUB", 1_000_000)])
+        .expect("failed to save foreign rate");
+
+    let rates = repo.list_exchange_rates(1).expect("failed to list rates");
+    let pairs: Vec<(&str, &str, i64)> = rates
+        .iter()
+        .map(|rate| {
+            (
+                rate.base_currency.as_str(),
+                rate.quote_currency.as_str(),
+                rate.rate_micros,
+            )
+        })
+        .collect();
+    assert_eq!(
+        pairs,
+        vec![("EUR", "RUB", 100_000_000), ("USD", "RUB", 92_500_000)]
+    );
+
+    assert!(matches!(
+        repo.delete_exchange_rate(rates[0].id, 2),
+        Err(RepositoryError::NotFound)
+    ));
+    repo.delete_exchange_rate(rates[0].id, 1)
+        .expect("failed to delete rate");
+    assert_eq!(repo.list_exchange_rates(1).expect("list").len(), 1);
+
+    let order = repo
+        .create_order(
+            &NewOrder::new(1, Money::new(9_250, "RUB")).with_products(vec![
+                OrderProduct::new("Coffee", Money::new(100, "USD"), 1)
+                    .with_exchange_rate(92_500_000),
+            ]),
+        )
+        .expect("failed to create order");
+    let fetched = repo
+        .get_order_by_id(order.id, 1)
+        .expect("failed to fetch order")
+        .expect("order should exist");
+    assert_eq!(fetched.products[0].exchange_rate_micros, 92_500_000);
+    assert_eq!(
+        fetched.products[0].total_in_order_currency("RUB"),
+        Ok(Money::new(9_250, "RUB"))
+    );
+}
+
+#[test]
+fn tax_categories_are_replaced_by_name_and_captured_on_order_lines() {
+    let test_db = common::TestDb::new("tax_categories.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let vat = repo
+        .save_tax_category(&NewTaxCategory::new(1, "VAT", 2_000))
+        .expect("failed to save category");
+    let replaced = repo
+        .save_tax_category(&NewTaxCategory::new(1, "VAT", 1_800))
+        .expect("failed to replace category");
+    assert_eq!(replaced.id, vat.id);
+    assert_eq!(replaced.rate_basis_points, 1_800);
+    let foreign = repo
+        .save_tax_category(&NewTaxCategory::new(2, "VAT", 2_000))
+        .expect("failed to save foreign category");
+    assert_eq!(repo.list_tax_categories(1).expect("list").len(), 1);
+
+    assert!(matches!(
+        repo.create_product(&NewProduct::new(1, "Ghost", "USD").with_tax_category_id(foreign.id)),
+        Err(RepositoryError::NotFound)
+    ));
+    let product = repo
+        .create_product(&NewProduct::new(1, "Coffee", "USD").with_tax_category_id(vat.id))
+        .expect("failed to create product");
+    assert_eq!(product.tax_category_id, Some(vat.id));
+
+    let mut line = OrderProduct::new("Coffee", Money::new(1_000, "USD"), 1).with_tax(1_800, false);
+    let amounts = line.calculate_amounts("USD").expect("amounts");
+    let order = repo
+        .create_order(
+            &NewOrder::new(1, amounts.gross.clone())
+                .with_amounts(amounts)
+                .with_products(vec![line]),
+        )
+        .expect("failed to create order");
+    let fetched = repo
+        .get_order_by_id(order.id, 1)
+        .expect("failed to fetch order")
+        .expect("order should exist");
+    assert_eq!((fetched.net.amount, fetched.tax.amount), (1_000, 180));
+    assert_eq!(fetched.total, Money::new(1_180, "USD"));
+    assert_eq!(fetched.products[0].tax_rate_basis_points, 1_800);
+    assert!(!fetched.products[0].prices_include_tax);
+    assert_eq!(fetched.products[0].gross, Money::new(1_180, "USD"));
+
+    assert!(matches!(
+        repo.delete_tax_category(vat.id, 2),
+        Err(RepositoryError::NotFound)
+    ));
+    repo.delete_tax_category(vat.id, 1)
+        .expect("failed to delete category");
+    let product = repo
+        .get_product_by_id(product.id, 1)
+        .expect("failed to fetch product")
+        .expect("product should exist");
+    assert_eq!(product.tax_category_id, None);
+}
+
+#[test]
+fn promotions_count_redemptions_and_snapshot_discounts() {
+    let test_db = common::TestDb::new("promotions.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let foreign_category = repo
+        .create_category(&DomainNewCategory::new(2, "Drinks"))
+        .expect("failed to create foreign category");
+    assert!(matches!(
+        repo.create_promotion(
+            &NewPromotion::new(1, "tea", PriceAdjustmentKind::Percent, 500, "USD")
+                .with_category_id(foreign_category.id)
+        ),
+        Err(RepositoryError::NotFound)
+    ));
+
+    let promotion = repo
+        .create_promotion(
+            &NewPromotion::new(1, "sale10", PriceAdjustmentKind::Percent, 1_000, "USD")
+                .with_usage_limit(1),
+        )
+        .expect("failed to create promotion");
+    assert_eq!(promotion.code, "SALE10");
+    assert_eq!(promotion.usage_count, 0);
+    assert!(matches!(
+        repo.create_promotion(&NewPromotion::new(
+            1,
+            "SALE10",
+            PriceAdjustmentKind::Fixed,
+            100,
+            "USD"
+        )),
+        Err(RepositoryError::ConstraintViolation(_))
+    ));
+    repo.create_promotion(&NewPromotion::new(
+        2,
+        "SALE10",
+        PriceAdjustmentKind::Percent,
+        1_000,
+        "USD",
+    ))
+    .expect("codes are unique per hub");
+    assert!(
+        repo.get_promotion_by_code("SALE10", 3)
+            .expect("failed to look up code")
+            .is_none()
+    );
+
+    let mut line = OrderProduct::new("Coffee", Money::new(1_000, "USD"), 1);
+    let amounts = line.calculate_amounts("USD").expect("amounts");
+    let mut discount = OrderDiscount::from_promotion(&promotion, "USD");
+    let discount_amounts = discount.calculate_amounts(&amounts).expect("discount");
+    let total = amounts
+        .gross
+        .checked_sub(&discount_amounts.gross)
+        .expect("total");
+    let new_order = NewOrder::new(1, total)
+        .with_products(vec![line])
+        .with_discount(discount);
+
+    let order = repo
+        .create_order(&new_order)
+        .expect("failed to create order");
+    let fetched = repo
+        .get_order_by_id(order.id, 1)
+        .expect("failed to fetch order")
+        .expect("order should exist");
+    let stored = fetched.discount.expect("discount stored");
+    assert_eq!(stored.promotion_id, Some(promotion.id));
+    assert_eq!(stored.gross, Money::new(100, "USD"));
+    assert_eq!(fetched.total, Money::new(900, "USD"));
+    let redeemed = repo
+        .get_promotion_by_code("SALE10", 1)
+        .expect("failed to look up code")
+        .expect("promotion should exist");
+    assert_eq!(redeemed.usage_count, 1);
+
+    assert!(matches!(
+        repo.create_order(&new_order),
+        Err(RepositoryError::ConstraintViolation(_))
+    ));
+    let (total, _) = repo
+        .list_orders(OrderListQuery::new(1))
+        .expect("failed to list orders");
+    assert_eq!(total, 1);
+
+    // Cancelling the order gives the redemption back.
+    let cancel = NewOrderStatusChange::new(
+        order.id,
+        order.status,
+        OrderStatus::Cancelled,
+        "manager@example.com",
+        "Manager",
+    );
+    repo.transition_order_status(1, &cancel)
+        .expect("failed to cancel order");
+    let released = repo
+        .get_promotion_by_code("SALE10", 1)
+        .expect("failed to look up code")
+        .expect("promotion should exist");
+    assert_eq!(released.usage_count, 0);
+    repo.create_order(&new_order)
+        .expect("released code should be redeemable again");
+
+    assert!(matches!(
+        repo.delete_promotion(promotion.id, 2),
+        Err(RepositoryError::NotFound)
+    ));
+    repo.delete_promotion(promotion.id, 1)
+        .expect("failed to delete promotion");
+    assert_eq!(repo.list_promotions(1).expect("list").len(), 0);
+    let (_, orders) = repo
+        .list_orders(OrderListQuery::new(1))
+        .expect("failed to list orders");
+    let kept = orders[0].discount.as_ref().expect("discount kept");
+    assert_eq!(kept.promotion_id, None);
+    assert_eq!(kept.code, "SALE10");
+}
+
+#[test]
+fn money_columns_hold_amounts_beyond_32_bits() {
+    let test_db = common::TestDb::new("money_columns_64_bit.db");
+    let repo = DieselRepository::new(test_db.pool());
+    let amount = i64::from(i32::MAX) * 10;
+
+    let order = repo
+        .create_order(
+            &NewOrder::new(1, Money::new(amount * 2, "RUB")).with_products(vec![
+                OrderProduct::new("Excavator", Money::new(amount, "RUB"), 2),
+            ]),
+        )
+        .expect("failed to create order");
+    let fetched = repo
+        .get_order_by_id(order.id, 1)
+        .expect("failed to fetch order")
+        .expect("order should exist");
+    assert_eq!(fetched.total, Money::new(amount * 2, "RUB"));
+    assert_eq!(fetched.products[0].price.amount, amount);
+
+    let (total, _) = repo
+        .list_orders(OrderListQuery::new(1).min_total(Money::new(amount, "RUB")))
+        .expect("failed to filter by total");
+    assert_eq!(total, 1);
+}
+
 #[test]
 fn test_order_repository_crud() {
     let test_db = common::TestDb::new("test_order_repository_crud.db");
     let repo = DieselRepository::new(test_db.pool());
 
-    let product_snapshot = OrderProduct::new("Apple", 150, "USD", 2)
+    let product_snapshot = OrderProduct::new("Apple", Money::new(150, "USD"), 2)
         .with_sku("APL-1")
         .with_description("Fresh apple");
 
-    let new_order = NewOrder::new(1, 300, "USD")
+    let new_order = NewOrder::new(1, Money::new(300, "USD"))
         .with_reference("REF-001")
         .with_notes("Handle with care")
         .with_status(OrderStatus::Pending)
@@ -660,20 +1762,23 @@ fn test_order_repository_crud() {
 
     let product_updates = vec![product_snapshot.clone().with_description("Sliced apple")];
     let updates = UpdateOrder {
-        status: OrderStatus::Processing,
+        expected_status: OrderStatus::Pending,
         notes: Some("Pack immediately".to_string()),
-        total_cents: order.total_cents,
+        total: order.total.clone(),
+        net: order.net.clone(),
+        tax: order.tax.clone(),
         currency: order.currency.clone(),
         customer_id: None,
         reference: order.reference.clone(),
         products: Some(product_updates.clone()),
+        discount: None,
         updated_at: chrono::Utc::now().naive_utc(),
     };
 
     let updated = repo
         .update_order(order.id, 1, &updates)
         .expect("failed to update order");
-    assert_eq!(updated.status, OrderStatus::Processing);
+    assert_eq!(updated.status, OrderStatus::Pending);
     assert_eq!(updated.products.len(), 1);
     assert_eq!(
         updated.products[0].description.as_deref(),
@@ -681,18 +1786,30 @@ fn test_order_repository_crud() {
     );
 
     let mut cross_hub_updates = updates.clone();
-    cross_hub_updates.status = OrderStatus::Completed;
+    cross_hub_updates.notes = Some("Foreign".to_string());
 
     let err = repo
         .update_order(order.id, 2, &cross_hub_updates)
         .expect_err("expected cross-hub update to fail");
     assert!(matches!(err, RepositoryError::NotFound));
 
+    let mut stale_updates = updates.clone();
+    stale_updates.expected_status = OrderStatus::Draft;
+    stale_updates.notes = Some("Stale".to_string());
+    let err = repo
+        .update_order(order.id, 1, &stale_updates)
+        .expect_err("expected stale update to fail");
+    assert!(matches!(err, RepositoryError::NotFound));
+
     let (total_after_update, orders_after_update) = repo
         .list_orders(OrderListQuery::new(1).paginate(1, 10))
         .expect("failed to paginate");
     assert_eq!(total_after_update, 1);
-    assert_eq!(orders_after_update[0].status, OrderStatus::Processing);
+    assert_eq!(orders_after_update[0].status, OrderStatus::Pending);
+    assert_eq!(
+        orders_after_update[0].notes.as_deref(),
+        Some("Pack immediately")
+    );
 
     let err = repo
         .delete_order(order.id, 2)
@@ -713,3 +1830,328 @@ fn test_order_repository_crud() {
     assert_eq!(total_final, 0);
     assert!(orders_final.is_empty());
 }
+
+#[test]
+fn test_order_list_filters() {
+    use pushkind_orders::schema::orders;
+
+    let test_db = common::TestDb::new("test_order_list_filters.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let customer = repo
+        .create_customer(&NewCustomer::new(1, "Alice", "alice@example.com"))
+        .expect("failed to create customer");
+
+    let draft = repo
+        .create_order(&NewOrder::new(1, Money::new(500, "USD")).with_status(OrderStatus::Draft))
+        .expect("failed to create draft order");
+    let pending = repo
+        .create_order(
+            &NewOrder::new(1, Money::new(1500, "USD"))
+                .with_status(OrderStatus::Pending)
+                .with_customer_id(customer.id),
+        )
+        .expect("failed to create pending order");
+    let completed = repo
+        .create_order(
+            &NewOrder::new(1, Money::new(3000, "USD")).with_status(OrderStatus::Completed),
+        )
+        .expect("failed to create completed order");
+    repo.create_order(&NewOrder::new(2, Money::new(1500, "USD")).with_status(OrderStatus::Pending))
+        .expect("failed to create foreign order");
+
+    let day = |day: u32| {
+        chrono::NaiveDate::from_ymd_opt(2024, 3, day)
+            .and_then(|date| date.and_hms_opt(12, 0, 0))
+            .expect("valid date")
+    };
+    {
+        let mut conn = test_db.pool().get().expect("failed to acquire connection");
+        for (id, created_at) in [
+            (draft.id, day(1)),
+            (pending.id, day(10)),
+            (completed.id, day(20)),
+        ] {
+            diesel::update(orders::table.filter(orders::id.eq(id)))
+                .set(orders::created_at.eq(created_at))
+                .execute(&mut conn)
+                .expect("failed to backdate order");
+        }
+    }
+
+    let ids = |query: OrderListQuery| {
+        let (total, items) = repo.list_orders(query).expect("failed to list orders");
+        assert_eq!(total, items.len());
+        let mut ids: Vec<i32> = items.iter().map(|order| order.id).collect();
+        ids.sort();
+        ids
+    };
+
+    assert_eq!(
+        ids(OrderListQuery::new(1).statuses([OrderStatus::Draft, OrderStatus::Completed])),
+        vec![draft.id, completed.id]
+    );
+    assert_eq!(
+        ids(OrderListQuery::new(1).customer_id(customer.id)),
+        vec![pending.id]
+    );
+    assert_eq!(
+        ids(OrderListQuery::new(1)
+            .created_from(day(10))
+            .created_before(day(20))),
+        vec![pending.id]
+    );
+    assert_eq!(
+        ids(OrderListQuery::new(1).min_total(Money::new(1_500, "USD"))),
+        vec![pending.id, completed.id]
+    );
+    assert_eq!(
+        ids(OrderListQuery::new(1).max_total(Money::new(1_500, "USD"))),
+        vec![draft.id, pending.id]
+    );
+    assert_eq!(
+        ids(OrderListQuery::new(1)
+            .min_total(Money::new(1_000, "USD"))
+            .max_total(Money::new(2_000, "USD"))
+            .status(OrderStatus::Pending)),
+        vec![pending.id]
+    );
+    assert!(ids(OrderListQuery::new(1).status(OrderStatus::Cancelled)).is_empty());
+
+    // Bounds only match orders in their own currency.
+    let yen = repo
+        .create_order(&NewOrder::new(3, Money::new(1_200, "JPY")))
+        .expect("failed to create JPY order");
+    let dinar = repo
+        .create_order(&NewOrder::new(3, Money::new(12_500, "KWD")))
+        .expect("failed to create KWD order");
+    let dollar = repo
+        .create_order(&NewOrder::new(3, Money::new(1_250, "USD")))
+        .expect("failed to create USD order");
+    let bound = |amount: &str, currency: &str| Money::parse(amount, currency).expect("amount");
+    assert_eq!(
+        ids(OrderListQuery::new(3).min_total(bound("12.5", "USD"))),
+        vec![dollar.id]
+    );
+    assert_eq!(
+        ids(OrderListQuery::new(3)
+            .min_total(bound("1000", "JPY"))
+            .max_total(bound("1500", "JPY"))),
+        vec![yen.id]
+    );
+    assert_eq!(
+        ids(OrderListQuery::new(3).max_total(bound("12.5", "KWD"))),
+        vec![dinar.id]
+    );
+    assert!(ids(OrderListQuery::new(3).max_total(bound("12.49", "KWD"))).is_empty());
+}
+
+#[test]
+fn test_order_status_transition_records_history() {
+    let test_db = common::TestDb::new("test_order_status_transition_records_history.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let order = repo
+        .create_order(&NewOrder::new(1, Money::new(0, "USD")).with_status(OrderStatus::Pending))
+        .expect("failed to create order");
+
+    let change = NewOrderStatusChange::new(
+        order.id,
+        OrderStatus::Pending,
+        OrderStatus::Processing,
+        "manager@example.com",
+        "Manager",
+    );
+
+    let err = repo
+        .transition_order_status(2, &change)
+        .expect_err("expected cross-hub transition to fail");
+    assert!(matches!(err, RepositoryError::NotFound));
+
+    let updated = repo
+        .transition_order_status(1, &change)
+        .expect("failed to transition order");
+    assert_eq!(updated.status, OrderStatus::Processing);
+
+    let err = repo
+        .transition_order_status(1, &change)
+        .expect_err("expected stale transition to fail");
+    assert!(matches!(err, RepositoryError::NotFound));
+
+    let history = repo
+        .list_order_status_history(order.id, 1)
+        .expect("failed to load history");
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].from_status, OrderStatus::Pending);
+    assert_eq!(history[0].to_status, OrderStatus::Processing);
+    assert_eq!(history[0].changed_by_email, "manager@example.com");
+
+    assert!(
+        repo.list_order_status_history(order.id, 2)
+            .expect("failed scoped history")
+            .is_empty()
+    );
+}
+
+#[test]
+fn order_transitions_reserve_deduct_and_release_stock() {
+    let test_db = common::TestDb::new("order_transitions_move_stock.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let product = repo
+        .create_product(&NewProduct::new(1, "Coffee", "USD"))
+        .expect("failed to create product");
+    assert_eq!(product.stock.on_hand, 0);
+    assert!(product.allow_oversell);
+
+    assert!(matches!(
+        repo.adjust_stock(&NewStockAdjustment::new(2, product.id, 10, "a@b.c", "A")),
+        Err(RepositoryError::NotFound)
+    ));
+    assert!(matches!(
+        repo.adjust_stock(&NewStockAdjustment::new(1, product.id, -1, "a@b.c", "A")),
+        Err(RepositoryError::ConstraintViolation(_))
+    ));
+    let delivery = repo
+        .adjust_stock(
+            &NewStockAdjustment::new(1, product.id, 10, "a@b.c", "A").with_note(" Delivery "),
+        )
+        .expect("failed to adjust stock");
+    assert_eq!(delivery.stock.on_hand, 10);
+    assert_eq!(delivery.note.as_deref(), Some("Delivery"));
+
+    let line =
+        || OrderProduct::new("Coffee", Money::new(100, "USD"), 3).with_product_id(product.id);
+    let completed = repo
+        .create_order(
+            &NewOrder::new(1, Money::new(600, "USD"))
+                .with_status(OrderStatus::Pending)
+                .with_products(vec![line(), line()]),
+        )
+        .expect("failed to create order");
+    let cancelled = repo
+        .create_order(
+            &NewOrder::new(1, Money::new(300, "USD"))
+                .with_status(OrderStatus::Pending)
+                .with_products(vec![line()]),
+        )
+        .expect("failed to create order");
+
+    let transition = |order_id: i32, from: OrderStatus, to: OrderStatus| {
+        repo.transition_order_status(
+            1,
+            &NewOrderStatusChange::new(order_id, from, to, "m@b.c", "M"),
+        )
+        .expect("failed to transition order");
+    };
+    let stock = || {
+        repo.get_product_by_id(product.id, 1)
+            .expect("failed to fetch product")
+            .expect("product should exist")
+            .stock
+    };
+
+    transition(completed.id, OrderStatus::Pending, OrderStatus::Processing);
+    transition(cancelled.id, OrderStatus::Pending, OrderStatus::Processing);
+    assert_eq!((stock().on_hand, stock().reserved), (10, 9));
+    assert_eq!(stock().available(), 1);
+
+    transition(
+        completed.id,
+        OrderStatus::Processing,
+        OrderStatus::Completed,
+    );
+    assert_eq!((stock().on_hand, stock().reserved), (4, 3));
+
+    transition(
+        cancelled.id,
+        OrderStatus::Processing,
+        OrderStatus::Cancelled,
+    );
+    assert_eq!((stock().on_hand, stock().reserved), (4, 0));
+
+    let movements = repo
+        .list_inventory_movements(product.id, 1)
+        .expect("failed to list movements");
+    let kinds: Vec<_> = movements
+        .iter()
+        .map(|movement| (movement.kind, movement.quantity, movement.order_id))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (InventoryMovementKind::Release, 3, Some(cancelled.id)),
+            (InventoryMovementKind::Deduction, 6, Some(completed.id)),
+            (InventoryMovementKind::Reservation, 3, Some(cancelled.id)),
+            (InventoryMovementKind::Reservation, 6, Some(completed.id)),
+            (InventoryMovementKind::Adjustment, 10, None),
+        ]
+    );
+    assert!(matches!(
+        repo.list_inventory_movements(product.id, 2),
+        Err(RepositoryError::NotFound)
+    ));
+}
+
+#[test]
+fn processing_transition_refuses_to_oversell_limited_stock() {
+    let test_db = common::TestDb::new("processing_refuses_oversell.db");
+    let repo = DieselRepository::new(test_db.pool());
+
+    let product = repo
+        .create_product(&NewProduct::new(1, "Coffee", "USD").with_allow_oversell(false))
+        .expect("failed to create product");
+    repo.adjust_stock(&NewStockAdjustment::new(1, product.id, 5, "a@b.c", "A"))
+        .expect("failed to adjust stock");
+
+    let pending = |quantity: i32| {
+        repo.create_order(
+            &NewOrder::new(1, Money::new(100 * i64::from(quantity), "USD"))
+                .with_status(OrderStatus::Pending)
+                .with_products(vec![
+                    OrderProduct::new("Coffee", Money::new(100, "USD"), quantity)
+                        .with_product_id(product.id),
+                ]),
+        )
+        .expect("failed to create order")
+    };
+    let to_processing = |order_id: i32| {
+        repo.transition_order_status(
+            1,
+            &NewOrderStatusChange::new(
+                order_id,
+                OrderStatus::Pending,
+                OrderStatus::Processing,
+                "m@b.c",
+                "M",
+            ),
+        )
+    };
+
+    // Pending orders reserve nothing, so both pass the check at checkout.
+    let first = pending(4);
+    let second = pending(4);
+
+    to_processing(first.id).expect("first order fits the stock");
+    assert!(matches!(
+        to_processing(second.id),
+        Err(RepositoryError::ConstraintViolation(_))
+    ));
+
+    let refused = repo
+        .get_order_by_id(second.id, 1)
+        .expect("failed to fetch order")
+        .expect("order should exist");
+    assert_eq!(refused.status, OrderStatus::Pending);
+    let stock = repo
+        .get_product_by_id(product.id, 1)
+        .expect("failed to fetch product")
+        .expect("product should exist")
+        .stock;
+    assert_eq!((stock.on_hand, stock.reserved), (5, 4));
+    assert!(
+        repo.list_order_status_history(second.id, 1)
+            .expect("failed to load history")
+            .is_empty()
+    );
+}
@@ -31,9 +31,12 @@ fn create_product_stores_price_levels() {
         units: None,
         currency: "USD".to_string(),
         category_id: None,
+        tax_category_id: None,
+        allow_oversell: None,
         price_levels: vec![AddProductPriceLevelForm {
             price_level_id: 1,
             price: Some("12.50".to_string()),
+            min_quantity: None,
         }],
     };
 
@@ -52,7 +55,7 @@ fn create_product_stores_price_levels() {
 
     assert_eq!(product.price_levels.len(), 1);
     assert_eq!(product.price_levels[0].price_level_id, 1);
-    assert_eq!(product.price_levels[0].price_cents, 1250);
+    assert_eq!(product.price_levels[0].price.amount, 1250);
 }
 
 #[test]
@@ -76,6 +79,8 @@ fn create_product_requires_service_role() {
         units: None,
         currency: "USD".to_string(),
         category_id: None,
+        tax_category_id: None,
+        allow_oversell: None,
         price_levels: Vec::new(),
     };
 